@@ -18,23 +18,39 @@ use elex_core::{
     knowledge::FeatureAgent as CoreFeatureAgent,
     types::{AgentId, FeatureCode, QueryType, Complexity, Confidence, Embedding, Timestamp, Action},
     traits::{Agent, Learnable, Routable, Validatable},
-    feature::Feature,
+    feature::{Feature, KPI},
     error::{Result as CoreResult, ElexError},
 };
 use elex_crypto::identity::{AgentIdentity, PublicKey};
 use elex_qlearning::{
     qtable::{QTable, QLearningConfig, StateHash, State, Reward},
+    learner::{Learner, create_learner},
     trajectory::{AgentTrajectoryBuffer, TrajectoryOutcome},
-    policy::{EpsilonGreedy, ActionSelection},
+    traces::trajectory_update,
+    trainer::{ReplayTrainer, ReplayReport},
+    policy::{Policy, ActionSelection},
+    exploration::{PolicyConfig, create_policy},
     replay::Transition,
+    kpi_reward::{KpiRewardModel, KpiRewardConfig, KpiSettlement},
+    format::{self, ExportOptions, QTableHeader},
 };
 use elex_simd::VectorOps;
 use elex_memory::{HnswIndex, HnswConfig, SearchResult};
-use elex_safety::{SafeZoneValidator, ValidationViolation, pre_change_check, BlockingManager};
+use elex_safety::{SafeZoneValidator, ValidationViolation, pre_change_check, BlockingManager, BlockingCondition, ChangeContext};
 use elex_routing::{FederatedMerger, MergeStrategy};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 
+/// Default per-cell change rate limit enforced by the blocking manager
+const DEFAULT_MAX_CHANGES_PER_HOUR: u32 = 4;
+
+/// Seed for learners with stochastic updates (Double Q-learning)
+const LEARNER_SEED: u64 = 42;
+
+/// Default seed for the exploration policy
+const POLICY_SEED: u64 = 42;
+
 // ============================================================================
 // Feature Agent Aggregate Root
 // ============================================================================
@@ -72,11 +88,21 @@ pub struct FeatureAgent {
     // ==================== Intelligence Layer (elex-qlearning) ====================
     /// Q-table for reinforcement learning
     pub q_table: QTable,
+    /// TD update rule selected by the Q-table's `QLearningConfig`
+    ///
+    /// State it keeps beyond the Q-table (Double Q-learning's estimators) is
+    /// saved with `export_learner_state`. Federated sync only exchanges the
+    /// Q-table, which holds the estimators' mean.
+    pub learner: Box<dyn Learner>,
     /// Trajectory buffer for experience replay
     pub trajectory_buffer: AgentTrajectoryBuffer,
-    /// Epsilon-greedy policy for action selection
+    /// Prioritized replay of rewarded transitions
+    pub replay: ReplayTrainer,
+    /// Exploration policy for action selection (epsilon-greedy by default)
     // serde skip (not serialized)
-    pub policy: EpsilonGreedy,
+    pub policy: Box<dyn Policy>,
+    /// Delayed KPI-delta rewards for applied changes
+    pub kpi_rewards: KpiRewardModel,
 
     // ==================== Processing Layer (elex-simd) ====================
     /// SIMD-accelerated vector operations
@@ -95,6 +121,8 @@ pub struct FeatureAgent {
     /// Blocking conditions manager
     // serde skip (not serialized)
     pub blocking_manager: BlockingManager,
+    /// MO for parameters given without one (empty if not MO-specific)
+    pub default_mo: String,
 
     // ==================== Coordination Layer (elex-routing) ====================
     /// Federated learning merger
@@ -142,8 +170,11 @@ impl FeatureAgent {
 
         // Initialize intelligence
         let q_table = QTable::new(QLearningConfig::elex_default());
+        let learner = create_learner(q_table.config(), LEARNER_SEED);
         let trajectory_buffer = AgentTrajectoryBuffer::new(1000);
-        let policy = EpsilonGreedy::default();
+        let replay = ReplayTrainer::elex_default();
+        let policy = create_policy(&PolicyConfig::default(), POLICY_SEED);
+        let kpi_rewards = KpiRewardModel::default();
 
         // Initialize processing
         let vector_ops = VectorOps::new();
@@ -154,7 +185,14 @@ impl FeatureAgent {
 
         // Initialize safety
         let safety_validator = SafeZoneValidator::new();
-        let blocking_manager: BlockingManager = ();
+        let mut blocking_manager = BlockingManager::with_defaults(DEFAULT_MAX_CHANGES_PER_HOUR);
+        blocking_manager.set_feature_active(feature.code.as_str(), feature.active);
+        if !feature.dependencies.is_empty() {
+            blocking_manager.add_condition(BlockingCondition::FeatureDependency {
+                parameters: Vec::new(),
+                features: feature.dependencies.clone(),
+            });
+        }
 
         // Initialize coordination
         let federated_merger = FederatedMerger::new(MergeStrategy::WeightedAverage);
@@ -171,12 +209,16 @@ impl FeatureAgent {
             feature_code,
             expertise_embedding,
             q_table,
+            learner,
             trajectory_buffer,
+            replay,
             policy,
+            kpi_rewards,
             vector_ops,
             vector_memory,
             safety_validator,
             blocking_manager,
+            default_mo: String::new(),
             federated_merger,
             query_count: 0,
             success_count: 0,
@@ -185,6 +227,48 @@ impl FeatureAgent {
         }
     }
 
+    /// Replace the Q-learning configuration (and with it, the learner)
+    ///
+    /// Starts from an empty Q-table; call before the agent learns. Fails
+    /// if the configuration combines settings that cannot work together.
+    pub fn with_learning_config(mut self, config: QLearningConfig) -> CoreResult<Self> {
+        config.validate().map_err(|reason| ElexError::QLearning { reason })?;
+        self.learner = create_learner(&config, LEARNER_SEED);
+        self.q_table = QTable::new(config);
+        Ok(self)
+    }
+
+    /// Replace the exploration policy
+    pub fn with_policy(mut self, config: &PolicyConfig, seed: u64) -> Self {
+        self.policy = create_policy(config, seed);
+        self
+    }
+
+    /// Replace the KPI reward weights and observation window
+    pub fn with_kpi_rewards(mut self, config: KpiRewardConfig) -> Self {
+        self.kpi_rewards = KpiRewardModel::new(config);
+        self
+    }
+
+    /// Replace the known feature activation states
+    ///
+    /// Changes are blocked while any of this feature's dependencies is
+    /// inactive or missing from `features`.
+    pub fn with_feature_states<'a>(mut self, features: impl IntoIterator<Item = &'a Feature>) -> Self {
+        self.blocking_manager.clear_feature_states();
+        self.blocking_manager.set_feature_active(self.feature.code.as_str(), self.feature.active);
+        for feature in features {
+            self.blocking_manager.set_feature_active(feature.code.as_str(), feature.active);
+        }
+        self
+    }
+
+    /// Set the MO for parameters given without one
+    pub fn with_default_mo(mut self, mo_path: impl Into<String>) -> Self {
+        self.default_mo = mo_path.into();
+        self
+    }
+
     /// Initialize the agent (call after creation)
     pub fn initialize(&mut self) -> CoreResult<()> {
         self.core.initialize()?;
@@ -216,11 +300,40 @@ impl FeatureAgent {
         query_type: QueryType,
         complexity: Complexity,
         context: Option<u64>,
+    ) -> CoreResult<String> {
+        self.process_query_inner(query_text, query_type, complexity, context, None)
+    }
+
+    /// Process a user query together with its embedding
+    ///
+    /// Approximating learners (`LearnerKind::LinearApproximation`) use the
+    /// embedding to generalise from similar queries in other contexts.
+    pub fn process_query_with_embedding(
+        &mut self,
+        query_text: &str,
+        query_type: QueryType,
+        complexity: Complexity,
+        context: Option<u64>,
+        embedding: &Embedding,
+    ) -> CoreResult<String> {
+        self.process_query_inner(query_text, query_type, complexity, context, Some(embedding))
+    }
+
+    fn process_query_inner(
+        &mut self,
+        query_text: &str,
+        query_type: QueryType,
+        complexity: Complexity,
+        context: Option<u64>,
+        embedding: Option<&Embedding>,
     ) -> CoreResult<String> {
         let start_time = Self::current_timestamp();
 
         // Encode state from query
         let state_hash = Self::encode_state(query_type, complexity, self.core.confidence, context.unwrap_or(0));
+        if let Some(embedding) = embedding {
+            self.learner.observe(state_hash, embedding);
+        }
 
         // Filter actions based on complexity to prevent premature escalation
         let ql_actions: Vec<elex_qlearning::policy::Action> = Action::all()
@@ -241,8 +354,9 @@ impl FeatureAgent {
             })
             .collect();
 
-        // Select action using policy
-        let action_selection = self.policy.select_action(&self.q_table, state_hash, &ql_actions);
+        // Select action using policy, on the values the learner exposes
+        let values = self.learner.view(&self.q_table, state_hash);
+        let action_selection = self.policy.select_action(&values, state_hash, &ql_actions);
 
         // Generate response based on action
         let response = match action_selection.action {
@@ -264,7 +378,8 @@ impl FeatureAgent {
             0.0, // Reward will be updated on feedback
             state_hash, // Same state for now
             0.0, // TD-error unknown yet
-        );
+        )
+        .with_behavior_prob(action_selection.probability);
         self.trajectory_buffer.add_transition(trajectory_id, transition);
 
         // Update metrics
@@ -295,22 +410,10 @@ impl FeatureAgent {
             TrajectoryOutcome::Failure
         };
         self.trajectory_buffer.complete(trajectory_id, outcome);
-
-        // Get trajectory transitions for Q-learning update
-        if let Some(trajectory) = self.trajectory_buffer.get(trajectory_id) {
-            for transition in &trajectory.transitions {
-                // Get max Q-value for next state
-                let next_max_q = self.q_table.get_max_q(transition.next_state);
-
-                // Update Q-value using Q-learning formula
-                self.q_table.update_q_value(
-                    transition.state,
-                    transition.action,
-                    reward,
-                    next_max_q,
-                );
-            }
+        if let Some(trajectory) = self.trajectory_buffer.get_mut(trajectory_id) {
+            trajectory.set_final_reward(reward);
         }
+        self.learn_from_trajectory(trajectory_id);
 
         // Update core metrics
         if success {
@@ -320,12 +423,101 @@ impl FeatureAgent {
         // Update confidence
         self.core.update_confidence(reward);
 
-        // Decay exploration rate
-        self.policy.decay_epsilon();
+        // Advance the exploration schedule
+        self.policy.decay();
 
         Ok(())
     }
 
+    /// Start the KPI observation window of a change proposed by a query
+    ///
+    /// # Arguments
+    /// * `trajectory_id` - Trajectory ID of the query that proposed the change
+    /// * `before` - KPIs measured before the change was applied
+    pub fn register_change(&mut self, trajectory_id: u64, before: &[KPI]) {
+        self.kpi_rewards.register_change(trajectory_id, before, Self::current_timestamp());
+    }
+
+    /// Record KPIs measured after a change; `false` if its window is not open
+    pub fn observe_kpis(&mut self, trajectory_id: u64, after: &[KPI]) -> bool {
+        self.kpi_rewards.observe(trajectory_id, after, Self::current_timestamp())
+    }
+
+    /// Credit the KPI changes whose observation window has elapsed at `now`
+    ///
+    /// Each credit is added to the reward of the originating query's
+    /// trajectory. The feedback reward was already learned, so only the
+    /// credit itself goes through the learner (`Learner::credit`), without
+    /// counting another visit or outcome and without adding the transitions
+    /// to the replay buffer again. A transition `k` steps before the last
+    /// one is credited `gamma^k * credit`; a (state, action) pair visited
+    /// more than once is credited for its last visit only.
+    pub fn settle_kpi_rewards(&mut self, now: Timestamp) -> KpiSettlement {
+        let settlement = self.kpi_rewards.settle(now);
+        let gamma = self.q_table.config().gamma;
+        for credit in &settlement.credits {
+            let Some(trajectory) = self.trajectory_buffer.get_mut(credit.trajectory_id) else {
+                continue;
+            };
+            let delta = credit.reward.total();
+            let reward = trajectory.transitions.last().map_or(0.0, |t| t.reward) + delta;
+            trajectory.set_final_reward(reward);
+
+            let mut credited = HashSet::new();
+            let mut discount = 1.0;
+            for transition in trajectory.transitions.iter().rev() {
+                if credited.insert((transition.state, transition.action)) {
+                    self.learner.credit(&mut self.q_table, transition.state, transition.action, discount * delta);
+                }
+                discount *= gamma;
+            }
+            self.core.update_confidence(delta);
+        }
+        settlement
+    }
+
+    /// Update the Q-table from a trajectory's final reward
+    ///
+    /// Multi-step returns credit the whole interaction from its final
+    /// reward (configuration validation guarantees the learner is plain
+    /// Q-learning then); otherwise every transition gets a one-step
+    /// learner update.
+    /// Either way the rewarded transitions are kept for idle-time replay.
+    fn learn_from_trajectory(&mut self, trajectory_id: u64) {
+        self.learner.set_behaviour(self.policy.as_ref());
+        let Some(trajectory) = self.trajectory_buffer.get(trajectory_id) else {
+            return;
+        };
+
+        if self.q_table.config().is_multi_step() {
+            trajectory_update(&mut self.q_table, trajectory);
+            for transition in &trajectory.transitions {
+                self.replay.record(transition.clone());
+            }
+        } else {
+            let reward = trajectory.transitions.last().map_or(0.0, |t| t.reward);
+            for transition in &trajectory.transitions {
+                self.learner.update(
+                    &mut self.q_table,
+                    transition.state,
+                    transition.action,
+                    reward,
+                    transition.next_state,
+                );
+                self.replay.record(Transition { reward, ..transition.clone() });
+            }
+        }
+    }
+
+    /// Replay stored transitions into the Q-table for at most `budget` mini-batches
+    ///
+    /// Intended for idle time between queries; does nothing until the
+    /// replay buffer holds enough transitions.
+    pub fn train_replay(&mut self, budget: u32) -> ReplayReport {
+        self.learner.set_behaviour(self.policy.as_ref());
+        self.replay.run(&mut self.q_table, self.learner.as_mut(), budget)
+    }
+
     /// Synchronize Q-table with federated peers
     ///
     /// # Arguments
@@ -344,9 +536,52 @@ impl FeatureAgent {
         Ok(0)
     }
 
-    /// Validate a parameter change against safety constraints
+    /// Export the Q-table in the versioned format, stamped with this agent's id
+    ///
+    /// The payload stays uncompressed so wasm32 peers can read it.
+    pub fn export_q_table(&self) -> CoreResult<Vec<u8>> {
+        let options = ExportOptions { agent_id: self.agent_id, compress: false };
+        self.q_table
+            .export_with(&options)
+            .map_err(|reason| ElexError::QLearning { reason })
+    }
+
+    /// Read a peer's exported Q-table for federated sync
+    ///
+    /// Fails if the table is corrupt, from a newer release, or was trained
+    /// with a different `QLearningConfig`.
+    pub fn import_peer_q_table(&self, data: &[u8]) -> CoreResult<(QTableHeader, QTable)> {
+        format::decode_for(data, self.q_table.config())
+            .map_err(|e| ElexError::QLearning { reason: e.to_string() })
+    }
+
+    /// Export the learner's own state (e.g. Double Q-learning's estimators)
+    ///
+    /// Complements `export_q_table`; None if the learner keeps no state
+    /// beyond the Q-table.
+    pub fn export_learner_state(&self) -> CoreResult<Option<Vec<u8>>> {
+        self.learner
+            .export_state()
+            .map_err(|e| ElexError::QLearning { reason: e.to_string() })
+    }
+
+    /// Restore learner state written by `export_learner_state`
+    ///
+    /// Fails if the state is corrupt or was written for a different
+    /// `QLearningConfig`.
+    pub fn import_learner_state(&mut self, data: &[u8]) -> CoreResult<()> {
+        self.learner
+            .import_state(data)
+            .map_err(|e| ElexError::QLearning { reason: e.to_string() })
+    }
+
+    /// Validate a parameter change on an MO against safety constraints
+    ///
+    /// Checks blocking conditions (alarms, maintenance windows, rollbacks,
+    /// feature dependencies, change rate) before safe zone constraints.
     ///
     /// # Arguments
+    /// * `mo_path` - MO the parameter belongs to
     /// * `parameter_name` - Name of parameter to change
     /// * `old_value` - Current parameter value
     /// * `new_value` - Proposed new value
@@ -355,11 +590,13 @@ impl FeatureAgent {
     /// Ok if safe, Err with violation details if unsafe
     pub fn validate_parameter_change(
         &self,
+        mo_path: &str,
         parameter_name: &str,
         old_value: f32,
         new_value: f32,
     ) -> CoreResult<()> {
         pre_change_check(
+            mo_path,
             parameter_name,
             old_value,
             new_value,
@@ -372,6 +609,25 @@ impl FeatureAgent {
         })
     }
 
+    /// Record a change that was applied on the network
+    ///
+    /// Starts the parameter's cooldown and counts the change towards the
+    /// MO's hourly change limit.
+    pub fn record_applied_change(&mut self, mo_path: &str, parameter_name: &str) {
+        self.blocking_manager.record_change(mo_path);
+        self.safety_validator.record_change(parameter_name);
+    }
+
+    /// Split a parameter into its MO and name
+    ///
+    /// Accepts `<FDN>.<parameter>`; a plain name belongs to the default MO.
+    fn resolve_parameter<'a>(&'a self, parameter: &'a str) -> (&'a str, &'a str) {
+        match parameter.rsplit_once('.') {
+            Some((mo_path, name)) if !mo_path.is_empty() && !name.is_empty() => (mo_path, name),
+            _ => (self.default_mo.as_str(), parameter),
+        }
+    }
+
     // ==================== Response Generation ====================
 
     fn generate_direct_answer(&self, query: &str) -> String {
@@ -508,9 +764,11 @@ impl FeatureAgent {
             health: self.core.health,
             q_table_entries: self.q_table.len() as u32,
             trajectory_count: self.trajectory_buffer.len(),
-            epsilon: self.policy.epsilon(),
+            epsilon: self.policy.exploration_rate(),
             memory_entries: self.vector_memory.len(),
             last_activity: self.last_activity,
+            constraints_version: elex_safety::CONSTRAINTS_VERSION.to_string(),
+            constraints_hash: elex_safety::CONSTRAINTS_HASH.to_string(),
         }
     }
 }
@@ -573,7 +831,8 @@ impl Learnable for FeatureAgent {
             .collect();
 
         let state_hash = state;
-        let selection = self.policy.select_action(&self.q_table, state_hash, &ql_actions);
+        let values = self.learner.view(&self.q_table, state_hash);
+        let selection = self.policy.select_action(&values, state_hash, &ql_actions);
 
         // Convert back to elex_core::types::Action
         match selection.action {
@@ -595,8 +854,8 @@ impl Learnable for FeatureAgent {
             Action::Escalate => elex_qlearning::policy::Action::Escalate,
         };
 
-        let next_max_q = self.q_table.get_max_q(state);
-        self.q_table.update_q_value(state, ql_action, reward, next_max_q);
+        // Direct feedback ends the interaction: nothing to bootstrap from
+        self.learner.update_terminal(&mut self.q_table, state, ql_action, reward);
         Ok(())
     }
 
@@ -623,8 +882,12 @@ impl Routable for FeatureAgent {
 // Validatable Trait Implementation
 // ============================================================================
 
+/// Parameters may be MO-qualified (`<FDN>.<parameter>`) so MO-scoped
+/// blocking conditions apply to the right cell; plain names use the
+/// agent's default MO.
 impl Validatable for FeatureAgent {
     fn validate_parameter(&self, parameter: &str, value: &str) -> CoreResult<bool> {
+        let (mo_path, parameter) = self.resolve_parameter(parameter);
         let parsed_value: f32 = value.parse()
             .map_err(|_| ElexError::ParameterValidation {
                 parameter: parameter.to_string(),
@@ -632,21 +895,25 @@ impl Validatable for FeatureAgent {
                 reason: "Invalid numeric value".to_string(),
             })?;
 
-        // Check against safe zone constraints
-        if let Ok(result) = self.validate_parameter_change(parameter, 0.0, parsed_value) {
-            Ok(true)
-        } else {
-            Ok(false)
-        }
+        // Check against safe zone constraints, starting from the tracked value
+        let old_value = self
+            .safety_validator
+            .current_value(mo_path, parameter)
+            .unwrap_or(parsed_value);
+        Ok(self.validate_parameter_change(mo_path, parameter, old_value, parsed_value).is_ok())
     }
 
     fn generate_command(&self, parameter: &str, value: &str) -> CoreResult<String> {
         Ok(format!("cmedit set {} {}", parameter, value))
     }
 
-    fn check_cooldown(&self, _parameter: &str) -> CoreResult<bool> {
-        // BlockingManager is a placeholder unit type, so we skip cooldown checks for now
-        Ok(true)
+    fn check_cooldown(&self, parameter: &str) -> CoreResult<bool> {
+        let (mo_path, parameter) = self.resolve_parameter(parameter);
+        // Clear to change only if out of cooldown and not blocked
+        if self.safety_validator.cooldown_remaining(parameter).is_some() {
+            return Ok(false);
+        }
+        Ok(self.blocking_manager.check(&ChangeContext::new(mo_path, parameter)).is_ok())
     }
 }
 
@@ -671,6 +938,10 @@ pub struct AgentStats {
     pub epsilon: f32,
     pub memory_entries: usize,
     pub last_activity: Timestamp,
+    /// Version of the embedded safe zone constraint set
+    pub constraints_version: String,
+    /// Content hash of the embedded safe zone constraint set
+    pub constraints_hash: String,
 }
 
 // ============================================================================
@@ -769,6 +1040,344 @@ mod tests {
         assert!(agent.core.confidence > 0.5);
     }
 
+    #[test]
+    fn test_feedback_routes_through_configured_learner() {
+        use elex_qlearning::learner::LearnerKind;
+
+        let code = FeatureCode::parse("FAJ 121 3094").unwrap();
+        let feature = Feature::new(
+            code.clone(),
+            "MIMO Sleep".to_string(),
+            "Energy Saving".to_string(),
+            "LTE".to_string(),
+        );
+
+        let config = QLearningConfig {
+            learner: LearnerKind::DoubleQLearning,
+            ..QLearningConfig::elex_default()
+        };
+        let mut agent = FeatureAgent::new(code.clone(), feature.clone())
+            .with_learning_config(config.clone())
+            .unwrap();
+        agent.initialize().unwrap();
+        assert_eq!(agent.learner.kind(), LearnerKind::DoubleQLearning);
+
+        agent.process_query(
+            "What is MIMO sleep?",
+            QueryType::General,
+            Complexity::Simple,
+            Some(12345),
+        ).unwrap();
+        agent.receive_feedback(0, 1.0, true).unwrap();
+
+        assert_eq!(agent.q_table.get_stats().total_updates, 1);
+        assert!(agent.q_table.iter().any(|(_, _, entry)| entry.value > 0.0));
+
+        // Both estimators survive a save and restore
+        let state = agent.export_learner_state().unwrap().unwrap();
+        let mut restored = FeatureAgent::new(code, feature).with_learning_config(config).unwrap();
+        restored.import_learner_state(&state).unwrap();
+        assert_eq!(restored.export_learner_state().unwrap(), Some(state));
+    }
+
+    #[test]
+    fn test_learnable_update_is_terminal() {
+        use elex_qlearning::policy::Action as QlAction;
+
+        let code = FeatureCode::parse("FAJ 121 3094").unwrap();
+        let feature = Feature::new(
+            code.clone(),
+            "MIMO Sleep".to_string(),
+            "Energy Saving".to_string(),
+            "LTE".to_string(),
+        );
+        let mut agent = FeatureAgent::new(code, feature);
+        agent.q_table.set_q_value(7, QlAction::ConsultPeer, 10.0);
+
+        // α (r - Q): no bootstrap from the best value of the same state
+        Learnable::update(&mut agent, 7, Action::DirectAnswer, 1.0).unwrap();
+        assert!((agent.q_table.get_q_value(7, QlAction::DirectAnswer) - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_feedback_credits_multi_turn_trajectory() {
+        let code = FeatureCode::parse("FAJ 121 3094").unwrap();
+        let feature = Feature::new(
+            code.clone(),
+            "MIMO Sleep".to_string(),
+            "Energy Saving".to_string(),
+            "LTE".to_string(),
+        );
+
+        // Multi-step returns are Q-learning backups only
+        let config = QLearningConfig {
+            n_step: 3,
+            learner: elex_qlearning::learner::LearnerKind::ExpectedSarsa,
+            ..QLearningConfig::elex_default()
+        };
+        assert!(FeatureAgent::new(code.clone(), feature.clone()).with_learning_config(config).is_err());
+
+        let config = QLearningConfig {
+            n_step: 3,
+            ..QLearningConfig::elex_default()
+        };
+        let mut agent = FeatureAgent::new(code, feature).with_learning_config(config).unwrap();
+        agent.initialize().unwrap();
+
+        // Two turns of the same interaction share one trajectory
+        agent.process_query("Sleep thresholds?", QueryType::Parameter, Complexity::Moderate, Some(77)).unwrap();
+        agent.process_query("For cell 1?", QueryType::Parameter, Complexity::Simple, Some(77)).unwrap();
+        agent.receive_feedback(0, 1.0, true).unwrap();
+
+        let trajectory = agent.trajectory_buffer.get(0).unwrap();
+        assert_eq!(trajectory.transitions.len(), 2);
+        assert_eq!(trajectory.total_reward, 1.0);
+
+        let first = &trajectory.transitions[0];
+        assert!(agent.q_table.get_q_value(first.state, first.action) > 0.0);
+    }
+
+    #[test]
+    fn test_train_replay_from_feedback() {
+        let code = FeatureCode::parse("FAJ 121 3094").unwrap();
+        let feature = Feature::new(
+            code.clone(),
+            "MIMO Sleep".to_string(),
+            "Energy Saving".to_string(),
+            "LTE".to_string(),
+        );
+
+        let mut agent = FeatureAgent::new(code, feature);
+        agent.initialize().unwrap();
+
+        let min_samples = agent.replay.config().min_samples as u64;
+        for context in 0..min_samples {
+            let id = agent.trajectory_buffer.len() as u64;
+            agent.process_query("What is MIMO sleep?", QueryType::General, Complexity::Simple, Some(context)).unwrap();
+            agent.receive_feedback(id, 1.0, true).unwrap();
+        }
+        assert_eq!(agent.replay.buffer().len() as u64, min_samples);
+
+        let updates = agent.q_table.get_stats().total_updates;
+        let report = agent.train_replay(3);
+        assert_eq!(report.steps, 3);
+        assert_eq!(agent.q_table.get_stats().total_updates, updates + report.samples);
+    }
+
+    #[test]
+    fn test_with_policy() {
+        let code = FeatureCode::parse("FAJ 121 3094").unwrap();
+        let feature = Feature::new(
+            code.clone(),
+            "MIMO Sleep".to_string(),
+            "Energy Saving".to_string(),
+            "LTE".to_string(),
+        );
+
+        let mut agent = FeatureAgent::new(code, feature).with_policy(&PolicyConfig::Thompson, 9);
+        agent.initialize().unwrap();
+        assert_eq!(agent.policy.name(), "thompson");
+
+        agent.process_query("What is MIMO sleep?", QueryType::General, Complexity::Simple, Some(1)).unwrap();
+        agent.receive_feedback(0, 1.0, true).unwrap();
+        assert_eq!(agent.get_stats().epsilon, 0.0);
+    }
+
+    #[test]
+    fn test_linear_approximation_generalises_across_contexts() {
+        use elex_qlearning::learner::LearnerKind;
+
+        let code = FeatureCode::parse("FAJ 121 3094").unwrap();
+        let feature = Feature::new(
+            code.clone(),
+            "MIMO Sleep".to_string(),
+            "Energy Saving".to_string(),
+            "LTE".to_string(),
+        );
+
+        let config = QLearningConfig {
+            learner: LearnerKind::LinearApproximation,
+            ..QLearningConfig::elex_default()
+        };
+        let mut agent = FeatureAgent::new(code, feature).with_learning_config(config).unwrap();
+        agent.initialize().unwrap();
+        let embedding = agent.expertise_embedding;
+
+        for context in 0..20 {
+            let id = agent.trajectory_buffer.len() as u64;
+            agent.process_query_with_embedding("Sleep KPI?", QueryType::Kpi, Complexity::Simple, Some(context), &embedding).unwrap();
+            agent.receive_feedback(id, 1.0, true).unwrap();
+        }
+
+        // A new context has no table entries but inherits a positive estimate
+        let unseen = FeatureAgent::encode_state(QueryType::Kpi, Complexity::Simple, agent.core.confidence, 999);
+        agent.learner.observe(unseen, &embedding);
+        assert!(agent.q_table.row(unseen).is_none());
+        let view = agent.learner.view(&agent.q_table, unseen);
+        assert!(view.values().iter().any(|&q| q > 0.0));
+    }
+
+    #[test]
+    fn test_off_policy_evaluation_from_logged_queries() {
+        use elex_qlearning::ope::{EpsilonGreedyTarget, Estimator, OffPolicyEvaluator, TrajectoryLog};
+
+        let code = FeatureCode::parse("FAJ 121 3094").unwrap();
+        let feature = Feature::new(
+            code.clone(),
+            "MIMO Sleep".to_string(),
+            "Energy Saving".to_string(),
+            "LTE".to_string(),
+        );
+
+        let mut agent = FeatureAgent::new(code, feature);
+        agent.initialize().unwrap();
+        for i in 0..10 {
+            agent.process_query("What is MIMO sleep?", QueryType::General, Complexity::Simple, Some(i)).unwrap();
+            agent.receive_feedback(i, 1.0, true).unwrap();
+        }
+
+        let log = TrajectoryLog::from_buffer(&agent.trajectory_buffer);
+        assert_eq!(log.episodes.len(), 10);
+        assert_eq!(log.skipped, 0);
+
+        let target = EpsilonGreedyTarget { q_table: &agent.q_table, epsilon: 0.1 };
+        let report = OffPolicyEvaluator::default().evaluate(&log, &target, &agent.q_table).unwrap();
+        let dr = report.estimate(Estimator::DoublyRobust).unwrap();
+        assert!(dr.lower <= dr.value && dr.value <= dr.upper);
+    }
+
+    #[test]
+    fn test_kpi_delta_credits_originating_query() {
+        let code = FeatureCode::parse("FAJ 121 3094").unwrap();
+        let feature = Feature::new(
+            code.clone(),
+            "MIMO Sleep".to_string(),
+            "Energy Saving".to_string(),
+            "LTE".to_string(),
+        );
+
+        let mut agent = FeatureAgent::new(code, feature).with_kpi_rewards(KpiRewardConfig {
+            window_ms: 60_000,
+            ..KpiRewardConfig::default()
+        });
+        agent.initialize().unwrap();
+        agent.process_query("Lower the sleep threshold", QueryType::Parameter, Complexity::Simple, Some(5)).unwrap();
+        agent.receive_feedback(0, 0.2, true).unwrap();
+
+        let transition = agent.trajectory_buffer.get(0).unwrap().transitions[0].clone();
+        let before_q = agent.q_table.get_q_value(transition.state, transition.action);
+        let before_visits = agent.q_table.entry(transition.state, transition.action).unwrap().visit_count;
+        let before_replay = agent.replay.buffer().len();
+
+        let mut kpi = KPI::new("energy_saving".to_string(), String::new(), 10.0);
+        kpi.direction = Some("HIGHER_IS_BETTER".to_string());
+        kpi.current_value = Some(20.0);
+        agent.register_change(0, &[kpi.clone()]);
+        kpi.current_value = Some(25.0);
+        assert!(agent.observe_kpis(0, &[kpi]));
+
+        // Nothing is credited before the window closes
+        assert!(agent.settle_kpi_rewards(FeatureAgent::current_timestamp()).credits.is_empty());
+
+        let settlement = agent.settle_kpi_rewards(u64::MAX);
+        assert_eq!(settlement.credits.len(), 1);
+        assert!((settlement.credits[0].reward.kpi_delta - 0.5).abs() < 1e-6);
+
+        let trajectory = agent.trajectory_buffer.get(0).unwrap();
+        assert!((trajectory.total_reward - 0.7).abs() < 1e-6);
+
+        // Only the credit is learned, once
+        let alpha = agent.q_table.config().alpha;
+        let after_q = agent.q_table.get_q_value(transition.state, transition.action);
+        assert!((after_q - before_q - alpha * 0.5).abs() < 1e-5);
+        assert_eq!(agent.q_table.entry(transition.state, transition.action).unwrap().visit_count, before_visits);
+        assert_eq!(agent.replay.buffer().len(), before_replay);
+    }
+
+    #[test]
+    fn test_kpi_credit_goes_through_double_q_learner() {
+        use elex_qlearning::learner::LearnerKind;
+
+        let code = FeatureCode::parse("FAJ 121 3094").unwrap();
+        let feature = Feature::new(
+            code.clone(),
+            "MIMO Sleep".to_string(),
+            "Energy Saving".to_string(),
+            "LTE".to_string(),
+        );
+        let config = QLearningConfig {
+            learner: LearnerKind::DoubleQLearning,
+            ..QLearningConfig::elex_default()
+        };
+
+        // Two identical agents; only the first gets the KPI credit
+        let mut agents: Vec<FeatureAgent> = (0..2)
+            .map(|_| {
+                let mut agent = FeatureAgent::new(code.clone(), feature.clone())
+                    .with_learning_config(config.clone())
+                    .unwrap()
+                    .with_kpi_rewards(KpiRewardConfig {
+                        window_ms: 60_000,
+                        ..KpiRewardConfig::default()
+                    });
+                agent.initialize().unwrap();
+                agent.process_query("Lower the sleep threshold", QueryType::Parameter, Complexity::Simple, Some(5)).unwrap();
+                agent.receive_feedback(0, 0.2, true).unwrap();
+                agent
+            })
+            .collect();
+
+        let mut kpi = KPI::new("energy_saving".to_string(), String::new(), 10.0);
+        kpi.direction = Some("HIGHER_IS_BETTER".to_string());
+        kpi.current_value = Some(20.0);
+        agents[0].register_change(0, &[kpi.clone()]);
+        kpi.current_value = Some(25.0);
+        assert!(agents[0].observe_kpis(0, &[kpi]));
+        let settlement = agents[0].settle_kpi_rewards(u64::MAX);
+        let delta = settlement.credits[0].reward.total();
+
+        let transition = agents[0].trajectory_buffer.get(0).unwrap().transitions.last().unwrap().clone();
+        let (state, action) = (transition.state, transition.action);
+        let alpha = config.alpha;
+        let credited = agents[0].q_table.get_q_value(state, action);
+        assert!((credited - agents[1].q_table.get_q_value(state, action) - alpha * delta).abs() < 1e-5);
+
+        // The next update writes the estimators' mean, which must keep the credit
+        let after: Vec<f32> = agents
+            .iter_mut()
+            .map(|agent| agent.learner.update_terminal(&mut agent.q_table, state, action, 0.0))
+            .collect();
+        let kept = alpha * delta * (1.0 - alpha / 2.0);
+        assert!((after[0] - after[1] - kept).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_q_table_export_round_trip() {
+        let code = FeatureCode::parse("FAJ 121 3094").unwrap();
+        let feature = Feature::new(
+            code.clone(),
+            "MIMO Sleep".to_string(),
+            "Energy Saving".to_string(),
+            "LTE".to_string(),
+        );
+
+        let mut agent = FeatureAgent::new(code.clone(), feature.clone());
+        agent.initialize().unwrap();
+        agent.process_query("What is MIMO sleep?", QueryType::General, Complexity::Simple, Some(1)).unwrap();
+        agent.receive_feedback(0, 1.0, true).unwrap();
+
+        let data = agent.export_q_table().unwrap();
+        let peer = FeatureAgent::new(code.clone(), feature.clone());
+        let (header, table) = peer.import_peer_q_table(&data).unwrap();
+        assert_eq!(header.agent_id, agent.agent_id);
+        assert_eq!(table.len(), agent.q_table.len());
+
+        // Peers trained with another configuration are refused
+        let config = QLearningConfig { gamma: 0.5, ..QLearningConfig::elex_default() };
+        let other = FeatureAgent::new(code, feature).with_learning_config(config).unwrap();
+        assert!(other.import_peer_q_table(&data).is_err());
+    }
+
     #[test]
     fn test_validate_parameter_change() {
         let code = FeatureCode::parse("FAJ 121 3094").unwrap();
@@ -779,15 +1388,81 @@ mod tests {
             "LTE".to_string(),
         );
 
-        let agent = FeatureAgent::new(code, feature);
+        let mut agent = FeatureAgent::new(code, feature);
         agent.initialize().unwrap();
 
         // Valid change
-        let result = agent.validate_parameter_change("testParam", 10.0, 15.0);
+        let result = agent.validate_parameter_change("EUtranCellFDD=Cell1", "testParam", 10.0, 15.0);
         // May fail if parameter not in safe zone, but should not crash
         assert!(result.is_ok() || result.is_err());
     }
 
+    #[test]
+    fn test_blocking_conditions() {
+        let code = FeatureCode::parse("FAJ 121 3094").unwrap();
+        let mut feature = Feature::new(
+            code.clone(),
+            "MIMO Sleep".to_string(),
+            "Energy Saving".to_string(),
+            "LTE".to_string(),
+        );
+        feature.add_dependency("FAJ 121 0001".to_string());
+
+        let mut dependency = Feature::new(
+            FeatureCode::parse("FAJ 121 0001").unwrap(),
+            "Dependency".to_string(),
+            "Energy Saving".to_string(),
+            "LTE".to_string(),
+        );
+        let cell = "EUtranCellFDD=Cell1";
+        let qualified = "EUtranCellFDD=Cell1.lbActivationThreshold";
+
+        // Dependency state unknown: blocked
+        let agent = FeatureAgent::new(code.clone(), feature.clone());
+        let result = agent.validate_parameter_change(cell, "lbActivationThreshold", 50.0, 55.0);
+        assert!(result.unwrap_err().to_string().contains("FAJ 121 0001"));
+
+        // Dependency reported inactive
+        let agent = FeatureAgent::new(code.clone(), feature.clone()).with_feature_states([&dependency]);
+        assert!(!agent.check_cooldown(qualified).unwrap());
+        assert!(!agent.check_cooldown("lbActivationThreshold").unwrap());
+
+        // Later states replace earlier ones without adding conditions
+        dependency.active = true;
+        let agent = FeatureAgent::new(code.clone(), feature.clone()).with_feature_states([&dependency]);
+        let conditions = agent.blocking_manager.conditions().len();
+        let agent = agent.with_feature_states(std::iter::empty());
+        assert_eq!(agent.blocking_manager.conditions().len(), conditions);
+        assert!(!agent.check_cooldown(qualified).unwrap());
+
+        let mut agent = FeatureAgent::new(code, feature)
+            .with_feature_states([&dependency])
+            .with_default_mo(cell);
+        assert!(agent.validate_parameter_change(cell, "lbActivationThreshold", 50.0, 55.0).is_ok());
+        assert!(agent.check_cooldown(qualified).unwrap());
+
+        // Plain names are checked on the default MO, from the tracked value
+        assert!(agent.validate_parameter("lbActivationThreshold", "55").unwrap());
+        agent.safety_validator.set_current_value(cell, "lbActivationThreshold", 50.0);
+        assert!(!agent.validate_parameter("lbActivationThreshold", "80").unwrap());
+        assert!(agent.validate_parameter("EUtranCellFDD=Cell2.lbActivationThreshold", "80").unwrap());
+
+        // Rollback in progress on the cell; other cells are unaffected
+        agent.blocking_manager.begin_rollback(cell);
+        assert!(!agent.check_cooldown(qualified).unwrap());
+        assert!(!agent.check_cooldown("lbActivationThreshold").unwrap());
+        assert!(agent.check_cooldown("EUtranCellFDD=Cell2.hoA3Offset").unwrap());
+        agent.blocking_manager.end_rollback(cell);
+
+        // Applied changes count towards the hourly limit of their cell
+        for _ in 0..DEFAULT_MAX_CHANGES_PER_HOUR {
+            agent.record_applied_change(cell, "hoA3Offset");
+        }
+        let result = agent.validate_parameter_change(cell, "lbActivationThreshold", 50.0, 55.0);
+        assert!(result.unwrap_err().to_string().contains("changes in the last hour"));
+        assert!(agent.validate_parameter_change("EUtranCellFDD=Cell2", "lbActivationThreshold", 50.0, 55.0).is_ok());
+    }
+
     #[test]
     fn test_agent_stats() {
         let code = FeatureCode::parse("FAJ 121 3094").unwrap();
@@ -806,6 +1481,7 @@ mod tests {
         assert_eq!(stats.success_rate, 0.0);
         assert_eq!(stats.feature_code, "FAJ 121 3094");
         assert_eq!(stats.feature_name, "MIMO Sleep");
+        assert_eq!(stats.constraints_hash, elex_safety::CONSTRAINTS_HASH);
     }
 
     #[test]
@@ -0,0 +1,745 @@
+//! cmedit Output and CM Dump Parser
+//!
+//! Parses the answers to `cmedit get` and bulk CM exports back into typed
+//! `(mo_path, parameter, value)` records, so the agent can work from the
+//! real network configuration instead of guessing current values.
+//!
+//! # Supported Formats
+//!
+//! Attribute list (default `cmedit get` output):
+//!
+//! ```text
+//! FDN : SubNetwork=ONRM_ROOT_MO,MeContext=ERBS001,ManagedElement=1,ENodeBFunction=1,EUtranCellFDD=Cell1
+//! qRxLevMin : -140
+//! sCellHandling : ACTIVATED
+//!
+//! 1 instance(s)
+//! ```
+//!
+//! Tabular (`cmedit get ... -t`, tab-separated):
+//!
+//! ```text
+//! NodeId   EUtranCellFDDId   qRxLevMin   sCellHandling
+//! ERBS001  Cell1             -140        ACTIVATED
+//!
+//! 1 instance(s)
+//! ```
+//!
+//! Bulk CM export (dynamic format):
+//!
+//! ```text
+//! create
+//! FDN : "SubNetwork=ONRM_ROOT_MO,MeContext=ERBS001,ManagedElement=1,ENodeBFunction=1,EUtranCellFDD=Cell1"
+//! qRxLevMin : -140
+//! ```
+
+use crate::cmedit::ParameterChange;
+use elex_core::{error::{Result, ElexError}, feature::Parameter};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+// ============================================================================
+// Typed Values
+// ============================================================================
+
+/// Typed attribute value from cmedit output
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CmValue {
+    /// `null` or empty
+    Null,
+
+    /// `true` / `false`
+    Boolean(bool),
+
+    /// Whole number
+    Integer(i64),
+
+    /// Decimal number
+    Float(f64),
+
+    /// Enum literal or string
+    Literal(String),
+
+    /// `[a, b, c]`
+    List(Vec<CmValue>),
+
+    /// `{member=value, ...}`
+    Struct(Vec<(String, CmValue)>),
+}
+
+impl CmValue {
+    /// Parse a raw attribute value
+    pub fn parse(raw: &str) -> Self {
+        let raw = raw.trim();
+
+        if raw.is_empty() || raw == "null" || raw == "<empty>" {
+            return CmValue::Null;
+        }
+        if let Some(inner) = raw.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+            return CmValue::List(
+                split_top_level(inner)
+                    .into_iter()
+                    .filter(|item| !item.trim().is_empty())
+                    .map(CmValue::parse)
+                    .collect(),
+            );
+        }
+        if let Some(inner) = raw.strip_prefix('{').and_then(|r| r.strip_suffix('}')) {
+            return CmValue::Struct(
+                split_top_level(inner)
+                    .into_iter()
+                    .filter_map(|member| {
+                        let (name, value) = member.split_once(['=', ':'])?;
+                        Some((name.trim().to_string(), CmValue::parse(value)))
+                    })
+                    .collect(),
+            );
+        }
+        if let Some(inner) = raw.strip_prefix('"').and_then(|r| r.strip_suffix('"')) {
+            return CmValue::Literal(inner.to_string());
+        }
+        match raw {
+            "true" => return CmValue::Boolean(true),
+            "false" => return CmValue::Boolean(false),
+            _ => {}
+        }
+        if let Ok(v) = raw.parse::<i64>() {
+            return CmValue::Integer(v);
+        }
+        if let Ok(v) = raw.parse::<f64>() {
+            return CmValue::Float(v);
+        }
+        CmValue::Literal(raw.to_string())
+    }
+
+    /// Numeric value (booleans as 0/1), None for other types
+    pub fn as_f32(&self) -> Option<f32> {
+        match self {
+            CmValue::Boolean(b) => Some(if *b { 1.0 } else { 0.0 }),
+            CmValue::Integer(v) => Some(*v as f32),
+            CmValue::Float(v) => Some(*v as f32),
+            _ => None,
+        }
+    }
+
+    /// Check if the value is null
+    pub fn is_null(&self) -> bool {
+        matches!(self, CmValue::Null)
+    }
+}
+
+impl fmt::Display for CmValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CmValue::Null => write!(f, "null"),
+            CmValue::Boolean(b) => write!(f, "{}", b),
+            CmValue::Integer(v) => write!(f, "{}", v),
+            CmValue::Float(v) => write!(f, "{}", v),
+            CmValue::Literal(s) => write!(f, "{}", s),
+            CmValue::List(items) => {
+                let items: Vec<String> = items.iter().map(|i| i.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+            CmValue::Struct(members) => {
+                let members: Vec<String> = members.iter().map(|(n, v)| format!("{}={}", n, v)).collect();
+                write!(f, "{{{}}}", members.join(", "))
+            }
+        }
+    }
+}
+
+/// Split on commas that are not nested in brackets, braces or quotes
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut quoted = false;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '[' | '{' if !quoted => depth += 1,
+            ']' | '}' if !quoted => depth -= 1,
+            ',' if !quoted && depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+// ============================================================================
+// Records
+// ============================================================================
+
+/// One attribute value of one MO
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CmRecord {
+    /// MO path (full FDN when available)
+    pub mo_path: String,
+
+    /// Attribute name
+    pub parameter: String,
+
+    /// Typed value
+    pub value: CmValue,
+}
+
+/// Output format of a cmedit answer
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CmOutputFormat {
+    /// `FDN : ...` followed by `attribute : value` lines
+    AttributeList,
+
+    /// Tab-separated table (`-t`)
+    Tabular,
+
+    /// Bulk CM export in dynamic format
+    BulkExport,
+}
+
+/// Detect the format of a cmedit answer
+pub fn detect_format(text: &str) -> CmOutputFormat {
+    let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+    match lines.next() {
+        Some(first) if is_operation(first) => CmOutputFormat::BulkExport,
+        Some(first) if first.starts_with("FDN") && first.contains(':') => CmOutputFormat::AttributeList,
+        _ => CmOutputFormat::Tabular,
+    }
+}
+
+/// Parse `cmedit get` output in either attribute-list or tabular form
+pub fn parse_get_output(text: &str) -> Result<Vec<CmRecord>> {
+    match detect_format(text) {
+        CmOutputFormat::AttributeList => parse_attribute_list(text),
+        CmOutputFormat::Tabular => parse_tabular(text),
+        CmOutputFormat::BulkExport => parse_bulk_export(text),
+    }
+}
+
+/// Parse attribute-list output (`FDN : ...` blocks)
+pub fn parse_attribute_list(text: &str) -> Result<Vec<CmRecord>> {
+    parse_blocks(text, false)
+}
+
+/// Parse a bulk CM export in dynamic format
+///
+/// MOs under a `delete` operation are skipped.
+pub fn parse_bulk_export(text: &str) -> Result<Vec<CmRecord>> {
+    parse_blocks(text, true)
+}
+
+/// Parse tabular output (`cmedit get ... -t`)
+///
+/// A line with a single word names the MO class of the table that follows.
+/// Key columns run from the start of the header up to `<MoClass>Id`; the
+/// remaining columns are attributes, even when their names end in `Id`. The
+/// MO path is built from the key columns, e.g.
+/// `MeContext=ERBS001,EUtranCellFDD=Cell1`. A header with an `MO` or `FDN`
+/// column takes the path from that column instead.
+///
+/// Output covering several MO classes holds one table per class; a new class
+/// line or a repeated header line starts the next table.
+pub fn parse_tabular(text: &str) -> Result<Vec<CmRecord>> {
+    let mut records = Vec::new();
+    let mut mo_class: Option<String> = None;
+    let mut header: Option<TableHeader> = None;
+    let mut instances = 0;
+
+    for (index, line) in text.lines().enumerate() {
+        let line_no = index + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        check_error_line(trimmed, line_no)?;
+        if let Some(expected) = parse_instance_count(trimmed) {
+            check_instance_count(expected, instances, line_no)?;
+            continue;
+        }
+
+        let columns = split_columns(line);
+        // A single word is the MO class of the next table
+        if columns.len() < 2 {
+            mo_class = Some(trimmed.to_string());
+            header = None;
+            continue;
+        }
+
+        let header = match &header {
+            Some(current) if columns[0] != current.names[0] => current,
+            _ => {
+                header = Some(TableHeader::parse(&columns, mo_class.as_deref(), line_no)?);
+                continue;
+            }
+        };
+
+        if columns.len() != header.names.len() {
+            return Err(ElexError::CmParse {
+                line: line_no,
+                reason: format!("Expected {} columns, found {}", header.names.len(), columns.len()),
+            });
+        }
+
+        let mo_path = match header.mo_column {
+            Some(column) => columns[column].trim_matches('"').to_string(),
+            None => header.names[..header.key_count]
+                .iter()
+                .zip(&columns)
+                .map(|(name, value)| {
+                    let class = match name.as_str() {
+                        "NodeId" => "MeContext",
+                        other => other.strip_suffix("Id").unwrap_or(other),
+                    };
+                    format!("{}={}", class, value)
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+        };
+
+        for (column, (name, value)) in header.names.iter().zip(&columns).enumerate() {
+            if column < header.key_count || Some(column) == header.mo_column {
+                continue;
+            }
+            records.push(CmRecord {
+                mo_path: mo_path.clone(),
+                parameter: name.clone(),
+                value: CmValue::parse(value),
+            });
+        }
+        instances += 1;
+    }
+
+    Ok(records)
+}
+
+/// Column layout of one table in tabular output
+struct TableHeader {
+    names: Vec<String>,
+
+    /// Number of leading key columns
+    key_count: usize,
+
+    /// Column holding the MO path, if the header has one
+    mo_column: Option<usize>,
+}
+
+impl TableHeader {
+    fn parse(columns: &[&str], mo_class: Option<&str>, line: usize) -> Result<Self> {
+        let names: Vec<String> = columns.iter().map(|c| c.to_string()).collect();
+        let mo_column = columns.iter().position(|c| *c == "MO" || *c == "FDN");
+
+        let key_count = match (mo_column, mo_class) {
+            (Some(_), _) => 0,
+            (None, Some(class)) => {
+                let id = format!("{}Id", class);
+                columns.iter().position(|c| *c == id).map(|i| i + 1).ok_or_else(|| {
+                    ElexError::CmParse {
+                        line,
+                        reason: format!("Tabular header has no '{}' column", id),
+                    }
+                })?
+            }
+            // Without a class line, MO class names are capitalised, attributes are not
+            (None, None) => columns
+                .iter()
+                .take_while(|c| {
+                    *c == &"NodeId" || (c.ends_with("Id") && c.starts_with(|ch: char| ch.is_ascii_uppercase()))
+                })
+                .count(),
+        };
+
+        let attributes = names.len() - key_count - usize::from(mo_column.is_some());
+        if (key_count == 0 && mo_column.is_none()) || attributes == 0 {
+            return Err(ElexError::CmParse {
+                line,
+                reason: "Tabular header must have key columns followed by attributes".to_string(),
+            });
+        }
+
+        Ok(Self {
+            names,
+            key_count,
+            mo_column,
+        })
+    }
+}
+
+/// Parse `FDN : ...` blocks, optionally with bulk export operation lines
+fn parse_blocks(text: &str, allow_operations: bool) -> Result<Vec<CmRecord>> {
+    let mut records = Vec::new();
+    let mut current: Option<String> = None;
+    let mut skipping = false;
+    let mut deleting = false;
+    let mut instances = 0;
+
+    for (index, line) in text.lines().enumerate() {
+        let line_no = index + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("//") {
+            continue;
+        }
+        check_error_line(trimmed, line_no)?;
+        if let Some(expected) = parse_instance_count(trimmed) {
+            check_instance_count(expected, instances, line_no)?;
+            continue;
+        }
+        if allow_operations && is_operation(trimmed) {
+            deleting = trimmed.eq_ignore_ascii_case("delete");
+            current = None;
+            continue;
+        }
+
+        let (name, value) = trimmed.split_once(':').ok_or_else(|| ElexError::CmParse {
+            line: line_no,
+            reason: format!("Expected 'name : value', found '{}'", trimmed),
+        })?;
+        let name = name.trim();
+        let value = value.trim();
+
+        if name == "FDN" {
+            let fdn = value.trim_matches('"');
+            if fdn.is_empty() || !fdn.contains('=') {
+                return Err(ElexError::CmParse {
+                    line: line_no,
+                    reason: format!("Invalid FDN '{}'", value),
+                });
+            }
+            current = Some(fdn.to_string());
+            skipping = deleting;
+            instances += 1;
+            continue;
+        }
+
+        let Some(mo_path) = &current else {
+            return Err(ElexError::CmParse {
+                line: line_no,
+                reason: format!("Attribute '{}' before any FDN", name),
+            });
+        };
+        if !skipping {
+            records.push(CmRecord {
+                mo_path: mo_path.clone(),
+                parameter: name.to_string(),
+                value: CmValue::parse(value),
+            });
+        }
+    }
+
+    Ok(records)
+}
+
+fn is_operation(line: &str) -> bool {
+    ["create", "set", "delete"].iter().any(|op| line.eq_ignore_ascii_case(op))
+}
+
+/// Split a table row on tabs, or on runs of two or more spaces
+fn split_columns(line: &str) -> Vec<&str> {
+    if line.contains('\t') {
+        line.split('\t').map(str::trim).collect()
+    } else {
+        line.split("  ").map(str::trim).filter(|c| !c.is_empty()).collect()
+    }
+}
+
+/// `N instance(s)` footer
+fn parse_instance_count(line: &str) -> Option<usize> {
+    line.strip_suffix("instance(s)")?.trim().parse().ok()
+}
+
+fn check_instance_count(expected: usize, found: usize, line: usize) -> Result<()> {
+    if expected != found {
+        return Err(ElexError::CmParse {
+            line,
+            reason: format!("Footer reports {} instance(s), parsed {}", expected, found),
+        });
+    }
+    Ok(())
+}
+
+/// cmedit reports failures as `Error <code> : <message>`
+fn check_error_line(line: &str, line_no: usize) -> Result<()> {
+    if line.starts_with("Error ") {
+        return Err(ElexError::CmParse {
+            line: line_no,
+            reason: line.to_string(),
+        });
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Configuration Snapshot
+// ============================================================================
+
+/// Current configuration assembled from parsed records
+///
+/// Lookups accept either the full FDN or a trailing part of it such as
+/// `EUtranCellFDD=Cell1`, as long as it identifies a single MO.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ConfigSnapshot {
+    values: HashMap<String, HashMap<String, CmValue>>,
+}
+
+impl ConfigSnapshot {
+    /// Create an empty snapshot
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a snapshot from parsed records (later records win)
+    pub fn from_records(records: impl IntoIterator<Item = CmRecord>) -> Self {
+        let mut snapshot = Self::new();
+        snapshot.extend(records);
+        snapshot
+    }
+
+    /// Add parsed records (later records win)
+    pub fn extend(&mut self, records: impl IntoIterator<Item = CmRecord>) {
+        for record in records {
+            self.insert(record);
+        }
+    }
+
+    /// Add one record
+    pub fn insert(&mut self, record: CmRecord) {
+        self.values
+            .entry(record.mo_path)
+            .or_default()
+            .insert(record.parameter, record.value);
+    }
+
+    /// Number of MOs
+    pub fn mo_count(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Check if the snapshot is empty
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Resolve an MO path to the stored FDN
+    ///
+    /// Returns None if no MO or more than one MO matches.
+    pub fn resolve(&self, mo_path: &str) -> Option<&str> {
+        if let Some((fdn, _)) = self.values.get_key_value(mo_path) {
+            return Some(fdn);
+        }
+        let suffix = format!(",{}", mo_path);
+        let mut matches = self.values.keys().filter(|fdn| fdn.ends_with(&suffix));
+        let first = matches.next()?;
+        match matches.next() {
+            Some(_) => None,
+            None => Some(first),
+        }
+    }
+
+    /// Value of an attribute on an MO
+    pub fn get(&self, mo_path: &str, parameter: &str) -> Option<&CmValue> {
+        let fdn = self.resolve(mo_path)?;
+        self.values.get(fdn)?.get(parameter)
+    }
+
+    /// Numeric value of an attribute on an MO
+    pub fn get_f32(&self, mo_path: &str, parameter: &str) -> Option<f32> {
+        self.get(mo_path, parameter)?.as_f32()
+    }
+
+    /// Set `Parameter::current_value` from the snapshot
+    ///
+    /// Returns false if the MO or attribute is not in the snapshot.
+    pub fn populate_parameter(&self, parameter: &mut Parameter, mo_path: &str) -> bool {
+        match self.get(mo_path, &parameter.name) {
+            Some(value) if !value.is_null() => {
+                parameter.set_value(value.to_string());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Fill `old_value` of a numeric change from the snapshot
+    ///
+    /// Enum and boolean literals are encoded by
+    /// `CmeditGenerator::fill_old_value`, which knows the parameter domains.
+    pub fn fill_old_value(&self, change: &mut ParameterChange) -> bool {
+        match self.get_f32(&change.mo_path, &change.parameter) {
+            Some(value) => {
+                change.old_value = Some(value);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elex_core::feature::SafeZone;
+
+    const CELL_FDN: &str =
+        "SubNetwork=ONRM_ROOT_MO,MeContext=ERBS001,ManagedElement=1,ENodeBFunction=1,EUtranCellFDD=Cell1";
+
+    #[test]
+    fn test_value_parsing() {
+        assert_eq!(CmValue::parse("-140"), CmValue::Integer(-140));
+        assert_eq!(CmValue::parse("2.5"), CmValue::Float(2.5));
+        assert_eq!(CmValue::parse("true"), CmValue::Boolean(true));
+        assert_eq!(CmValue::parse("ACTIVATED"), CmValue::Literal("ACTIVATED".to_string()));
+        assert_eq!(CmValue::parse("null"), CmValue::Null);
+        assert_eq!(
+            CmValue::parse("[1, 2]"),
+            CmValue::List(vec![CmValue::Integer(1), CmValue::Integer(2)])
+        );
+        let value = CmValue::parse("{qci=9, ref=[a, b]}");
+        assert_eq!(value.to_string(), "{qci=9, ref=[a, b]}");
+    }
+
+    #[test]
+    fn test_parse_attribute_list() {
+        let output = format!(
+            "FDN : {}\nqRxLevMin : -140\nsCellHandling : ACTIVATED\n\n1 instance(s)\n",
+            CELL_FDN
+        );
+        assert_eq!(detect_format(&output), CmOutputFormat::AttributeList);
+
+        let records = parse_get_output(&output).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].mo_path, CELL_FDN);
+        assert_eq!(records[0].parameter, "qRxLevMin");
+        assert_eq!(records[0].value, CmValue::Integer(-140));
+    }
+
+    #[test]
+    fn test_parse_tabular() {
+        let output = "EUtranCellFDD\nNodeId\tEUtranCellFDDId\tqRxLevMin\tsCellHandling\n\
+                      ERBS001\tCell1\t-140\tACTIVATED\n\
+                      ERBS001\tCell2\t-128\tDEACTIVATED\n\n2 instance(s)\n";
+        assert_eq!(detect_format(output), CmOutputFormat::Tabular);
+
+        let records = parse_get_output(output).unwrap();
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[2].mo_path, "MeContext=ERBS001,EUtranCellFDD=Cell2");
+        assert_eq!(records[2].value, CmValue::Integer(-128));
+    }
+
+    #[test]
+    fn test_tabular_id_attribute_is_not_a_key() {
+        let output = "EUtranCellFDD\nNodeId\tEUtranCellFDDId\tcellId\tqRxLevMin\n\
+                      ERBS001\tCell1\t11\t-140\n\n1 instance(s)\n";
+        let records = parse_tabular(output).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].mo_path, "MeContext=ERBS001,EUtranCellFDD=Cell1");
+        assert_eq!(records[0].parameter, "cellId");
+        assert_eq!(records[0].value, CmValue::Integer(11));
+
+        // Without a class line, lowercase `...Id` columns are attributes too
+        let output = "NodeId\tEUtranCellFDDId\tcellId\tqRxLevMin\nERBS001\tCell1\t11\t-140\n";
+        let records = parse_tabular(output).unwrap();
+        assert_eq!(records[0].parameter, "cellId");
+
+        let output = "EUtranCellFDD\nNodeId\tcellId\tqRxLevMin\nERBS001\t11\t-140\n";
+        assert!(matches!(parse_tabular(output), Err(ElexError::CmParse { line: 2, .. })));
+    }
+
+    #[test]
+    fn test_tabular_multiple_tables() {
+        let output = "EUtranCellFDD\n\
+                      NodeId\tEUtranCellFDDId\tqRxLevMin\n\
+                      ERBS001\tCell1\t-140\n\n\
+                      EUtranFreqRelation\n\
+                      NodeId\tEUtranCellFDDId\tEUtranFreqRelationId\tcellReselectionPriority\n\
+                      ERBS001\tCell1\t1800\t5\n\n\
+                      NodeId\tEUtranCellFDDId\tEUtranFreqRelationId\tcellReselectionPriority\tqOffsetFreq\n\
+                      ERBS002\tCell1\t2600\t3\t0\n\n3 instance(s)\n";
+        let records = parse_tabular(output).unwrap();
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0].mo_path, "MeContext=ERBS001,EUtranCellFDD=Cell1");
+        assert_eq!(records[1].mo_path, "MeContext=ERBS001,EUtranCellFDD=Cell1,EUtranFreqRelation=1800");
+        assert_eq!(records[1].parameter, "cellReselectionPriority");
+        assert_eq!(records[3].mo_path, "MeContext=ERBS002,EUtranCellFDD=Cell1,EUtranFreqRelation=2600");
+        assert_eq!(records[3].parameter, "qOffsetFreq");
+    }
+
+    #[test]
+    fn test_tabular_mo_column() {
+        let output = format!("MO\tqRxLevMin\n{}\t-140\n", CELL_FDN);
+        let records = parse_tabular(&output).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].mo_path, CELL_FDN);
+        assert_eq!(records[0].parameter, "qRxLevMin");
+    }
+
+    #[test]
+    fn test_instance_count_mismatch() {
+        let output = "NodeId\tEUtranCellFDDId\tqRxLevMin\nERBS001\tCell1\t-140\n\n2 instance(s)\n";
+        assert!(matches!(parse_tabular(output), Err(ElexError::CmParse { line: 4, .. })));
+    }
+
+    #[test]
+    fn test_parse_bulk_export() {
+        let dump = format!(
+            "create\nFDN : \"{}\"\nqRxLevMin : -140\n\ndelete\nFDN : \"{},UtranCellRelation=1\"\nisRemoveAllowed : true\n",
+            CELL_FDN, CELL_FDN
+        );
+        let records = parse_bulk_export(&dump).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].mo_path, CELL_FDN);
+    }
+
+    #[test]
+    fn test_error_output() {
+        let output = "Error 1010 : The MO does not exist\n";
+        assert!(parse_get_output(output).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_lookup_and_fill() {
+        let output = format!("FDN : {}\nhoA3Offset : 2\nanrMode : MONITOR\n", CELL_FDN);
+        let snapshot = ConfigSnapshot::from_records(parse_attribute_list(&output).unwrap());
+
+        assert_eq!(snapshot.resolve("EUtranCellFDD=Cell1"), Some(CELL_FDN));
+        assert_eq!(snapshot.get_f32("EUtranCellFDD=Cell1", "hoA3Offset"), Some(2.0));
+        assert_eq!(snapshot.get("EUtranCellFDD=Cell2", "hoA3Offset"), None);
+
+        let mut change = ParameterChange {
+            mo_path: "EUtranCellFDD=Cell1".to_string(),
+            parameter: "hoA3Offset".to_string(),
+            old_value: None,
+            new_value: 2.1,
+        };
+        assert!(snapshot.fill_old_value(&mut change));
+        assert_eq!(change.old_value, Some(2.0));
+
+        let mut param = Parameter::new(
+            "anrMode".to_string(),
+            "ENUM".to_string(),
+            SafeZone::new(0.0, 2.0, 0.0, 2.0, 100.0, 0),
+        );
+        assert!(snapshot.populate_parameter(&mut param, "EUtranCellFDD=Cell1"));
+        assert_eq!(param.current_value.as_deref(), Some("MONITOR"));
+    }
+
+    #[test]
+    fn test_snapshot_ambiguous_suffix() {
+        let snapshot = ConfigSnapshot::from_records(vec![
+            CmRecord {
+                mo_path: "MeContext=A,EUtranCellFDD=1".to_string(),
+                parameter: "x".to_string(),
+                value: CmValue::Integer(1),
+            },
+            CmRecord {
+                mo_path: "MeContext=B,EUtranCellFDD=1".to_string(),
+                parameter: "x".to_string(),
+                value: CmValue::Integer(2),
+            },
+        ]);
+        assert_eq!(snapshot.get("EUtranCellFDD=1", "x"), None);
+        assert_eq!(snapshot.get("MeContext=B,EUtranCellFDD=1", "x"), Some(&CmValue::Integer(2)));
+    }
+}
@@ -8,6 +8,10 @@
 //! ```text
 //! cmedit set <MO_Path> <Parameter>=<Value>
 //! cmedit get <MO_Path> <Parameter>
+//! cmedit get <Scope> <MoClass>.(<attr>,<attr>) --scopefilter (<filter>) -t
+//! cmedit create <MO_Path> <MoClass>Id=<Id>, <attr>=<value>
+//! cmedit delete <MO_Path> [-ALL]
+//! cmedit action <MO_Path> <action>.(<arg>=<value>)
 //! ```
 //!
 //! # Example MO Paths
@@ -15,11 +19,13 @@
 //! - `UtranCell=CellName-1` (LTE cell)
 //! - `NrCellDU=NrCellDU-1` (NR 5G cell DU)
 //! - `EnodebFunction=ENodeB-1` (eNodeB)
+//! - `SubNetwork=ONRM_ROOT_MO,MeContext=ERBS001,ManagedElement=1,ENodeBFunction=1,EUtranCellFDD=Cell1` (full FDN)
 //!
 //! # Safety Validation
 //!
 //! All generated commands are validated against:
 //! - Absolute bounds (hard reject)
+//! - Typed domain: enum literals, booleans, integer step (hard reject)
 //! - Safe bounds (warning)
 //! - Change limit percentage (warning)
 //! - Cooldown period (block)
@@ -50,10 +56,13 @@
 //! assert_eq!(cmd.command, "cmedit set UtranCell=CellName-1 lbActivationThreshold=55");
 //! ```
 
-use elex_core::{error::{Result, ElexError}, feature::Parameter};
-use elex_safety::{SafeZone, SafeZoneValidator, SafetyError, ValidationViolation, ValidationSeverity};
+use crate::cm_parser::ConfigSnapshot;
+use crate::undo::{CmeditBatch, UndoScript};
+use elex_core::{error::{Result, ElexError}, feature::{Parameter, ParameterDomain}};
+use elex_safety::{SafeZone, SafeZoneValidator, ValidationViolation, ValidationSeverity, RollbackCommand, ChangeSet, ChangeSetEntry};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 // ============================================================================
 // Command Types
@@ -84,6 +93,29 @@ pub struct CmeditCommand {
     pub is_safe: bool,
 }
 
+impl CmeditCommand {
+    /// Build a command; it is safe unless a violation is critical
+    fn new(
+        command: String,
+        command_type: CmeditType,
+        mo_path: &str,
+        parameter: &str,
+        value: Option<String>,
+        violations: Vec<ValidationViolation>,
+    ) -> Self {
+        let is_safe = !violations.iter().any(|v| v.severity == ValidationSeverity::Critical);
+        Self {
+            command,
+            command_type,
+            mo_path: mo_path.to_string(),
+            parameter: parameter.to_string(),
+            value,
+            violations,
+            is_safe,
+        }
+    }
+}
+
 /// Type of cmedit command
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CmeditType {
@@ -92,6 +124,15 @@ pub enum CmeditType {
 
     /// GET command - query parameter value
     Get,
+
+    /// CREATE command - create a new MO
+    Create,
+
+    /// DELETE command - remove an MO
+    Delete,
+
+    /// ACTION command - invoke an MO action
+    Action,
 }
 
 /// Parameter change request
@@ -110,6 +151,17 @@ pub struct ParameterChange {
     pub new_value: f32,
 }
 
+impl From<&ParameterChange> for ChangeSetEntry {
+    fn from(change: &ParameterChange) -> Self {
+        ChangeSetEntry {
+            mo_path: change.mo_path.clone(),
+            parameter: change.parameter.clone(),
+            old_value: change.old_value,
+            new_value: change.new_value,
+        }
+    }
+}
+
 /// Parameter query request
 #[derive(Clone, Debug)]
 pub struct ParameterQuery {
@@ -120,6 +172,61 @@ pub struct ParameterQuery {
     pub parameter: String,
 }
 
+/// Scoped or wildcard query over many MOs
+#[derive(Clone, Debug, Default)]
+pub struct MoQuery {
+    /// Node names (`;`-separated, `*` wildcards allowed) or an FDN pattern
+    pub scope: String,
+
+    /// MO class to select (e.g. "EUtranCellFDD")
+    pub mo_class: Option<String>,
+
+    /// Attributes to return (all if empty)
+    pub attributes: Vec<String>,
+
+    /// `--scopefilter` expression (e.g. "NetworkElement.neType==RadioNode")
+    pub scope_filter: Option<String>,
+
+    /// Tabular output (`-t`)
+    pub table: bool,
+}
+
+/// MO creation request
+#[derive(Clone, Debug)]
+pub struct MoCreate {
+    /// FDN of the MO to create
+    pub mo_path: String,
+
+    /// Initial attribute values as literals
+    pub attributes: Vec<(String, String)>,
+}
+
+/// MO action request
+#[derive(Clone, Debug)]
+pub struct MoAction {
+    /// FDN of the target MO
+    pub mo_path: String,
+
+    /// Action name (e.g. "restartUnit")
+    pub action: String,
+
+    /// Action arguments as literals
+    pub arguments: Vec<(String, String)>,
+
+    /// Operator confirmed a disruptive action; without it the command is unsafe
+    pub confirmed: bool,
+}
+
+/// Actions that interrupt traffic on the target MO
+pub const DISRUPTIVE_ACTIONS: &[&str] = &[
+    "restart",
+    "manualRestart",
+    "restartUnit",
+    "lock",
+    "shutdown",
+    "reset",
+];
+
 // ============================================================================
 // Generator Implementation
 // ============================================================================
@@ -154,6 +261,16 @@ impl CmeditGenerator {
         self.validator.add_constraint(param_name, zone);
     }
 
+    /// Add a typed domain for a parameter not in the hardcoded list
+    pub fn add_domain(&mut self, param_name: String, domain: ParameterDomain) {
+        self.validator.add_domain(param_name, domain);
+    }
+
+    /// Safe zone validator backing this generator
+    pub fn validator(&self) -> &SafeZoneValidator {
+        &self.validator
+    }
+
     /// Remove a custom safe zone constraint
     pub fn remove_safe_zone(&mut self, param_name: &str) -> bool {
         self.custom_zones.remove(param_name).is_some()
@@ -175,95 +292,16 @@ impl CmeditGenerator {
         // Validate parameter name
         self.validate_parameter_name(&change.parameter)?;
 
-        // Parse and validate new value
-        let new_value_str = self.format_value(change.new_value)?;
-
-        // Collect validation violations
-        let mut violations = Vec::new();
-        let mut is_safe = true;
-
-        // Check if we have a safe zone for this parameter
-        if let Some(zone) = self.get_safe_zone(&change.parameter) {
-            // Check absolute bounds
-            if !zone.is_within_absolute_bounds(change.new_value) {
-                is_safe = false;
-                if change.new_value > zone.absolute_max {
-                    violations.push(ValidationViolation {
-                        parameter: change.parameter.clone(),
-                        old_value: change.old_value.unwrap_or(0.0),
-                        new_value: change.new_value,
-                        violation_type: elex_safety::ViolationType::ExceedsAbsoluteMax,
-                        severity: ValidationSeverity::Critical,
-                        message: format!(
-                            "Value {} exceeds absolute maximum {}",
-                            change.new_value, zone.absolute_max
-                        ),
-                    });
-                } else {
-                    violations.push(ValidationViolation {
-                        parameter: change.parameter.clone(),
-                        old_value: change.old_value.unwrap_or(0.0),
-                        new_value: change.new_value,
-                        violation_type: elex_safety::ViolationType::BelowAbsoluteMin,
-                        severity: ValidationSeverity::Critical,
-                        message: format!(
-                            "Value {} below absolute minimum {}",
-                            change.new_value, zone.absolute_min
-                        ),
-                    });
-                }
-            }
-
-            // Check safe bounds (warning only)
-            if zone.is_within_absolute_bounds(change.new_value)
-                && !zone.is_within_safe_bounds(change.new_value)
-            {
-                violations.push(ValidationViolation {
-                    parameter: change.parameter.clone(),
-                    old_value: change.old_value.unwrap_or(0.0),
-                    new_value: change.new_value,
-                    violation_type: elex_safety::ViolationType::OutsideSafeBounds,
-                    severity: ValidationSeverity::Warning,
-                    message: format!(
-                        "Value {} outside safe range [{}, {}]",
-                        change.new_value, zone.safe_min, zone.safe_max
-                    ),
-                });
-            }
-
-            // Check change limit (if old value exists)
-            if let Some(old_val) = change.old_value {
-                if !zone.is_change_within_limit(old_val, change.new_value) {
-                    is_safe = false;
-                    let change_pct = ((change.new_value - old_val).abs() / old_val.abs()) * 100.0;
-                    violations.push(ValidationViolation {
-                        parameter: change.parameter.clone(),
-                        old_value: old_val,
-                        new_value: change.new_value,
-                        violation_type: elex_safety::ViolationType::ExceedsChangeLimit,
-                        severity: ValidationSeverity::Critical,
-                        message: format!(
-                            "Change of {:.1}% exceeds limit of {:.1}%",
-                            change_pct, zone.change_limit_percent
-                        ),
-                    });
-                }
-            }
+        // Run the shared safety validation path
+        let violations = self.validate_value_change(
+            &change.mo_path,
+            &change.parameter,
+            change.old_value,
+            change.new_value,
+        );
 
-            // Check cooldown - note: we can't check cooldown here since last_change is private
-            // The validation will happen when trying to apply the change via SafeZoneValidator
-            // For now, we skip cooldown check in command generation phase
-        } else {
-            // No safe zone defined - add info violation
-            violations.push(ValidationViolation {
-                parameter: change.parameter.clone(),
-                old_value: change.old_value.unwrap_or(0.0),
-                new_value: change.new_value,
-                violation_type: elex_safety::ViolationType::OutsideSafeBounds,
-                severity: ValidationSeverity::Info,
-                message: "No safe zone defined for this parameter".to_string(),
-            });
-        }
+        // Parse and validate new value
+        let new_value_str = self.format_domain_value(&change.parameter, change.new_value)?;
 
         // Generate command string
         let command = format!(
@@ -271,17 +309,58 @@ impl CmeditGenerator {
             change.mo_path, change.parameter, new_value_str
         );
 
-        Ok(CmeditCommand {
+        Ok(CmeditCommand::new(
             command,
-            command_type: CmeditType::Set,
-            mo_path: change.mo_path.clone(),
-            parameter: change.parameter.clone(),
-            value: Some(new_value_str),
+            CmeditType::Set,
+            &change.mo_path,
+            &change.parameter,
+            Some(new_value_str),
             violations,
-            is_safe,
+        ))
+    }
+
+    /// Generate a cmedit SET command from literal values
+    ///
+    /// Literals are parsed through the parameter's typed domain, so enum
+    /// parameters take their literal (e.g. `anrMode=ACTIVATED`) and booleans
+    /// take `true`/`false`. Illegal literals are rejected.
+    pub fn generate_set_literal_command(
+        &self,
+        mo_path: &str,
+        parameter: &str,
+        old_literal: Option<&str>,
+        new_literal: &str,
+    ) -> Result<CmeditCommand> {
+        let old_value = old_literal
+            .map(|literal| self.parse_literal(parameter, literal))
+            .transpose()?;
+        let new_value = self.parse_literal(parameter, new_literal)?;
+
+        self.generate_set_command(&ParameterChange {
+            mo_path: mo_path.to_string(),
+            parameter: parameter.to_string(),
+            old_value,
+            new_value,
         })
     }
 
+    /// Fill `old_value` of a change from parsed configuration
+    ///
+    /// Enum and boolean literals are encoded through the parameter's typed
+    /// domain. Returns false if the value is missing or cannot be encoded.
+    pub fn fill_old_value(&self, change: &mut ParameterChange, snapshot: &ConfigSnapshot) -> bool {
+        let Some(value) = snapshot.get(&change.mo_path, &change.parameter) else {
+            return false;
+        };
+        match self.parse_literal(&change.parameter, &value.to_string()) {
+            Ok(old_value) => {
+                change.old_value = Some(old_value);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
     /// Generate a cmedit GET command
     ///
     /// GET commands don't require validation as they only query values.
@@ -298,15 +377,190 @@ impl CmeditGenerator {
             query.mo_path, query.parameter
         );
 
-        Ok(CmeditCommand {
+        Ok(CmeditCommand::new(
             command,
-            command_type: CmeditType::Get,
-            mo_path: query.mo_path.clone(),
-            parameter: query.parameter.clone(),
-            value: None,
-            violations: Vec::new(),
-            is_safe: true,
-        })
+            CmeditType::Get,
+            &query.mo_path,
+            &query.parameter,
+            None,
+            Vec::new(),
+        ))
+    }
+
+    /// Generate a scoped or wildcard cmedit GET command
+    ///
+    /// Queries are read-only and always safe; an unfiltered network-wide
+    /// wildcard is flagged with a warning.
+    pub fn generate_scoped_get_command(&self, query: &MoQuery) -> Result<CmeditCommand> {
+        self.validate_scope(&query.scope)?;
+
+        let mut command = format!("cmedit get {}", query.scope);
+        if let Some(mo_class) = &query.mo_class {
+            self.validate_mo_class(mo_class)?;
+            command.push(' ');
+            command.push_str(mo_class);
+            for attribute in &query.attributes {
+                self.validate_parameter_name(attribute)?;
+            }
+            match query.attributes.len() {
+                0 => {}
+                1 => command.push_str(&format!(".{}", query.attributes[0])),
+                _ => command.push_str(&format!(".({})", query.attributes.join(","))),
+            }
+        } else if !query.attributes.is_empty() {
+            return Err(ElexError::ParameterValidation {
+                parameter: "attributes".to_string(),
+                value: query.attributes.join(","),
+                reason: "Attributes require an MO class".to_string(),
+            });
+        }
+
+        if let Some(filter) = &query.scope_filter {
+            self.validate_scope_filter(filter)?;
+            command.push_str(&format!(" --scopefilter ({})", filter));
+        }
+        if query.table {
+            command.push_str(" -t");
+        }
+
+        let mut violations = Vec::new();
+        if query.scope.trim() == "*" && query.scope_filter.is_none() {
+            violations.push(ValidationViolation {
+                parameter: query.attributes.join(","),
+                old_value: 0.0,
+                new_value: 0.0,
+                violation_type: elex_safety::ViolationType::OutsideSafeBounds,
+                severity: ValidationSeverity::Warning,
+                message: "Unfiltered wildcard query over the whole network".to_string(),
+            });
+        }
+
+        Ok(CmeditCommand::new(
+            command,
+            CmeditType::Get,
+            &query.scope,
+            &query.attributes.join(","),
+            None,
+            violations,
+        ))
+    }
+
+    /// Generate a cmedit CREATE command
+    ///
+    /// Every attribute goes through the same validation as a SET (domain,
+    /// absolute and safe bounds). Attributes without a safe zone or domain
+    /// are passed through as literals.
+    pub fn generate_create_command(&self, create: &MoCreate) -> Result<CmeditCommand> {
+        let fdn = Fdn::parse(&create.mo_path)?;
+        let (mo_class, mo_id) = fdn.leaf().ok_or_else(|| ElexError::ParameterValidation {
+            parameter: "mo_path".to_string(),
+            value: create.mo_path.clone(),
+            reason: "MO path cannot be empty".to_string(),
+        })?;
+
+        let mut violations = Vec::new();
+        let mut assignments = vec![format!("{}Id={}", mo_class, mo_id)];
+        for (name, literal) in &create.attributes {
+            self.validate_parameter_name(name)?;
+            let typed = self.validator.get_domain(name).is_some()
+                || self.get_safe_zone(&create.mo_path, name).is_some();
+            if typed {
+                let value = self.parse_literal(name, literal)?;
+                violations.extend(self.validate_value_change(&create.mo_path, name, None, value));
+                assignments.push(format!("{}={}", name, self.format_domain_value(name, value)?));
+            } else {
+                violations.push(ValidationViolation {
+                    parameter: name.clone(),
+                    old_value: 0.0,
+                    new_value: 0.0,
+                    violation_type: elex_safety::ViolationType::OutsideSafeBounds,
+                    severity: ValidationSeverity::Info,
+                    message: "No safe zone defined for this parameter".to_string(),
+                });
+                self.validate_literal(name, literal)?;
+                assignments.push(format!("{}={}", name, literal));
+            }
+        }
+
+        let value = assignments.join(", ");
+        Ok(CmeditCommand::new(
+            format!("cmedit create {} {}", fdn, value),
+            CmeditType::Create,
+            &create.mo_path,
+            "",
+            Some(value),
+            violations,
+        ))
+    }
+
+    /// Generate a cmedit DELETE command
+    ///
+    /// `include_children` adds `-ALL`, deleting the whole subtree. Deletes
+    /// lose the MO's configuration, so they are blocked (critical) unless
+    /// `confirmed` is set, in which case they only carry a warning.
+    pub fn generate_delete_command(
+        &self,
+        mo_path: &str,
+        include_children: bool,
+        confirmed: bool,
+    ) -> Result<CmeditCommand> {
+        let fdn = Fdn::parse(mo_path)?;
+
+        let mut command = format!("cmedit delete {}", fdn);
+        if include_children {
+            command.push_str(" -ALL");
+        }
+
+        let violations = vec![ValidationViolation {
+            parameter: String::new(),
+            old_value: 0.0,
+            new_value: 0.0,
+            violation_type: elex_safety::ViolationType::DestructiveOperation,
+            severity: Self::destructive_severity(confirmed),
+            message: if include_children {
+                format!("Deletes {} and all children", fdn)
+            } else {
+                format!("Deletes {}", fdn)
+            },
+        }];
+
+        Ok(CmeditCommand::new(command, CmeditType::Delete, mo_path, "", None, violations))
+    }
+
+    /// Generate a cmedit ACTION command
+    ///
+    /// Actions in `DISRUPTIVE_ACTIONS` are blocked (critical) unless the
+    /// request is `confirmed`, in which case they only carry a warning.
+    pub fn generate_action_command(&self, action: &MoAction) -> Result<CmeditCommand> {
+        let fdn = Fdn::parse(&action.mo_path)?;
+        self.validate_parameter_name(&action.action)?;
+
+        let mut arguments = Vec::new();
+        for (name, literal) in &action.arguments {
+            self.validate_parameter_name(name)?;
+            self.validate_literal(name, literal)?;
+            arguments.push(format!("{}={}", name, literal));
+        }
+
+        let mut command = format!("cmedit action {} {}", fdn, action.action);
+        let value = (!arguments.is_empty()).then(|| arguments.join(", "));
+        if let Some(args) = &value {
+            command.push_str(&format!(".({})", args));
+        }
+
+        let mut violations = Vec::new();
+        if DISRUPTIVE_ACTIONS.contains(&action.action.as_str()) {
+            violations.push(ValidationViolation {
+                parameter: action.action.clone(),
+                old_value: 0.0,
+                new_value: 0.0,
+                violation_type: elex_safety::ViolationType::DestructiveOperation,
+                severity: Self::destructive_severity(action.confirmed),
+                message: format!("Action '{}' interrupts traffic on {}", action.action, fdn),
+            });
+        }
+
+        Ok(CmeditCommand::new(command, CmeditType::Action, &action.mo_path, &action.action, value, violations))
     }
 
     /// Generate multiple SET commands with batch validation
@@ -326,6 +580,71 @@ impl CmeditGenerator {
         Ok(commands)
     }
 
+    /// Generate an ordered, all-or-nothing batch for a change set
+    ///
+    /// The change set is validated as one unit; if any entry or
+    /// cross-parameter invariant fails, no commands are produced. Commands
+    /// are emitted in an order that keeps invariants satisfied after each
+    /// individual command.
+    pub fn generate_change_set_commands(&self, set: &ChangeSet) -> Result<Vec<CmeditCommand>> {
+        self.ordered_changes(set)?
+            .iter()
+            .map(|change| self.generate_set_command(change))
+            .collect()
+    }
+
+    /// Generate a batch of SET commands paired with its undo script
+    ///
+    /// Every change with a known `old_value` gets an inverse SET command,
+    /// and the inverses run in reverse order so the undo script unwinds the
+    /// batch one step at a time. Changes without an old value are listed
+    /// as irreversible in the script header.
+    pub fn generate_batch(&self, batch_id: &str, changes: &[ParameterChange]) -> Result<CmeditBatch> {
+        let forward = self.generate_batch_commands(changes)?;
+        let undo = self.generate_undo_script(batch_id, changes, &forward)?;
+        Ok(CmeditBatch {
+            batch_id: batch_id.to_string(),
+            forward,
+            undo,
+        })
+    }
+
+    /// Generate an ordered change set batch paired with its undo script
+    ///
+    /// Reversing an invariant-preserving order keeps the invariants
+    /// satisfied at every step of the undo script as well.
+    pub fn generate_change_set_batch(&self, set: &ChangeSet) -> Result<CmeditBatch> {
+        let changes = self.ordered_changes(set)?;
+        let forward = self.generate_batch_commands(&changes)?;
+        let undo = self.generate_undo_script(&set.id, &changes, &forward)?;
+        Ok(CmeditBatch {
+            batch_id: set.id.clone(),
+            forward,
+            undo,
+        })
+    }
+
+    /// Generate SET commands reverting checkpointed changes
+    ///
+    /// Rollbacks restore a previously applied value, so the change limit
+    /// is not enforced; absolute bounds are still validated.
+    pub fn generate_rollback_commands(
+        &self,
+        rollbacks: &[RollbackCommand],
+    ) -> Result<Vec<CmeditCommand>> {
+        rollbacks
+            .iter()
+            .map(|rollback| {
+                self.generate_set_command(&ParameterChange {
+                    mo_path: rollback.mo_path.clone(),
+                    parameter: rollback.parameter.clone(),
+                    old_value: None,
+                    new_value: rollback.value,
+                })
+            })
+            .collect()
+    }
+
     /// Record parameter changes (updates cooldown timers)
     pub fn record_changes(&mut self, param_names: &[&str]) {
         self.validator.record_changes(param_names);
@@ -336,80 +655,177 @@ impl CmeditGenerator {
         self.validator.clear_cooldown(param_name);
     }
 
-    /// Get safe zone for a parameter
-    fn get_safe_zone(&self, param_name: &str) -> Option<&SafeZone> {
-        // Check hardcoded constraints first (via validator)
-        self.validator.get_constraint(param_name)
-    }
-
-    /// Validate MO path format
-    fn validate_mo_path(&self, mo_path: &str) -> Result<()> {
-        if mo_path.is_empty() {
-            return Err(ElexError::ParameterValidation {
-                parameter: "mo_path".to_string(),
-                value: mo_path.to_string(),
-                reason: "MO path cannot be empty".to_string(),
+    /// Validate a change set and return its changes in execution order
+    fn ordered_changes(&self, set: &ChangeSet) -> Result<Vec<ParameterChange>> {
+        let report = self.validator.validate_change_set(set);
+        if !report.approved {
+            let reasons: Vec<String> = report.violations.iter()
+                .filter(|v| v.severity == ValidationSeverity::Critical)
+                .map(|v| format!("{}: {}", v.parameter, v.message))
+                .collect();
+            return Err(ElexError::OptimizationBlocked {
+                rule: format!("change set '{}' rejected: {}", set.id, reasons.join("; ")),
             });
         }
 
-        // Check for valid MO path format: <MOClass>=<MOId>
-        // Examples: "UtranCell=CellName-1", "NrCellDU=NrCellDU-1"
-        if !mo_path.contains('=') {
-            return Err(ElexError::ParameterValidation {
-                parameter: "mo_path".to_string(),
-                value: mo_path.to_string(),
-                reason: "MO path must contain '=' separator".to_string(),
-            });
+        Ok(report.order.iter()
+            .map(|&i| {
+                let entry = &set.entries()[i];
+                ParameterChange {
+                    mo_path: entry.mo_path.clone(),
+                    parameter: entry.parameter.clone(),
+                    old_value: entry.old_value,
+                    new_value: entry.new_value,
+                }
+            })
+            .collect())
+    }
+
+    /// Build the undo script for forward commands generated from `changes`
+    fn generate_undo_script(
+        &self,
+        batch_id: &str,
+        changes: &[ParameterChange],
+        forward: &[CmeditCommand],
+    ) -> Result<UndoScript> {
+        let mut commands = Vec::new();
+        let mut irreversible = Vec::new();
+
+        for (change, command) in changes.iter().zip(forward).rev() {
+            match change.old_value {
+                Some(old_value) => commands.push(self.generate_inverse_command(change, old_value)?),
+                None => irreversible.push(command.command.clone()),
+            }
         }
 
-        let parts: Vec<&str> = mo_path.split('=').collect();
-        if parts.len() != 2 {
-            return Err(ElexError::ParameterValidation {
-                parameter: "mo_path".to_string(),
-                value: mo_path.to_string(),
-                reason: "MO path must have format '<MOClass>=<MOId>'".to_string(),
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        Ok(UndoScript::new(batch_id, created_at, commands, irreversible))
+    }
+
+    /// SET command restoring the value a change replaces
+    ///
+    /// Like rollbacks, the inverse restores a previously applied value, so
+    /// the change limit is not enforced; absolute bounds, the typed domain
+    /// and enum transitions (from the new value back to the old) are.
+    fn generate_inverse_command(&self, change: &ParameterChange, old_value: f32) -> Result<CmeditCommand> {
+        let mut command = self.generate_set_command(&ParameterChange {
+            mo_path: change.mo_path.clone(),
+            parameter: change.parameter.clone(),
+            old_value: None,
+            new_value: old_value,
+        })?;
+
+        let transition = self.validator.get_domain(&change.parameter)
+            .map(|d| d.check_transition(change.new_value, old_value));
+        if let Some(Err(reason)) = transition {
+            command.violations.push(ValidationViolation {
+                parameter: change.parameter.clone(),
+                old_value: change.new_value,
+                new_value: old_value,
+                violation_type: elex_safety::ViolationType::InvalidDomainValue,
+                severity: ValidationSeverity::Critical,
+                message: reason,
             });
+            command.is_safe = false;
         }
 
-        let mo_class = parts[0];
-        let mo_id = parts[1];
+        Ok(command)
+    }
+
+    /// Get safe zone for a parameter on an MO
+    fn get_safe_zone(&self, mo_path: &str, param_name: &str) -> Option<&SafeZone> {
+        // MO-class scoped constraints first, then unscoped (via validator)
+        self.validator.get_constraint_for(mo_path, param_name)
+    }
 
-        if mo_class.is_empty() {
+    /// Validate MO path format (flat `Class=Id` or full FDN, no wildcards)
+    fn validate_mo_path(&self, mo_path: &str) -> Result<()> {
+        Fdn::parse(mo_path).map(|_| ())
+    }
+
+    /// Validate a query scope: node names or an FDN pattern, wildcards allowed
+    fn validate_scope(&self, scope: &str) -> Result<()> {
+        let scope = scope.trim();
+        if scope.contains('=') {
+            return Fdn::parse_pattern(scope).map(|_| ());
+        }
+        let valid = !scope.is_empty()
+            && scope.split(';').all(|node| {
+                !node.is_empty() && node.chars().all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '*'))
+            });
+        if !valid {
             return Err(ElexError::ParameterValidation {
-                parameter: "mo_path".to_string(),
-                value: mo_path.to_string(),
-                reason: "MO class cannot be empty".to_string(),
+                parameter: "scope".to_string(),
+                value: scope.to_string(),
+                reason: "Scope must be node names, '*' wildcards or an FDN".to_string(),
             });
         }
+        Ok(())
+    }
 
-        if mo_id.is_empty() {
+    /// Validate an MO class name
+    fn validate_mo_class(&self, mo_class: &str) -> Result<()> {
+        if mo_class.is_empty() || !mo_class.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.') {
             return Err(ElexError::ParameterValidation {
-                parameter: "mo_path".to_string(),
-                value: mo_path.to_string(),
-                reason: "MO ID cannot be empty".to_string(),
+                parameter: "mo_class".to_string(),
+                value: mo_class.to_string(),
+                reason: "MO class contains invalid characters".to_string(),
             });
         }
+        Ok(())
+    }
 
-        // Check for valid characters (alphanumeric, dash, underscore)
-        if !mo_class.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.') {
+    /// Validate a `--scopefilter` expression
+    fn validate_scope_filter(&self, filter: &str) -> Result<()> {
+        let balanced = filter.chars().try_fold(0i32, |depth, c| {
+            let depth = match c {
+                '(' => depth + 1,
+                ')' => depth - 1,
+                _ => depth,
+            };
+            (depth >= 0).then_some(depth)
+        }) == Some(0);
+        let forbidden = filter.chars().any(|c| matches!(c, ';' | '`' | '$' | '\n' | '\r'));
+        if filter.trim().is_empty() || !balanced || forbidden {
             return Err(ElexError::ParameterValidation {
-                parameter: "mo_path".to_string(),
-                value: mo_path.to_string(),
-                reason: "MO class contains invalid characters".to_string(),
+                parameter: "scope_filter".to_string(),
+                value: filter.to_string(),
+                reason: "Scope filter is empty, unbalanced or contains invalid characters".to_string(),
             });
         }
+        Ok(())
+    }
 
-        if !mo_id.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+    /// Validate an untyped literal written verbatim into a command
+    ///
+    /// Rejects separators and quoting characters that would let the value
+    /// terminate the assignment or inject further attributes/commands.
+    fn validate_literal(&self, param_name: &str, literal: &str) -> Result<()> {
+        let forbidden = literal
+            .chars()
+            .any(|c| matches!(c, ';' | ',' | '(' | ')' | '"' | '\'' | '`' | '$' | '\\') || c.is_control());
+        if literal.is_empty() || forbidden {
             return Err(ElexError::ParameterValidation {
-                parameter: "mo_path".to_string(),
-                value: mo_path.to_string(),
-                reason: "MO ID contains invalid characters".to_string(),
+                parameter: param_name.to_string(),
+                value: literal.to_string(),
+                reason: "Literal is empty or contains invalid characters".to_string(),
             });
         }
-
         Ok(())
     }
 
+    /// Severity of a destructive operation: blocking unless confirmed
+    fn destructive_severity(confirmed: bool) -> ValidationSeverity {
+        if confirmed {
+            ValidationSeverity::Warning
+        } else {
+            ValidationSeverity::Critical
+        }
+    }
+
     /// Validate parameter name format
     fn validate_parameter_name(&self, param_name: &str) -> Result<()> {
         if param_name.is_empty() {
@@ -420,8 +836,8 @@ impl CmeditGenerator {
             });
         }
 
-        // Check for valid characters (alphanumeric, no spaces)
-        if !param_name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        // Check for valid characters ([A-Za-z0-9_], no spaces)
+        if !param_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
             return Err(ElexError::ParameterValidation {
                 parameter: "parameter".to_string(),
                 value: param_name.to_string(),
@@ -442,6 +858,143 @@ impl CmeditGenerator {
         }
     }
 
+    /// Shared safety validation for a single attribute value
+    ///
+    /// Used by set, create and rollback commands alike. Any critical
+    /// violation makes the resulting command unsafe.
+    fn validate_value_change(
+        &self,
+        mo_path: &str,
+        parameter: &str,
+        old_value: Option<f32>,
+        new_value: f32,
+    ) -> Vec<ValidationViolation> {
+        // Collect validation violations
+        let mut violations = Vec::new();
+
+        // Check typed domain (enum literal, boolean, integer step)
+        if let Err(reason) = self.validator.check_domain(parameter, old_value, new_value) {
+            violations.push(ValidationViolation {
+                parameter: parameter.to_string(),
+                old_value: old_value.unwrap_or(0.0),
+                new_value,
+                violation_type: elex_safety::ViolationType::InvalidDomainValue,
+                severity: ValidationSeverity::Critical,
+                message: reason,
+            });
+        }
+        let discrete = self.validator.get_domain(parameter).is_some_and(|d| d.is_discrete());
+
+        // Check if we have a safe zone for this parameter
+        if let Some(zone) = self.get_safe_zone(mo_path, parameter) {
+            // Check absolute bounds
+            if !zone.is_within_absolute_bounds(new_value) {
+                if new_value > zone.absolute_max {
+                    violations.push(ValidationViolation {
+                        parameter: parameter.to_string(),
+                        old_value: old_value.unwrap_or(0.0),
+                        new_value,
+                        violation_type: elex_safety::ViolationType::ExceedsAbsoluteMax,
+                        severity: ValidationSeverity::Critical,
+                        message: format!(
+                            "Value {} exceeds absolute maximum {}",
+                            new_value, zone.absolute_max
+                        ),
+                    });
+                } else {
+                    violations.push(ValidationViolation {
+                        parameter: parameter.to_string(),
+                        old_value: old_value.unwrap_or(0.0),
+                        new_value,
+                        violation_type: elex_safety::ViolationType::BelowAbsoluteMin,
+                        severity: ValidationSeverity::Critical,
+                        message: format!(
+                            "Value {} below absolute minimum {}",
+                            new_value, zone.absolute_min
+                        ),
+                    });
+                }
+            }
+
+            // Check safe bounds (warning only)
+            if !discrete
+                && zone.is_within_absolute_bounds(new_value)
+                && !zone.is_within_safe_bounds(new_value)
+            {
+                violations.push(ValidationViolation {
+                    parameter: parameter.to_string(),
+                    old_value: old_value.unwrap_or(0.0),
+                    new_value,
+                    violation_type: elex_safety::ViolationType::OutsideSafeBounds,
+                    severity: ValidationSeverity::Warning,
+                    message: format!(
+                        "Value {} outside safe range [{}, {}]",
+                        new_value, zone.safe_min, zone.safe_max
+                    ),
+                });
+            }
+
+            // Check change limit (if old value exists, continuous domains only)
+            if let Some(old_val) = old_value.filter(|_| !discrete) {
+                if !zone.is_change_within_limit(old_val, new_value) {
+                    let change_pct = ((new_value - old_val).abs() / old_val.abs()) * 100.0;
+                    violations.push(ValidationViolation {
+                        parameter: parameter.to_string(),
+                        old_value: old_val,
+                        new_value,
+                        violation_type: elex_safety::ViolationType::ExceedsChangeLimit,
+                        severity: ValidationSeverity::Critical,
+                        message: format!(
+                            "Change of {:.1}% exceeds limit of {:.1}%",
+                            change_pct, zone.change_limit_percent
+                        ),
+                    });
+                }
+            }
+
+            // Check cooldown - note: we can't check cooldown here since last_change is private
+            // The validation will happen when trying to apply the change via SafeZoneValidator
+            // For now, we skip cooldown check in command generation phase
+        } else {
+            // No safe zone defined - add info violation
+            violations.push(ValidationViolation {
+                parameter: parameter.to_string(),
+                old_value: old_value.unwrap_or(0.0),
+                new_value,
+                violation_type: elex_safety::ViolationType::OutsideSafeBounds,
+                severity: ValidationSeverity::Info,
+                message: "No safe zone defined for this parameter".to_string(),
+            });
+        }
+
+        violations
+    }
+
+    /// Format value using the parameter's typed domain (if any)
+    pub(crate) fn format_domain_value(&self, param_name: &str, value: f32) -> Result<String> {
+        let origin = self.validator.get_constraint(param_name).map(|z| z.absolute_min).unwrap_or(0.0);
+        match self.validator.get_domain(param_name).map(|d| d.render(value, origin)) {
+            Some(Ok(rendered)) => Ok(rendered),
+            _ => self.format_value(value),
+        }
+    }
+
+    /// Parse a literal value through the parameter's typed domain
+    fn parse_literal(&self, param_name: &str, literal: &str) -> Result<f32> {
+        let parsed = match self.validator.get_domain(param_name) {
+            Some(domain) => domain.encode(literal),
+            None => literal
+                .trim()
+                .parse::<f32>()
+                .map_err(|_| format!("'{}' is not a valid number", literal)),
+        };
+        parsed.map_err(|reason| ElexError::ParameterValidation {
+            parameter: param_name.to_string(),
+            value: literal.to_string(),
+            reason,
+        })
+    }
+
     /// Get validation summary for a command
     pub fn get_validation_summary(&self, command: &CmeditCommand) -> String {
         if command.is_safe {
@@ -471,7 +1024,135 @@ impl Default for CmeditGenerator {
 // MO Path Parsing
 // ============================================================================
 
-/// Parse MO class from MO path
+/// Fully Distinguished Name of an MO
+///
+/// An ordered list of `Class=Id` pairs from the root, e.g.
+/// `SubNetwork=ONRM_ROOT_MO,MeContext=ERBS001,ManagedElement=1,ENodeBFunction=1,EUtranCellFDD=Cell1`.
+/// A flat `Class=Id` path is an FDN with a single element.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Fdn {
+    rdns: Vec<(String, String)>,
+}
+
+impl Fdn {
+    /// Create an empty FDN
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse an FDN (no wildcards)
+    pub fn parse(mo_path: &str) -> Result<Self> {
+        Self::parse_with(mo_path, false)
+    }
+
+    /// Parse an FDN pattern where IDs may contain `*` wildcards
+    pub fn parse_pattern(mo_path: &str) -> Result<Self> {
+        Self::parse_with(mo_path, true)
+    }
+
+    fn parse_with(mo_path: &str, allow_wildcards: bool) -> Result<Self> {
+        let invalid = |reason: &str| ElexError::ParameterValidation {
+            parameter: "mo_path".to_string(),
+            value: mo_path.to_string(),
+            reason: reason.to_string(),
+        };
+
+        if mo_path.is_empty() {
+            return Err(invalid("MO path cannot be empty"));
+        }
+        if !mo_path.contains('=') {
+            return Err(invalid("MO path must contain '=' separator"));
+        }
+
+        let mut rdns = Vec::new();
+        for rdn in mo_path.split(',') {
+            let parts: Vec<&str> = rdn.split('=').collect();
+            if parts.len() != 2 {
+                return Err(invalid("MO path must have format '<MOClass>=<MOId>'"));
+            }
+            let (mo_class, mo_id) = (parts[0].trim(), parts[1].trim());
+
+            if mo_class.is_empty() {
+                return Err(invalid("MO class cannot be empty"));
+            }
+            if mo_id.is_empty() {
+                return Err(invalid("MO ID cannot be empty"));
+            }
+            if !mo_class.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.') {
+                return Err(invalid("MO class contains invalid characters"));
+            }
+            let id_char = |c: char| c.is_alphanumeric() || c == '-' || c == '_' || (allow_wildcards && c == '*');
+            if !mo_id.chars().all(id_char) {
+                return Err(invalid("MO ID contains invalid characters"));
+            }
+            rdns.push((mo_class.to_string(), mo_id.to_string()));
+        }
+
+        Ok(Self { rdns })
+    }
+
+    /// Append a child `Class=Id`
+    pub fn child(mut self, mo_class: &str, mo_id: &str) -> Self {
+        self.rdns.push((mo_class.to_string(), mo_id.to_string()));
+        self
+    }
+
+    /// FDN of an LTE cell under an eNodeB
+    pub fn lte_cell(subnetwork: &str, me_context: &str, cell_class: &str, cell_id: &str) -> Self {
+        Self::new()
+            .child("SubNetwork", subnetwork)
+            .child("MeContext", me_context)
+            .child("ManagedElement", "1")
+            .child("ENodeBFunction", "1")
+            .child(cell_class, cell_id)
+    }
+
+    /// FDN of an NR DU cell under a gNodeB
+    pub fn nr_du_cell(subnetwork: &str, me_context: &str, cell_id: &str) -> Self {
+        Self::new()
+            .child("SubNetwork", subnetwork)
+            .child("MeContext", me_context)
+            .child("ManagedElement", "1")
+            .child("GNBDUFunction", "1")
+            .child("NRCellDU", cell_id)
+    }
+
+    /// `(class, id)` pairs from the root
+    pub fn rdns(&self) -> &[(String, String)] {
+        &self.rdns
+    }
+
+    /// Class and ID of the MO itself (last element)
+    pub fn leaf(&self) -> Option<(&str, &str)> {
+        self.rdns.last().map(|(c, i)| (c.as_str(), i.as_str()))
+    }
+
+    /// FDN of the parent MO
+    pub fn parent(&self) -> Option<Fdn> {
+        (self.rdns.len() > 1).then(|| Fdn {
+            rdns: self.rdns[..self.rdns.len() - 1].to_vec(),
+        })
+    }
+
+    /// ID of the first element with the given class
+    pub fn id_of(&self, mo_class: &str) -> Option<&str> {
+        self.rdns.iter().find(|(c, _)| c == mo_class).map(|(_, i)| i.as_str())
+    }
+
+    /// Check if any ID contains a wildcard
+    pub fn is_wildcard(&self) -> bool {
+        self.rdns.iter().any(|(_, id)| id.contains('*'))
+    }
+}
+
+impl std::fmt::Display for Fdn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rdns: Vec<String> = self.rdns.iter().map(|(c, i)| format!("{}={}", c, i)).collect();
+        write!(f, "{}", rdns.join(","))
+    }
+}
+
+/// Parse MO class from MO path (the last element of an FDN)
 ///
 /// # Example
 ///
@@ -480,10 +1161,10 @@ impl Default for CmeditGenerator {
 /// assert_eq!(mo_class, "UtranCell");
 /// ```
 pub fn parse_mo_class(mo_path: &str) -> Option<String> {
-    mo_path.split('=').next().map(|s| s.to_string())
+    mo_path.rsplit(',').next()?.split('=').next().map(|s| s.to_string())
 }
 
-/// Parse MO ID from MO path
+/// Parse MO ID from MO path (the last element of an FDN)
 ///
 /// # Example
 ///
@@ -492,7 +1173,7 @@ pub fn parse_mo_class(mo_path: &str) -> Option<String> {
 /// assert_eq!(mo_id, "CellName-1");
 /// ```
 pub fn parse_mo_id(mo_path: &str) -> Option<String> {
-    mo_path.split('=').nth(1).map(|s| s.to_string())
+    mo_path.rsplit(',').next()?.split('=').nth(1).map(|s| s.to_string())
 }
 
 /// Format MO path from components
@@ -759,6 +1440,300 @@ mod tests {
         assert!(commands[1].is_safe);
     }
 
+    /// Generator with a three-state enum that forbids OFF -> ACTIVE
+    fn mode_generator() -> CmeditGenerator {
+        let mut generator = CmeditGenerator::new();
+        generator.add_safe_zone("customMode".to_string(), SafeZone::new(0.0, 2.0, 0.0, 2.0, 5.0, 0));
+        generator.add_domain(
+            "customMode".to_string(),
+            ParameterDomain::enumerated(&["OFF", "MONITOR", "ACTIVE"]).forbid_transition("OFF", "ACTIVE"),
+        );
+        generator
+    }
+
+    #[test]
+    fn test_enum_literal_commands() {
+        let generator = mode_generator();
+
+        let cmd = generator
+            .generate_set_literal_command("EUtranCellFDD=Cell1", "anrMode", Some("DEACTIVATED"), "ACTIVATED")
+            .unwrap();
+        assert!(cmd.is_safe);
+        assert_eq!(cmd.command, "cmedit set EUtranCellFDD=Cell1 anrMode=ACTIVATED");
+
+        // Unknown literal
+        assert!(generator
+            .generate_set_literal_command("EUtranCellFDD=Cell1", "anrMode", None, "AUTO")
+            .is_err());
+
+        // Forbidden transition
+        let cmd = generator
+            .generate_set_literal_command("EUtranCellFDD=Cell1", "customMode", Some("OFF"), "ACTIVE")
+            .unwrap();
+        assert!(!cmd.is_safe);
+        assert!(cmd.violations.iter().any(|v| v.violation_type == elex_safety::ViolationType::InvalidDomainValue));
+    }
+
+    #[test]
+    fn test_boolean_and_integer_domains() {
+        let mut generator = CmeditGenerator::new();
+
+        let cmd = generator
+            .generate_set_literal_command("EUtranCellFDD=Cell1", "drxEnabled", Some("true"), "false")
+            .unwrap();
+        assert!(cmd.is_safe);
+        assert_eq!(cmd.value.as_deref(), Some("false"));
+
+        let change = ParameterChange {
+            mo_path: "EUtranCellFDD=Cell1".to_string(),
+            parameter: "mimoMinActiveTime".to_string(),
+            old_value: Some(120.0),
+            new_value: 125.0,
+        };
+        assert!(!generator.generate_set_command(&change).unwrap().is_safe);
+
+        generator.add_domain("customStep".to_string(), ParameterDomain::Integer { step: 4 });
+        generator.add_safe_zone("customStep".to_string(), SafeZone::new(0.0, 64.0, 0.0, 64.0, 50.0, 0));
+        let change = ParameterChange {
+            mo_path: "EUtranCellFDD=Cell1".to_string(),
+            parameter: "customStep".to_string(),
+            old_value: Some(16.0),
+            new_value: 18.0,
+        };
+        assert!(!generator.generate_set_command(&change).unwrap().is_safe);
+    }
+
+    #[test]
+    fn test_fill_old_value_from_snapshot() {
+        use crate::cm_parser::{parse_get_output, ConfigSnapshot};
+
+        let generator = CmeditGenerator::new();
+        let output = "NodeId\tEUtranCellFDDId\tanrMode\tdrxEnabled\n\
+                      ERBS001\tCell1\tACTIVATED\ttrue\n";
+        let snapshot = ConfigSnapshot::from_records(parse_get_output(output).unwrap());
+
+        let mut change = ParameterChange {
+            mo_path: "EUtranCellFDD=Cell1".to_string(),
+            parameter: "anrMode".to_string(),
+            old_value: None,
+            new_value: 0.0,
+        };
+        assert!(generator.fill_old_value(&mut change, &snapshot));
+        assert_eq!(change.old_value, Some(1.0));
+
+        change.parameter = "drxEnabled".to_string();
+        assert!(generator.fill_old_value(&mut change, &snapshot));
+        assert_eq!(change.old_value, Some(1.0));
+
+        change.parameter = "hoA3Offset".to_string();
+        assert!(!generator.fill_old_value(&mut change, &snapshot));
+    }
+
+    const CELL_FDN: &str =
+        "SubNetwork=ONRM_ROOT_MO,MeContext=ERBS001,ManagedElement=1,ENodeBFunction=1,EUtranCellFDD=Cell1";
+
+    #[test]
+    fn test_fdn_hierarchy() {
+        let fdn = Fdn::lte_cell("ONRM_ROOT_MO", "ERBS001", "EUtranCellFDD", "Cell1");
+        assert_eq!(fdn.to_string(), CELL_FDN);
+        assert_eq!(Fdn::parse(CELL_FDN).unwrap(), fdn);
+        assert_eq!(fdn.leaf(), Some(("EUtranCellFDD", "Cell1")));
+        assert_eq!(fdn.id_of("MeContext"), Some("ERBS001"));
+        assert_eq!(fdn.parent().unwrap().leaf(), Some(("ENodeBFunction", "1")));
+        assert_eq!(parse_mo_class(CELL_FDN), Some("EUtranCellFDD".to_string()));
+        assert_eq!(parse_mo_id(CELL_FDN), Some("Cell1".to_string()));
+
+        assert!(Fdn::parse("MeContext=ERBS*,EUtranCellFDD=1").is_err());
+        assert!(Fdn::parse_pattern("MeContext=ERBS*,EUtranCellFDD=1").unwrap().is_wildcard());
+        assert!(Fdn::parse("MeContext=ERBS001,,EUtranCellFDD=1").is_err());
+    }
+
+    #[test]
+    fn test_set_command_with_fdn() {
+        let generator = CmeditGenerator::new();
+        let change = ParameterChange {
+            mo_path: CELL_FDN.to_string(),
+            parameter: "hoA3Offset".to_string(),
+            old_value: Some(2.0),
+            new_value: 2.05,
+        };
+        let cmd = generator.generate_set_command(&change).unwrap();
+        assert!(cmd.is_safe);
+        assert_eq!(cmd.command, format!("cmedit set {} hoA3Offset=2.05", CELL_FDN));
+    }
+
+    #[test]
+    fn test_scoped_get_command() {
+        let generator = CmeditGenerator::new();
+        let query = MoQuery {
+            scope: "ERBS*".to_string(),
+            mo_class: Some("EUtranCellFDD".to_string()),
+            attributes: vec!["qRxLevMin".to_string(), "anrMode".to_string()],
+            scope_filter: Some("NetworkElement.neType==RadioNode".to_string()),
+            table: true,
+        };
+        let cmd = generator.generate_scoped_get_command(&query).unwrap();
+        assert_eq!(
+            cmd.command,
+            "cmedit get ERBS* EUtranCellFDD.(qRxLevMin,anrMode) --scopefilter (NetworkElement.neType==RadioNode) -t"
+        );
+        assert!(cmd.is_safe);
+        assert!(cmd.violations.is_empty());
+
+        let unfiltered = MoQuery {
+            scope: "*".to_string(),
+            mo_class: Some("EUtranCellFDD".to_string()),
+            attributes: vec!["qRxLevMin".to_string()],
+            ..MoQuery::default()
+        };
+        let cmd = generator.generate_scoped_get_command(&unfiltered).unwrap();
+        assert_eq!(cmd.command, "cmedit get * EUtranCellFDD.qRxLevMin");
+        assert_eq!(cmd.violations[0].severity, ValidationSeverity::Warning);
+
+        let injected = MoQuery {
+            scope_filter: Some("x==1; cmedit delete *".to_string()),
+            ..unfiltered
+        };
+        assert!(generator.generate_scoped_get_command(&injected).is_err());
+    }
+
+    #[test]
+    fn test_create_command_validated() {
+        let generator = CmeditGenerator::new();
+        let create = MoCreate {
+            mo_path: CELL_FDN.to_string(),
+            attributes: vec![
+                ("anrMode".to_string(), "ACTIVATED".to_string()),
+                ("userLabel".to_string(), "Cell-1".to_string()),
+            ],
+        };
+        let cmd = generator.generate_create_command(&create).unwrap();
+        assert_eq!(cmd.command_type, CmeditType::Create);
+        assert_eq!(
+            cmd.command,
+            format!("cmedit create {} EUtranCellFDDId=Cell1, anrMode=ACTIVATED, userLabel=Cell-1", CELL_FDN)
+        );
+        assert!(cmd.is_safe);
+
+        // Same validation as set: absolute bounds are critical
+        let create = MoCreate {
+            mo_path: CELL_FDN.to_string(),
+            attributes: vec![("hoA3Offset".to_string(), "15".to_string())],
+        };
+        assert!(!generator.generate_create_command(&create).unwrap().is_safe);
+
+        let create = MoCreate {
+            mo_path: CELL_FDN.to_string(),
+            attributes: vec![("anrMode".to_string(), "AUTO".to_string())],
+        };
+        assert!(generator.generate_create_command(&create).is_err());
+
+        // Untyped literals cannot smuggle extra attributes or commands
+        for literal in ["Cell-1, administrativeState=LOCKED", "a;b", "a\nb", "\"x\""] {
+            let create = MoCreate {
+                mo_path: CELL_FDN.to_string(),
+                attributes: vec![("userLabel".to_string(), literal.to_string())],
+            };
+            assert!(generator.generate_create_command(&create).is_err());
+        }
+        let create = MoCreate {
+            mo_path: CELL_FDN.to_string(),
+            attributes: vec![("userLabél".to_string(), "x".to_string())],
+        };
+        assert!(generator.generate_create_command(&create).is_err());
+    }
+
+    #[test]
+    fn test_delete_and_action_commands() {
+        let generator = CmeditGenerator::new();
+
+        let cmd = generator.generate_delete_command(CELL_FDN, true, false).unwrap();
+        assert_eq!(cmd.command, format!("cmedit delete {} -ALL", CELL_FDN));
+        assert_eq!(cmd.violations[0].violation_type, elex_safety::ViolationType::DestructiveOperation);
+        assert!(!cmd.is_safe);
+        assert!(generator.generate_delete_command(CELL_FDN, false, true).unwrap().is_safe);
+        assert!(generator.generate_delete_command("MeContext=*", false, true).is_err());
+
+        let action = MoAction {
+            mo_path: CELL_FDN.to_string(),
+            action: "restartUnit".to_string(),
+            arguments: vec![("restartRank".to_string(), "RESTART_WARM".to_string())],
+            confirmed: false,
+        };
+        let cmd = generator.generate_action_command(&action).unwrap();
+        assert_eq!(cmd.command, format!("cmedit action {} restartUnit.(restartRank=RESTART_WARM)", CELL_FDN));
+        assert_eq!(cmd.violations.len(), 1);
+        assert!(!cmd.is_safe);
+        let confirmed = MoAction { confirmed: true, ..action.clone() };
+        assert!(generator.generate_action_command(&confirmed).unwrap().is_safe);
+
+        let injected = MoAction {
+            arguments: vec![("restartRank".to_string(), "RESTART_WARM,x=1".to_string())],
+            ..action
+        };
+        assert!(generator.generate_action_command(&injected).is_err());
+    }
+
+    #[test]
+    fn test_rollback_commands() {
+        let generator = CmeditGenerator::new();
+        let mut manager = elex_safety::RollbackManager::default();
+
+        let baseline = vec![("accessibility".to_string(), 99.0)];
+        let id = manager.checkpoint("UtranCell=CellName-1", "lbActivationThreshold", 50.0, 55.0, &baseline);
+
+        let degraded = vec![("accessibility".to_string(), 90.0)];
+        let rollbacks = manager.rollback_on_degradation(&degraded).unwrap();
+        assert_eq!(rollbacks[0].checkpoint_id, id);
+
+        let commands = generator.generate_rollback_commands(&rollbacks).unwrap();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].command, "cmedit set UtranCell=CellName-1 lbActivationThreshold=50");
+        assert_eq!(commands[0].command, rollbacks[0].to_cmedit());
+        assert!(commands[0].is_safe);
+    }
+
+    #[test]
+    fn test_change_set_commands_ordered() {
+        let generator = CmeditGenerator::new();
+
+        // Lowering the window: min must move before max
+        let changes = vec![
+            ParameterChange {
+                mo_path: "UtranCell=CellName-1".to_string(),
+                parameter: "lbMaxLoadOffset".to_string(),
+                old_value: Some(5.0),
+                new_value: 4.6,
+            },
+            ParameterChange {
+                mo_path: "UtranCell=CellName-1".to_string(),
+                parameter: "lbMinLoadOffset".to_string(),
+                old_value: Some(4.8),
+                new_value: 4.4,
+            },
+        ];
+        let mut set = ChangeSet::with_standard_invariants("CR-1");
+        for change in &changes {
+            set.add_change(change.into());
+        }
+
+        let commands = generator.generate_change_set_commands(&set).unwrap();
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].parameter, "lbMinLoadOffset");
+        assert_eq!(commands[1].parameter, "lbMaxLoadOffset");
+    }
+
+    #[test]
+    fn test_change_set_commands_rejected() {
+        let generator = CmeditGenerator::new();
+        let set = ChangeSet::with_standard_invariants("CR-2")
+            .with_change("UtranCell=CellName-1", "lbMinLoadOffset", Some(4.4), 4.6)
+            .with_change("UtranCell=CellName-1", "lbMaxLoadOffset", Some(5.0), 4.5);
+
+        let result = generator.generate_change_set_commands(&set);
+        assert!(result.unwrap_err().to_string().contains("CR-2"));
+    }
+
     #[test]
     fn test_value_formatting() {
         let generator = CmeditGenerator::new();
@@ -859,4 +1834,86 @@ mod tests {
         assert_eq!(cmd.command, "cmedit set NrCellDU=NrCellDU-1 mimoMode=4");
         assert!(cmd.is_safe);
     }
+
+    #[test]
+    fn test_generate_batch_undo_script() {
+        let generator = CmeditGenerator::new();
+        let changes = vec![
+            ParameterChange {
+                mo_path: "EUtranCellFDD=Cell1".to_string(),
+                parameter: "lbActivationThreshold".to_string(),
+                old_value: Some(70.0),
+                new_value: 75.0,
+            },
+            ParameterChange {
+                mo_path: "EUtranCellFDD=Cell1".to_string(),
+                parameter: "hoTriggerTime".to_string(),
+                old_value: Some(320.0),
+                new_value: 330.0,
+            },
+            ParameterChange {
+                mo_path: "EUtranCellFDD=Cell2".to_string(),
+                parameter: "hoTriggerTime".to_string(),
+                old_value: None,
+                new_value: 330.0,
+            },
+        ];
+
+        let batch = generator.generate_batch("CR-7", &changes).unwrap();
+        assert_eq!(batch.forward.len(), 3);
+
+        let undo: Vec<&str> = batch.undo.commands.iter().map(|c| c.command.as_str()).collect();
+        assert_eq!(undo, vec![
+            "cmedit set EUtranCellFDD=Cell1 hoTriggerTime=320",
+            "cmedit set EUtranCellFDD=Cell1 lbActivationThreshold=70",
+        ]);
+        assert_eq!(batch.undo.irreversible, vec!["cmedit set EUtranCellFDD=Cell2 hoTriggerTime=330"]);
+        assert!(!batch.undo.is_complete());
+        assert!(batch.undo.is_safe());
+        assert!(batch.undo.verify());
+        assert!(batch.forward_script().contains(&batch.undo.checksum));
+    }
+
+    #[test]
+    fn test_undo_forbidden_transition() {
+        let generator = mode_generator();
+        let change = ParameterChange {
+            mo_path: "EUtranCellFDD=Cell1".to_string(),
+            parameter: "customMode".to_string(),
+            old_value: Some(0.0),
+            new_value: 1.0,
+        };
+
+        let batch = generator.generate_batch("CR-8", &[change]).unwrap();
+        assert!(batch.undo.is_safe());
+        assert_eq!(batch.undo.commands[0].command, "cmedit set EUtranCellFDD=Cell1 customMode=OFF");
+
+        // ACTIVE -> OFF is allowed, but the inverse OFF -> ACTIVE is not
+        let change = ParameterChange {
+            mo_path: "EUtranCellFDD=Cell1".to_string(),
+            parameter: "customMode".to_string(),
+            old_value: Some(2.0),
+            new_value: 0.0,
+        };
+        let batch = generator.generate_batch("CR-9", &[change]).unwrap();
+        assert!(batch.is_safe());
+        assert!(!batch.undo.is_safe());
+    }
+
+    #[test]
+    fn test_change_set_batch_reverses_order() {
+        let generator = CmeditGenerator::new();
+        let set = ChangeSet::with_standard_invariants("CR-10")
+            .with_change("EUtranCellFDD=Cell1", "lbMaxLoadOffset", Some(5.0), 5.4)
+            .with_change("EUtranCellFDD=Cell1", "lbMinLoadOffset", Some(-5.0), -4.6);
+
+        let batch = generator.generate_change_set_batch(&set).unwrap();
+        let forward: Vec<&str> = batch.forward.iter().map(|c| c.parameter.as_str()).collect();
+        let undo: Vec<&str> = batch.undo.commands.iter().map(|c| c.parameter.as_str()).collect();
+
+        let mut reversed = forward.clone();
+        reversed.reverse();
+        assert_eq!(undo, reversed);
+        assert!(batch.undo.is_complete());
+    }
 }
@@ -84,6 +84,9 @@
 pub mod agent;
 pub mod query_handler;
 pub mod cmedit;
+pub mod cm_parser;
+pub mod planner;
+pub mod undo;
 
 // Re-export the main FeatureAgent type
 pub use agent::FeatureAgent;
@@ -106,12 +109,44 @@ pub use cmedit::{
     CmeditType,
     ParameterChange,
     ParameterQuery,
+    MoQuery,
+    MoCreate,
+    MoAction,
+    Fdn,
     MoClass,
     parse_mo_class,
     parse_mo_id,
     format_mo_path,
 };
 
+// Re-export cmedit output parser
+pub use cm_parser::{
+    CmRecord,
+    CmValue,
+    CmOutputFormat,
+    ConfigSnapshot,
+    parse_get_output,
+    parse_attribute_list,
+    parse_tabular,
+    parse_bulk_export,
+};
+
+// Re-export undo scripts
+pub use undo::{
+    CmeditBatch,
+    UndoScript,
+    verify_undo_script,
+};
+
+// Re-export dry-run planner
+pub use planner::{
+    ChangePlanner,
+    ChangePlan,
+    MoDiff,
+    AttributeDiff,
+    CooldownImpact,
+};
+
 // Re-export statistics
 pub use agent::AgentStats;
 
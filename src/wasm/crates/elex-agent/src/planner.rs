@@ -0,0 +1,668 @@
+//! Dry-Run Change Planner
+//!
+//! Previews a batch of parameter changes against the current configuration
+//! before anything is pushed to ENM. The plan contains the before/after diff
+//! per MO, every validation violation (per-parameter and cross-parameter),
+//! an aggregated risk level, the cooldowns the batch would start, and the
+//! suggested execution order. Plans export to JSON and Markdown so they can
+//! be attached to a change ticket.
+//!
+//! # Risk Scoring
+//!
+//! Each change is scored in `[0, 1]`:
+//! - 1.0 if it has a critical violation or the parameter is in cooldown
+//! - otherwise 0.1, plus 0.3 without a safe zone, 0.2 if the current value
+//!   is unknown, 0.2 for a warning, and up to 0.3 for the size of the change
+//!   relative to its change limit (a full step for enum/boolean changes)
+//!
+//! The plan score is the highest change score plus 0.01 for every further
+//! change, capped at 1.0, and maps to a level via `RiskLevel::from_score`.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let generator = CmeditGenerator::new();
+//! let snapshot = ConfigSnapshot::from_records(parse_get_output(&output)?);
+//!
+//! let plan = ChangePlanner::new(&generator).plan("CR-1042", &changes, &snapshot)?;
+//! if plan.approved {
+//!     attach_to_ticket(plan.to_markdown());
+//! }
+//! ```
+
+use crate::cm_parser::ConfigSnapshot;
+use crate::cmedit::{CmeditCommand, CmeditGenerator, ParameterChange};
+use elex_core::error::{ElexError, Result};
+use elex_core::types::RiskLevel;
+use elex_safety::{ChangeSet, ValidationSeverity, ValidationViolation, ViolationType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// ============================================================================
+// Plan Types
+// ============================================================================
+
+/// Before/after value of one attribute
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AttributeDiff {
+    /// Parameter name
+    pub parameter: String,
+
+    /// Current value (None if not in the snapshot)
+    pub before: Option<String>,
+
+    /// Value after the change
+    pub after: String,
+
+    /// Relative change in percent (continuous parameters with a known value)
+    pub change_percent: Option<f32>,
+
+    /// Risk score of this change (0.0-1.0)
+    pub risk_score: f32,
+}
+
+/// All attribute changes on one MO
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MoDiff {
+    /// Managed Object path
+    pub mo_path: String,
+
+    /// Changed attributes, in request order
+    pub attributes: Vec<AttributeDiff>,
+}
+
+/// Cooldown started (or blocking) for a changed parameter
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CooldownImpact {
+    /// Parameter name
+    pub parameter: String,
+
+    /// Cooldown started by applying the change
+    pub cooldown_seconds: u64,
+
+    /// Seconds left of a cooldown that is already running (blocks the change)
+    pub remaining_seconds: Option<u64>,
+}
+
+/// Dry-run result for a batch of parameter changes
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChangePlan {
+    /// Plan identifier (e.g. the change ticket)
+    pub plan_id: String,
+
+    /// Creation time (Unix seconds)
+    pub created_at: u64,
+
+    /// Version of the safe zone constraints used
+    pub constraints_version: String,
+
+    /// Hash of the safe zone constraints used
+    pub constraints_hash: String,
+
+    /// Whether the batch can be applied (no critical violations)
+    pub approved: bool,
+
+    /// Aggregated risk score (0.0-1.0)
+    pub risk_score: f32,
+
+    /// Aggregated risk level
+    pub risk_level: RiskLevel,
+
+    /// Before/after diff grouped by MO
+    pub diffs: Vec<MoDiff>,
+
+    /// All violations found
+    pub violations: Vec<ValidationViolation>,
+
+    /// Cooldowns triggered by the batch
+    pub cooldowns: Vec<CooldownImpact>,
+
+    /// Invariant-preserving order as indices into the changes (empty if rejected)
+    pub execution_order: Vec<usize>,
+
+    /// cmedit commands, in execution order if approved
+    pub commands: Vec<String>,
+}
+
+impl ChangePlan {
+    /// Number of attribute changes in the plan
+    pub fn change_count(&self) -> usize {
+        self.diffs.iter().map(|d| d.attributes.len()).sum()
+    }
+
+    /// Critical violations only
+    pub fn critical_violations(&self) -> impl Iterator<Item = &ValidationViolation> {
+        self.violations.iter().filter(|v| v.severity == ValidationSeverity::Critical)
+    }
+
+    /// Export as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| ElexError::Generic {
+            message: format!("failed to serialize plan: {}", e),
+        })
+    }
+
+    /// Export as a Markdown report for a change ticket
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+
+        md.push_str(&format!("# Change Plan: {}\n\n", self.plan_id));
+        md.push_str(&format!(
+            "- **Status:** {}\n",
+            if self.approved { "APPROVED" } else { "REJECTED" }
+        ));
+        md.push_str(&format!("- **Risk:** {:?} ({:.2})\n", self.risk_level, self.risk_score));
+        md.push_str(&format!(
+            "- **Changes:** {} on {} MO(s)\n",
+            self.change_count(),
+            self.diffs.len()
+        ));
+        md.push_str(&format!(
+            "- **Constraints:** {} ({})\n",
+            self.constraints_version, self.constraints_hash
+        ));
+
+        md.push_str("\n## Diff\n");
+        for diff in &self.diffs {
+            md.push_str(&format!("\n### {}\n\n", diff.mo_path));
+            md.push_str("| Parameter | Before | After | Change | Risk |\n");
+            md.push_str("|---|---|---|---|---|\n");
+            for attr in &diff.attributes {
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} | {:.2} |\n",
+                    cell(&attr.parameter),
+                    cell(attr.before.as_deref().unwrap_or("?")),
+                    cell(&attr.after),
+                    attr.change_percent
+                        .map(|p| format!("{:+.1}%", p))
+                        .unwrap_or_else(|| "-".to_string()),
+                    attr.risk_score
+                ));
+            }
+        }
+
+        md.push_str("\n## Violations\n\n");
+        if self.violations.is_empty() {
+            md.push_str("None\n");
+        } else {
+            md.push_str("| Severity | Parameter | Type | Message |\n");
+            md.push_str("|---|---|---|---|\n");
+            for v in &self.violations {
+                md.push_str(&format!(
+                    "| {:?} | {} | {:?} | {} |\n",
+                    v.severity,
+                    cell(&v.parameter),
+                    v.violation_type,
+                    cell(&v.message)
+                ));
+            }
+        }
+
+        md.push_str("\n## Cooldowns\n\n");
+        if self.cooldowns.is_empty() {
+            md.push_str("None\n");
+        } else {
+            md.push_str("| Parameter | Cooldown (s) | Remaining (s) |\n");
+            md.push_str("|---|---|---|\n");
+            for c in &self.cooldowns {
+                md.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    cell(&c.parameter),
+                    c.cooldown_seconds,
+                    c.remaining_seconds.map(|r| r.to_string()).unwrap_or_else(|| "-".to_string())
+                ));
+            }
+        }
+
+        md.push_str("\n## Execution Order\n\n");
+        if !self.approved {
+            md.push_str("Not available: plan rejected\n");
+        } else {
+            for (i, command) in self.commands.iter().enumerate() {
+                md.push_str(&format!("{}. `{}`\n", i + 1, command));
+            }
+        }
+
+        md
+    }
+}
+
+/// Escape a value for a Markdown table cell
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+// ============================================================================
+// Planner
+// ============================================================================
+
+/// Builds dry-run plans using a generator's safe zones and domains
+pub struct ChangePlanner<'a> {
+    generator: &'a CmeditGenerator,
+}
+
+impl<'a> ChangePlanner<'a> {
+    /// Create a planner for a generator
+    pub fn new(generator: &'a CmeditGenerator) -> Self {
+        Self { generator }
+    }
+
+    /// Plan a batch of changes against the current configuration
+    ///
+    /// Missing `old_value`s are filled from the snapshot, and the snapshot
+    /// also supplies unchanged parameters needed to evaluate invariants and
+    /// relational constraints on each MO. Nothing is applied or recorded.
+    /// Fails only if a change is malformed (invalid MO path or parameter).
+    pub fn plan(
+        &self,
+        plan_id: &str,
+        changes: &[ParameterChange],
+        snapshot: &ConfigSnapshot,
+    ) -> Result<ChangePlan> {
+        let validator = self.generator.validator();
+
+        let changes: Vec<ParameterChange> = changes
+            .iter()
+            .map(|change| {
+                let mut change = change.clone();
+                if change.old_value.is_none() {
+                    self.generator.fill_old_value(&mut change, snapshot);
+                }
+                change
+            })
+            .collect();
+
+        let commands = self.generator.generate_batch_commands(&changes)?;
+
+        // Commands keep the paths as given; everything keyed by MO uses FDNs
+        let changes = normalize_mo_paths(changes, snapshot);
+        let set = self.build_change_set(plan_id, &changes, snapshot);
+        let report = validator.validate_change_set(&set);
+
+        // Per-change checks come from the commands; the change set adds
+        // cooldowns and cross-parameter violations
+        let mut violations: Vec<ValidationViolation> = commands
+            .iter()
+            .flat_map(|c| c.violations.iter().cloned())
+            .collect();
+        violations.extend(report.violations.into_iter().filter(|v| {
+            matches!(
+                v.violation_type,
+                ViolationType::InCooldown
+                    | ViolationType::InvariantViolated
+                    | ViolationType::RelationalConstraint
+            )
+        }));
+
+        let approved = !violations.iter().any(|v| v.severity == ValidationSeverity::Critical);
+        let execution_order = if approved { report.order } else { Vec::new() };
+
+        let scores: Vec<f32> = changes
+            .iter()
+            .zip(&commands)
+            .map(|(change, command)| self.change_risk(change, command))
+            .collect();
+        let risk_score = if approved {
+            let max = scores.iter().cloned().fold(0.0f32, f32::max);
+            (max + 0.01 * scores.len().saturating_sub(1) as f32).min(1.0)
+        } else {
+            1.0
+        };
+
+        let ordered_commands: Vec<String> = if approved {
+            execution_order.iter().map(|&i| commands[i].command.clone()).collect()
+        } else {
+            commands.iter().map(|c| c.command.clone()).collect()
+        };
+
+        Ok(ChangePlan {
+            plan_id: plan_id.to_string(),
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            constraints_version: elex_safety::CONSTRAINTS_VERSION.to_string(),
+            constraints_hash: elex_safety::CONSTRAINTS_HASH.to_string(),
+            approved,
+            risk_score,
+            risk_level: RiskLevel::from_score(risk_score),
+            diffs: self.build_diffs(&changes, &commands, &scores, snapshot),
+            violations,
+            cooldowns: self.cooldowns(&changes),
+            execution_order,
+            commands: ordered_commands,
+        })
+    }
+
+    /// Change set with the standard invariants and snapshot context
+    fn build_change_set(
+        &self,
+        plan_id: &str,
+        changes: &[ParameterChange],
+        snapshot: &ConfigSnapshot,
+    ) -> ChangeSet {
+        let mut set = ChangeSet::with_standard_invariants(plan_id);
+        for change in changes {
+            set.add_change(change.into());
+        }
+
+        let mut context: Vec<String> = Vec::new();
+        let referenced = set
+            .invariants()
+            .iter()
+            .flat_map(|inv| inv.parameters())
+            .chain(self.generator.validator().relations().iter().flat_map(|r| r.parameters()));
+        for parameter in referenced {
+            if !context.iter().any(|p| p == parameter) {
+                context.push(parameter.to_string());
+            }
+        }
+
+        let mut seen = HashSet::new();
+        for mo_path in changes.iter().map(|c| c.mo_path.as_str()) {
+            if !seen.insert(mo_path) {
+                continue;
+            }
+            for parameter in &context {
+                if changes.iter().any(|c| c.mo_path == mo_path && &c.parameter == parameter) {
+                    continue;
+                }
+                let mut current = ParameterChange {
+                    mo_path: mo_path.to_string(),
+                    parameter: parameter.clone(),
+                    old_value: None,
+                    new_value: 0.0,
+                };
+                if self.generator.fill_old_value(&mut current, snapshot) {
+                    if let Some(value) = current.old_value {
+                        set.set_current_value(mo_path, parameter, value);
+                    }
+                }
+            }
+        }
+
+        set
+    }
+
+    /// Risk score of a single change (see module docs)
+    fn change_risk(&self, change: &ParameterChange, command: &CmeditCommand) -> f32 {
+        let validator = self.generator.validator();
+        if !command.is_safe || validator.cooldown_remaining(&change.parameter).is_some() {
+            return 1.0;
+        }
+
+        let mut score = 0.1;
+        if command.violations.iter().any(|v| v.severity == ValidationSeverity::Warning) {
+            score += 0.2;
+        }
+
+        let Some(zone) = validator.get_constraint_for(&change.mo_path, &change.parameter) else {
+            return score + 0.3;
+        };
+        let Some(old_value) = change.old_value else {
+            return score + 0.2;
+        };
+
+        let discrete = validator
+            .get_domain(&change.parameter)
+            .is_some_and(|d| d.is_discrete());
+        let magnitude = if discrete {
+            if old_value != change.new_value { 1.0 } else { 0.0 }
+        } else if old_value != 0.0 && zone.change_limit_percent > 0.0 {
+            let change_pct = ((change.new_value - old_value).abs() / old_value.abs()) * 100.0;
+            (change_pct / zone.change_limit_percent).min(1.0)
+        } else {
+            1.0
+        };
+
+        (score + 0.3 * magnitude).min(1.0)
+    }
+
+    /// Diff grouped by MO, MOs in order of first appearance
+    fn build_diffs(
+        &self,
+        changes: &[ParameterChange],
+        commands: &[CmeditCommand],
+        scores: &[f32],
+        snapshot: &ConfigSnapshot,
+    ) -> Vec<MoDiff> {
+        let validator = self.generator.validator();
+        let mut diffs: Vec<MoDiff> = Vec::new();
+
+        for ((change, command), &risk_score) in changes.iter().zip(commands).zip(scores) {
+            let before = match snapshot.get(&change.mo_path, &change.parameter) {
+                Some(value) if !value.is_null() => Some(value.to_string()),
+                _ => change
+                    .old_value
+                    .and_then(|v| self.generator.format_domain_value(&change.parameter, v).ok()),
+            };
+            let discrete = validator
+                .get_domain(&change.parameter)
+                .is_some_and(|d| d.is_discrete());
+            let change_percent = change
+                .old_value
+                .filter(|&old| !discrete && old != 0.0)
+                .map(|old| (change.new_value - old) / old.abs() * 100.0);
+
+            let attribute = AttributeDiff {
+                parameter: change.parameter.clone(),
+                before,
+                after: command.value.clone().unwrap_or_default(),
+                change_percent,
+                risk_score,
+            };
+
+            match diffs.iter_mut().find(|d| d.mo_path == change.mo_path) {
+                Some(diff) => diff.attributes.push(attribute),
+                None => diffs.push(MoDiff {
+                    mo_path: change.mo_path.clone(),
+                    attributes: vec![attribute],
+                }),
+            }
+        }
+
+        diffs
+    }
+
+    /// Cooldowns of the changed parameters, one entry per parameter
+    fn cooldowns(&self, changes: &[ParameterChange]) -> Vec<CooldownImpact> {
+        let validator = self.generator.validator();
+        let mut cooldowns: Vec<CooldownImpact> = Vec::new();
+
+        for change in changes {
+            if cooldowns.iter().any(|c| c.parameter == change.parameter) {
+                continue;
+            }
+            if let Some(zone) = validator.get_constraint_for(&change.mo_path, &change.parameter) {
+                if zone.cooldown_seconds > 0 {
+                    cooldowns.push(CooldownImpact {
+                        parameter: change.parameter.clone(),
+                        cooldown_seconds: zone.cooldown_seconds,
+                        remaining_seconds: validator.cooldown_remaining(&change.parameter),
+                    });
+                }
+            }
+        }
+
+        cooldowns
+    }
+}
+
+/// Rewrite MO paths to FDNs so an MO given both ways is planned once
+///
+/// Paths are resolved through the snapshot; a path it doesn't know is
+/// matched against the longest path in the batch ending with it, unless
+/// that is ambiguous.
+fn normalize_mo_paths(mut changes: Vec<ParameterChange>, snapshot: &ConfigSnapshot) -> Vec<ParameterChange> {
+    for change in &mut changes {
+        if let Some(fdn) = snapshot.resolve(&change.mo_path) {
+            change.mo_path = fdn.to_string();
+        }
+    }
+
+    let paths: Vec<String> = changes.iter().map(|c| c.mo_path.clone()).collect();
+    for change in &mut changes {
+        let suffix = format!(",{}", change.mo_path);
+        let mut matches: Vec<&String> = paths.iter().filter(|p| p.ends_with(&suffix)).collect();
+        matches.sort_by_key(|p| std::cmp::Reverse(p.len()));
+        matches.dedup();
+        if let Some(&longest) = matches.first() {
+            if matches.iter().all(|p| longest.ends_with(p.as_str())) {
+                change.mo_path = longest.clone();
+            }
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cm_parser::{CmRecord, CmValue};
+
+    const CELL: &str = "SubNetwork=ONRM_ROOT_MO,MeContext=ERBS001,ManagedElement=1,ENodeBFunction=1,EUtranCellFDD=Cell1";
+
+    fn snapshot(values: &[(&str, &str)]) -> ConfigSnapshot {
+        ConfigSnapshot::from_records(values.iter().map(|(parameter, value)| CmRecord {
+            mo_path: CELL.to_string(),
+            parameter: parameter.to_string(),
+            value: CmValue::parse(value),
+        }))
+    }
+
+    fn change(parameter: &str, new_value: f32) -> ParameterChange {
+        ParameterChange {
+            mo_path: "EUtranCellFDD=Cell1".to_string(),
+            parameter: parameter.to_string(),
+            old_value: None,
+            new_value,
+        }
+    }
+
+    #[test]
+    fn test_plan_approved_with_diff() {
+        let generator = CmeditGenerator::new();
+        let snapshot = snapshot(&[("lbActivationThreshold", "70"), ("anrMode", "DEACTIVATED")]);
+        let changes = vec![change("lbActivationThreshold", 75.0), change("anrMode", 1.0)];
+
+        let plan = ChangePlanner::new(&generator).plan("CR-1", &changes, &snapshot).unwrap();
+
+        assert!(plan.approved);
+        assert_eq!(plan.diffs.len(), 1);
+        let attrs = &plan.diffs[0].attributes;
+        assert_eq!(attrs[0].before.as_deref(), Some("70"));
+        assert_eq!(attrs[0].after, "75");
+        assert!((attrs[0].change_percent.unwrap() - 7.14).abs() < 0.01);
+        assert_eq!(attrs[1].before.as_deref(), Some("DEACTIVATED"));
+        assert_eq!(attrs[1].after, "ACTIVATED");
+        assert_eq!(attrs[1].change_percent, None);
+
+        assert_eq!(plan.cooldowns.len(), 2);
+        assert_eq!(plan.cooldowns[0].cooldown_seconds, 3600);
+        assert_eq!(plan.cooldowns[0].remaining_seconds, None);
+
+        assert_eq!(plan.execution_order.len(), 2);
+        assert_eq!(plan.commands.len(), 2);
+        assert_eq!(plan.risk_level, RiskLevel::Medium);
+    }
+
+    #[test]
+    fn test_plan_relational_violation_from_snapshot() {
+        let generator = CmeditGenerator::new();
+        let snapshot = snapshot(&[("anrAddThreshold", "-100"), ("anrRemoveThreshold", "-105")]);
+        let changes = vec![change("anrRemoveThreshold", -99.0)];
+
+        let plan = ChangePlanner::new(&generator).plan("CR-2", &changes, &snapshot).unwrap();
+
+        assert!(!plan.approved);
+        assert!(plan.execution_order.is_empty());
+        assert_eq!(plan.risk_level, RiskLevel::Critical);
+        assert!(plan
+            .critical_violations()
+            .any(|v| v.violation_type == ViolationType::RelationalConstraint));
+    }
+
+    #[test]
+    fn test_plan_rejects_large_change() {
+        let generator = CmeditGenerator::new();
+        let snapshot = snapshot(&[("lbActivationThreshold", "60")]);
+        let changes = vec![change("lbActivationThreshold", 85.0)];
+
+        let plan = ChangePlanner::new(&generator).plan("CR-3", &changes, &snapshot).unwrap();
+
+        assert!(!plan.approved);
+        assert_eq!(plan.risk_score, 1.0);
+        assert_eq!(plan.diffs[0].attributes[0].risk_score, 1.0);
+        assert!(plan
+            .critical_violations()
+            .any(|v| v.violation_type == ViolationType::ExceedsChangeLimit));
+    }
+
+    #[test]
+    fn test_plan_unknown_value_raises_risk() {
+        let generator = CmeditGenerator::new();
+        let known = snapshot(&[("hoTriggerTime", "320")]);
+        let changes = vec![change("hoTriggerTime", 330.0)];
+
+        let planner = ChangePlanner::new(&generator);
+        let with_value = planner.plan("CR-4", &changes, &known).unwrap();
+        let without_value = planner.plan("CR-4", &changes, &ConfigSnapshot::new()).unwrap();
+
+        assert_eq!(without_value.diffs[0].attributes[0].before, None);
+        assert!(without_value.risk_score > with_value.risk_score);
+    }
+
+    #[test]
+    fn test_plan_reports() {
+        let generator = CmeditGenerator::new();
+        let snapshot = snapshot(&[("lbActivationThreshold", "70")]);
+        let changes = vec![change("lbActivationThreshold", 75.0)];
+
+        let plan = ChangePlanner::new(&generator).plan("CR-5", &changes, &snapshot).unwrap();
+
+        let json = plan.to_json().unwrap();
+        let parsed: ChangePlan = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, plan);
+        assert!(json.contains("\"risk_level\": \"Low\""));
+
+        let md = plan.to_markdown();
+        assert!(md.starts_with("# Change Plan: CR-5"));
+        assert!(md.contains("| lbActivationThreshold | 70 | 75 | +7.1% |"));
+        assert!(md.contains("1. `cmedit set EUtranCellFDD=Cell1 lbActivationThreshold=75`"));
+    }
+
+    #[test]
+    fn test_plan_merges_fdn_and_suffix() {
+        let generator = CmeditGenerator::new();
+        let planner = ChangePlanner::new(&generator);
+        let changes = vec![
+            ParameterChange {
+                mo_path: CELL.to_string(),
+                ..change("lbActivationThreshold", 75.0)
+            },
+            change("hoTriggerTime", 330.0),
+        ];
+
+        // Resolved through the snapshot, or within the batch without one
+        let known = snapshot(&[("lbActivationThreshold", "70"), ("hoTriggerTime", "320")]);
+        for snapshot in [known, ConfigSnapshot::new()] {
+            let plan = planner.plan("CR-6", &changes, &snapshot).unwrap();
+            assert_eq!(plan.diffs.len(), 1);
+            assert_eq!(plan.diffs[0].mo_path, CELL);
+            assert_eq!(plan.diffs[0].attributes.len(), 2);
+            assert!(plan.commands.iter().any(|c| c.starts_with("cmedit set EUtranCellFDD=Cell1 ")));
+        }
+    }
+
+    #[test]
+    fn test_plan_markdown_escapes_pipes() {
+        let generator = CmeditGenerator::new();
+        let snapshot = snapshot(&[("lbActivationThreshold", "70|80")]);
+        let changes = vec![change("lbActivationThreshold", 75.0)];
+
+        let plan = ChangePlanner::new(&generator).plan("CR-7", &changes, &snapshot).unwrap();
+
+        assert!(plan.to_markdown().contains("| lbActivationThreshold | 70\\|80 | 75 |"));
+    }
+}
@@ -0,0 +1,231 @@
+//! Reverse-Apply Undo Scripts
+//!
+//! Every batch of SET commands is paired with an undo script that restores
+//! the previous values, with the inverse commands in reverse order. The undo
+//! script is a plain cmedit script protected by a SHA-256 checksum, so it can
+//! be stored next to the forward batch and run by ops without the agent.
+//!
+//! # Script Format
+//!
+//! ```text
+//! # cmedit undo script
+//! # batch: CR-1042
+//! # created: 1760000000
+//! # commands: 2
+//! # irreversible: cmedit set EUtranCellFDD=Cell2 hoA3Offset=2
+//! # sha256: 3f5a...
+//! cmedit set EUtranCellFDD=Cell1 hoHysteresis=2
+//! cmedit set EUtranCellFDD=Cell1 hoA3Offset=1
+//! ```
+//!
+//! The checksum covers the command lines only (each terminated by `\n`),
+//! so it can be checked by hand with `grep -v '^#' undo.txt | sha256sum`.
+
+use crate::cmedit::CmeditCommand;
+use elex_core::error::{ElexError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Header line carrying the checksum
+const CHECKSUM_PREFIX: &str = "# sha256: ";
+
+// ============================================================================
+// Undo Script
+// ============================================================================
+
+/// Inverse of a forward batch
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UndoScript {
+    /// Forward batch this script reverts
+    pub batch_id: String,
+
+    /// Creation time (Unix seconds)
+    pub created_at: u64,
+
+    /// Inverse SET commands, in reverse order of the forward batch
+    pub commands: Vec<CmeditCommand>,
+
+    /// Forward commands that cannot be reverted (old value unknown)
+    pub irreversible: Vec<String>,
+
+    /// SHA-256 of the command lines (lowercase hex)
+    pub checksum: String,
+}
+
+impl UndoScript {
+    /// Build a script and compute its checksum
+    pub fn new(
+        batch_id: &str,
+        created_at: u64,
+        commands: Vec<CmeditCommand>,
+        irreversible: Vec<String>,
+    ) -> Self {
+        let checksum = checksum(commands.iter().map(|c| c.command.as_str()));
+        Self {
+            batch_id: batch_id.to_string(),
+            created_at,
+            commands,
+            irreversible,
+            checksum,
+        }
+    }
+
+    /// Check if every forward command has an inverse
+    pub fn is_complete(&self) -> bool {
+        self.irreversible.is_empty()
+    }
+
+    /// Check if every inverse command passed validation
+    pub fn is_safe(&self) -> bool {
+        self.commands.iter().all(|c| c.is_safe)
+    }
+
+    /// Check that the checksum matches the commands
+    pub fn verify(&self) -> bool {
+        checksum(self.commands.iter().map(|c| c.command.as_str())) == self.checksum
+    }
+
+    /// Command lines, one per inverse command
+    pub fn body(&self) -> String {
+        self.commands.iter().map(|c| format!("{}\n", c.command)).collect()
+    }
+
+    /// Render as a standalone cmedit script with a checksummed header
+    pub fn to_script(&self) -> String {
+        let mut script = String::new();
+        script.push_str("# cmedit undo script\n");
+        script.push_str(&format!("# batch: {}\n", self.batch_id));
+        script.push_str(&format!("# created: {}\n", self.created_at));
+        script.push_str(&format!("# commands: {}\n", self.commands.len()));
+        for command in &self.irreversible {
+            script.push_str(&format!("# irreversible: {}\n", command));
+        }
+        script.push_str(&format!("{}{}\n", CHECKSUM_PREFIX, self.checksum));
+        script.push_str(&self.body());
+        script
+    }
+}
+
+/// Verify a rendered undo script and return its commands
+///
+/// Fails if the checksum header is missing or does not match the
+/// command lines, e.g. because the script was edited after generation.
+pub fn verify_undo_script(script: &str) -> Result<Vec<String>> {
+    let mut expected = None;
+    let mut commands = Vec::new();
+
+    for line in script.lines() {
+        if let Some(sum) = line.strip_prefix(CHECKSUM_PREFIX) {
+            expected = Some(sum.trim().to_string());
+        } else if !line.starts_with('#') && !line.trim().is_empty() {
+            commands.push(line.to_string());
+        }
+    }
+
+    let expected = expected.ok_or_else(|| ElexError::RollbackFailed {
+        reason: "undo script has no checksum header".to_string(),
+    })?;
+    let actual = checksum(commands.iter().map(|c| c.as_str()));
+    if actual != expected {
+        return Err(ElexError::RollbackFailed {
+            reason: format!("undo script checksum mismatch: expected {}, got {}", expected, actual),
+        });
+    }
+
+    Ok(commands)
+}
+
+/// SHA-256 over command lines, each terminated by `\n`
+fn checksum<'a>(lines: impl Iterator<Item = &'a str>) -> String {
+    let mut hasher = Sha256::new();
+    for line in lines {
+        hasher.update(line.as_bytes());
+        hasher.update(b"\n");
+    }
+    hex::encode(hasher.finalize())
+}
+
+// ============================================================================
+// Batch
+// ============================================================================
+
+/// Forward batch stored together with its undo script
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CmeditBatch {
+    /// Batch identifier (e.g. the change ticket)
+    pub batch_id: String,
+
+    /// Forward SET commands, in execution order
+    pub forward: Vec<CmeditCommand>,
+
+    /// Paired undo script
+    pub undo: UndoScript,
+}
+
+impl CmeditBatch {
+    /// Check if all forward commands passed validation
+    pub fn is_safe(&self) -> bool {
+        self.forward.iter().all(|c| c.is_safe)
+    }
+
+    /// Render the forward batch as a script referencing its undo checksum
+    pub fn forward_script(&self) -> String {
+        let mut script = String::new();
+        script.push_str("# cmedit batch\n");
+        script.push_str(&format!("# batch: {}\n", self.batch_id));
+        script.push_str(&format!("# created: {}\n", self.undo.created_at));
+        script.push_str(&format!("# commands: {}\n", self.forward.len()));
+        script.push_str(&format!("# undo-sha256: {}\n", self.undo.checksum));
+        for command in &self.forward {
+            script.push_str(&command.command);
+            script.push('\n');
+        }
+        script
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmedit::{CmeditGenerator, ParameterChange};
+
+    fn script() -> UndoScript {
+        let generator = CmeditGenerator::new();
+        let change = ParameterChange {
+            mo_path: "EUtranCellFDD=Cell1".to_string(),
+            parameter: "hoTriggerTime".to_string(),
+            old_value: Some(320.0),
+            new_value: 330.0,
+        };
+        generator.generate_batch("CR-1", &[change]).unwrap().undo
+    }
+
+    #[test]
+    fn test_checksum_covers_body() {
+        let undo = script();
+        assert_eq!(undo.checksum.len(), 64);
+        assert_eq!(undo.checksum, checksum(undo.body().lines()));
+    }
+
+    #[test]
+    fn test_verify_rendered_script() {
+        let undo = script();
+        let rendered = undo.to_script();
+        assert!(rendered.starts_with("# cmedit undo script\n# batch: CR-1\n"));
+
+        let commands = verify_undo_script(&rendered).unwrap();
+        assert_eq!(commands, vec!["cmedit set EUtranCellFDD=Cell1 hoTriggerTime=320"]);
+    }
+
+    #[test]
+    fn test_verify_detects_tampering() {
+        let rendered = script().to_script().replace("=320", "=3200");
+        assert!(verify_undo_script(&rendered).is_err());
+
+        let mut undo = script();
+        undo.commands[0].command.push('0');
+        assert!(!undo.verify());
+
+        assert!(verify_undo_script("cmedit set EUtranCellFDD=Cell1 hoTriggerTime=320\n").is_err());
+    }
+}
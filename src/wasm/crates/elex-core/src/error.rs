@@ -44,6 +44,9 @@ pub enum ElexError {
     /// Rollback failed
     RollbackFailed { reason: String },
 
+    /// cmedit output or CM dump could not be parsed
+    CmParse { line: usize, reason: String },
+
     // ==================== Coordination Context ====================
     /// Invalid query format or content
     InvalidQuery { reason: String },
@@ -127,6 +130,9 @@ impl fmt::Display for ElexError {
             ElexError::RollbackFailed { reason } => {
                 write!(f, "Rollback failed: {}", reason)
             }
+            ElexError::CmParse { line, reason } => {
+                write!(f, "CM parse error at line {}: {}", line, reason)
+            }
             ElexError::InvalidQuery { reason } => {
                 write!(f, "Invalid query: {}", reason)
             }
@@ -78,6 +78,149 @@ impl SafeZone {
     }
 }
 
+// ============================================================================
+// Parameter Domain
+// ============================================================================
+
+/// Typed value domain for a parameter
+///
+/// Refines the numeric safe zone for parameters that are not continuous.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParameterDomain {
+    /// Whole numbers aligned to `step`, counted from the absolute minimum
+    Integer { step: i64 },
+    /// `true` / `false`
+    Boolean,
+    /// One of a fixed set of literals
+    Enumerated {
+        /// Allowed literals
+        values: Vec<String>,
+        /// Transitions (from, to) that may not be applied directly
+        forbidden_transitions: Vec<(String, String)>,
+    },
+}
+
+impl ParameterDomain {
+    /// Create an enumerated domain without forbidden transitions
+    pub fn enumerated(values: &[&str]) -> Self {
+        ParameterDomain::Enumerated {
+            values: values.iter().map(|v| v.to_string()).collect(),
+            forbidden_transitions: Vec::new(),
+        }
+    }
+
+    /// Forbid a direct transition between two enum literals
+    pub fn forbid_transition(mut self, from: &str, to: &str) -> Self {
+        if let ParameterDomain::Enumerated { forbidden_transitions, .. } = &mut self {
+            forbidden_transitions.push((from.to_string(), to.to_string()));
+        }
+        self
+    }
+
+    /// Check if a transition is allowed (always true for non-enum domains)
+    pub fn allows_transition(&self, from: &str, to: &str) -> bool {
+        match self {
+            ParameterDomain::Enumerated { forbidden_transitions, .. } => !forbidden_transitions
+                .iter()
+                .any(|(f, t)| f == from && t == to),
+            _ => true,
+        }
+    }
+
+    /// Discrete domains have no meaningful percentage change or safe range
+    pub fn is_discrete(&self) -> bool {
+        matches!(self, ParameterDomain::Boolean | ParameterDomain::Enumerated { .. })
+    }
+
+    /// Encode a literal as its numeric value
+    ///
+    /// Safe zones are numeric, so booleans are encoded as `false` = 0 /
+    /// `true` = 1 and enum literals as their position in the list.
+    pub fn encode(&self, literal: &str) -> std::result::Result<f32, String> {
+        match self {
+            ParameterDomain::Integer { .. } => literal
+                .trim()
+                .parse::<i64>()
+                .map(|v| v as f32)
+                .map_err(|_| format!("'{}' is not a valid integer", literal)),
+            ParameterDomain::Boolean => match literal.trim().to_lowercase().as_str() {
+                "false" | "0" => Ok(0.0),
+                "true" | "1" => Ok(1.0),
+                _ => Err(format!("'{}' is not a valid boolean", literal)),
+            },
+            ParameterDomain::Enumerated { values, .. } => values
+                .iter()
+                .position(|v| v == literal.trim())
+                .map(|i| i as f32)
+                .ok_or_else(|| {
+                    format!("'{}' is not one of the allowed values ({})", literal, values.join(", "))
+                }),
+        }
+    }
+
+    /// Check that an encoded value is legal
+    ///
+    /// `origin` is the absolute minimum integer steps are counted from.
+    pub fn check_value(&self, value: f32, origin: f32) -> std::result::Result<(), String> {
+        if value.fract() != 0.0 {
+            return Err(format!("{} is not a whole number", value));
+        }
+        match self {
+            ParameterDomain::Integer { step } => {
+                let offset = (value - origin) as i64;
+                if *step > 0 && offset % step != 0 {
+                    return Err(format!("{} is not aligned to step {} from {}", value, step, origin));
+                }
+                Ok(())
+            }
+            ParameterDomain::Boolean => {
+                if value == 0.0 || value == 1.0 {
+                    Ok(())
+                } else {
+                    Err(format!("{} is not a boolean (0 or 1)", value))
+                }
+            }
+            ParameterDomain::Enumerated { values, .. } => {
+                if value >= 0.0 && (value as usize) < values.len() {
+                    Ok(())
+                } else {
+                    Err(format!("{} is not a valid enum index (0..{})", value, values.len()))
+                }
+            }
+        }
+    }
+
+    /// Check that a transition between two encoded values is allowed
+    pub fn check_transition(&self, old_value: f32, new_value: f32) -> std::result::Result<(), String> {
+        match (self.literal(old_value), self.literal(new_value)) {
+            (Some(from), Some(to)) if !self.allows_transition(from, to) => {
+                Err(format!("transition from {} to {} is forbidden", from, to))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Render an encoded value for cmedit
+    pub fn render(&self, value: f32, origin: f32) -> std::result::Result<String, String> {
+        self.check_value(value, origin)?;
+        Ok(match self {
+            ParameterDomain::Integer { .. } => (value as i64).to_string(),
+            ParameterDomain::Boolean => (value == 1.0).to_string(),
+            ParameterDomain::Enumerated { values, .. } => values[value as usize].clone(),
+        })
+    }
+
+    /// Enum literal for an encoded value
+    fn literal(&self, value: f32) -> Option<&str> {
+        match self {
+            ParameterDomain::Enumerated { values, .. } if value >= 0.0 && value.fract() == 0.0 => {
+                values.get(value as usize).map(String::as_str)
+            }
+            _ => None,
+        }
+    }
+}
+
 // ============================================================================
 // Parameter Value Object
 // ============================================================================
@@ -97,6 +240,9 @@ pub struct Parameter {
     pub description: Option<String>,
     /// MO class path (e.g., "UtranCell/EutranCell/NrCell")
     pub mo_class: Option<String>,
+    /// Typed value domain (enum literals, boolean, integer step)
+    #[serde(default)]
+    pub domain: Option<ParameterDomain>,
 }
 
 impl Parameter {
@@ -113,19 +259,38 @@ impl Parameter {
             current_value: None,
             description: None,
             mo_class: None,
+            domain: None,
         }
     }
 
+    /// Set the typed value domain
+    pub fn with_domain(mut self, domain: ParameterDomain) -> Self {
+        self.domain = Some(domain);
+        self
+    }
+
     /// Validate a parameter value
     pub fn validate_value(&self, value: &str) -> Result<bool> {
         match self.value_type.as_str() {
             "INTEGER" => {
                 let v = value.parse::<f32>()
-                    .map_err(|_| ElexError::ParameterValidation {
+                    .ok()
+                    .filter(|v| v.fract() == 0.0)
+                    .ok_or_else(|| ElexError::ParameterValidation {
                         parameter: self.name.clone(),
                         value: value.to_string(),
                         reason: "Not a valid integer".to_string(),
                     })?;
+                if let Some(ParameterDomain::Integer { step }) = &self.domain {
+                    let offset = (v - self.safe_zone.absolute_min) as i64;
+                    if *step > 0 && offset % step != 0 {
+                        return Err(ElexError::ParameterValidation {
+                            parameter: self.name.clone(),
+                            value: value.to_string(),
+                            reason: format!("Value {} is not aligned to step {} from {}", v, step, self.safe_zone.absolute_min),
+                        });
+                    }
+                }
                 if self.safe_zone.validate_safe(v) {
                     Ok(true)
                 } else {
@@ -162,7 +327,33 @@ impl Parameter {
                         reason: "Not a valid boolean".to_string(),
                     })
             }
-            "ENUM" => Ok(true), // ENUM validation would need enum definition
+            "ENUM" => match &self.domain {
+                Some(ParameterDomain::Enumerated { values, .. }) => values
+                    .iter()
+                    .any(|v| v == value)
+                    .then_some(true)
+                    .ok_or_else(|| ElexError::ParameterValidation {
+                        parameter: self.name.clone(),
+                        value: value.to_string(),
+                        reason: format!("Not one of the allowed values ({})", values.join(", ")),
+                    }),
+                _ => Ok(true),
+            },
+            _ => Ok(true),
+        }
+    }
+
+    /// Validate a change from `old` to `new`
+    ///
+    /// Checks the new value and rejects forbidden enum transitions.
+    pub fn validate_transition(&self, old: &str, new: &str) -> Result<bool> {
+        self.validate_value(new)?;
+        match &self.domain {
+            Some(domain) if !domain.allows_transition(old, new) => Err(ElexError::ParameterValidation {
+                parameter: self.name.clone(),
+                value: new.to_string(),
+                reason: format!("Transition from {} to {} is forbidden", old, new),
+            }),
             _ => Ok(true),
         }
     }
@@ -448,5 +639,54 @@ mod tests {
 
         assert!(param.validate_value("50").is_ok());
         assert!(param.validate_value("5").is_err()); // Below safe_min
+        assert!(param.validate_value("50.5").is_err());
+    }
+
+    #[test]
+    fn test_parameter_integer_step() {
+        let zone = SafeZone::new(10.0, 1024.0, 10.0, 1024.0, 50.0, 3600);
+        let param = Parameter::new("drxCycle".to_string(), "INTEGER".to_string(), zone)
+            .with_domain(ParameterDomain::Integer { step: 10 });
+
+        assert!(param.validate_value("320").is_ok());
+        assert!(param.validate_value("325").is_err());
+    }
+
+    #[test]
+    fn test_domain_encoding() {
+        let anr_mode = ParameterDomain::enumerated(&["OFF", "MONITOR", "ACTIVE"]).forbid_transition("OFF", "ACTIVE");
+        assert_eq!(anr_mode.encode("MONITOR"), Ok(1.0));
+        assert!(anr_mode.encode("AUTO").is_err());
+        assert_eq!(anr_mode.render(2.0, 0.0), Ok("ACTIVE".to_string()));
+        assert!(anr_mode.render(3.0, 0.0).is_err());
+        assert!(anr_mode.check_transition(0.0, 1.0).is_ok());
+        assert!(anr_mode.check_transition(0.0, 2.0).is_err());
+        assert!(anr_mode.is_discrete());
+
+        assert_eq!(ParameterDomain::Boolean.encode("TRUE"), Ok(1.0));
+        assert_eq!(ParameterDomain::Boolean.render(0.0, 0.0), Ok("false".to_string()));
+        assert!(ParameterDomain::Boolean.check_value(0.5, 0.0).is_err());
+
+        let step = ParameterDomain::Integer { step: 10 };
+        assert!(step.check_value(320.0, 10.0).is_ok());
+        assert!(step.check_value(325.0, 10.0).is_err());
+        assert!(step.encode("32.5").is_err());
+        assert!(!step.is_discrete());
+    }
+
+    #[test]
+    fn test_parameter_enum_domain() {
+        let zone = SafeZone::new(0.0, 2.0, 0.0, 2.0, 100.0, 1800);
+        let param = Parameter::new("anrMode".to_string(), "ENUM".to_string(), zone.clone())
+            .with_domain(ParameterDomain::enumerated(&["OFF", "MONITOR", "ACTIVE"]).forbid_transition("OFF", "ACTIVE"));
+
+        assert!(param.validate_value("MONITOR").is_ok());
+        assert!(param.validate_value("AUTO").is_err());
+        assert!(param.validate_transition("OFF", "MONITOR").is_ok());
+        assert!(param.validate_transition("OFF", "ACTIVE").is_err());
+
+        // ENUM without a domain accepts any value
+        let untyped = Parameter::new("anrMode".to_string(), "ENUM".to_string(), zone);
+        assert!(untyped.validate_value("OFF").unwrap());
     }
 }
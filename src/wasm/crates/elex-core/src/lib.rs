@@ -10,6 +10,6 @@ pub mod traits;
 
 // Re-export main types
 pub use error::{ElexError, Result};
-pub use feature::{Feature, Parameter, Counter, KPI, SafeZone, Procedure, ProcedureStep};
+pub use feature::{Feature, Parameter, ParameterDomain, Counter, KPI, SafeZone, Procedure, ProcedureStep};
 pub use knowledge::{FeatureAgent, AgentStats, AgentStatus};
 pub use traits::{Agent, Learnable, Routable, Validatable, VectorIndex, QTable, cosine_similarity};
@@ -0,0 +1,460 @@
+//! Linear Value Function Approximation
+//!
+//! The Q-table is keyed by a state hash whose upper 55 bits are a context
+//! hash, so a query in a context the agent has never seen starts from
+//! `initial_q_value` even if it is nearly identical to a familiar one. The
+//! `LinearApproximator` learns a linear Q-function over hashed features that
+//! generalise across contexts, and blends it with the table:
+//!
+//! ```text
+//! φ(s)       = hashed one-hots of query type, complexity, confidence bucket
+//!              and type × complexity, plus the signed-hashed query embedding
+//! Q̂(s,a)     = w_a · φ(s)
+//! Q(s,a)     = β Q_table(s,a) + (1 - β) Q̂(s,a),   β = min(visits / tabular_visits, 1)
+//! w_a       <- w_a + α_f (target - Q̂(s,a)) φ(s) / |φ(s)|²
+//! ```
+//!
+//! Cold states are driven by the approximation; once a state-action pair has
+//! `tabular_visits` visits the tabular value takes over. Policies see the
+//! blended values through `Learner::view`, so any `Policy` works unchanged.
+//!
+//! Query embeddings are cached for the `max_contexts` most recently observed
+//! states. Weights and cached embeddings are saved through
+//! `Learner::export_state`.
+
+use crate::encoding::StateHash as EncodedStateHash;
+use crate::format::FormatError;
+use crate::learner::{Learner, LearnerKind};
+use crate::policy::{Action, StateView};
+use crate::qtable::{QTable, StateHash, ACTION_COUNT};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Linear approximation hyperparameters
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ApproximationConfig {
+    /// Hashed feature slots per action
+    pub dims: usize,
+    /// Learning rate of the normalised weight update
+    pub alpha: f32,
+    /// Visits after which the tabular value is used alone
+    pub tabular_visits: u32,
+    /// Maximum number of cached query embeddings
+    pub max_contexts: usize,
+}
+
+impl Default for ApproximationConfig {
+    fn default() -> Self {
+        Self {
+            dims: 1024,
+            alpha: 0.1,
+            tabular_visits: 10,
+            max_contexts: 4096,
+        }
+    }
+}
+
+// ============================================================================
+// Linear Approximator
+// ============================================================================
+
+/// Linear Q-function over hashed state features, blended with the Q-table
+#[derive(Clone, Debug)]
+pub struct LinearApproximator {
+    /// Configuration
+    config: ApproximationConfig,
+    /// Weights, `dims` per action
+    weights: Vec<f32>,
+    /// Query embeddings of recently observed states, with the tick they were last seen
+    embeddings: HashMap<StateHash, (Vec<f32>, u64)>,
+    /// Cached states by last-seen tick, oldest first
+    recency: BTreeMap<u64, StateHash>,
+    /// Observation counter
+    tick: u64,
+}
+
+/// Serialized weights and cached embeddings
+#[derive(Serialize, Deserialize)]
+struct ApproximatorState {
+    dims: usize,
+    weights: Vec<f32>,
+    /// Least recently seen first
+    embeddings: Vec<(StateHash, Vec<f32>)>,
+}
+
+impl LinearApproximator {
+    /// Create with zero weights
+    pub fn new(config: ApproximationConfig) -> Self {
+        let dims = config.dims.max(1);
+        Self {
+            config: ApproximationConfig { dims, ..config },
+            weights: vec![0.0; dims * ACTION_COUNT],
+            embeddings: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Get configuration
+    pub fn config(&self) -> &ApproximationConfig {
+        &self.config
+    }
+
+    /// Sparse hashed feature vector of a state
+    pub fn features(&self, state: StateHash) -> Vec<(usize, f32)> {
+        let decoded = EncodedStateHash::from_u64(state).decode();
+        let (query_type, complexity) = (decoded.query_type.index() as u64, decoded.complexity.index() as u64);
+
+        let mut features = vec![
+            (self.slot(1), 1.0),
+            (self.slot(2 << 8 | query_type), 1.0),
+            (self.slot(3 << 8 | complexity), 1.0),
+            (self.slot(4 << 8 | decoded.confidence_bucket as u64), 1.0),
+            (self.slot(5 << 16 | query_type << 8 | complexity), 1.0),
+        ];
+
+        if let Some((embedding, _)) = self.embeddings.get(&state) {
+            for (i, &value) in embedding.iter().enumerate() {
+                let h = mix(6 << 32 | i as u64);
+                let sign = if h >> 63 == 0 { 1.0 } else { -1.0 };
+                features.push(((h % self.config.dims as u64) as usize, sign * value));
+            }
+        }
+
+        features
+    }
+
+    /// Approximate Q-value `w_a · φ(s)`
+    pub fn predict(&self, state: StateHash, action: Action) -> f32 {
+        let weights = self.action_weights(action);
+        self.features(state).iter().map(|&(i, x)| weights[i] * x).sum()
+    }
+
+    /// Blend of tabular and approximate value, weighted by visit count
+    pub fn value(&self, table: &QTable, state: StateHash, action: Action) -> f32 {
+        let Some(entry) = table.entry(state, action) else {
+            return self.predict(state, action);
+        };
+        let beta = self.tabular_weight(entry.visit_count);
+        beta * entry.value + (1.0 - beta) * self.predict(state, action)
+    }
+
+    /// Share of the tabular value after `visits` visits
+    pub fn tabular_weight(&self, visits: u32) -> f32 {
+        if self.config.tabular_visits == 0 {
+            return 1.0;
+        }
+        (visits as f32 / self.config.tabular_visits as f32).min(1.0)
+    }
+
+    /// Move the approximation of Q(s,a) towards `target`
+    pub fn fit(&mut self, state: StateHash, action: Action, target: f32) {
+        let features = self.features(state);
+        let norm: f32 = features.iter().map(|&(_, x)| x * x).sum();
+        if norm <= f32::EPSILON {
+            return;
+        }
+
+        let error = target - self.predict(state, action);
+        let step = self.config.alpha * error / norm;
+        let offset = action.index() as usize * self.config.dims;
+        for (i, x) in features {
+            self.weights[offset + i] += step * x;
+        }
+    }
+
+    /// Cache the query embedding of a state, evicting the least recently seen
+    pub fn remember(&mut self, state: StateHash, embedding: &[f32]) {
+        self.tick += 1;
+        if let Some((_, last_seen)) = self.embeddings.get(&state) {
+            self.recency.remove(last_seen);
+        } else if self.embeddings.len() >= self.config.max_contexts {
+            if let Some((_, evicted)) = self.recency.pop_first() {
+                self.embeddings.remove(&evicted);
+            }
+        }
+        self.embeddings.insert(state, (embedding.to_vec(), self.tick));
+        self.recency.insert(self.tick, state);
+    }
+
+    /// Number of cached query embeddings
+    pub fn cached_contexts(&self) -> usize {
+        self.embeddings.len()
+    }
+
+    fn action_weights(&self, action: Action) -> &[f32] {
+        let offset = action.index() as usize * self.config.dims;
+        &self.weights[offset..offset + self.config.dims]
+    }
+
+    fn slot(&self, key: u64) -> usize {
+        (mix(key) % self.config.dims as u64) as usize
+    }
+}
+
+impl LinearApproximator {
+    /// Update table and weights with a step scaled by `weight`
+    ///
+    /// `next_state` is None for a terminal transition. Returns the new
+    /// tabular value and the TD error before the update.
+    fn apply(
+        &mut self,
+        table: &mut QTable,
+        state: StateHash,
+        action: Action,
+        reward: f32,
+        next_state: Option<StateHash>,
+        weight: f32,
+    ) -> (f32, f32) {
+        let next_value = next_state.map_or(0.0, |next_state| {
+            Action::all()
+                .iter()
+                .map(|&a| self.value(table, next_state, a))
+                .fold(f32::NEG_INFINITY, f32::max)
+        });
+        let target = reward + table.config().gamma * next_value;
+
+        // First visit starts from the generalised estimate
+        let current = table
+            .entry(state, action)
+            .map(|e| e.value)
+            .unwrap_or_else(|| self.predict(state, action));
+        let td_error = target - current;
+        let updated = current + table.config().alpha * weight * td_error;
+        table.record_update(state, action, updated, reward);
+
+        let predicted = self.predict(state, action);
+        self.fit(state, action, predicted + weight * (target - predicted));
+        (updated, td_error)
+    }
+}
+
+impl Learner for LinearApproximator {
+    fn update(
+        &mut self,
+        table: &mut QTable,
+        state: StateHash,
+        action: Action,
+        reward: f32,
+        next_state: StateHash,
+    ) -> f32 {
+        self.apply(table, state, action, reward, Some(next_state), 1.0).0
+    }
+
+    fn update_terminal(
+        &mut self,
+        table: &mut QTable,
+        state: StateHash,
+        action: Action,
+        reward: f32,
+    ) -> f32 {
+        self.apply(table, state, action, reward, None, 1.0).0
+    }
+
+    fn update_weighted(
+        &mut self,
+        table: &mut QTable,
+        state: StateHash,
+        action: Action,
+        reward: f32,
+        next_state: StateHash,
+        weight: f32,
+    ) -> f32 {
+        self.apply(table, state, action, reward, Some(next_state), weight).1
+    }
+
+    /// Shifts the tabular value and moves the approximation by the same step
+    fn credit(&mut self, table: &mut QTable, state: StateHash, action: Action, delta: f32) -> f32 {
+        let step = table.config().alpha * delta;
+        let value = table
+            .entry(state, action)
+            .map(|e| e.value)
+            .unwrap_or_else(|| self.predict(state, action))
+            + step;
+        table.set_q_value(state, action, value);
+
+        // `fit` moves the prediction by alpha * (target - prediction)
+        let predicted = self.predict(state, action);
+        self.fit(state, action, predicted + delta);
+        value
+    }
+
+    fn observe(&mut self, state: StateHash, embedding: &[f32]) {
+        self.remember(state, embedding);
+    }
+
+    /// Weights and cached embeddings as JSON
+    fn export_state(&self) -> Result<Option<Vec<u8>>, FormatError> {
+        let state = ApproximatorState {
+            dims: self.config.dims,
+            weights: self.weights.clone(),
+            embeddings: self
+                .recency
+                .values()
+                .map(|s| (*s, self.embeddings[s].0.clone()))
+                .collect(),
+        };
+        serde_json::to_vec(&state)
+            .map(Some)
+            .map_err(|e| FormatError::Serialization(e.to_string()))
+    }
+
+    fn import_state(&mut self, data: &[u8]) -> Result<(), FormatError> {
+        let state: ApproximatorState =
+            serde_json::from_slice(data).map_err(|e| FormatError::Serialization(e.to_string()))?;
+        if state.dims != self.config.dims || state.weights.len() != self.weights.len() {
+            return Err(FormatError::Serialization(format!(
+                "expected {} weights over {} dims, found {} over {}",
+                self.weights.len(),
+                self.config.dims,
+                state.weights.len(),
+                state.dims
+            )));
+        }
+
+        self.weights = state.weights;
+        self.embeddings.clear();
+        self.recency.clear();
+        for (s, embedding) in &state.embeddings {
+            self.remember(*s, embedding);
+        }
+        Ok(())
+    }
+
+    fn view<'t>(&self, table: &'t QTable, state: StateHash) -> StateView<'t> {
+        let values = std::array::from_fn(|i| self.value(table, state, Action::all()[i]));
+        StateView::new(table, state, values)
+    }
+
+    fn kind(&self) -> LearnerKind {
+        LearnerKind::LinearApproximation
+    }
+}
+
+/// SplitMix64 finalizer for feature hashing
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E3779B97F4A7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D049BB133111EB);
+    x ^ (x >> 31)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{Complexity, QueryType};
+    use crate::policy::EpsilonGreedy;
+    use crate::qtable::{QEntry, QLearningConfig};
+
+    fn state(query_type: QueryType, context: u64) -> StateHash {
+        EncodedStateHash::encode(query_type, Complexity::Simple, context, 0.5).value()
+    }
+
+    fn embedding(seed: f32) -> Vec<f32> {
+        (0..128).map(|i| ((i as f32 + seed) * 0.37).sin() / 8.0).collect()
+    }
+
+    #[test]
+    fn test_generalises_to_unseen_context() {
+        let mut table = QTable::new(QLearningConfig::elex_default());
+        let mut learner = LinearApproximator::new(ApproximationConfig::default());
+
+        for context in 0..50 {
+            let s = state(QueryType::Kpi, context);
+            learner.observe(s, &embedding(0.0));
+            learner.update(&mut table, s, Action::ContextAnswer, 1.0, s);
+        }
+
+        let unseen = state(QueryType::Kpi, 9999);
+        learner.observe(unseen, &embedding(0.1));
+        assert_eq!(table.get_q_value(unseen, Action::ContextAnswer), 0.0);
+        assert!(learner.value(&table, unseen, Action::ContextAnswer) > 0.5);
+        assert!(learner.value(&table, unseen, Action::DirectAnswer).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_tabular_value_takes_over() {
+        let mut table = QTable::new(QLearningConfig::elex_default());
+        let learner = LinearApproximator::new(ApproximationConfig {
+            tabular_visits: 4,
+            ..ApproximationConfig::default()
+        });
+        let s = state(QueryType::Parameter, 1);
+
+        table.set_entry(s, Action::Escalate, QEntry { visit_count: 2, ..QEntry::new(0.8) });
+        assert!((learner.value(&table, s, Action::Escalate) - 0.4).abs() < 1e-6);
+
+        table.set_entry(s, Action::Escalate, QEntry { visit_count: 40, ..QEntry::new(0.8) });
+        assert_eq!(learner.value(&table, s, Action::Escalate), 0.8);
+    }
+
+    #[test]
+    fn test_policy_acts_on_blended_view() {
+        let mut table = QTable::new(QLearningConfig::elex_default());
+        let mut learner = LinearApproximator::new(ApproximationConfig::default());
+        for context in 0..30 {
+            let s = state(QueryType::Troubleshoot, context);
+            learner.update(&mut table, s, Action::ConsultPeer, 1.0, s);
+        }
+
+        let unseen = state(QueryType::Troubleshoot, 4242);
+        let view = learner.view(&table, unseen);
+        let mut policy = EpsilonGreedy::exploit_only(1);
+        assert_eq!(policy.select_action(&view, unseen, Action::all()).action, Action::ConsultPeer);
+
+        // Tabular learners hand the table's values through untouched
+        let view = crate::learner::QLearning.view(&table, unseen);
+        assert_eq!(view.values(), &[0.0; ACTION_COUNT]);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_seen_context() {
+        let mut learner = LinearApproximator::new(ApproximationConfig {
+            max_contexts: 2,
+            ..ApproximationConfig::default()
+        });
+        let (a, b, c) = (state(QueryType::Kpi, 1), state(QueryType::Kpi, 2), state(QueryType::Kpi, 3));
+
+        learner.observe(a, &embedding(0.0));
+        learner.observe(b, &embedding(1.0));
+        learner.observe(a, &embedding(0.0));
+        learner.observe(c, &embedding(2.0));
+
+        assert_eq!(learner.cached_contexts(), 2);
+        assert!(learner.embeddings.contains_key(&a));
+        assert!(!learner.embeddings.contains_key(&b));
+        assert!(learner.embeddings.contains_key(&c));
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let mut table = QTable::new(QLearningConfig::elex_default());
+        let mut learner = LinearApproximator::new(ApproximationConfig::default());
+        for context in 0..20 {
+            let s = state(QueryType::Kpi, context);
+            learner.observe(s, &embedding(context as f32));
+            learner.update(&mut table, s, Action::ContextAnswer, 1.0, s);
+        }
+
+        let data = learner.export_state().unwrap().unwrap();
+        let mut restored = LinearApproximator::new(ApproximationConfig::default());
+        restored.import_state(&data).unwrap();
+
+        assert_eq!(restored.weights, learner.weights);
+        assert_eq!(restored.cached_contexts(), 20);
+        let s = state(QueryType::Kpi, 7);
+        assert_eq!(restored.value(&table, s, Action::ContextAnswer), learner.value(&table, s, Action::ContextAnswer));
+
+        let mut smaller = LinearApproximator::new(ApproximationConfig { dims: 64, ..ApproximationConfig::default() });
+        assert!(smaller.import_state(&data).is_err());
+    }
+}
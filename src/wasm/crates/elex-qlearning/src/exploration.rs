@@ -0,0 +1,516 @@
+//! Exploration Policies
+//!
+//! Alternatives to `EpsilonGreedy` behind the common `Policy` trait, all
+//! seeded for reproducible action sequences:
+//!
+//! - **UCB1**: optimism under uncertainty over the outcome counts of each
+//!   entry, `rate(a) + c * sqrt(ln N / n(a))`, where `rate` is the success
+//!   rate and `n` the number of rewarded outcomes. Untried actions go first.
+//! - **Boltzmann**: softmax over Q-values, `P(a) ∝ exp(Q(s,a) / τ)`, with the
+//!   temperature cooled geometrically on every `decay`.
+//! - **Thompson sampling**: one draw from `Beta(successes + 1, failures + 1)`
+//!   per action, picking the largest draw.
+//!
+//! Each policy also reports its action probabilities for a state, which
+//! Expected SARSA averages over. Thompson sampling has no closed form for
+//! them, so they are estimated from a fixed number of seeded draws.
+//!
+//! Policies are chosen per agent with a `PolicyConfig`.
+
+use crate::policy::{Action, ActionSelection, EpsilonGreedy, Policy, QValues};
+use crate::qtable::{StateHash, ACTION_COUNT};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use rand_distr::{Beta, Distribution};
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Policy Selection
+// ============================================================================
+
+/// Exploration policy and its parameters
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PolicyConfig {
+    /// Random action with probability epsilon
+    EpsilonGreedy {
+        epsilon: f32,
+        epsilon_min: f32,
+        decay: f32,
+    },
+
+    /// Upper confidence bound over outcome counts
+    Ucb {
+        /// Exploration weight (sqrt(2) for classic UCB1)
+        c: f32,
+    },
+
+    /// Softmax over Q-values with a cooling temperature
+    Boltzmann {
+        temperature: f32,
+        temperature_min: f32,
+        cooling: f32,
+    },
+
+    /// Beta-Bernoulli Thompson sampling
+    Thompson,
+}
+
+impl Default for PolicyConfig {
+    fn default() -> Self {
+        PolicyConfig::EpsilonGreedy {
+            epsilon: 0.1,
+            epsilon_min: 0.01,
+            decay: 0.995,
+        }
+    }
+}
+
+impl PolicyConfig {
+    /// Get policy name
+    pub fn name(&self) -> &'static str {
+        match self {
+            PolicyConfig::EpsilonGreedy { .. } => "epsilon-greedy",
+            PolicyConfig::Ucb { .. } => "ucb1",
+            PolicyConfig::Boltzmann { .. } => "boltzmann",
+            PolicyConfig::Thompson => "thompson",
+        }
+    }
+}
+
+/// Create the policy described by a configuration
+pub fn create_policy(config: &PolicyConfig, seed: u64) -> Box<dyn Policy> {
+    match *config {
+        PolicyConfig::EpsilonGreedy { epsilon, epsilon_min, decay } => {
+            Box::new(EpsilonGreedy::new(seed, true).with_epsilon(epsilon, epsilon_min, decay))
+        }
+        PolicyConfig::Ucb { c } => Box::new(UcbPolicy::new(seed, c)),
+        PolicyConfig::Boltzmann { temperature, temperature_min, cooling } => {
+            Box::new(BoltzmannPolicy::new(seed, temperature).with_cooling(temperature_min, cooling))
+        }
+        PolicyConfig::Thompson => Box::new(ThompsonSampling::new(seed)),
+    }
+}
+
+/// Fall back to `DirectAnswer` when no action is available
+fn candidates(available_actions: &[Action]) -> &[Action] {
+    if available_actions.is_empty() {
+        &[Action::DirectAnswer]
+    } else {
+        available_actions
+    }
+}
+
+/// Build a selection, flagging actions other than the greedy one
+fn selection(
+    q_table: &dyn QValues,
+    state: StateHash,
+    actions: &[Action],
+    action: Action,
+    probability: Option<f32>,
+) -> ActionSelection {
+    let greedy = q_table.best_action(state, actions).unwrap_or(action);
+    let q_value = q_table.get_q_value(state, action);
+    ActionSelection {
+        action,
+        q_value,
+        is_exploration: q_value < q_table.get_q_value(state, greedy),
+        probability,
+    }
+}
+
+// ============================================================================
+// UCB1
+// ============================================================================
+
+/// UCB1 over the success rate of each state-action pair
+#[derive(Clone, Debug)]
+pub struct UcbPolicy {
+    /// Exploration weight
+    c: f32,
+    /// Seeded RNG breaking ties between untried actions
+    rng: StdRng,
+}
+
+impl UcbPolicy {
+    /// Create with an exploration weight
+    pub fn new(seed: u64, c: f32) -> Self {
+        Self {
+            c: c.max(0.0),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Upper confidence bound of an action, `None` if it has no outcomes yet
+    pub fn bound(&self, q_table: &dyn QValues, state: StateHash, action: Action, total: u32) -> Option<f32> {
+        let entry = q_table.entry(state, action)?;
+        let n = entry.successes + entry.failures;
+        if n == 0 {
+            return None;
+        }
+        let bonus = self.c * ((total.max(1) as f32).ln() / n as f32).sqrt();
+        Some(entry.success_rate() + bonus)
+    }
+
+    /// Actions without outcomes yet, and the action with the highest bound
+    fn choices(&self, q_table: &dyn QValues, state: StateHash, actions: &[Action]) -> (Vec<Action>, Action) {
+        let total: u32 = actions
+            .iter()
+            .filter_map(|&a| q_table.entry(state, a))
+            .map(|e| e.successes + e.failures)
+            .sum();
+
+        let untried: Vec<Action> = actions
+            .iter()
+            .copied()
+            .filter(|&a| self.bound(q_table, state, a, total).is_none())
+            .collect();
+
+        let best = actions
+            .iter()
+            .copied()
+            .max_by(|&a, &b| {
+                let ua = self.bound(q_table, state, a, total).unwrap_or(f32::INFINITY);
+                let ub = self.bound(q_table, state, b, total).unwrap_or(f32::INFINITY);
+                ua.partial_cmp(&ub).unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap_or(Action::DirectAnswer);
+
+        (untried, best)
+    }
+}
+
+impl Policy for UcbPolicy {
+    fn select_action(
+        &mut self,
+        q_table: &dyn QValues,
+        state: StateHash,
+        available_actions: &[Action],
+    ) -> ActionSelection {
+        let actions = candidates(available_actions);
+        let (untried, best) = self.choices(q_table, state, actions);
+
+        let (action, probability) = if untried.is_empty() {
+            (best, 1.0)
+        } else {
+            let action = untried[self.rng.gen_range(0..untried.len())];
+            (action, 1.0 / untried.len() as f32)
+        };
+
+        selection(q_table, state, actions, action, Some(probability))
+    }
+
+    fn action_probabilities(&self, q_table: &dyn QValues, state: StateHash) -> [f32; ACTION_COUNT] {
+        let mut probabilities = [0.0; ACTION_COUNT];
+        let (untried, best) = self.choices(q_table, state, Action::all());
+        if untried.is_empty() {
+            probabilities[best.index() as usize] = 1.0;
+        }
+        for action in &untried {
+            probabilities[action.index() as usize] = 1.0 / untried.len() as f32;
+        }
+        probabilities
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Policy> {
+        Box::new(self.clone())
+    }
+
+    fn name(&self) -> &str {
+        "ucb1"
+    }
+}
+
+// ============================================================================
+// Boltzmann
+// ============================================================================
+
+/// Softmax action selection with a temperature schedule
+#[derive(Clone, Debug)]
+pub struct BoltzmannPolicy {
+    /// Current temperature (higher = more uniform)
+    temperature: f32,
+    /// Temperature floor
+    temperature_min: f32,
+    /// Cooling factor per `decay`
+    cooling: f32,
+    /// Seeded RNG for sampling
+    rng: StdRng,
+}
+
+impl BoltzmannPolicy {
+    /// Create with an initial temperature and no cooling
+    pub fn new(seed: u64, temperature: f32) -> Self {
+        let temperature = temperature.max(f32::EPSILON);
+        Self {
+            temperature,
+            temperature_min: temperature,
+            cooling: 1.0,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Set the cooling schedule `τ = max(τ_min, τ * cooling)`
+    pub fn with_cooling(mut self, temperature_min: f32, cooling: f32) -> Self {
+        self.temperature_min = temperature_min.clamp(f32::EPSILON, self.temperature);
+        self.cooling = cooling.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Current temperature
+    pub fn temperature(&self) -> f32 {
+        self.temperature
+    }
+
+    /// Selection probabilities over the available actions
+    pub fn probabilities(&self, q_table: &dyn QValues, state: StateHash, available_actions: &[Action]) -> Vec<(Action, f32)> {
+        let actions = candidates(available_actions);
+        let values: Vec<f32> = actions.iter().map(|&a| q_table.get_q_value(state, a)).collect();
+
+        // Subtract the max before exponentiating to avoid overflow
+        let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let weights: Vec<f32> = values.iter().map(|q| ((q - max) / self.temperature).exp()).collect();
+        let total: f32 = weights.iter().sum();
+
+        actions.iter().copied().zip(weights.iter().map(|w| w / total)).collect()
+    }
+}
+
+impl Policy for BoltzmannPolicy {
+    fn select_action(
+        &mut self,
+        q_table: &dyn QValues,
+        state: StateHash,
+        available_actions: &[Action],
+    ) -> ActionSelection {
+        let actions = candidates(available_actions);
+        let probabilities = self.probabilities(q_table, state, actions);
+
+        let mut threshold = self.rng.gen::<f32>();
+        let mut chosen = probabilities[probabilities.len() - 1];
+        for &(candidate, p) in &probabilities {
+            if threshold < p {
+                chosen = (candidate, p);
+                break;
+            }
+            threshold -= p;
+        }
+
+        selection(q_table, state, actions, chosen.0, Some(chosen.1))
+    }
+
+    fn action_probabilities(&self, q_table: &dyn QValues, state: StateHash) -> [f32; ACTION_COUNT] {
+        let mut probabilities = [0.0; ACTION_COUNT];
+        for (action, p) in self.probabilities(q_table, state, Action::all()) {
+            probabilities[action.index() as usize] = p;
+        }
+        probabilities
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Policy> {
+        Box::new(self.clone())
+    }
+
+    fn name(&self) -> &str {
+        "boltzmann"
+    }
+
+    fn decay(&mut self) {
+        self.temperature = (self.temperature * self.cooling).max(self.temperature_min);
+    }
+}
+
+// ============================================================================
+// Thompson Sampling
+// ============================================================================
+
+/// Posterior draws per action when estimating Thompson action probabilities
+const THOMPSON_PROBABILITY_DRAWS: usize = 256;
+
+/// Beta-Bernoulli Thompson sampling over recorded outcomes
+///
+/// Each action's success probability has a `Beta(1, 1)` prior updated with
+/// the successes and failures counted on its Q-entry.
+#[derive(Clone, Debug)]
+pub struct ThompsonSampling {
+    /// Seeded RNG for posterior draws
+    rng: StdRng,
+}
+
+impl ThompsonSampling {
+    /// Create a seeded sampler
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Draw a success probability from an action's posterior
+    fn draw(&mut self, q_table: &dyn QValues, state: StateHash, action: Action) -> f32 {
+        posterior_draw(q_table, state, action, &mut self.rng)
+    }
+}
+
+/// One draw from `Beta(successes + 1, failures + 1)` for a state-action pair
+fn posterior_draw(q_table: &dyn QValues, state: StateHash, action: Action, rng: &mut StdRng) -> f32 {
+    let (successes, failures) = q_table
+        .entry(state, action)
+        .map(|e| (e.successes, e.failures))
+        .unwrap_or((0, 0));
+    Beta::new(successes as f32 + 1.0, failures as f32 + 1.0)
+        .map(|beta| beta.sample(rng))
+        .unwrap_or(0.5)
+}
+
+impl Policy for ThompsonSampling {
+    fn select_action(
+        &mut self,
+        q_table: &dyn QValues,
+        state: StateHash,
+        available_actions: &[Action],
+    ) -> ActionSelection {
+        let actions = candidates(available_actions);
+        let mut best = (actions[0], f32::NEG_INFINITY);
+        for &action in actions {
+            let draw = self.draw(q_table, state, action);
+            if draw > best.1 {
+                best = (action, draw);
+            }
+        }
+
+        selection(q_table, state, actions, best.0, None)
+    }
+
+    fn action_probabilities(&self, q_table: &dyn QValues, state: StateHash) -> [f32; ACTION_COUNT] {
+        // Share of seeded draws each action wins; seeded by state so the
+        // estimate is stable and the selection RNG is left untouched
+        let mut rng = StdRng::seed_from_u64(state);
+        let mut wins = [0u32; ACTION_COUNT];
+        for _ in 0..THOMPSON_PROBABILITY_DRAWS {
+            let mut best = (Action::DirectAnswer, f32::NEG_INFINITY);
+            for &action in Action::all() {
+                let draw = posterior_draw(q_table, state, action, &mut rng);
+                if draw > best.1 {
+                    best = (action, draw);
+                }
+            }
+            wins[best.0.index() as usize] += 1;
+        }
+        wins.map(|w| w as f32 / THOMPSON_PROBABILITY_DRAWS as f32)
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Policy> {
+        Box::new(self.clone())
+    }
+
+    fn name(&self) -> &str {
+        "thompson"
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qtable::{QEntry, QLearningConfig, QTable};
+
+    fn outcomes(qt: &mut QTable, state: StateHash, action: Action, successes: u32, failures: u32) {
+        qt.set_entry(state, action, QEntry { successes, failures, ..QEntry::new(0.0) });
+    }
+
+    #[test]
+    fn test_create_policy() {
+        let configs = [
+            PolicyConfig::default(),
+            PolicyConfig::Ucb { c: 1.4 },
+            PolicyConfig::Boltzmann { temperature: 1.0, temperature_min: 0.1, cooling: 0.99 },
+            PolicyConfig::Thompson,
+        ];
+        for config in &configs {
+            assert_eq!(create_policy(config, 1).name(), config.name());
+        }
+
+        let json = serde_json::to_string(&configs[1]).unwrap();
+        assert_eq!(json, r#"{"kind":"ucb","c":1.4}"#);
+    }
+
+    #[test]
+    fn test_ucb_tries_untried_then_best_bound() {
+        let mut qt = QTable::new(QLearningConfig::elex_default());
+        let actions = [Action::DirectAnswer, Action::ConsultPeer];
+        outcomes(&mut qt, 1, Action::DirectAnswer, 9, 1);
+
+        let mut policy = UcbPolicy::new(7, 1.4);
+        assert_eq!(policy.select_action(&qt, 1, &actions).action, Action::ConsultPeer);
+
+        // One failure on ConsultPeer: its bonus still outweighs DirectAnswer's lead
+        outcomes(&mut qt, 1, Action::ConsultPeer, 0, 1);
+        assert_eq!(policy.select_action(&qt, 1, &actions).action, Action::ConsultPeer);
+
+        outcomes(&mut qt, 1, Action::ConsultPeer, 0, 20);
+        assert_eq!(policy.select_action(&qt, 1, &actions).action, Action::DirectAnswer);
+    }
+
+    #[test]
+    fn test_boltzmann_cooling_sharpens_distribution() {
+        let mut qt = QTable::new(QLearningConfig::elex_default());
+        qt.set_q_value(1, Action::ContextAnswer, 1.0);
+
+        let mut policy = BoltzmannPolicy::new(3, 10.0).with_cooling(0.05, 0.5);
+        let p_hot = policy.probabilities(&qt, 1, Action::all())[1].1;
+        for _ in 0..20 {
+            policy.decay();
+        }
+        let p_cold = policy.probabilities(&qt, 1, Action::all())[1].1;
+
+        assert_eq!(policy.temperature(), 0.05);
+        assert!(p_hot < 0.3 && p_cold > 0.99);
+        assert_eq!(policy.select_action(&qt, 1, Action::all()).action, Action::ContextAnswer);
+    }
+
+    #[test]
+    fn test_thompson_prefers_successful_action() {
+        let mut qt = QTable::new(QLearningConfig::elex_default());
+        outcomes(&mut qt, 1, Action::DirectAnswer, 2, 30);
+        outcomes(&mut qt, 1, Action::Escalate, 30, 2);
+
+        let mut policy = ThompsonSampling::new(5);
+        let escalations = (0..100)
+            .filter(|_| policy.select_action(&qt, 1, &[Action::DirectAnswer, Action::Escalate]).action == Action::Escalate)
+            .count();
+        assert!(escalations > 95);
+    }
+
+    #[test]
+    fn test_action_probabilities() {
+        let mut qt = QTable::new(QLearningConfig::elex_default());
+        outcomes(&mut qt, 1, Action::DirectAnswer, 9, 1);
+        outcomes(&mut qt, 1, Action::Escalate, 30, 2);
+        qt.set_q_value(1, Action::Escalate, 0.5);
+
+        let policies: Vec<Box<dyn Policy>> = vec![
+            create_policy(&PolicyConfig::default(), 1),
+            Box::new(UcbPolicy::new(1, 1.4)),
+            Box::new(BoltzmannPolicy::new(1, 0.5)),
+            Box::new(ThompsonSampling::new(1)),
+        ];
+        for policy in &policies {
+            let probabilities = policy.action_probabilities(&qt, 1);
+            let total: f32 = probabilities.iter().sum();
+            assert!((total - 1.0).abs() < 1e-5, "{}: {:?}", policy.name(), probabilities);
+            assert_eq!(policy.boxed_clone().action_probabilities(&qt, 1), probabilities);
+        }
+
+        // UCB splits evenly between the three untried actions
+        let ucb = policies[1].action_probabilities(&qt, 1);
+        assert_eq!(ucb[Action::DirectAnswer.index() as usize], 0.0);
+        assert!((ucb[Action::ConsultPeer.index() as usize] - 1.0 / 3.0).abs() < 1e-6);
+
+        // Thompson favours the action with the best outcome record
+        let thompson = policies[3].action_probabilities(&qt, 1);
+        let best = (0..ACTION_COUNT).max_by(|&a, &b| thompson[a].total_cmp(&thompson[b])).unwrap();
+        assert_eq!(best, Action::Escalate.index() as usize);
+    }
+}
@@ -0,0 +1,404 @@
+//! Versioned Q-Table Format
+//!
+//! On-disk and wire format used by `QTable::export`/`import`. A fixed
+//! header identifies the table, the payload is the serde representation
+//! of the `QTable`, optionally LZ4-compressed through
+//! `elex_memory::storage::CompressedStorage`, and a CRC-32 closes the frame.
+//!
+//! # Layout (little-endian)
+//! ```text
+//! offset  size  field
+//!      0     4  magic "ELQT"
+//!      4     2  schema version
+//!      6     2  flags (bit 0: LZ4 payload)
+//!      8     8  config hash (FNV-1a 64, see `config_hash`)
+//!     16    32  agent id
+//!     48     8  uncompressed payload length
+//!     56     8  stored payload length
+//!     64     N  payload
+//!   64+N     4  CRC-32 of bytes [0, 64+N)
+//! ```
+//!
+//! # Versions
+//! - 1: headerless JSON written by earlier releases (also covers the
+//!   legacy `"state::action"` key layout)
+//! - 2: this layout
+//!
+//! Older versions are migrated on read; newer versions are rejected with
+//! `FormatError::UnsupportedVersion`.
+//!
+//! # Compression
+//! LZ4 is only available on native targets; wasm32 cannot decompress an
+//! LZ4 payload. Exports exchanged between agents (federation) are
+//! therefore written uncompressed, which is also the default.
+
+use crate::qtable::{QLearningConfig, QTable};
+use elex_memory::storage::CompressedStorage;
+use std::fmt;
+
+/// Magic number opening every framed Q-table
+pub const MAGIC: [u8; 4] = *b"ELQT";
+
+/// Schema version written by this release
+pub const CURRENT_VERSION: u16 = 2;
+
+/// Header length in bytes
+pub const HEADER_LEN: usize = 64;
+
+/// Flag: payload is LZ4-compressed
+const FLAG_LZ4: u16 = 1;
+
+/// Trailing checksum length in bytes
+const CHECKSUM_LEN: usize = 4;
+
+/// Largest uncompressed payload accepted on read (256 MiB)
+pub const MAX_PAYLOAD: u64 = 256 * 1024 * 1024;
+
+/// Upper bound of the LZ4 compression ratio
+const MAX_LZ4_RATIO: u64 = 255;
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Reasons a Q-table cannot be read or written
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FormatError {
+    /// Input ends before the frame does
+    Truncated { expected: usize, actual: usize },
+    /// Written by a newer release
+    UnsupportedVersion { found: u16, supported: u16 },
+    /// Frame was corrupted in storage or transit
+    ChecksumMismatch { expected: u32, actual: u32 },
+    /// Table was trained with a different configuration
+    ConfigMismatch { expected: u64, found: u64 },
+    /// Payload could not be compressed or decompressed
+    Compression(String),
+    /// Payload could not be (de)serialized
+    Serialization(String),
+    /// Declared payload length is implausible for the stored body
+    InvalidPayloadLength { declared: u64, stored: u64 },
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::Truncated { expected, actual } => {
+                write!(f, "Q-table truncated: expected {} bytes, got {}", expected, actual)
+            }
+            FormatError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "Q-table format version {} is newer than supported version {}",
+                found, supported
+            ),
+            FormatError::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "Q-table checksum mismatch: expected {:08x}, computed {:08x}",
+                expected, actual
+            ),
+            FormatError::ConfigMismatch { expected, found } => write!(
+                f,
+                "Q-table config hash {:016x} does not match expected {:016x}",
+                found, expected
+            ),
+            FormatError::Compression(e) => write!(f, "Q-table compression failed: {}", e),
+            FormatError::Serialization(e) => write!(f, "Q-table serialization failed: {}", e),
+            FormatError::InvalidPayloadLength { declared, stored } => write!(
+                f,
+                "Q-table declares {} payload bytes for a {} byte body",
+                declared, stored
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+// ============================================================================
+// Header
+// ============================================================================
+
+/// Options for writing a Q-table
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExportOptions {
+    /// Owning agent (zeros if anonymous)
+    pub agent_id: [u8; 32],
+    /// Compress the payload with LZ4 (native readers only)
+    pub compress: bool,
+}
+
+/// Header of a decoded Q-table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QTableHeader {
+    /// Schema version the table was written with
+    pub version: u16,
+    /// Whether the payload was LZ4-compressed
+    pub compressed: bool,
+    /// Hash of the table's `QLearningConfig`
+    pub config_hash: u64,
+    /// Owning agent (zeros if anonymous or unknown)
+    pub agent_id: [u8; 32],
+    /// Uncompressed payload length
+    pub payload_len: u64,
+}
+
+// ============================================================================
+// Encoding
+// ============================================================================
+
+/// Write a Q-table in the current format
+pub fn encode(table: &QTable, options: &ExportOptions) -> Result<Vec<u8>, FormatError> {
+    let payload = serde_json::to_vec(table).map_err(|e| FormatError::Serialization(e.to_string()))?;
+
+    let mut flags = 0;
+    let mut body = None;
+    if options.compress {
+        let compressed = CompressedStorage::default()
+            .compress(&payload)
+            .map_err(|e| FormatError::Compression(e.to_string()))?;
+        // Targets without LZ4 return the input unchanged; keep it raw then
+        if compressed.len() < payload.len() {
+            flags |= FLAG_LZ4;
+            body = Some(compressed);
+        }
+    }
+    let body = body.as_deref().unwrap_or(&payload);
+
+    let mut out = Vec::with_capacity(HEADER_LEN + body.len() + CHECKSUM_LEN);
+    out.extend_from_slice(&MAGIC);
+    out.extend_from_slice(&CURRENT_VERSION.to_le_bytes());
+    out.extend_from_slice(&flags.to_le_bytes());
+    out.extend_from_slice(&config_hash(table.config()).to_le_bytes());
+    out.extend_from_slice(&options.agent_id);
+    out.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    out.extend_from_slice(&(body.len() as u64).to_le_bytes());
+    out.extend_from_slice(body);
+    out.extend_from_slice(&crc32(&out).to_le_bytes());
+    Ok(out)
+}
+
+/// Read a Q-table written by this or an earlier release
+pub fn decode(data: &[u8]) -> Result<(QTableHeader, QTable), FormatError> {
+    if !data.starts_with(&MAGIC) {
+        return decode_v1(data);
+    }
+    if data.len() < HEADER_LEN + CHECKSUM_LEN {
+        return Err(FormatError::Truncated {
+            expected: HEADER_LEN + CHECKSUM_LEN,
+            actual: data.len(),
+        });
+    }
+
+    let version = u16::from_le_bytes([data[4], data[5]]);
+    if version > CURRENT_VERSION {
+        return Err(FormatError::UnsupportedVersion {
+            found: version,
+            supported: CURRENT_VERSION,
+        });
+    }
+
+    let flags = u16::from_le_bytes([data[6], data[7]]);
+    let config_hash = read_u64(data, 8);
+    let mut agent_id = [0u8; 32];
+    agent_id.copy_from_slice(&data[16..48]);
+    let payload_len = read_u64(data, 48);
+    let body_len = read_u64(data, 56);
+
+    let frame_len = (HEADER_LEN as u64)
+        .checked_add(body_len)
+        .and_then(|n| n.checked_add(CHECKSUM_LEN as u64))
+        .filter(|&n| n <= data.len() as u64)
+        .ok_or(FormatError::Truncated {
+            expected: (body_len as usize).saturating_add(HEADER_LEN + CHECKSUM_LEN),
+            actual: data.len(),
+        })? as usize;
+
+    let checksum_at = frame_len - CHECKSUM_LEN;
+    let expected = u32::from_le_bytes([
+        data[checksum_at],
+        data[checksum_at + 1],
+        data[checksum_at + 2],
+        data[checksum_at + 3],
+    ]);
+    let actual = crc32(&data[..checksum_at]);
+    if expected != actual {
+        return Err(FormatError::ChecksumMismatch { expected, actual });
+    }
+
+    let body = &data[HEADER_LEN..checksum_at];
+    let compressed = flags & FLAG_LZ4 != 0;
+    let max_len = if compressed { body_len.saturating_mul(MAX_LZ4_RATIO) } else { body_len };
+    if payload_len > MAX_PAYLOAD || payload_len > max_len || (!compressed && payload_len != body_len) {
+        return Err(FormatError::InvalidPayloadLength {
+            declared: payload_len,
+            stored: body_len,
+        });
+    }
+    let payload = if compressed {
+        CompressedStorage::default()
+            .decompress(body, payload_len as usize)
+            .map_err(|e| FormatError::Compression(e.to_string()))?
+    } else {
+        body.to_vec()
+    };
+
+    let table = serde_json::from_slice(&payload).map_err(|e| FormatError::Serialization(e.to_string()))?;
+    let header = QTableHeader {
+        version,
+        compressed,
+        config_hash,
+        agent_id,
+        payload_len,
+    };
+    Ok((header, table))
+}
+
+/// Read a Q-table and require it to match `config`
+pub fn decode_for(data: &[u8], config: &QLearningConfig) -> Result<(QTableHeader, QTable), FormatError> {
+    let (header, table) = decode(data)?;
+    let expected = config_hash(config);
+    if header.config_hash != expected {
+        return Err(FormatError::ConfigMismatch {
+            expected,
+            found: header.config_hash,
+        });
+    }
+    Ok((header, table))
+}
+
+/// Version 1: headerless JSON; the serde layer migrates its key layout
+fn decode_v1(data: &[u8]) -> Result<(QTableHeader, QTable), FormatError> {
+    let table: QTable = serde_json::from_slice(data).map_err(|e| FormatError::Serialization(e.to_string()))?;
+    let header = QTableHeader {
+        version: 1,
+        compressed: false,
+        config_hash: config_hash(table.config()),
+        agent_id: [0u8; 32],
+        payload_len: data.len() as u64,
+    };
+    Ok((header, table))
+}
+
+/// FNV-1a 64 of the config fields that give Q-values their meaning
+///
+/// Only the discount, the initial value and the update rule change what a
+/// stored Q-value means; learning rate, exploration and pruning settings
+/// can differ between agents sharing a table.
+pub fn config_hash(config: &QLearningConfig) -> u64 {
+    let fields = (config.gamma, config.initial_q_value, config.learner);
+    let bytes = serde_json::to_vec(&fields).unwrap_or_default();
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// CRC-32 (IEEE 802.3)
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&data[offset..offset + 8]);
+    u64::from_le_bytes(bytes)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::Action;
+
+    fn trained_table() -> QTable {
+        let mut qt = QTable::new(QLearningConfig::elex_default());
+        for state in 0..200u64 {
+            qt.update_q_value(state, Action::all()[(state % 5) as usize], 1.0, 0.5);
+        }
+        qt
+    }
+
+    #[test]
+    fn test_round_trip_with_header() {
+        let qt = trained_table();
+        let options = ExportOptions { agent_id: [7u8; 32], compress: true };
+        let data = encode(&qt, &options).unwrap();
+        assert_eq!(&data[..4], &MAGIC);
+
+        let (header, restored) = decode(&data).unwrap();
+        assert_eq!(header.version, CURRENT_VERSION);
+        assert_eq!(header.agent_id, [7u8; 32]);
+        assert_eq!(header.config_hash, config_hash(qt.config()));
+        assert_eq!(restored.len(), qt.len());
+        assert_eq!(restored.get_q_value(3, Action::all()[3]), qt.get_q_value(3, Action::all()[3]));
+
+        let raw = encode(&qt, &ExportOptions { compress: false, ..options }).unwrap();
+        assert!(!decode(&raw).unwrap().0.compressed);
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            assert!(header.compressed);
+            assert!(data.len() < raw.len());
+        }
+    }
+
+    #[test]
+    fn test_migrates_headerless_json() {
+        let qt = trained_table();
+        let legacy = serde_json::to_vec(&qt).unwrap();
+
+        let (header, restored) = decode(&legacy).unwrap();
+        assert_eq!(header.version, 1);
+        assert_eq!(restored.len(), qt.len());
+    }
+
+    #[test]
+    fn test_rejects_incompatible_tables() {
+        let qt = trained_table();
+        let mut data = encode(&qt, &ExportOptions::default()).unwrap();
+
+        assert!(matches!(decode(&data[..data.len() - 1]), Err(FormatError::Truncated { .. })));
+
+        let mut corrupted = data.clone();
+        corrupted[HEADER_LEN + 3] ^= 0xFF;
+        assert!(matches!(decode(&corrupted), Err(FormatError::ChecksumMismatch { .. })));
+
+        data[4..6].copy_from_slice(&(CURRENT_VERSION + 1).to_le_bytes());
+        let err = decode(&data).err().unwrap();
+        assert_eq!(err, FormatError::UnsupportedVersion { found: CURRENT_VERSION + 1, supported: CURRENT_VERSION });
+        assert!(err.to_string().contains("newer than supported"));
+
+        let data = encode(&qt, &ExportOptions::default()).unwrap();
+        let tuned = QLearningConfig { alpha: 0.2, epsilon: 0.05, ..QLearningConfig::elex_default() };
+        let other = QLearningConfig { gamma: 0.5, ..QLearningConfig::elex_default() };
+        assert!(decode_for(&data, qt.config()).is_ok());
+        assert!(decode_for(&data, &tuned).is_ok());
+        assert!(matches!(decode_for(&data, &other), Err(FormatError::ConfigMismatch { .. })));
+    }
+
+    #[test]
+    fn test_rejects_oversized_payload_length() {
+        let qt = trained_table();
+        let mut data = encode(&qt, &ExportOptions { compress: true, ..ExportOptions::default() }).unwrap();
+        data[6..8].copy_from_slice(&FLAG_LZ4.to_le_bytes());
+        data[48..56].copy_from_slice(&u64::MAX.to_le_bytes());
+        let checksum_at = data.len() - CHECKSUM_LEN;
+        let crc = crc32(&data[..checksum_at]);
+        data[checksum_at..].copy_from_slice(&crc.to_le_bytes());
+
+        assert!(matches!(decode(&data), Err(FormatError::InvalidPayloadLength { .. })));
+    }
+
+    #[test]
+    fn test_crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF43926);
+    }
+}
@@ -0,0 +1,346 @@
+//! KPI-Delta Reward Model
+//!
+//! Closed-loop optimization (ADR-010) rewards an applied change by what it
+//! did to the network. The `KpiRewardModel` keeps the KPIs measured before a
+//! change, collects KPI samples during an observation window, and once the
+//! window has elapsed turns the difference into the `kpi_delta` component of
+//! a `Reward` for the query that proposed the change.
+//!
+//! ```text
+//! d_k    = sign_k (mean(after_k) - before_k) / |threshold_k|,  clamped to [-1, 1]
+//! reward = scale Σ w_k d_k / Σ |w_k|
+//! ```
+//!
+//! `sign_k` is +1 for HIGHER_IS_BETTER and -1 for LOWER_IS_BETTER KPIs.
+//! Averaging the samples of the window damps measurement noise; changes
+//! that receive no samples before the window closes expire without credit.
+
+use crate::qtable::Reward;
+use elex_core::feature::KPI;
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Which way a KPI should move
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum KpiDirection {
+    /// Larger values are improvements (e.g. throughput)
+    HigherIsBetter,
+    /// Smaller values are improvements (e.g. drop rate)
+    LowerIsBetter,
+}
+
+impl KpiDirection {
+    /// Direction declared by a KPI
+    ///
+    /// Undeclared directions are treated as lower-is-better, as in
+    /// `KPI::is_healthy`.
+    pub fn of(kpi: &KPI) -> Self {
+        match kpi.direction.as_deref() {
+            Some("HIGHER_IS_BETTER") => KpiDirection::HigherIsBetter,
+            _ => KpiDirection::LowerIsBetter,
+        }
+    }
+
+    fn sign(&self) -> f64 {
+        match self {
+            KpiDirection::HigherIsBetter => 1.0,
+            KpiDirection::LowerIsBetter => -1.0,
+        }
+    }
+}
+
+/// Weight and direction override of one KPI
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KpiWeight {
+    /// Relative importance (0 ignores the KPI)
+    pub weight: f32,
+    /// Direction, overriding the KPI's own
+    #[serde(default)]
+    pub direction: Option<KpiDirection>,
+}
+
+/// KPI reward model settings
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KpiRewardConfig {
+    /// Per-KPI weights, by KPI name
+    #[serde(default)]
+    pub weights: HashMap<String, KpiWeight>,
+    /// Weight of KPIs without an entry in `weights`
+    pub default_weight: f32,
+    /// Observation window after a change (ms)
+    pub window_ms: u64,
+    /// Largest magnitude of the reward component
+    pub scale: f32,
+}
+
+impl Default for KpiRewardConfig {
+    fn default() -> Self {
+        Self {
+            weights: HashMap::new(),
+            default_weight: 1.0,
+            window_ms: 30 * 60 * 1000,
+            scale: 1.0,
+        }
+    }
+}
+
+impl KpiRewardConfig {
+    /// Set the weight and direction of one KPI
+    pub fn with_weight(mut self, name: &str, weight: f32, direction: Option<KpiDirection>) -> Self {
+        self.weights.insert(name.to_string(), KpiWeight { weight, direction });
+        self
+    }
+}
+
+// ============================================================================
+// Reward Model
+// ============================================================================
+
+/// KPI reward credited to the query that proposed a change
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KpiCredit {
+    /// Trajectory of the originating query
+    pub trajectory_id: u64,
+    /// Reward with the `kpi_delta` component set
+    pub reward: Reward,
+    /// Normalised, signed change per KPI
+    pub deltas: Vec<(String, f32)>,
+}
+
+/// Outcome of settling the elapsed observation windows
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct KpiSettlement {
+    /// Changes credited
+    pub credits: Vec<KpiCredit>,
+    /// Trajectories whose window closed without KPI samples
+    pub expired: Vec<u64>,
+}
+
+/// Change awaiting the end of its observation window
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct PendingChange {
+    /// Trajectory of the originating query
+    trajectory_id: u64,
+    /// When the change was applied (Unix ms)
+    applied_at: u64,
+    /// KPIs measured before the change
+    before: Vec<KPI>,
+    /// Sum and count of samples per KPI name
+    samples: HashMap<String, (f64, u32)>,
+}
+
+/// Maps KPI changes after applied actions into rewards
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct KpiRewardModel {
+    /// Configuration
+    config: KpiRewardConfig,
+    /// Changes inside their observation window
+    pending: Vec<PendingChange>,
+}
+
+impl KpiRewardModel {
+    /// Create with settings
+    pub fn new(config: KpiRewardConfig) -> Self {
+        Self {
+            config,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Get configuration
+    pub fn config(&self) -> &KpiRewardConfig {
+        &self.config
+    }
+
+    /// Start the observation window of a change
+    ///
+    /// Registering the same trajectory again restarts its window.
+    pub fn register_change(&mut self, trajectory_id: u64, before: &[KPI], applied_at: u64) {
+        self.pending.retain(|p| p.trajectory_id != trajectory_id);
+        self.pending.push(PendingChange {
+            trajectory_id,
+            applied_at,
+            before: before.to_vec(),
+            samples: HashMap::new(),
+        });
+    }
+
+    /// Record KPI samples taken after a change
+    ///
+    /// Returns `false` if the trajectory has no open window at `timestamp`.
+    pub fn observe(&mut self, trajectory_id: u64, after: &[KPI], timestamp: u64) -> bool {
+        let window_ms = self.config.window_ms;
+        let Some(change) = self
+            .pending
+            .iter_mut()
+            .find(|p| p.trajectory_id == trajectory_id)
+        else {
+            return false;
+        };
+        if timestamp < change.applied_at || timestamp >= change.applied_at + window_ms {
+            return false;
+        }
+
+        for kpi in after {
+            if let Some(value) = kpi.current_value {
+                let sample = change.samples.entry(kpi.name.clone()).or_insert((0.0, 0));
+                sample.0 += value;
+                sample.1 += 1;
+            }
+        }
+        true
+    }
+
+    /// Credit every change whose observation window has elapsed at `now`
+    pub fn settle(&mut self, now: u64) -> KpiSettlement {
+        let window_ms = self.config.window_ms;
+        let (elapsed, open): (Vec<_>, Vec<_>) = self
+            .pending
+            .drain(..)
+            .partition(|p| now >= p.applied_at + window_ms);
+        self.pending = open;
+
+        let mut settlement = KpiSettlement::default();
+        for change in elapsed {
+            match self.credit(&change) {
+                Some(credit) => settlement.credits.push(credit),
+                None => settlement.expired.push(change.trajectory_id),
+            }
+        }
+        settlement
+    }
+
+    /// Number of changes inside their observation window
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    fn credit(&self, change: &PendingChange) -> Option<KpiCredit> {
+        let mut deltas = Vec::new();
+        let mut weighted = 0.0f64;
+        let mut total_weight = 0.0f64;
+
+        for kpi in &change.before {
+            let Some(before) = kpi.current_value else { continue };
+            let Some(&(sum, count)) = change.samples.get(&kpi.name) else { continue };
+            let (weight, direction) = self.weight_of(kpi);
+            if weight == 0.0 {
+                continue;
+            }
+
+            let after = sum / count as f64;
+            let scale = if kpi.threshold.abs() > f64::EPSILON { kpi.threshold.abs() } else { 1.0 };
+            let delta = (direction.sign() * (after - before) / scale).clamp(-1.0, 1.0);
+
+            weighted += weight * delta;
+            total_weight += weight.abs();
+            deltas.push((kpi.name.clone(), delta as f32));
+        }
+
+        if deltas.is_empty() {
+            return None;
+        }
+
+        let kpi_delta = self.config.scale * (weighted / total_weight) as f32;
+        Some(KpiCredit {
+            trajectory_id: change.trajectory_id,
+            reward: Reward::default().with_kpi_delta(kpi_delta),
+            deltas,
+        })
+    }
+
+    fn weight_of(&self, kpi: &KPI) -> (f64, KpiDirection) {
+        match self.config.weights.get(&kpi.name) {
+            Some(w) => (w.weight as f64, w.direction.unwrap_or_else(|| KpiDirection::of(kpi))),
+            None => (self.config.default_weight as f64, KpiDirection::of(kpi)),
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: u64 = 1000;
+
+    fn kpi(name: &str, threshold: f64, value: f64, direction: &str) -> KPI {
+        KPI {
+            current_value: Some(value),
+            direction: Some(direction.to_string()),
+            ..KPI::new(name.to_string(), String::new(), threshold)
+        }
+    }
+
+    fn model(config: KpiRewardConfig) -> KpiRewardModel {
+        KpiRewardModel::new(KpiRewardConfig { window_ms: WINDOW, ..config })
+    }
+
+    #[test]
+    fn test_direction_and_threshold_normalisation() {
+        let mut model = model(KpiRewardConfig::default());
+        let before = [
+            kpi("throughput", 10.0, 50.0, "HIGHER_IS_BETTER"),
+            kpi("drop_rate", 2.0, 1.0, "LOWER_IS_BETTER"),
+        ];
+        model.register_change(7, &before, 0);
+        assert!(model.observe(7, &[kpi("throughput", 10.0, 55.0, "HIGHER_IS_BETTER")], 100));
+        assert!(model.observe(7, &[kpi("throughput", 10.0, 57.0, "HIGHER_IS_BETTER")], 200));
+        assert!(model.observe(7, &[kpi("drop_rate", 2.0, 2.0, "LOWER_IS_BETTER")], 300));
+
+        let settlement = model.settle(WINDOW);
+        assert_eq!(settlement.credits.len(), 1);
+        let credit = &settlement.credits[0];
+        assert_eq!(credit.trajectory_id, 7);
+        // throughput +6/10 = 0.6, drop rate +1/2 in the wrong direction = -0.5
+        assert_eq!(credit.deltas, vec![("throughput".to_string(), 0.6), ("drop_rate".to_string(), -0.5)]);
+        assert!((credit.reward.kpi_delta - 0.05).abs() < 1e-6);
+        assert!((credit.reward.total() - 0.05).abs() < 1e-6);
+        assert_eq!(model.pending(), 0);
+    }
+
+    #[test]
+    fn test_weights_override_direction() {
+        let config = KpiRewardConfig::default()
+            .with_weight("throughput", 3.0, None)
+            .with_weight("drop_rate", 1.0, Some(KpiDirection::HigherIsBetter));
+        let mut model = model(config);
+        model.register_change(1, &[kpi("throughput", 1.0, 0.0, "HIGHER_IS_BETTER"), kpi("drop_rate", 1.0, 0.0, "LOWER_IS_BETTER")], 0);
+        model.observe(1, &[kpi("throughput", 1.0, 5.0, "HIGHER_IS_BETTER"), kpi("drop_rate", 1.0, 1.0, "LOWER_IS_BETTER")], 10);
+
+        // Both deltas clamp to +1
+        let credit = &model.settle(WINDOW).credits[0];
+        assert!((credit.reward.kpi_delta - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_delayed_credit_window() {
+        let mut model = model(KpiRewardConfig::default());
+        let before = [kpi("drop_rate", 1.0, 1.0, "LOWER_IS_BETTER")];
+        model.register_change(1, &before, 0);
+        model.register_change(2, &before, 500);
+
+        assert!(model.observe(1, &[kpi("drop_rate", 1.0, 0.5, "LOWER_IS_BETTER")], 400));
+        assert!(!model.observe(3, &before, 400));
+
+        // Only the first window has elapsed
+        let settlement = model.settle(WINDOW);
+        assert_eq!(settlement.credits.len(), 1);
+        assert!((settlement.credits[0].reward.kpi_delta - 0.5).abs() < 1e-6);
+        assert_eq!(model.pending(), 1);
+
+        // Samples after the window closes are ignored; no samples means no credit
+        assert!(!model.observe(2, &before, 500 + WINDOW));
+        let settlement = model.settle(500 + WINDOW);
+        assert!(settlement.credits.is_empty());
+        assert_eq!(settlement.expired, vec![2]);
+    }
+}
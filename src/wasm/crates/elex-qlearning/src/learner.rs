@@ -0,0 +1,674 @@
+//! Pluggable TD Learners
+//!
+//! `QTable::update_q_value` bootstraps from `max Q(s',a')`, which
+//! overestimates values when rewards are noisy (user feedback). A `Learner`
+//! decides which target to bootstrap from, so the update rule can be swapped
+//! per agent through `QLearningConfig::learner`.
+//!
+//! # Update Rules
+//! ```text
+//! Q-learning:      Q(s,a) <- Q(s,a) + α[r + γ max_a' Q(s',a') - Q(s,a)]
+//! Double Q:        QA(s,a) <- QA(s,a) + α[r + γ QB(s', argmax_a' QA(s',a')) - QA(s,a)]
+//!                  (A and B swap roles at random)
+//! Expected SARSA:  Q(s,a) <- Q(s,a) + α[r + γ Σ π(a'|s') Q(s',a') - Q(s,a)]
+//!                  with π the behaviour policy's `action_probabilities`
+//! ```
+//!
+//! The table passed to `update` is always the one policies act on. Double
+//! Q-learning keeps its two estimators internally and writes their mean into
+//! that table, so action selection and federated sync see a single estimate.
+//! State a learner keeps beyond that table is saved and restored through
+//! `Learner::export_state` / `import_state`.
+
+use crate::approx::LinearApproximator;
+use crate::format::{self, ExportOptions, FormatError};
+use crate::policy::{Action, EpsilonGreedy, Policy, StateView};
+use crate::qtable::{QLearningConfig, QTable, StateHash};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Learner Selection
+// ============================================================================
+
+/// Temporal-difference update rule
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LearnerKind {
+    /// Off-policy Q-learning (max bootstrap)
+    #[default]
+    QLearning,
+
+    /// Double Q-learning with two decoupled estimators
+    DoubleQLearning,
+
+    /// Expected SARSA under the behaviour policy
+    ExpectedSarsa,
+
+    /// Linear function approximation blended with the table
+    LinearApproximation,
+}
+
+impl LearnerKind {
+    /// Get learner name
+    pub fn name(&self) -> &'static str {
+        match self {
+            LearnerKind::QLearning => "q-learning",
+            LearnerKind::DoubleQLearning => "double-q-learning",
+            LearnerKind::ExpectedSarsa => "expected-sarsa",
+            LearnerKind::LinearApproximation => "linear-approximation",
+        }
+    }
+}
+
+/// Create the learner selected by a configuration
+///
+/// `seed` drives the estimator choice of Double Q-learning.
+pub fn create_learner(config: &QLearningConfig, seed: u64) -> Box<dyn Learner> {
+    match config.learner {
+        LearnerKind::QLearning => Box::new(QLearning),
+        LearnerKind::DoubleQLearning => Box::new(DoubleQLearning::new(config, seed)),
+        LearnerKind::ExpectedSarsa => Box::new(ExpectedSarsa::new(config.epsilon)),
+        LearnerKind::LinearApproximation => {
+            Box::new(LinearApproximator::new(config.approximation.clone()))
+        }
+    }
+}
+
+// ============================================================================
+// Learner Trait
+// ============================================================================
+
+/// TD update rule applied to a Q-table
+pub trait Learner: Send + Sync {
+    /// Apply one transition and return the new Q(s,a) in `table`
+    fn update(
+        &mut self,
+        table: &mut QTable,
+        state: StateHash,
+        action: Action,
+        reward: f32,
+        next_state: StateHash,
+    ) -> f32;
+
+    /// Apply a transition that ends the interaction and return the new Q(s,a)
+    ///
+    /// The target is the reward alone; nothing is bootstrapped.
+    fn update_terminal(
+        &mut self,
+        table: &mut QTable,
+        state: StateHash,
+        action: Action,
+        reward: f32,
+    ) -> f32;
+
+    /// Apply one transition with its step scaled by `weight`
+    ///
+    /// Used by prioritized replay to apply importance-sampling weights.
+    /// Returns the TD error before the update.
+    fn update_weighted(
+        &mut self,
+        table: &mut QTable,
+        state: StateHash,
+        action: Action,
+        reward: f32,
+        next_state: StateHash,
+        weight: f32,
+    ) -> f32;
+
+    /// Add reward credited after Q(s,a) was learned
+    ///
+    /// Moves Q(s,a) by `alpha * delta`, as if `delta` had been part of the
+    /// reward of the last update, without counting another visit or
+    /// outcome. Returns the new Q(s,a).
+    fn credit(&mut self, table: &mut QTable, state: StateHash, action: Action, delta: f32) -> f32 {
+        let value = table.get_q_value(state, action) + table.config().alpha * delta;
+        table.set_q_value(state, action, value);
+        value
+    }
+
+    /// Inform the learner of the policy selecting actions
+    ///
+    /// Learners that bootstrap from the behaviour policy keep a snapshot.
+    fn set_behaviour(&mut self, _policy: &dyn Policy) {}
+
+    /// Record the query embedding of a state (used by approximating learners)
+    fn observe(&mut self, _state: StateHash, _embedding: &[f32]) {}
+
+    /// Serialize the state kept beyond the shared table
+    ///
+    /// Returns None for learners that keep no state of their own.
+    fn export_state(&self) -> Result<Option<Vec<u8>>, FormatError> {
+        Ok(None)
+    }
+
+    /// Restore state written by `export_state`
+    fn import_state(&mut self, _data: &[u8]) -> Result<(), FormatError> {
+        Ok(())
+    }
+
+    /// Q-values a policy should act on in `state`
+    ///
+    /// Tabular learners return the table's own values.
+    fn view<'t>(&self, table: &'t QTable, state: StateHash) -> StateView<'t> {
+        StateView::of(table, state)
+    }
+
+    /// Get learner kind
+    fn kind(&self) -> LearnerKind;
+}
+
+/// `Q(s,a) <- Q(s,a) + α w [r + γ next_value - Q(s,a)]`; returns the TD error
+pub(crate) fn weighted_update(
+    table: &mut QTable,
+    state: StateHash,
+    action: Action,
+    reward: f32,
+    next_value: f32,
+    weight: f32,
+) -> f32 {
+    let (alpha, gamma) = (table.config().alpha, table.config().gamma);
+    let current = table.get_q_value(state, action);
+    let td_error = reward + gamma * next_value - current;
+    table.record_update(state, action, current + alpha * weight * td_error, reward);
+    td_error
+}
+
+// ============================================================================
+// Q-Learning
+// ============================================================================
+
+/// Vanilla off-policy Q-learning
+#[derive(Clone, Copy, Debug, Default)]
+pub struct QLearning;
+
+impl Learner for QLearning {
+    fn update(
+        &mut self,
+        table: &mut QTable,
+        state: StateHash,
+        action: Action,
+        reward: f32,
+        next_state: StateHash,
+    ) -> f32 {
+        let next_max_q = table.get_max_q(next_state);
+        table.update_q_value(state, action, reward, next_max_q)
+    }
+
+    fn update_terminal(
+        &mut self,
+        table: &mut QTable,
+        state: StateHash,
+        action: Action,
+        reward: f32,
+    ) -> f32 {
+        table.update_q_value(state, action, reward, 0.0)
+    }
+
+    fn update_weighted(
+        &mut self,
+        table: &mut QTable,
+        state: StateHash,
+        action: Action,
+        reward: f32,
+        next_state: StateHash,
+        weight: f32,
+    ) -> f32 {
+        let next_max_q = table.get_max_q(next_state);
+        weighted_update(table, state, action, reward, next_max_q, weight)
+    }
+
+    fn kind(&self) -> LearnerKind {
+        LearnerKind::QLearning
+    }
+}
+
+// ============================================================================
+// Double Q-Learning
+// ============================================================================
+
+/// Double Q-learning (van Hasselt, 2010)
+///
+/// Selecting the greedy action with one estimator and evaluating it with
+/// the other removes the maximisation bias of Q-learning. Entries missing
+/// from an estimator fall back to the shared table, so an agent can switch
+/// to this learner without losing what it already learned.
+#[derive(Clone)]
+pub struct DoubleQLearning {
+    /// Estimator A
+    table_a: QTable,
+    /// Estimator B
+    table_b: QTable,
+    /// RNG choosing which estimator to update
+    rng: StdRng,
+}
+
+impl DoubleQLearning {
+    /// Create with empty estimators
+    pub fn new(config: &QLearningConfig, seed: u64) -> Self {
+        Self {
+            table_a: QTable::new(config.clone()),
+            table_b: QTable::new(config.clone()),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Get the two estimators
+    pub fn estimators(&self) -> (&QTable, &QTable) {
+        (&self.table_a, &self.table_b)
+    }
+
+    /// Value of an estimator, falling back to the shared table
+    fn value(estimator: &QTable, table: &QTable, state: StateHash, action: Action) -> f32 {
+        estimator
+            .entry(state, action)
+            .map(|e| e.value)
+            .unwrap_or_else(|| table.get_q_value(state, action))
+    }
+
+    /// Update one estimator with a step scaled by `weight`
+    ///
+    /// `next_state` is None for a terminal transition. Returns the new mean
+    /// estimate and the TD error before the update.
+    fn apply(
+        &mut self,
+        table: &mut QTable,
+        state: StateHash,
+        action: Action,
+        reward: f32,
+        next_state: Option<StateHash>,
+        weight: f32,
+    ) -> (f32, f32) {
+        let (alpha, gamma) = (table.config().alpha, table.config().gamma);
+        let update_a = self.rng.gen::<bool>();
+        let (select, evaluate) = if update_a {
+            (&self.table_a, &self.table_b)
+        } else {
+            (&self.table_b, &self.table_a)
+        };
+
+        // a* = argmax_a' Q_select(s',a'), evaluated by the other estimator
+        let next_value = next_state.map_or(0.0, |next_state| {
+            let best_next = Action::all()
+                .iter()
+                .copied()
+                .max_by(|&a, &b| {
+                    Self::value(select, table, next_state, a)
+                        .partial_cmp(&Self::value(select, table, next_state, b))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .unwrap_or(Action::DirectAnswer);
+            Self::value(evaluate, table, next_state, best_next)
+        });
+        let target = reward + gamma * next_value;
+
+        let current = Self::value(select, table, state, action);
+        let td_error = target - current;
+        let updated = current + alpha * weight * td_error;
+        let other = Self::value(evaluate, table, state, action);
+
+        let estimator = if update_a { &mut self.table_a } else { &mut self.table_b };
+        estimator.record_update(state, action, updated, reward);
+
+        let mean = (updated + other) / 2.0;
+        table.record_update(state, action, mean, reward);
+        (mean, td_error)
+    }
+}
+
+impl Learner for DoubleQLearning {
+    fn update(
+        &mut self,
+        table: &mut QTable,
+        state: StateHash,
+        action: Action,
+        reward: f32,
+        next_state: StateHash,
+    ) -> f32 {
+        self.apply(table, state, action, reward, Some(next_state), 1.0).0
+    }
+
+    fn update_terminal(
+        &mut self,
+        table: &mut QTable,
+        state: StateHash,
+        action: Action,
+        reward: f32,
+    ) -> f32 {
+        self.apply(table, state, action, reward, None, 1.0).0
+    }
+
+    fn update_weighted(
+        &mut self,
+        table: &mut QTable,
+        state: StateHash,
+        action: Action,
+        reward: f32,
+        next_state: StateHash,
+        weight: f32,
+    ) -> f32 {
+        self.apply(table, state, action, reward, Some(next_state), weight).1
+    }
+
+    /// Shifts both estimators along with the table, so the mean written by
+    /// the next update keeps the credit
+    fn credit(&mut self, table: &mut QTable, state: StateHash, action: Action, delta: f32) -> f32 {
+        let step = table.config().alpha * delta;
+        let a = Self::value(&self.table_a, table, state, action) + step;
+        let b = Self::value(&self.table_b, table, state, action) + step;
+        self.table_a.set_q_value(state, action, a);
+        self.table_b.set_q_value(state, action, b);
+
+        let value = table.get_q_value(state, action) + step;
+        table.set_q_value(state, action, value);
+        value
+    }
+
+    /// Both estimators as versioned Q-table frames, the first one
+    /// length-prefixed (u64, little-endian)
+    fn export_state(&self) -> Result<Option<Vec<u8>>, FormatError> {
+        let options = ExportOptions::default();
+        let a = format::encode(&self.table_a, &options)?;
+        let b = format::encode(&self.table_b, &options)?;
+
+        let mut data = Vec::with_capacity(8 + a.len() + b.len());
+        data.extend_from_slice(&(a.len() as u64).to_le_bytes());
+        data.extend_from_slice(&a);
+        data.extend_from_slice(&b);
+        Ok(Some(data))
+    }
+
+    fn import_state(&mut self, data: &[u8]) -> Result<(), FormatError> {
+        let truncated = |expected: usize| FormatError::Truncated { expected, actual: data.len() };
+        let prefix: [u8; 8] = data.get(..8).and_then(|p| p.try_into().ok()).ok_or(truncated(8))?;
+        let split = usize::try_from(u64::from_le_bytes(prefix)).unwrap_or(usize::MAX);
+        let (a, b) = data[8..]
+            .split_at_checked(split)
+            .ok_or(truncated(8usize.saturating_add(split)))?;
+
+        let config = self.table_a.config().clone();
+        let (_, table_a) = format::decode_for(a, &config)?;
+        let (_, table_b) = format::decode_for(b, &config)?;
+        self.table_a = table_a;
+        self.table_b = table_b;
+        Ok(())
+    }
+
+    fn kind(&self) -> LearnerKind {
+        LearnerKind::DoubleQLearning
+    }
+}
+
+// ============================================================================
+// Expected SARSA
+// ============================================================================
+
+/// Expected SARSA under the agent's behaviour policy
+///
+/// The expectation weighs Q(s',·) by the `action_probabilities` of a
+/// snapshot of the behaviour policy, refreshed through `set_behaviour`.
+/// Until then the behaviour is a fixed-epsilon `EpsilonGreedy`.
+#[derive(Clone)]
+pub struct ExpectedSarsa {
+    /// Snapshot of the behaviour policy
+    behaviour: Box<dyn Policy>,
+}
+
+impl ExpectedSarsa {
+    /// Create assuming an epsilon-greedy behaviour with a fixed exploration rate
+    pub fn new(epsilon: f32) -> Self {
+        let epsilon = epsilon.clamp(0.0, 1.0);
+        Self {
+            behaviour: Box::new(EpsilonGreedy::new(0, true).with_epsilon(epsilon, epsilon, 1.0)),
+        }
+    }
+
+    /// Current behaviour policy snapshot
+    pub fn behaviour(&self) -> &dyn Policy {
+        self.behaviour.as_ref()
+    }
+
+    /// Expected Q(s',·) under the behaviour policy
+    pub fn expected_value(&self, table: &QTable, state: StateHash) -> f32 {
+        let probabilities = self.behaviour.action_probabilities(table, state);
+        Action::all()
+            .iter()
+            .map(|&a| probabilities[a.index() as usize] * table.get_q_value(state, a))
+            .sum()
+    }
+}
+
+impl std::fmt::Debug for ExpectedSarsa {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExpectedSarsa")
+            .field("behaviour", &self.behaviour.name())
+            .finish()
+    }
+}
+
+impl Learner for ExpectedSarsa {
+    fn update(
+        &mut self,
+        table: &mut QTable,
+        state: StateHash,
+        action: Action,
+        reward: f32,
+        next_state: StateHash,
+    ) -> f32 {
+        let expected = self.expected_value(table, next_state);
+        table.update_q_value(state, action, reward, expected)
+    }
+
+    fn update_terminal(
+        &mut self,
+        table: &mut QTable,
+        state: StateHash,
+        action: Action,
+        reward: f32,
+    ) -> f32 {
+        table.update_q_value(state, action, reward, 0.0)
+    }
+
+    fn update_weighted(
+        &mut self,
+        table: &mut QTable,
+        state: StateHash,
+        action: Action,
+        reward: f32,
+        next_state: StateHash,
+        weight: f32,
+    ) -> f32 {
+        let expected = self.expected_value(table, next_state);
+        weighted_update(table, state, action, reward, expected, weight)
+    }
+
+    fn set_behaviour(&mut self, policy: &dyn Policy) {
+        self.behaviour = policy.boxed_clone();
+    }
+
+    fn kind(&self) -> LearnerKind {
+        LearnerKind::ExpectedSarsa
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exploration::BoltzmannPolicy;
+
+    fn config(learner: LearnerKind) -> QLearningConfig {
+        QLearningConfig {
+            learner,
+            ..QLearningConfig::elex_default()
+        }
+    }
+
+    #[test]
+    fn test_create_learner() {
+        for kind in [
+            LearnerKind::QLearning,
+            LearnerKind::DoubleQLearning,
+            LearnerKind::ExpectedSarsa,
+            LearnerKind::LinearApproximation,
+        ] {
+            assert_eq!(create_learner(&config(kind), 7).kind(), kind);
+        }
+        assert_eq!(QLearningConfig::default().learner, LearnerKind::QLearning);
+    }
+
+    #[test]
+    fn test_q_learning_matches_table_update() {
+        let mut table = QTable::new(config(LearnerKind::QLearning));
+        let mut reference = table.clone();
+        table.set_q_value(2, Action::ConsultPeer, 0.6);
+        reference.set_q_value(2, Action::ConsultPeer, 0.6);
+
+        let q = QLearning.update(&mut table, 1, Action::DirectAnswer, 1.0, 2);
+        let expected = reference.update_q_value(1, Action::DirectAnswer, 1.0, 0.6);
+        assert_eq!(q, expected);
+
+        // Half weight moves half as far: δ = 1 + 0.95 * 0.6 - q
+        let td_error = QLearning.update_weighted(&mut table, 1, Action::DirectAnswer, 1.0, 2, 0.5);
+        assert!((td_error - (1.57 - q)).abs() < 1e-6);
+        assert!((table.get_q_value(1, Action::DirectAnswer) - (q + 0.05 * td_error)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_expected_sarsa_target() {
+        let mut table = QTable::new(config(LearnerKind::ExpectedSarsa));
+        table.set_q_value(2, Action::DirectAnswer, 1.0);
+
+        let mut learner = ExpectedSarsa::new(0.5);
+        // (1 - 0.5) * 1.0 + 0.5 * (1.0 / 5)
+        assert!((learner.expected_value(&table, 2) - 0.6).abs() < 1e-6);
+
+        learner.set_behaviour(&EpsilonGreedy::exploit_only(1));
+        let q = learner.update(&mut table, 1, Action::DirectAnswer, 0.0, 2);
+        assert!((q - 0.1 * 0.95).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_expected_sarsa_follows_behaviour_policy() {
+        let mut table = QTable::new(config(LearnerKind::ExpectedSarsa));
+        table.set_q_value(2, Action::DirectAnswer, 1.0);
+
+        // Boltzmann has no exploration rate, yet still explores
+        let policy = BoltzmannPolicy::new(3, 1.0);
+        let mut learner = ExpectedSarsa::new(0.0);
+        learner.set_behaviour(&policy);
+        assert_eq!(learner.behaviour().name(), "boltzmann");
+
+        // e / (e + 4) of the mass on the only non-zero value
+        let e = std::f32::consts::E;
+        let expected = learner.expected_value(&table, 2);
+        assert!((expected - e / (e + 4.0)).abs() < 1e-6);
+        assert!(expected < 1.0);
+    }
+
+    #[test]
+    fn test_double_q_writes_mean_of_estimators() {
+        let mut table = QTable::new(config(LearnerKind::DoubleQLearning));
+        let mut learner = DoubleQLearning::new(table.config(), 3);
+
+        for _ in 0..20 {
+            learner.update(&mut table, 1, Action::DirectAnswer, 1.0, 1);
+        }
+
+        let (a, b) = learner.estimators();
+        let mean = (a.get_q_value(1, Action::DirectAnswer) + b.get_q_value(1, Action::DirectAnswer)) / 2.0;
+        assert!((table.get_q_value(1, Action::DirectAnswer) - mean).abs() < 1e-6);
+        assert_eq!(table.entry(1, Action::DirectAnswer).unwrap().visit_count, 20);
+        assert!(a.len() == 1 && b.len() == 1);
+    }
+
+    #[test]
+    fn test_terminal_update_does_not_bootstrap() {
+        for kind in [
+            LearnerKind::QLearning,
+            LearnerKind::DoubleQLearning,
+            LearnerKind::ExpectedSarsa,
+            LearnerKind::LinearApproximation,
+        ] {
+            let mut table = QTable::new(config(kind));
+            table.set_q_value(1, Action::ConsultPeer, 10.0);
+            let mut learner = create_learner(table.config(), 5);
+
+            // Q(1, DirectAnswer) = 0 + 0.1 * (1 - 0), whatever Q(1, ·) holds;
+            // Double Q averages it with the untouched estimator
+            let expected = if kind == LearnerKind::DoubleQLearning { 0.05 } else { 0.1 };
+            let q = learner.update_terminal(&mut table, 1, Action::DirectAnswer, 1.0);
+            assert!((q - expected).abs() < 1e-6, "{:?}: {}", kind, q);
+        }
+    }
+
+    #[test]
+    fn test_double_q_credit_survives_next_update() {
+        let mut table = QTable::new(config(LearnerKind::DoubleQLearning));
+        let mut learner = DoubleQLearning::new(table.config(), 3);
+        learner.update_terminal(&mut table, 1, Action::DirectAnswer, 1.0);
+        let (mut plain_table, mut plain) = (table.clone(), learner.clone());
+
+        let before = table.get_q_value(1, Action::DirectAnswer);
+        let credited = learner.credit(&mut table, 1, Action::DirectAnswer, 0.5);
+        assert!((credited - before - 0.05).abs() < 1e-6);
+        assert_eq!(table.entry(1, Action::DirectAnswer).unwrap().visit_count, 1);
+
+        // The next update rewrites the mean of the estimators: the updated
+        // one keeps (1 - α) of the credit, the other all of it
+        let q = learner.update_terminal(&mut table, 1, Action::DirectAnswer, 0.0);
+        let q_plain = plain.update_terminal(&mut plain_table, 1, Action::DirectAnswer, 0.0);
+        assert!((q - q_plain - 0.05 * (1.0 - 0.1 / 2.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_double_q_state_round_trip() {
+        let mut table = QTable::new(config(LearnerKind::DoubleQLearning));
+        let mut learner = DoubleQLearning::new(table.config(), 3);
+        for step in 0..10 {
+            learner.update(&mut table, step % 3, Action::all()[step as usize % 5], 1.0, 0);
+        }
+
+        let data = learner.export_state().unwrap().unwrap();
+        let mut restored = DoubleQLearning::new(table.config(), 3);
+        restored.import_state(&data).unwrap();
+        let (a, b) = learner.estimators();
+        let (restored_a, restored_b) = restored.estimators();
+        assert_eq!((restored_a.len(), restored_b.len()), (a.len(), b.len()));
+        for state in 0..3 {
+            for &action in Action::all() {
+                assert_eq!(restored_a.entry(state, action), a.entry(state, action));
+                assert_eq!(restored_b.entry(state, action), b.entry(state, action));
+            }
+        }
+
+        assert!(matches!(restored.import_state(&data[..20]), Err(FormatError::Truncated { .. })));
+        let other = QTable::new(QLearningConfig { gamma: 0.5, ..config(LearnerKind::DoubleQLearning) });
+        let mut mismatched = DoubleQLearning::new(other.config(), 3);
+        assert!(matches!(mismatched.import_state(&data), Err(FormatError::ConfigMismatch { .. })));
+
+        // Stateless learners have nothing to save
+        assert_eq!(QLearning.export_state(), Ok(None));
+    }
+
+    #[test]
+    fn test_double_q_reduces_overestimation() {
+        // Zero-mean noisy rewards on every action of the next state: the
+        // true value of every action is 0, so any positive estimate is bias.
+        let rewards = [1.0, -1.0, 0.8, -0.8, 0.6, -0.6, 0.4, -0.4];
+        let mut q_table = QTable::new(config(LearnerKind::QLearning));
+        let mut dq_table = QTable::new(config(LearnerKind::DoubleQLearning));
+        let mut double_q = DoubleQLearning::new(dq_table.config(), 11);
+
+        for step in 0..400 {
+            let action = Action::all()[step % 5];
+            let reward = rewards[(step * 7 + step / 5) % rewards.len()];
+            QLearning.update(&mut q_table, 2, action, reward, 2);
+            double_q.update(&mut dq_table, 2, action, reward, 2);
+        }
+
+        assert!(dq_table.get_max_q(2) < q_table.get_max_q(2));
+    }
+}
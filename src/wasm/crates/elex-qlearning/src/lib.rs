@@ -37,9 +37,18 @@
 pub mod batch;
 pub mod encoding;
 pub mod qtable;
+pub mod pruning;
+pub mod format;
+pub mod learner;
+pub mod approx;
 pub mod policy;
+pub mod exploration;
 pub mod replay;
 pub mod trajectory;
+pub mod traces;
+pub mod trainer;
+pub mod ope;
+pub mod kpi_reward;
 
 // Re-export main functions and types
 pub use batch::{
@@ -54,8 +63,19 @@ pub use batch::batch_q_update_simd;
 
 // Re-export Q-learning types
 pub use qtable::{
-    QTable, QEntry, QLearningConfig, QTableStats, State, StateHash, Reward,
+    QTable, QEntry, QRow, QLearningConfig, QTableStats, State, StateHash, Reward,
+    ACTION_COUNT,
 };
+pub use pruning::{PruningConfig, QueryTypePrior, PruneReport};
+pub use format::{ExportOptions, FormatError, QTableHeader};
+
+// Re-export learner types
+pub use learner::{
+    Learner, LearnerKind, QLearning, DoubleQLearning, ExpectedSarsa, create_learner,
+};
+
+// Re-export function approximation types
+pub use approx::{LinearApproximator, ApproximationConfig};
 
 // Re-export encoding types
 pub use encoding::{
@@ -65,7 +85,12 @@ pub use encoding::{
 
 // Re-export policy types
 pub use policy::{
-    Action, Policy, EpsilonGreedy, ActionSelection,
+    Action, Policy, EpsilonGreedy, ActionSelection, QValues, StateView,
+};
+
+// Re-export exploration policies
+pub use exploration::{
+    PolicyConfig, UcbPolicy, BoltzmannPolicy, ThompsonSampling, create_policy,
 };
 
 // Re-export replay buffer types
@@ -81,6 +106,23 @@ pub use trajectory::{
     TrajectoryOutcome,
 };
 
+// Re-export multi-step update routines
+pub use traces::{trajectory_update, n_step_update, q_lambda_update};
+
+// Re-export replay training types
+pub use trainer::{ReplayTrainer, ReplayConfig, ReplayReport};
+
+// Re-export off-policy evaluation types
+pub use ope::{
+    OffPolicyEvaluator, OpeConfig, OpeEstimate, OpeReport, Estimator, TargetPolicy,
+    EpsilonGreedyTarget, TrajectoryLog, LoggedEpisode, LoggedStep,
+};
+
+// Re-export KPI reward model types
+pub use kpi_reward::{
+    KpiRewardModel, KpiRewardConfig, KpiWeight, KpiDirection, KpiCredit, KpiSettlement,
+};
+
 /// Version information
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -0,0 +1,472 @@
+//! Off-Policy Evaluation
+//!
+//! Estimates how a candidate (target) policy would have performed on
+//! trajectories logged under the behaviour policy, before it is deployed.
+//! Every logged step must carry the behaviour policy's probability of the
+//! action it took (`Transition::behavior_prob`).
+//!
+//! # Estimators
+//! ```text
+//! ρ_t  = π(a_t|s_t) / μ(a_t|s_t)                  per-step ratio
+//! G    = Σ_t γ^t r_t                              discounted return
+//!
+//! IS:  mean_i (Π_t ρ_t) G_i
+//! WIS: Σ_i (Π_t ρ_t) G_i / Σ_i Π_t ρ_t
+//! DR:  mean_i V_0,  V_t = V̂(s_t) + ρ_t (r_t + γ V_{t+1} - Q̂(s_t,a_t)),  V_T = 0
+//!      with V̂(s) = Σ_a π(a|s) Q̂(s,a) from a Q-table model
+//! ```
+//!
+//! IS is unbiased but high-variance, WIS trades a small bias for much lower
+//! variance, and DR stays unbiased while using the Q-table model as a
+//! control variate. Confidence intervals are percentile bootstrap intervals
+//! over episodes, with a seeded RNG so reports are reproducible.
+
+use crate::policy::Action;
+use crate::qtable::{QTable, StateHash};
+use crate::trajectory::{AgentTrajectory, AgentTrajectoryBuffer, TrajectoryOutcome};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Trajectory Log
+// ============================================================================
+
+/// One logged decision
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LoggedStep {
+    /// State hash
+    pub state: StateHash,
+    /// Action taken by the behaviour policy
+    pub action: Action,
+    /// Reward received
+    pub reward: f32,
+    /// Behaviour policy's probability of `action`
+    pub behavior_prob: f32,
+}
+
+/// One completed logged trajectory
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LoggedEpisode {
+    /// Trajectory outcome
+    pub outcome: TrajectoryOutcome,
+    /// Decisions in order
+    pub steps: Vec<LoggedStep>,
+}
+
+impl LoggedEpisode {
+    /// Convert a completed trajectory
+    ///
+    /// Returns `None` for ongoing or empty trajectories and for trajectories
+    /// with a step lacking a positive behaviour probability.
+    pub fn from_trajectory(trajectory: &AgentTrajectory) -> Option<Self> {
+        if trajectory.is_ongoing() || trajectory.transitions.is_empty() {
+            return None;
+        }
+
+        let steps = trajectory
+            .transitions
+            .iter()
+            .map(|t| {
+                t.behavior_prob.filter(|&p| p > 0.0).map(|behavior_prob| LoggedStep {
+                    state: t.state,
+                    action: t.action,
+                    reward: t.reward,
+                    behavior_prob,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self {
+            outcome: trajectory.outcome,
+            steps,
+        })
+    }
+
+    /// Discounted return
+    pub fn discounted_return(&self, gamma: f32) -> f32 {
+        self.steps
+            .iter()
+            .rev()
+            .fold(0.0, |acc, step| step.reward + gamma * acc)
+    }
+}
+
+/// Exported trajectory log
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrajectoryLog {
+    /// Usable episodes
+    pub episodes: Vec<LoggedEpisode>,
+    /// Trajectories left out (ongoing or without behaviour probabilities)
+    pub skipped: usize,
+}
+
+impl TrajectoryLog {
+    /// Collect the usable trajectories of a buffer
+    pub fn from_buffer(buffer: &AgentTrajectoryBuffer) -> Self {
+        let mut log = Self::default();
+        for trajectory in buffer.iter() {
+            match LoggedEpisode::from_trajectory(trajectory) {
+                Some(episode) => log.episodes.push(episode),
+                None => log.skipped += 1,
+            }
+        }
+        log
+    }
+
+    /// Export to JSON
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(self).map_err(|e| format!("Failed to export trajectory log: {}", e))
+    }
+
+    /// Import from JSON
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("Failed to import trajectory log: {}", e))
+    }
+}
+
+// ============================================================================
+// Target Policies
+// ============================================================================
+
+/// Action probabilities of a candidate policy
+pub trait TargetPolicy {
+    /// Probability of taking `action` in `state`
+    fn probability(&self, state: StateHash, action: Action) -> f32;
+}
+
+impl<F: Fn(StateHash, Action) -> f32> TargetPolicy for F {
+    fn probability(&self, state: StateHash, action: Action) -> f32 {
+        self(state, action)
+    }
+}
+
+/// Epsilon-greedy over a Q-table, with uniform exploration over all actions
+pub struct EpsilonGreedyTarget<'a> {
+    /// Q-table the greedy action is taken from
+    pub q_table: &'a QTable,
+    /// Exploration rate
+    pub epsilon: f32,
+}
+
+impl TargetPolicy for EpsilonGreedyTarget<'_> {
+    fn probability(&self, state: StateHash, action: Action) -> f32 {
+        let epsilon = self.epsilon.clamp(0.0, 1.0);
+        let mut p = epsilon / Action::all().len() as f32;
+        if action == self.q_table.get_best_action(state) {
+            p += 1.0 - epsilon;
+        }
+        p
+    }
+}
+
+// ============================================================================
+// Evaluator
+// ============================================================================
+
+/// Off-policy estimator
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Estimator {
+    /// Trajectory-wise importance sampling
+    ImportanceSampling,
+    /// Weighted (self-normalised) importance sampling
+    WeightedImportanceSampling,
+    /// Per-decision doubly robust
+    DoublyRobust,
+}
+
+impl Estimator {
+    /// All estimators, in report order
+    pub fn all() -> &'static [Estimator] {
+        &[
+            Estimator::ImportanceSampling,
+            Estimator::WeightedImportanceSampling,
+            Estimator::DoublyRobust,
+        ]
+    }
+}
+
+/// Evaluation settings
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OpeConfig {
+    /// Discount factor for returns
+    pub gamma: f32,
+    /// Confidence level of the intervals (e.g. 0.95)
+    pub confidence: f32,
+    /// Bootstrap resamples
+    pub bootstrap_samples: usize,
+    /// Bootstrap RNG seed
+    pub seed: u64,
+}
+
+impl Default for OpeConfig {
+    fn default() -> Self {
+        Self {
+            gamma: 0.95,
+            confidence: 0.95,
+            bootstrap_samples: 1000,
+            seed: 42,
+        }
+    }
+}
+
+/// Estimate with its confidence interval
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OpeEstimate {
+    /// Estimator used
+    pub estimator: Estimator,
+    /// Point estimate of the target policy's value
+    pub value: f32,
+    /// Lower confidence bound
+    pub lower: f32,
+    /// Upper confidence bound
+    pub upper: f32,
+}
+
+/// Evaluation report
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OpeReport {
+    /// Episodes evaluated
+    pub episodes: usize,
+    /// Average discounted return of the behaviour policy
+    pub behavior_value: f32,
+    /// Effective sample size of the importance weights, `(Σw)² / Σw²`
+    pub effective_sample_size: f32,
+    /// One estimate per estimator
+    pub estimates: Vec<OpeEstimate>,
+}
+
+impl OpeReport {
+    /// Get the estimate of one estimator
+    pub fn estimate(&self, estimator: Estimator) -> Option<&OpeEstimate> {
+        self.estimates.iter().find(|e| e.estimator == estimator)
+    }
+}
+
+/// Per-episode quantities the estimators are built from
+#[derive(Clone, Copy, Debug)]
+struct EpisodeTerms {
+    /// Discounted return
+    ret: f32,
+    /// Product of per-step ratios
+    weight: f32,
+    /// Doubly robust value
+    dr: f32,
+}
+
+/// Off-policy evaluator
+#[derive(Clone, Debug, Default)]
+pub struct OffPolicyEvaluator {
+    /// Configuration
+    config: OpeConfig,
+}
+
+impl OffPolicyEvaluator {
+    /// Create with settings
+    pub fn new(config: OpeConfig) -> Self {
+        Self { config }
+    }
+
+    /// Evaluate a target policy on a log
+    ///
+    /// `model` is the Q-table used by the doubly robust estimator; an empty
+    /// table reduces DR to per-decision importance sampling.
+    pub fn evaluate(
+        &self,
+        log: &TrajectoryLog,
+        target: &dyn TargetPolicy,
+        model: &QTable,
+    ) -> Result<OpeReport, String> {
+        if log.episodes.is_empty() {
+            return Err("No complete trajectories with behaviour probabilities".to_string());
+        }
+
+        let terms: Vec<EpisodeTerms> = log
+            .episodes
+            .iter()
+            .map(|episode| self.episode_terms(episode, target, model))
+            .collect();
+
+        let weight_sum: f32 = terms.iter().map(|t| t.weight).sum();
+        let weight_sq_sum: f32 = terms.iter().map(|t| t.weight * t.weight).sum();
+        let effective_sample_size = if weight_sq_sum > 0.0 {
+            weight_sum * weight_sum / weight_sq_sum
+        } else {
+            0.0
+        };
+
+        let all: Vec<usize> = (0..terms.len()).collect();
+        let estimates = Estimator::all()
+            .iter()
+            .map(|&estimator| {
+                let value = estimate(estimator, &terms, &all);
+                let (lower, upper) = self.bootstrap(estimator, &terms);
+                OpeEstimate { estimator, value, lower, upper }
+            })
+            .collect();
+
+        Ok(OpeReport {
+            episodes: terms.len(),
+            behavior_value: terms.iter().map(|t| t.ret).sum::<f32>() / terms.len() as f32,
+            effective_sample_size,
+            estimates,
+        })
+    }
+
+    fn episode_terms(&self, episode: &LoggedEpisode, target: &dyn TargetPolicy, model: &QTable) -> EpisodeTerms {
+        let gamma = self.config.gamma;
+        let ratio = |step: &LoggedStep| target.probability(step.state, step.action) / step.behavior_prob;
+
+        let weight = episode.steps.iter().map(ratio).product();
+
+        let mut dr = 0.0;
+        for step in episode.steps.iter().rev() {
+            let v_hat: f32 = Action::all()
+                .iter()
+                .map(|&a| target.probability(step.state, a) * model.get_q_value(step.state, a))
+                .sum();
+            let q_hat = model.get_q_value(step.state, step.action);
+            dr = v_hat + ratio(step) * (step.reward + gamma * dr - q_hat);
+        }
+
+        EpisodeTerms {
+            ret: episode.discounted_return(gamma),
+            weight,
+            dr,
+        }
+    }
+
+    /// Percentile bootstrap interval over episodes
+    fn bootstrap(&self, estimator: Estimator, terms: &[EpisodeTerms]) -> (f32, f32) {
+        let mut rng = StdRng::seed_from_u64(self.config.seed);
+        let mut samples: Vec<f32> = (0..self.config.bootstrap_samples.max(1))
+            .map(|_| {
+                let indices: Vec<usize> = (0..terms.len()).map(|_| rng.gen_range(0..terms.len())).collect();
+                estimate(estimator, terms, &indices)
+            })
+            .collect();
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let tail = (1.0 - self.config.confidence.clamp(0.0, 1.0)) / 2.0;
+        let last = samples.len() - 1;
+        let lower = samples[(tail * last as f32).floor() as usize];
+        let upper = samples[((1.0 - tail) * last as f32).ceil() as usize];
+        (lower, upper)
+    }
+}
+
+/// Estimate over a (re)sample of episodes
+fn estimate(estimator: Estimator, terms: &[EpisodeTerms], indices: &[usize]) -> f32 {
+    let n = indices.len() as f32;
+    match estimator {
+        Estimator::ImportanceSampling => {
+            indices.iter().map(|&i| terms[i].weight * terms[i].ret).sum::<f32>() / n
+        }
+        Estimator::WeightedImportanceSampling => {
+            let weights: f32 = indices.iter().map(|&i| terms[i].weight).sum();
+            if weights <= 0.0 {
+                return 0.0;
+            }
+            indices.iter().map(|&i| terms[i].weight * terms[i].ret).sum::<f32>() / weights
+        }
+        Estimator::DoublyRobust => indices.iter().map(|&i| terms[i].dr).sum::<f32>() / n,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qtable::QLearningConfig;
+    use crate::replay::Transition;
+
+    /// One-step episodes: behaviour picks DirectAnswer or Escalate uniformly;
+    /// DirectAnswer earns 1.0 and Escalate 0.0.
+    fn bandit_log(episodes: usize) -> TrajectoryLog {
+        let episodes = (0..episodes)
+            .map(|i| {
+                let (action, reward) = if i % 2 == 0 {
+                    (Action::DirectAnswer, 1.0)
+                } else {
+                    (Action::Escalate, 0.0)
+                };
+                LoggedEpisode {
+                    outcome: TrajectoryOutcome::Success,
+                    steps: vec![LoggedStep { state: 1, action, reward, behavior_prob: 0.5 }],
+                }
+            })
+            .collect();
+        TrajectoryLog { episodes, skipped: 0 }
+    }
+
+    fn empty_model() -> QTable {
+        QTable::new(QLearningConfig::elex_default())
+    }
+
+    #[test]
+    fn test_on_policy_estimates_match_behavior() {
+        let log = bandit_log(20);
+        let behavior = |_: StateHash, a: Action| {
+            if matches!(a, Action::DirectAnswer | Action::Escalate) { 0.5 } else { 0.0 }
+        };
+
+        let report = OffPolicyEvaluator::default().evaluate(&log, &behavior, &empty_model()).unwrap();
+        assert_eq!(report.episodes, 20);
+        assert!((report.behavior_value - 0.5).abs() < 1e-6);
+        assert!((report.effective_sample_size - 20.0).abs() < 1e-3);
+        for estimate in &report.estimates {
+            assert!((estimate.value - 0.5).abs() < 1e-6);
+            assert!(estimate.lower <= estimate.value && estimate.value <= estimate.upper);
+        }
+    }
+
+    #[test]
+    fn test_estimates_better_target_policy() {
+        let log = bandit_log(40);
+        let always_direct = |_: StateHash, a: Action| if a == Action::DirectAnswer { 1.0 } else { 0.0 };
+
+        let mut model = empty_model();
+        model.set_q_value(1, Action::DirectAnswer, 1.0);
+
+        let report = OffPolicyEvaluator::default().evaluate(&log, &always_direct, &model).unwrap();
+        let is = report.estimate(Estimator::ImportanceSampling).unwrap();
+        let wis = report.estimate(Estimator::WeightedImportanceSampling).unwrap();
+        let dr = report.estimate(Estimator::DoublyRobust).unwrap();
+
+        assert!((is.value - 1.0).abs() < 1e-6);
+        assert!((wis.value - 1.0).abs() < 1e-6);
+        // A correct model leaves DR without variance
+        assert!((dr.value - 1.0).abs() < 1e-6);
+        assert!(dr.upper - dr.lower < 1e-6);
+        assert!(is.upper - is.lower > dr.upper - dr.lower);
+        assert!((report.effective_sample_size - 20.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_log_from_buffer_and_json() {
+        let mut buffer = AgentTrajectoryBuffer::new(10);
+        let logged = buffer.start([0u8; 32], 1);
+        buffer.add_transition(logged, Transition::new(1, Action::ConsultPeer, 0.0, 2, 0.0).with_behavior_prob(Some(0.9)));
+        buffer.add_transition(logged, Transition::new(2, Action::ContextAnswer, 1.0, 3, 0.0).with_behavior_prob(Some(0.8)));
+        buffer.complete(logged, TrajectoryOutcome::Success);
+
+        let unlogged = buffer.start([0u8; 32], 2);
+        buffer.add_transition(unlogged, Transition::new(1, Action::Escalate, 0.0, 1, 0.0));
+        buffer.complete(unlogged, TrajectoryOutcome::Failure);
+        buffer.start([0u8; 32], 3);
+
+        let log = TrajectoryLog::from_buffer(&buffer);
+        assert_eq!(log.episodes.len(), 1);
+        assert_eq!(log.skipped, 2);
+        assert!((log.episodes[0].discounted_return(0.5) - 0.5).abs() < 1e-6);
+
+        let restored = TrajectoryLog::from_json(&log.to_json().unwrap()).unwrap();
+        assert_eq!(restored, log);
+        assert!(TrajectoryLog::from_json("{").is_err());
+        assert!(OffPolicyEvaluator::default()
+            .evaluate(&TrajectoryLog::default(), &|_: StateHash, _: Action| 1.0, &empty_model())
+            .is_err());
+    }
+}
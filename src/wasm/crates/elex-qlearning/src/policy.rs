@@ -3,7 +3,7 @@
 //! Exploration-exploitation tradeoff for action selection with decay and user consent.
 
 use serde::{Deserialize, Serialize};
-use crate::qtable::{QTable, StateHash};
+use crate::qtable::{QEntry, QTable, StateHash, ACTION_COUNT};
 use rand::{Rng, SeedableRng, rngs::StdRng};
 
 // ============================================================================
@@ -41,6 +41,18 @@ impl Action {
             Action::Escalate => 4,
         }
     }
+
+    /// Create from index
+    pub fn from_index(idx: u8) -> Option<Self> {
+        match idx {
+            0 => Some(Action::DirectAnswer),
+            1 => Some(Action::ContextAnswer),
+            2 => Some(Action::ConsultPeer),
+            3 => Some(Action::RequestClarification),
+            4 => Some(Action::Escalate),
+            _ => None,
+        }
+    }
 }
 
 // ============================================================================
@@ -56,6 +68,104 @@ pub struct ActionSelection {
     pub q_value: f32,
     /// Whether this was an exploration action
     pub is_exploration: bool,
+    /// Probability the policy had of selecting this action
+    ///
+    /// `None` if it has no closed form (Thompson sampling). Logged with
+    /// transitions for off-policy evaluation.
+    pub probability: Option<f32>,
+}
+
+// ============================================================================
+// Policy Input
+// ============================================================================
+
+/// Q-values a policy reads when selecting an action
+///
+/// Implemented by `QTable` and by `StateView`, so learners can hand a
+/// policy adjusted values for one state without copying the table.
+pub trait QValues {
+    /// Q-value of a state-action pair
+    fn get_q_value(&self, state: StateHash, action: Action) -> f32;
+
+    /// Stored entry of a state-action pair, if any
+    fn entry(&self, state: StateHash, action: Action) -> Option<QEntry>;
+
+    /// Best action among `available_actions` (None if empty)
+    fn best_action(&self, state: StateHash, available_actions: &[Action]) -> Option<Action> {
+        available_actions.iter().copied().max_by(|&a, &b| {
+            self.get_q_value(state, a)
+                .partial_cmp(&self.get_q_value(state, b))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+    }
+
+    /// Best action over all actions
+    fn get_best_action(&self, state: StateHash) -> Action {
+        self.best_action(state, Action::all()).unwrap_or(Action::DirectAnswer)
+    }
+}
+
+impl QValues for QTable {
+    fn get_q_value(&self, state: StateHash, action: Action) -> f32 {
+        QTable::get_q_value(self, state, action)
+    }
+
+    fn entry(&self, state: StateHash, action: Action) -> Option<QEntry> {
+        QTable::entry(self, state, action)
+    }
+}
+
+/// Q-values of one state layered over a table
+///
+/// Returned by `Learner::view`. Lookups of `state` read the view's values
+/// (visit and outcome counts still come from the table); other states go
+/// to the table unchanged.
+#[derive(Clone, Copy)]
+pub struct StateView<'t> {
+    table: &'t QTable,
+    state: StateHash,
+    values: [f32; ACTION_COUNT],
+}
+
+impl<'t> StateView<'t> {
+    /// View `state` with its own values, indexed by `Action::index()`
+    pub fn new(table: &'t QTable, state: StateHash, values: [f32; ACTION_COUNT]) -> Self {
+        Self { table, state, values }
+    }
+
+    /// View `state` with the values stored in the table
+    pub fn of(table: &'t QTable, state: StateHash) -> Self {
+        let values = std::array::from_fn(|i| table.get_q_value(state, Action::all()[i]));
+        Self::new(table, state, values)
+    }
+
+    /// Viewed state
+    pub fn state(&self) -> StateHash {
+        self.state
+    }
+
+    /// Values of the viewed state, indexed by `Action::index()`
+    pub fn values(&self) -> &[f32; ACTION_COUNT] {
+        &self.values
+    }
+}
+
+impl QValues for StateView<'_> {
+    fn get_q_value(&self, state: StateHash, action: Action) -> f32 {
+        if state == self.state {
+            self.values[action.index() as usize]
+        } else {
+            self.table.get_q_value(state, action)
+        }
+    }
+
+    fn entry(&self, state: StateHash, action: Action) -> Option<QEntry> {
+        let entry = self.table.entry(state, action)?;
+        Some(QEntry {
+            value: self.get_q_value(state, action),
+            ..entry
+        })
+    }
 }
 
 // ============================================================================
@@ -67,13 +177,37 @@ pub trait Policy: Send + Sync {
     /// Select action based on Q-values
     fn select_action(
         &mut self,
-        q_table: &QTable,
+        q_table: &dyn QValues,
         state: StateHash,
         available_actions: &[Action],
     ) -> ActionSelection;
 
+    /// Probability of selecting each action in `state`, indexed by `Action::index()`
+    ///
+    /// Considers all actions, without advancing the policy's RNG or
+    /// schedule. Used by learners that need the behaviour policy, e.g.
+    /// Expected SARSA.
+    fn action_probabilities(&self, q_table: &dyn QValues, state: StateHash) -> [f32; ACTION_COUNT];
+
+    /// Copy of the policy in its current state
+    fn boxed_clone(&self) -> Box<dyn Policy>;
+
     /// Get policy name
     fn name(&self) -> &str;
+
+    /// Probability of a uniformly random action (epsilon), if the policy has one
+    fn exploration_rate(&self) -> f32 {
+        0.0
+    }
+
+    /// Advance the exploration schedule (called once per episode)
+    fn decay(&mut self) {}
+}
+
+impl Clone for Box<dyn Policy> {
+    fn clone(&self) -> Self {
+        self.boxed_clone()
+    }
 }
 
 // ============================================================================
@@ -167,7 +301,7 @@ impl EpsilonGreedy {
     /// Selected action with metadata
     fn select_action_internal(
         &mut self,
-        q_table: &QTable,
+        q_table: &dyn QValues,
         state: StateHash,
         available_actions: &[Action],
     ) -> ActionSelection {
@@ -184,13 +318,25 @@ impl EpsilonGreedy {
             };
             (action, true)
         } else {
-            // Exploit: best action from Q-table
-            let action = q_table.get_best_action(state);
+            // Exploit: best available action from Q-table
+            let action = q_table
+                .best_action(state, available_actions)
+                .unwrap_or_else(|| q_table.get_best_action(state));
             (action, false)
         };
 
         let q_value = q_table.get_q_value(state, action);
 
+        // P(a) = ε/n + (1 - ε)·[a is greedy], evaluated before decay
+        let epsilon = if self.exploration_enabled { self.epsilon } else { 0.0 };
+        let greedy = q_table
+            .best_action(state, available_actions)
+            .unwrap_or_else(|| q_table.get_best_action(state));
+        let mut probability = epsilon / available_actions.len().max(1) as f32;
+        if action == greedy {
+            probability += 1.0 - epsilon;
+        }
+
         // Decay epsilon after selection
         self.decay_epsilon();
 
@@ -198,13 +344,14 @@ impl EpsilonGreedy {
             action,
             q_value,
             is_exploration,
+            probability: Some(probability.min(1.0)),
         }
     }
 
     /// Public interface for action selection
     pub fn select_action(
         &mut self,
-        q_table: &QTable,
+        q_table: &dyn QValues,
         state: StateHash,
         available_actions: &[Action],
     ) -> ActionSelection {
@@ -242,7 +389,7 @@ impl EpsilonGreedy {
 impl Policy for EpsilonGreedy {
     fn select_action(
         &mut self,
-        q_table: &QTable,
+        q_table: &dyn QValues,
         state: StateHash,
         available_actions: &[Action],
     ) -> ActionSelection {
@@ -250,9 +397,29 @@ impl Policy for EpsilonGreedy {
         self.select_action_internal(q_table, state, available_actions)
     }
 
+    fn action_probabilities(&self, q_table: &dyn QValues, state: StateHash) -> [f32; ACTION_COUNT] {
+        // ε/n on every action plus 1 - ε on the greedy one
+        let epsilon = if self.exploration_enabled { self.epsilon } else { 0.0 };
+        let mut probabilities = [epsilon / ACTION_COUNT as f32; ACTION_COUNT];
+        probabilities[q_table.get_best_action(state).index() as usize] += 1.0 - epsilon;
+        probabilities
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Policy> {
+        Box::new(self.clone())
+    }
+
     fn name(&self) -> &str {
         "epsilon-greedy"
     }
+
+    fn exploration_rate(&self) -> f32 {
+        self.epsilon
+    }
+
+    fn decay(&mut self) {
+        self.decay_epsilon();
+    }
 }
 
 // ============================================================================
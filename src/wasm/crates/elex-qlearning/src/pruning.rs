@@ -0,0 +1,231 @@
+//! Q-Table Pruning
+//!
+//! A Q-table gains a row for every state it updates and never forgets one,
+//! so a long-running agent outgrows any memory budget. With
+//! `PruningConfig::max_states` set, the table evicts its lowest-scoring
+//! states whenever a new state would exceed the bound:
+//!
+//! ```text
+//! score(s) = w_v ln(1 + Σ_a visits(s,a))
+//!          + w_r 0.5^(age(s) / half_life)        age relative to the newest update
+//!          + w_m max_a |Q(s,a)|
+//! ```
+//!
+//! Rarely visited, stale states with values near zero go first. Evicted rows
+//! can be folded into a visit-weighted `QueryTypePrior`, which then stands in
+//! for unknown states of the same query type instead of `initial_q_value`.
+
+use crate::policy::Action;
+use crate::qtable::{QRow, ACTION_COUNT};
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Capacity bound and eviction score weights
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PruningConfig {
+    /// Maximum number of states kept (None = unbounded)
+    pub max_states: Option<usize>,
+    /// Share of `max_states` freed by one pruning pass
+    pub prune_fraction: f32,
+    /// Weight of the visit term
+    pub visit_weight: f32,
+    /// Weight of the recency term
+    pub recency_weight: f32,
+    /// Weight of the value magnitude term
+    pub value_weight: f32,
+    /// Age at which the recency term halves (ms)
+    pub recency_half_life_ms: u64,
+    /// Fold evicted states into per-QueryType priors
+    pub summarize_priors: bool,
+}
+
+impl Default for PruningConfig {
+    fn default() -> Self {
+        Self {
+            max_states: None,
+            prune_fraction: 0.1,
+            visit_weight: 1.0,
+            recency_weight: 1.0,
+            value_weight: 1.0,
+            recency_half_life_ms: 60 * 60 * 1000,
+            summarize_priors: true,
+        }
+    }
+}
+
+impl PruningConfig {
+    /// Bounded to `max_states` with default weights
+    pub fn bounded(max_states: usize) -> Self {
+        Self {
+            max_states: Some(max_states),
+            ..Self::default()
+        }
+    }
+
+    /// Number of states kept after a pass triggered at capacity
+    pub fn target_states(&self) -> Option<usize> {
+        let max = self.max_states?.max(1);
+        let freed = ((max as f32 * self.prune_fraction.clamp(0.0, 1.0)).ceil() as usize).max(1);
+        Some(max.saturating_sub(freed))
+    }
+
+    /// Eviction score of a row; lower scores are evicted first
+    pub fn score(&self, row: &QRow, newest: u64) -> f32 {
+        let (mut visits, mut last_updated, mut magnitude) = (0u64, 0u64, 0.0f32);
+        for action in row.actions() {
+            let i = action.index() as usize;
+            visits += row.visits[i] as u64;
+            last_updated = last_updated.max(row.last_updated[i]);
+            magnitude = magnitude.max(row.values[i].abs());
+        }
+
+        let age = newest.saturating_sub(last_updated) as f32;
+        let half_life = self.recency_half_life_ms.max(1) as f32;
+        let recency = 0.5f32.powf(age / half_life);
+
+        self.visit_weight * (visits as f32).ln_1p()
+            + self.recency_weight * recency
+            + self.value_weight * magnitude
+    }
+}
+
+// ============================================================================
+// Query-Type Prior
+// ============================================================================
+
+/// Visit-weighted summary of evicted states of one query type
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct QueryTypePrior {
+    /// Mean Q-value per action
+    pub values: [f32; ACTION_COUNT],
+    /// Visit weight behind each mean
+    pub weights: [u64; ACTION_COUNT],
+    /// Number of states folded in
+    pub states: u64,
+}
+
+impl QueryTypePrior {
+    /// Fold a row into the prior
+    ///
+    /// Every entry counts at least once, so unvisited entries still shape
+    /// the mean.
+    pub fn absorb(&mut self, row: &QRow) {
+        for action in row.actions() {
+            let i = action.index() as usize;
+            let weight = (row.visits[i] as u64).max(1);
+            let total = self.weights[i] + weight;
+            self.values[i] += (row.values[i] - self.values[i]) * weight as f32 / total as f32;
+            self.weights[i] = total;
+        }
+        self.states += 1;
+    }
+
+    /// Prior value of an action, if any evicted state had it
+    pub fn value(&self, action: Action) -> Option<f32> {
+        let i = action.index() as usize;
+        (self.weights[i] > 0).then_some(self.values[i])
+    }
+}
+
+/// Result of one pruning pass
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PruneReport {
+    /// States evicted
+    pub states: usize,
+    /// State-action entries evicted
+    pub entries: usize,
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{Complexity, QueryType, StateHash as EncodedStateHash};
+    use crate::qtable::{QEntry, QLearningConfig, QTable};
+
+    fn state(query_type: QueryType, context: u64) -> u64 {
+        EncodedStateHash::encode(query_type, Complexity::Simple, context, 0.5).value()
+    }
+
+    fn entry(value: f32, visit_count: u32, last_updated: u64) -> QEntry {
+        QEntry { visit_count, last_updated, ..QEntry::new(value) }
+    }
+
+    fn table(pruning: PruningConfig) -> QTable {
+        QTable::new(QLearningConfig { pruning, ..QLearningConfig::elex_default() })
+    }
+
+    #[test]
+    fn test_prune_evicts_lowest_scores() {
+        let mut qt = table(PruningConfig::default());
+        let (hot, stale, weak) = (state(QueryType::Kpi, 1), state(QueryType::Kpi, 2), state(QueryType::Kpi, 3));
+        qt.set_entry(hot, Action::DirectAnswer, entry(0.5, 40, 10_000_000));
+        qt.set_entry(stale, Action::DirectAnswer, entry(0.5, 40, 0));
+        qt.set_entry(weak, Action::DirectAnswer, entry(0.0, 1, 10_000_000));
+
+        let report = qt.prune(2);
+        assert_eq!(report, PruneReport { states: 1, entries: 1 });
+        assert!(qt.row(weak).is_none());
+
+        qt.prune(1);
+        assert!(qt.row(hot).is_some());
+        assert!(qt.row(stale).is_none());
+
+        let stats = qt.get_stats();
+        assert_eq!((stats.state_count, stats.pruned_states, stats.prune_passes), (1, 2, 2));
+    }
+
+    #[test]
+    fn test_capacity_bound() {
+        let mut qt = table(PruningConfig { prune_fraction: 0.25, ..PruningConfig::bounded(8) });
+        for context in 0..100 {
+            qt.update_q_value(state(QueryType::Parameter, context), Action::ContextAnswer, 1.0, 0.0);
+            assert!(qt.state_count() <= 8);
+        }
+
+        let stats = qt.get_stats();
+        assert_eq!(stats.max_states, Some(8));
+        assert_eq!(stats.pruned_states, 92);
+        assert_eq!(stats.pruned_entries, 92);
+    }
+
+    #[test]
+    fn test_pruned_states_feed_query_type_prior() {
+        let mut qt = table(PruningConfig::default());
+        qt.set_entry(state(QueryType::Troubleshoot, 1), Action::ConsultPeer, entry(0.9, 3, 0));
+        qt.set_entry(state(QueryType::Troubleshoot, 2), Action::ConsultPeer, entry(0.1, 1, 0));
+        qt.prune(0);
+
+        let prior = qt.prior(QueryType::Troubleshoot).unwrap();
+        assert_eq!(prior.states, 2);
+        assert!((prior.value(Action::ConsultPeer).unwrap() - 0.7).abs() < 1e-6);
+        assert!(qt.prior(QueryType::Kpi).is_none());
+
+        // Unknown states of the same type start from the prior
+        let unseen = state(QueryType::Troubleshoot, 99);
+        assert!((qt.get_q_value(unseen, Action::ConsultPeer) - 0.7).abs() < 1e-6);
+        assert_eq!(qt.get_q_value(unseen, Action::Escalate), 0.0);
+        assert_eq!(qt.get_q_value(state(QueryType::Kpi, 99), Action::ConsultPeer), 0.0);
+
+        // Priors survive export
+        let restored = QTable::import(&qt.export().unwrap()).unwrap();
+        assert_eq!(restored.prior(QueryType::Troubleshoot), Some(prior));
+        assert_eq!(restored.get_stats().pruned_states, 2);
+    }
+
+    #[test]
+    fn test_prune_without_priors() {
+        let mut qt = table(PruningConfig { summarize_priors: false, ..PruningConfig::default() });
+        qt.set_entry(state(QueryType::Counter, 1), Action::Escalate, entry(1.0, 5, 0));
+        qt.prune(0);
+        assert!(qt.is_empty());
+        assert!(qt.prior(QueryType::Counter).is_none());
+        assert_eq!(qt.get_stats().prior_count, 0);
+    }
+}
@@ -30,8 +30,15 @@
 //! - resolution_success: +0.5 for successful resolution
 //! - latency_penalty: small penalty for slow responses
 //! - consultation_cost: small penalty for peer consultation
+//! - kpi_delta: [-1, +1] network KPI change after an applied action
 
 use serde::{Deserialize, Serialize};
+use crate::batch::batch_q_update;
+use crate::approx::ApproximationConfig;
+use crate::encoding::{QueryType, StateHash as EncodedStateHash};
+use crate::format::{self, ExportOptions};
+use crate::pruning::{PruneReport, PruningConfig, QueryTypePrior};
+use crate::learner::LearnerKind;
 use crate::policy::Action;
 use hashbrown::HashMap;
 
@@ -50,6 +57,9 @@ pub struct Reward {
     pub latency_penalty: f32,
     /// Consultation cost (small negative value)
     pub consultation_cost: f32,
+    /// Network KPI improvement after an applied change [-1.0, +1.0]
+    #[serde(default)]
+    pub kpi_delta: f32,
 }
 
 impl Reward {
@@ -65,12 +75,17 @@ impl Reward {
             resolution_success,
             latency_penalty,
             consultation_cost,
+            kpi_delta: 0.0,
         }
     }
 
     /// Calculate total reward
     pub fn total(&self) -> f32 {
-        self.user_rating + self.resolution_success + self.latency_penalty + self.consultation_cost
+        self.user_rating
+            + self.resolution_success
+            + self.latency_penalty
+            + self.consultation_cost
+            + self.kpi_delta
     }
 
     /// Create reward from user rating only
@@ -80,6 +95,7 @@ impl Reward {
             resolution_success: 0.0,
             latency_penalty: 0.0,
             consultation_cost: 0.0,
+            kpi_delta: 0.0,
         }
     }
 
@@ -90,6 +106,7 @@ impl Reward {
             resolution_success: 0.5,
             latency_penalty: 0.0,
             consultation_cost: 0.0,
+            kpi_delta: 0.0,
         }
     }
 
@@ -100,6 +117,7 @@ impl Reward {
             resolution_success: 0.0,
             latency_penalty: 0.0,
             consultation_cost: 0.0,
+            kpi_delta: 0.0,
         }
     }
 
@@ -114,6 +132,12 @@ impl Reward {
         self.consultation_cost = -cost.abs(); // Always negative
         self
     }
+
+    /// Add network KPI component
+    pub fn with_kpi_delta(mut self, delta: f32) -> Self {
+        self.kpi_delta = delta.clamp(-1.0, 1.0);
+        self
+    }
 }
 
 impl Default for Reward {
@@ -123,6 +147,7 @@ impl Default for Reward {
             resolution_success: 0.0,
             latency_penalty: 0.0,
             consultation_cost: 0.0,
+            kpi_delta: 0.0,
         }
     }
 }
@@ -197,6 +222,25 @@ pub struct QLearningConfig {
     pub epsilon_min: f32,
     /// Initial Q-value for new entries
     pub initial_q_value: f32,
+    /// TD update rule used by `create_learner`
+    #[serde(default)]
+    pub learner: LearnerKind,
+    /// Steps of reward before bootstrapping in n-step TD (1 = one-step)
+    #[serde(default = "default_n_step")]
+    pub n_step: usize,
+    /// Trace decay for Watkins Q(λ) (0 = disabled)
+    #[serde(default)]
+    pub lambda: f32,
+    /// Settings of `LearnerKind::LinearApproximation`
+    #[serde(default)]
+    pub approximation: ApproximationConfig,
+    /// Capacity bound and eviction of stale states
+    #[serde(default)]
+    pub pruning: PruningConfig,
+}
+
+fn default_n_step() -> usize {
+    1
 }
 
 impl Default for QLearningConfig {
@@ -208,6 +252,11 @@ impl Default for QLearningConfig {
             epsilon_decay: 0.995,
             epsilon_min: 0.01,
             initial_q_value: 0.0,
+            learner: LearnerKind::QLearning,
+            n_step: 1,
+            lambda: 0.0,
+            approximation: ApproximationConfig::default(),
+            pruning: PruningConfig::default(),
         }
     }
 }
@@ -222,19 +271,45 @@ impl QLearningConfig {
             epsilon_decay: 0.995,
             epsilon_min: 0.01,
             initial_q_value: 0.0,
+            learner: LearnerKind::QLearning,
+            n_step: 1,
+            lambda: 0.0,
+            approximation: ApproximationConfig::default(),
+            pruning: PruningConfig::default(),
         }
     }
+
+    /// Check if trajectories are credited with multi-step returns
+    pub fn is_multi_step(&self) -> bool {
+        self.n_step > 1 || self.lambda > 0.0
+    }
+
+    /// Reject settings that cannot be combined
+    ///
+    /// Multi-step returns (`crate::traces`) are Q-learning backups written
+    /// straight into the table, so they bypass the estimators of any other
+    /// learner; they are only allowed with `LearnerKind::QLearning`.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.is_multi_step() && self.learner != LearnerKind::QLearning {
+            return Err(format!(
+                "n-step/lambda returns require the q-learning learner, not {}",
+                self.learner.name()
+            ));
+        }
+        Ok(())
+    }
 }
 
 // ============================================================================
 // Q-Table Entry
 // ============================================================================
 
-/// Single Q-table entry
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Number of actions per state (one slot per `Action`)
+pub const ACTION_COUNT: usize = 5;
+
+/// Value and statistics of a single state-action pair
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct QEntry {
-    /// State-action key
-    pub state_action_key: String,
     /// Q-value
     pub value: f32,
     /// Visit count
@@ -247,14 +322,10 @@ pub struct QEntry {
 }
 
 impl QEntry {
-    pub fn new(state_action_key: String) -> Self {
+    pub fn new(value: f32) -> Self {
         Self {
-            state_action_key,
-            value: 0.0,
-            visit_count: 0,
-            last_updated: 0,
-            successes: 0,
-            failures: 0,
+            value,
+            ..Self::default()
         }
     }
 
@@ -268,15 +339,101 @@ impl QEntry {
     }
 }
 
+/// Dense row holding every action of one state
+///
+/// Columns are indexed by `Action::index()`. Values of actions without an
+/// entry hold the table's initial Q-value, so `values` can be passed to
+/// batch kernels as-is.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QRow {
+    /// Q-values
+    pub values: [f32; ACTION_COUNT],
+    /// Visit counts
+    pub visits: [u32; ACTION_COUNT],
+    /// Last update timestamps
+    pub last_updated: [u64; ACTION_COUNT],
+    /// Positive outcomes
+    pub successes: [u32; ACTION_COUNT],
+    /// Negative outcomes
+    pub failures: [u32; ACTION_COUNT],
+    /// Bitmask of actions that have an entry
+    pub present: u8,
+}
+
+impl QRow {
+    /// Create an empty row
+    pub fn new(initial_q_value: f32) -> Self {
+        Self {
+            values: [initial_q_value; ACTION_COUNT],
+            visits: [0; ACTION_COUNT],
+            last_updated: [0; ACTION_COUNT],
+            successes: [0; ACTION_COUNT],
+            failures: [0; ACTION_COUNT],
+            present: 0,
+        }
+    }
+
+    /// Check if an action has an entry
+    pub fn contains(&self, action: Action) -> bool {
+        self.present & (1 << action.index()) != 0
+    }
+
+    /// Entry for an action
+    pub fn get(&self, action: Action) -> Option<QEntry> {
+        if !self.contains(action) {
+            return None;
+        }
+        let i = action.index() as usize;
+        Some(QEntry {
+            value: self.values[i],
+            visit_count: self.visits[i],
+            last_updated: self.last_updated[i],
+            successes: self.successes[i],
+            failures: self.failures[i],
+        })
+    }
+
+    /// Store an entry for an action
+    pub fn set(&mut self, action: Action, entry: QEntry) {
+        let i = action.index() as usize;
+        self.values[i] = entry.value;
+        self.visits[i] = entry.visit_count;
+        self.last_updated[i] = entry.last_updated;
+        self.successes[i] = entry.successes;
+        self.failures[i] = entry.failures;
+        self.present |= 1 << i;
+    }
+
+    /// Number of actions with an entry
+    pub fn len(&self) -> usize {
+        self.present.count_ones() as usize
+    }
+
+    /// Check if no action has an entry
+    pub fn is_empty(&self) -> bool {
+        self.present == 0
+    }
+
+    /// Actions with an entry, in index order
+    pub fn actions(&self) -> impl Iterator<Item = Action> + '_ {
+        Action::all().iter().copied().filter(|&a| self.contains(a))
+    }
+}
+
 // ============================================================================
 // Q-Table
 // ============================================================================
 
 /// Q-table for storing state-action values
+///
+/// Values are keyed by `(StateHash, Action)` and stored as one dense
+/// `QRow` per state. Tables serialized by older versions, which used
+/// `"state::action"` string keys, are migrated on deserialization.
 #[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "QTableRepr")]
 pub struct QTable {
-    /// Q-value storage: state::action -> QEntry
-    pub entries: HashMap<String, QEntry>,
+    /// Q-value storage: state -> row of action entries
+    rows: HashMap<StateHash, QRow>,
 
     /// Configuration
     config: QLearningConfig,
@@ -284,20 +441,30 @@ pub struct QTable {
     /// Current exploration rate
     current_epsilon: f32,
 
+    /// Summaries of evicted states, by query type index
+    priors: HashMap<u8, QueryTypePrior>,
+
     /// Statistics
     pub total_updates: u32,
     pub total_episodes: u32,
+    pub pruned_states: u64,
+    pub pruned_entries: u64,
+    pub prune_passes: u32,
 }
 
 impl QTable {
     /// Create a new Q-table
     pub fn new(config: QLearningConfig) -> Self {
         Self {
-            entries: HashMap::new(),
+            rows: HashMap::new(),
             current_epsilon: config.epsilon,
             config,
+            priors: HashMap::new(),
             total_updates: 0,
             total_episodes: 0,
+            pruned_states: 0,
+            pruned_entries: 0,
+            prune_passes: 0,
         }
     }
 
@@ -307,25 +474,23 @@ impl QTable {
     }
 
     /// Get Q-value for state-action pair
+    ///
+    /// Unknown pairs fall back to the prior of the state's query type, if
+    /// pruning has built one, and otherwise to `initial_q_value`.
     pub fn get_q_value(&self, state: StateHash, action: Action) -> f32 {
-        let key = Self::make_key(state, action);
-        self.entries
-            .get(&key)
+        self.rows
+            .get(&state)
+            .and_then(|row| row.get(action))
             .map(|e| e.value)
+            .or_else(|| self.prior_value(state, action))
             .unwrap_or(self.config.initial_q_value)
     }
 
     /// Set Q-value for state-action pair
     pub fn set_q_value(&mut self, state: StateHash, action: Action, value: f32) {
-        let key = Self::make_key(state, action);
-        self.entries
-            .entry(key.clone())
-            .and_modify(|e| e.value = value)
-            .or_insert_with(|| {
-                let mut entry = QEntry::new(key.clone());
-                entry.value = value;
-                entry
-            });
+        let row = self.row_mut(state);
+        let entry = row.get(action).unwrap_or_default();
+        row.set(action, QEntry { value, ..entry });
     }
 
     /// Update Q-value using Q-learning formula
@@ -337,7 +502,6 @@ impl QTable {
         reward: f32,
         next_max_q: f32,
     ) -> f32 {
-        let key = Self::make_key(state, action);
         let current_q = self.get_q_value(state, action);
 
         // TD-error: r + γ max(Q(s',a')) - Q(s,a)
@@ -347,12 +511,48 @@ impl QTable {
         // Q(s,a) ← Q(s,a) + α * td_error
         let new_q = current_q + self.config.alpha * td_error;
 
-        // Update entry
-        let entry = self.entries
-            .entry(key.clone())
-            .or_insert_with(|| QEntry::new(key.clone()));
+        self.record_update(state, action, new_q, reward);
+        new_q
+    }
 
-        entry.value = new_q;
+    /// Apply a batch of Q-learning updates with `batch_q_update`
+    ///
+    /// Each transition is `(state, action, reward, next_state)`. All targets
+    /// are computed from the table as it was before the batch; if a
+    /// state-action pair appears more than once, the last update wins.
+    pub fn batch_update(&mut self, transitions: &[(StateHash, Action, f32, StateHash)]) {
+        let mut q_values: Vec<f32> = transitions
+            .iter()
+            .map(|&(state, action, _, _)| self.get_q_value(state, action))
+            .collect();
+        let rewards: Vec<f32> = transitions.iter().map(|t| t.2).collect();
+        let next_max_q: Vec<f32> = transitions.iter().map(|t| self.get_max_q(t.3)).collect();
+
+        batch_q_update(&mut q_values, &rewards, &next_max_q, self.config.alpha, self.config.gamma);
+
+        for (&(state, action, reward, _), &new_q) in transitions.iter().zip(&q_values) {
+            self.record_update(state, action, new_q, reward);
+        }
+    }
+
+    /// Get the entry for a state-action pair
+    pub fn entry(&self, state: StateHash, action: Action) -> Option<QEntry> {
+        self.rows.get(&state)?.get(action)
+    }
+
+    /// Store an entry for a state-action pair
+    pub fn set_entry(&mut self, state: StateHash, action: Action, entry: QEntry) {
+        self.row_mut(state).set(action, entry);
+    }
+
+    /// Store an updated value and count the visit and its outcome
+    ///
+    /// Used by learners that compute their own TD target.
+    pub fn record_update(&mut self, state: StateHash, action: Action, value: f32, reward: f32) {
+        let row = self.row_mut(state);
+        let mut entry = row.get(action).unwrap_or_default();
+
+        entry.value = value;
         entry.visit_count += 1;
         entry.last_updated = current_timestamp();
 
@@ -363,8 +563,20 @@ impl QTable {
             entry.failures += 1;
         }
 
+        row.set(action, entry);
         self.total_updates += 1;
-        new_q
+    }
+
+    /// Get the row of a state
+    pub fn row(&self, state: StateHash) -> Option<&QRow> {
+        self.rows.get(&state)
+    }
+
+    /// Iterate over all entries
+    pub fn iter(&self) -> impl Iterator<Item = (StateHash, Action, QEntry)> + '_ {
+        self.rows.iter().flat_map(|(&state, row)| {
+            row.actions().filter_map(move |action| row.get(action).map(|e| (state, action, e)))
+        })
     }
 
     /// Get all Q-values for a state
@@ -447,9 +659,14 @@ impl QTable {
         self.current_epsilon
     }
 
+    /// Get configuration
+    pub fn config(&self) -> &QLearningConfig {
+        &self.config
+    }
+
     /// Get statistics
     pub fn get_stats(&self) -> QTableStats {
-        let values: Vec<f32> = self.entries.values().map(|e| e.value).collect();
+        let values: Vec<f32> = self.iter().map(|(_, _, e)| e.value).collect();
 
         let (min_q, max_q, mean_q) = if values.is_empty() {
             (0.0, 0.0, 0.0)
@@ -461,7 +678,7 @@ impl QTable {
         };
 
         QTableStats {
-            entries_count: self.entries.len() as u32,
+            entries_count: self.len() as u32,
             total_updates: self.total_updates,
             total_episodes: self.total_episodes,
             current_epsilon: self.current_epsilon,
@@ -470,6 +687,12 @@ impl QTable {
             mean_q_value: mean_q,
             alpha: self.config.alpha,
             gamma: self.config.gamma,
+            state_count: self.state_count() as u32,
+            max_states: self.config.pruning.max_states.map(|m| m as u32),
+            pruned_states: self.pruned_states,
+            pruned_entries: self.pruned_entries,
+            prune_passes: self.prune_passes,
+            prior_count: self.priors.len() as u32,
         }
     }
 
@@ -478,53 +701,57 @@ impl QTable {
         let weight = weight.max(0.0).min(1.0);
         let self_weight = 1.0 - weight;
 
-        for (key, other_entry) in &other.entries {
-            let merged_value = if let Some(self_entry) = self.entries.get(key) {
-                // Visit-weighted average
-                let total_visits = self_entry.visit_count + other_entry.visit_count;
-                if total_visits == 0 {
-                    self_entry.value * self_weight + other_entry.value * weight
-                } else {
-                    (self_entry.value * self_entry.visit_count as f32
-                        + other_entry.value * other_entry.visit_count as f32)
-                        / total_visits as f32
+        for (state, action, other_entry) in other.iter() {
+            let merged = match self.entry(state, action) {
+                Some(self_entry) => {
+                    // Visit-weighted average
+                    let total_visits = self_entry.visit_count + other_entry.visit_count;
+                    let value = if total_visits == 0 {
+                        self_entry.value * self_weight + other_entry.value * weight
+                    } else {
+                        (self_entry.value * self_entry.visit_count as f32
+                            + other_entry.value * other_entry.visit_count as f32)
+                            / total_visits as f32
+                    };
+                    QEntry {
+                        value,
+                        visit_count: (self_entry.visit_count as f32 * self_weight
+                            + other_entry.visit_count as f32 * weight) as u32,
+                        ..self_entry
+                    }
                 }
-            } else {
-                other_entry.value * weight
+                None => QEntry {
+                    value: other_entry.value * weight,
+                    visit_count: (other_entry.visit_count as f32 * weight) as u32,
+                    ..QEntry::default()
+                },
             };
-
-            self.entries
-                .entry(key.clone())
-                .and_modify(|e| {
-                    e.value = merged_value;
-                    e.visit_count = (e.visit_count as f32 * self_weight
-                        + other_entry.visit_count as f32 * weight) as u32;
-                })
-                .or_insert_with(|| {
-                    let mut entry = QEntry::new(key.clone());
-                    entry.value = merged_value;
-                    entry.visit_count = (other_entry.visit_count as f32 * weight) as u32;
-                    entry
-                });
+            self.set_entry(state, action, merged);
         }
     }
 
     /// Export Q-table to bytes for federated learning
     ///
-    /// Serializes the Q-table to a binary format for transmission
-    /// to other agents or central coordinator.
+    /// Writes the versioned, checksummed format of `crate::format` with an
+    /// uncompressed payload (readable on every target) and no agent id.
     pub fn export(&self) -> Result<Vec<u8>, String> {
-        serde_json::to_vec(self)
-            .map_err(|e| format!("Failed to serialize Q-table: {}", e))
+        self.export_with(&ExportOptions::default())
+    }
+
+    /// Export Q-table with an agent id and compression choice
+    pub fn export_with(&self, options: &ExportOptions) -> Result<Vec<u8>, String> {
+        format::encode(self, options).map_err(|e| e.to_string())
     }
 
     /// Import Q-table from bytes for federated learning
     ///
-    /// Deserializes a Q-table from binary format received from
-    /// another agent or central coordinator.
+    /// Accepts the current format and the headerless JSON of earlier
+    /// releases; tables from newer releases or with a bad checksum are
+    /// rejected.
     pub fn import(data: &[u8]) -> Result<QTable, String> {
-        serde_json::from_slice(data)
-            .map_err(|e| format!("Failed to deserialize Q-table: {}", e))
+        format::decode(data)
+            .map(|(_, table)| table)
+            .map_err(|e| e.to_string())
     }
 
     /// Merge with another Q-table and return the result
@@ -539,31 +766,179 @@ impl QTable {
 
     /// Reset Q-table
     pub fn reset(&mut self) {
-        self.entries.clear();
+        self.rows.clear();
+        self.priors.clear();
         self.total_updates = 0;
         self.total_episodes = 0;
+        self.pruned_states = 0;
+        self.pruned_entries = 0;
+        self.prune_passes = 0;
         self.current_epsilon = self.config.epsilon;
     }
 
-    /// Get entry count
+    /// Evict the lowest-scoring states until at most `target_states` remain
+    ///
+    /// Scores follow `PruningConfig::score`. Evicted rows are folded into
+    /// their query type's prior if `summarize_priors` is set.
+    pub fn prune(&mut self, target_states: usize) -> PruneReport {
+        if self.rows.len() <= target_states {
+            return PruneReport::default();
+        }
+
+        let pruning = &self.config.pruning;
+        let newest = self
+            .rows
+            .values()
+            .flat_map(|row| row.last_updated.iter().copied())
+            .max()
+            .unwrap_or(0);
+        let mut scored: Vec<(f32, StateHash)> = self
+            .rows
+            .iter()
+            .map(|(&state, row)| (pruning.score(row, newest), state))
+            .collect();
+        // Break ties by state hash so pruning is deterministic
+        scored.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal).then(a.1.cmp(&b.1)));
+
+        let mut report = PruneReport::default();
+        for &(_, state) in scored.iter().take(self.rows.len() - target_states) {
+            let Some(row) = self.rows.remove(&state) else { continue };
+            if self.config.pruning.summarize_priors {
+                self.priors.entry(query_type_of(state).index()).or_default().absorb(&row);
+            }
+            report.states += 1;
+            report.entries += row.len();
+        }
+
+        self.pruned_states += report.states as u64;
+        self.pruned_entries += report.entries as u64;
+        self.prune_passes += 1;
+        report
+    }
+
+    /// Prior built from evicted states of a query type
+    pub fn prior(&self, query_type: QueryType) -> Option<&QueryTypePrior> {
+        self.priors.get(&query_type.index())
+    }
+
+    /// Get entry count (state-action pairs)
     pub fn len(&self) -> usize {
-        self.entries.len()
+        self.rows.values().map(|row| row.len()).sum()
     }
 
     /// Check if empty
     pub fn is_empty(&self) -> bool {
-        self.entries.is_empty()
+        self.rows.is_empty()
+    }
+
+    /// Number of states with at least one entry
+    pub fn state_count(&self) -> usize {
+        self.rows.len()
     }
 
     // ========================================================================
     // Internal Helpers
     // ========================================================================
 
-    fn make_key(state: StateHash, action: Action) -> String {
-        format!("{}::{}", state, action.index())
+    fn row_mut(&mut self, state: StateHash) -> &mut QRow {
+        let pruning = &self.config.pruning;
+        if let (Some(max_states), Some(target)) = (pruning.max_states, pruning.target_states()) {
+            if self.rows.len() >= max_states.max(1) && !self.rows.contains_key(&state) {
+                self.prune(target);
+            }
+        }
+
+        let initial_q_value = self.config.initial_q_value;
+        self.rows.entry(state).or_insert_with(|| QRow::new(initial_q_value))
+    }
+
+    fn prior_value(&self, state: StateHash, action: Action) -> Option<f32> {
+        if self.priors.is_empty() {
+            return None;
+        }
+        self.priors.get(&query_type_of(state).index())?.value(action)
     }
 }
 
+// ============================================================================
+// Serialization
+// ============================================================================
+
+/// Serialized Q-table, current or legacy layout
+#[derive(Deserialize)]
+struct QTableRepr {
+    /// Current layout: state -> row
+    #[serde(default)]
+    rows: Option<HashMap<StateHash, QRow>>,
+
+    /// Legacy layout: "state::action" -> entry
+    #[serde(default)]
+    entries: Option<HashMap<String, LegacyQEntry>>,
+
+    config: QLearningConfig,
+    current_epsilon: f32,
+    total_updates: u32,
+    total_episodes: u32,
+
+    #[serde(default)]
+    priors: HashMap<u8, QueryTypePrior>,
+    #[serde(default)]
+    pruned_states: u64,
+    #[serde(default)]
+    pruned_entries: u64,
+    #[serde(default)]
+    prune_passes: u32,
+}
+
+/// Entry of the legacy string-keyed layout
+#[derive(Deserialize)]
+struct LegacyQEntry {
+    value: f32,
+    visit_count: u32,
+    last_updated: u64,
+    successes: u32,
+    failures: u32,
+}
+
+impl TryFrom<QTableRepr> for QTable {
+    type Error = String;
+
+    fn try_from(repr: QTableRepr) -> Result<Self, Self::Error> {
+        let mut table = QTable::new(repr.config);
+        table.current_epsilon = repr.current_epsilon;
+        table.total_updates = repr.total_updates;
+        table.total_episodes = repr.total_episodes;
+        table.priors = repr.priors;
+        table.pruned_states = repr.pruned_states;
+        table.pruned_entries = repr.pruned_entries;
+        table.prune_passes = repr.prune_passes;
+
+        if let Some(rows) = repr.rows {
+            table.rows = rows;
+        }
+
+        for (key, legacy) in repr.entries.unwrap_or_default() {
+            let (state, action) = parse_legacy_key(&key)
+                .ok_or_else(|| format!("invalid legacy Q-table key '{}'", key))?;
+            table.set_entry(state, action, QEntry {
+                value: legacy.value,
+                visit_count: legacy.visit_count,
+                last_updated: legacy.last_updated,
+                successes: legacy.successes,
+                failures: legacy.failures,
+            });
+        }
+
+        Ok(table)
+    }
+}
+
+/// Parse a legacy `"state::action_index"` key
+fn parse_legacy_key(key: &str) -> Option<(StateHash, Action)> {
+    let (state, action) = key.split_once("::")?;
+    Some((state.parse().ok()?, Action::from_index(action.parse().ok()?)?))
+}
+
 /// Q-table statistics snapshot
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct QTableStats {
@@ -576,13 +951,44 @@ pub struct QTableStats {
     pub mean_q_value: f32,
     pub alpha: f32,
     pub gamma: f32,
+    /// States held
+    #[serde(default)]
+    pub state_count: u32,
+    /// Capacity bound, if any
+    #[serde(default)]
+    pub max_states: Option<u32>,
+    /// States evicted by pruning
+    #[serde(default)]
+    pub pruned_states: u64,
+    /// State-action entries evicted by pruning
+    #[serde(default)]
+    pub pruned_entries: u64,
+    /// Pruning passes run
+    #[serde(default)]
+    pub prune_passes: u32,
+    /// Query types with a prior
+    #[serde(default)]
+    pub prior_count: u32,
+}
+
+/// Query type encoded in the low bits of a state hash
+fn query_type_of(state: StateHash) -> QueryType {
+    EncodedStateHash::from_u64(state).decode().query_type
+}
+
+/// Get current timestamp (Unix milliseconds)
+#[cfg(target_arch = "wasm32")]
+fn current_timestamp() -> u64 {
+    js_sys::Date::now() as u64
 }
 
-/// Get current timestamp
+#[cfg(not(target_arch = "wasm32"))]
 fn current_timestamp() -> u64 {
-    // In WASM, would use js_sys::Date::now()
-    // For now, placeholder
-    0
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
 }
 
 /// Random number generator (0.0-1.0)
@@ -605,7 +1011,7 @@ mod tests {
     #[test]
     fn test_q_table_creation() {
         let qt = QTable::default();
-        assert_eq!(qt.entries.len(), 0);
+        assert_eq!(qt.len(), 0);
         assert_eq!(qt.config.alpha, 0.1);
         assert_eq!(qt.config.gamma, 0.95);
     }
@@ -643,7 +1049,7 @@ mod tests {
         assert!(new_q > 0.0);
 
         // Entry was created
-        assert_eq!(qt.entries.len(), 1);
+        assert_eq!(qt.len(), 1);
     }
 
     #[test]
@@ -836,4 +1242,109 @@ mod tests {
         assert_eq!(config.epsilon, 0.1);
         assert_eq!(config.epsilon_min, 0.01);
     }
+
+    #[test]
+    fn test_row_layout() {
+        let mut qt = QTable::default();
+        let state = 12345;
+
+        qt.set_q_value(state, Action::ContextAnswer, 0.8);
+        qt.update_q_value(state, Action::Escalate, -1.0, 0.0);
+
+        let row = qt.row(state).unwrap();
+        assert_eq!(row.len(), 2);
+        assert_eq!(row.values, [0.0, 0.8, 0.0, 0.0, -0.1]);
+        assert!(row.contains(Action::Escalate));
+        assert!(!row.contains(Action::DirectAnswer));
+        assert_eq!(qt.entry(state, Action::Escalate).unwrap().failures, 1);
+        assert_eq!(qt.entry(state, Action::DirectAnswer), None);
+        assert_eq!(qt.state_count(), 1);
+        assert_eq!(qt.iter().count(), 2);
+    }
+
+    #[test]
+    fn test_batch_update_matches_scalar() {
+        let mut batched = QTable::default();
+        let mut scalar = QTable::default();
+        for qt in [&mut batched, &mut scalar] {
+            qt.set_q_value(2, Action::DirectAnswer, 1.0);
+            qt.set_q_value(1, Action::ConsultPeer, 0.4);
+        }
+
+        let transitions = [
+            (1, Action::ConsultPeer, 1.0, 2),
+            (1, Action::Escalate, -0.5, 3),
+            (2, Action::ContextAnswer, 0.2, 1),
+        ];
+        let next_max: Vec<f32> = transitions.iter().map(|t| scalar.get_max_q(t.3)).collect();
+
+        batched.batch_update(&transitions);
+        for (&(state, action, reward, _), next_max_q) in transitions.iter().zip(next_max) {
+            scalar.update_q_value(state, action, reward, next_max_q);
+        }
+
+        for &(state, action, _, _) in &transitions {
+            assert!((batched.get_q_value(state, action) - scalar.get_q_value(state, action)).abs() < 1e-6);
+            assert_eq!(batched.entry(state, action), scalar.entry(state, action));
+        }
+        assert_eq!(batched.total_updates, 3);
+    }
+
+    #[test]
+    fn test_import_legacy_format() {
+        let legacy = serde_json::json!({
+            "entries": {
+                "12345::1": {
+                    "state_action_key": "12345::1",
+                    "value": 0.5,
+                    "visit_count": 3,
+                    "last_updated": 7,
+                    "successes": 2,
+                    "failures": 1
+                },
+                "12345::4": {
+                    "state_action_key": "12345::4",
+                    "value": -0.2,
+                    "visit_count": 1,
+                    "last_updated": 0,
+                    "successes": 0,
+                    "failures": 1
+                }
+            },
+            "config": QLearningConfig::default(),
+            "current_epsilon": 0.05,
+            "total_updates": 4,
+            "total_episodes": 2
+        });
+
+        let qt = QTable::import(&serde_json::to_vec(&legacy).unwrap()).unwrap();
+        assert_eq!(qt.len(), 2);
+        assert_eq!(qt.state_count(), 1);
+        assert_eq!(qt.get_q_value(12345, Action::ContextAnswer), 0.5);
+        assert_eq!(qt.entry(12345, Action::ContextAnswer).unwrap().visit_count, 3);
+        assert_eq!(qt.get_q_value(12345, Action::Escalate), -0.2);
+        assert_eq!(qt.epsilon(), 0.05);
+        assert_eq!(qt.total_updates, 4);
+
+        // Re-exported in the current layout
+        let data = qt.export().unwrap();
+        assert!(!String::from_utf8_lossy(&data).contains("state_action_key"));
+        let qt2 = QTable::import(&data).unwrap();
+        assert_eq!(qt2.entry(12345, Action::ContextAnswer), qt.entry(12345, Action::ContextAnswer));
+    }
+
+    #[test]
+    fn test_import_legacy_invalid_key() {
+        let legacy = serde_json::json!({
+            "entries": {
+                "12345::9": { "value": 0.5, "visit_count": 0, "last_updated": 0, "successes": 0, "failures": 0 }
+            },
+            "config": QLearningConfig::default(),
+            "current_epsilon": 0.1,
+            "total_updates": 0,
+            "total_episodes": 0
+        });
+
+        assert!(QTable::import(&serde_json::to_vec(&legacy).unwrap()).is_err());
+    }
 }
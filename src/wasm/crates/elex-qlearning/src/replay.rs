@@ -40,6 +40,9 @@ pub struct Transition {
     pub td_error: f32,
     /// Timestamp (Unix milliseconds)
     pub timestamp: u64,
+    /// Probability the behaviour policy had of taking `action`
+    #[serde(default)]
+    pub behavior_prob: Option<f32>,
 }
 
 impl Transition {
@@ -58,9 +61,16 @@ impl Transition {
             next_state,
             td_error,
             timestamp: current_timestamp(),
+            behavior_prob: None,
         }
     }
 
+    /// Record the behaviour policy's probability of `action`
+    pub fn with_behavior_prob(mut self, probability: Option<f32>) -> Self {
+        self.behavior_prob = probability;
+        self
+    }
+
     /// Compute priority score for PER sampling
     ///
     /// Priority = |TD-error|^alpha
@@ -216,8 +226,45 @@ impl PrioritizedBuffer {
         result
     }
 
+    /// Sample batch indices with normalized importance weights
+    ///
+    /// Returns `(index, weight)` pairs for `update_priority`. Weights are
+    /// `(N * P(i))^(-beta)` divided by the largest weight in the buffer, so
+    /// they only ever scale updates down. Unlike `sample`, beta is not
+    /// annealed; the caller owns the schedule via `set_beta`.
+    pub fn sample_indices(&self, batch_size: usize) -> Vec<(usize, f32)> {
+        let n = self.experiences.len();
+        if n == 0 {
+            return Vec::new();
+        }
+
+        // Largest weight belongs to the lowest priority
+        let min_priority = self.priorities.iter().copied().fold(f32::INFINITY, f32::min);
+        let max_weight = self.experiences[0]
+            .importance_weight(min_priority, self.total_priority, n, self.beta);
+
+        (0..batch_size.min(n))
+            .filter_map(|_| self.sample_index())
+            .map(|i| {
+                let weight = self.experiences[i]
+                    .importance_weight(self.priorities[i], self.total_priority, n, self.beta);
+                (i, weight / max_weight)
+            })
+            .collect()
+    }
+
+    /// Get transition by index
+    pub fn get(&self, index: usize) -> Option<&Transition> {
+        self.experiences.get(index)
+    }
+
     /// Sample single transition (weighted by priority)
     fn sample_one(&self) -> Option<&Transition> {
+        self.sample_index().and_then(|i| self.experiences.get(i))
+    }
+
+    /// Sample single index (weighted by priority)
+    fn sample_index(&self) -> Option<usize> {
         if self.experiences.is_empty() {
             return None;
         }
@@ -226,22 +273,20 @@ impl PrioritizedBuffer {
             // Uniform sampling if no priority
             let idx = (random_f32() * self.experiences.len() as f32) as usize
                 % self.experiences.len();
-            return self.experiences.get(idx);
+            return Some(idx);
         }
 
         // Weighted sampling by priority
         let mut threshold = random_f32() * self.total_priority;
-        for (i, exp) in self.experiences.iter().enumerate() {
-            if let Some(&priority) = self.priorities.get(i) {
-                if threshold <= priority {
-                    return Some(exp);
-                }
-                threshold -= priority;
+        for (i, &priority) in self.priorities.iter().enumerate() {
+            if threshold <= priority {
+                return Some(i);
             }
+            threshold -= priority;
         }
 
         // Fallback to last
-        self.experiences.last()
+        Some(self.experiences.len() - 1)
     }
 
     /// Update priority for a transition (after TD-error recomputation)
@@ -254,6 +299,7 @@ impl PrioritizedBuffer {
         if let Some(p) = self.priorities.get_mut(index) {
             *p = new_priority;
             self.total_priority += new_priority;
+            self.experiences[index].td_error = td_error;
         }
     }
 
@@ -468,7 +514,7 @@ fn random_f32() -> f32 {
     STATE.with(|s| {
         let mut state = s.borrow_mut();
         *state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
-        ((*state >> 32) & 0x7FFFFFFF) as f32 / (u32::MAX as f32)
+        (*state >> 40) as f32 / (1u64 << 24) as f32
     })
 }
 
@@ -0,0 +1,226 @@
+//! Multi-Step Returns over Agent Trajectories
+//!
+//! One-step updates only move credit one transition back per feedback, so
+//! the value of an early ConsultPeer in a ConsultPeer → ContextAnswer
+//! interaction lags behind the answer that earned the reward. The routines
+//! here consume a whole `AgentTrajectory` and propagate its rewards back
+//! through every transition in one pass.
+//!
+//! # Update Rules
+//! ```text
+//! n-step TD:   G_t = r_t + γ r_{t+1} + ... + γ^{n-1} r_{t+n-1} + γ^n max_a Q(s_{t+n},a)
+//!              Q(s_t,a_t) <- Q(s_t,a_t) + α[G_t - Q(s_t,a_t)]
+//!
+//! Watkins Q(λ): δ_t = r_t + γ max_a Q(s_{t+1},a) - Q(s_t,a_t)
+//!              e(s_t,a_t) <- 1, Q <- Q + α δ_t e for every traced pair
+//!              e <- γλ e if a_{t+1} is greedy, otherwise e <- 0
+//! ```
+//!
+//! A completed trajectory (any outcome other than `Ongoing`) ends in a
+//! terminal state, so its last transition does not bootstrap. An ongoing
+//! trajectory bootstraps from the Q-table at its last next-state.
+
+use crate::policy::Action;
+use crate::qtable::{QTable, StateHash};
+use crate::trajectory::AgentTrajectory;
+use hashbrown::HashMap;
+
+/// Traces below this weight are dropped
+const MIN_TRACE: f32 = 1e-4;
+
+// ============================================================================
+// Dispatch
+// ============================================================================
+
+/// Update a Q-table from a trajectory using the table's configuration
+///
+/// Uses Watkins Q(λ) when `lambda > 0`, otherwise n-step TD with `n_step`.
+/// Returns the number of state-action updates applied.
+pub fn trajectory_update(table: &mut QTable, trajectory: &AgentTrajectory) -> usize {
+    let (n_step, lambda) = (table.config().n_step, table.config().lambda);
+    if lambda > 0.0 {
+        q_lambda_update(table, trajectory, lambda)
+    } else {
+        n_step_update(table, trajectory, n_step)
+    }
+}
+
+// ============================================================================
+// n-Step TD
+// ============================================================================
+
+/// Apply n-step Q-learning to every transition of a trajectory
+///
+/// Transitions are updated in order; returns near the end of the trajectory
+/// are truncated at the terminal state. `n = 1` is one-step Q-learning.
+pub fn n_step_update(table: &mut QTable, trajectory: &AgentTrajectory, n: usize) -> usize {
+    let transitions = &trajectory.transitions;
+    let terminal = !trajectory.is_ongoing();
+    let gamma = table.config().gamma;
+    let alpha = table.config().alpha;
+    let n = n.max(1);
+
+    for t in 0..transitions.len() {
+        let horizon = (t + n).min(transitions.len());
+
+        let mut target = 0.0;
+        let mut discount = 1.0;
+        for transition in &transitions[t..horizon] {
+            target += discount * transition.reward;
+            discount *= gamma;
+        }
+        if horizon < transitions.len() || !terminal {
+            target += discount * table.get_max_q(transitions[horizon - 1].next_state);
+        }
+
+        let transition = &transitions[t];
+        let current = table.get_q_value(transition.state, transition.action);
+        let updated = current + alpha * (target - current);
+        table.record_update(transition.state, transition.action, updated, transition.reward);
+    }
+
+    transitions.len()
+}
+
+// ============================================================================
+// Watkins Q(λ)
+// ============================================================================
+
+/// Apply Watkins Q(λ) with replacing traces to a trajectory
+///
+/// Traces are cut after an exploratory (non-greedy) action, since the
+/// returns that follow it no longer estimate the greedy policy. Returns the
+/// number of state-action updates applied, counting trace updates.
+pub fn q_lambda_update(table: &mut QTable, trajectory: &AgentTrajectory, lambda: f32) -> usize {
+    let transitions = &trajectory.transitions;
+    let terminal = !trajectory.is_ongoing();
+    let gamma = table.config().gamma;
+    let alpha = table.config().alpha;
+    let decay = gamma * lambda.clamp(0.0, 1.0);
+
+    let mut traces: HashMap<(StateHash, Action), f32> = HashMap::new();
+    let mut updates = 0;
+
+    for (t, transition) in transitions.iter().enumerate() {
+        let last = t + 1 == transitions.len();
+        let bootstrap = if last && terminal {
+            0.0
+        } else {
+            table.get_max_q(transition.next_state)
+        };
+        let key = (transition.state, transition.action);
+        let delta = transition.reward + gamma * bootstrap - table.get_q_value(key.0, key.1);
+
+        traces.insert(key, 1.0);
+        for (&(state, action), &trace) in &traces {
+            let updated = table.get_q_value(state, action) + alpha * delta * trace;
+            if (state, action) == key {
+                table.record_update(state, action, updated, transition.reward);
+            } else {
+                table.set_q_value(state, action, updated);
+            }
+            updates += 1;
+        }
+
+        // Decay traces while the next action follows the greedy policy
+        let greedy_next = transitions.get(t + 1).is_some_and(|next| {
+            table.get_q_value(next.state, next.action) >= table.get_max_q(next.state)
+        });
+        if greedy_next {
+            traces.retain(|_, trace| {
+                *trace *= decay;
+                *trace >= MIN_TRACE
+            });
+        } else {
+            traces.clear();
+        }
+    }
+
+    updates
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qtable::QLearningConfig;
+    use crate::replay::Transition;
+    use crate::trajectory::TrajectoryOutcome;
+
+    /// ConsultPeer (1 → 2), then ContextAnswer (2 → 3) rewarded with 1.0
+    fn consult_then_answer(outcome: TrajectoryOutcome) -> AgentTrajectory {
+        let mut trajectory = AgentTrajectory::new(0, [0u8; 32], 7);
+        trajectory.add_transition(Transition::new(1, Action::ConsultPeer, 0.0, 2, 0.0));
+        trajectory.add_transition(Transition::new(2, Action::ContextAnswer, 1.0, 3, 0.0));
+        trajectory.complete(outcome);
+        trajectory
+    }
+
+    fn table(n_step: usize, lambda: f32) -> QTable {
+        QTable::new(QLearningConfig {
+            n_step,
+            lambda,
+            ..QLearningConfig::elex_default()
+        })
+    }
+
+    #[test]
+    fn test_one_step_matches_q_learning() {
+        let trajectory = consult_then_answer(TrajectoryOutcome::Ongoing);
+        let mut qt = table(1, 0.0);
+        let mut reference = table(1, 0.0);
+        reference.set_q_value(3, Action::Escalate, 0.4);
+        qt.set_q_value(3, Action::Escalate, 0.4);
+
+        assert_eq!(trajectory_update(&mut qt, &trajectory), 2);
+        reference.update_q_value(1, Action::ConsultPeer, 0.0, 0.0);
+        reference.update_q_value(2, Action::ContextAnswer, 1.0, 0.4);
+
+        assert_eq!(qt.get_q_value(1, Action::ConsultPeer), reference.get_q_value(1, Action::ConsultPeer));
+        assert_eq!(qt.get_q_value(2, Action::ContextAnswer), reference.get_q_value(2, Action::ContextAnswer));
+    }
+
+    #[test]
+    fn test_n_step_credits_first_transition() {
+        let trajectory = consult_then_answer(TrajectoryOutcome::Success);
+
+        let mut one_step = table(1, 0.0);
+        n_step_update(&mut one_step, &trajectory, 1);
+        assert_eq!(one_step.get_q_value(1, Action::ConsultPeer), 0.0);
+
+        let mut two_step = table(2, 0.0);
+        n_step_update(&mut two_step, &trajectory, 2);
+        // α γ r = 0.1 * 0.95 * 1.0; terminal, so no bootstrap
+        assert!((two_step.get_q_value(1, Action::ConsultPeer) - 0.095).abs() < 1e-6);
+        assert!((two_step.get_q_value(2, Action::ContextAnswer) - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_q_lambda_propagates_through_traces() {
+        let trajectory = consult_then_answer(TrajectoryOutcome::Success);
+        let mut qt = table(1, 0.8);
+
+        // Q(2, ContextAnswer) = 0 is greedy (ties count), so the trace survives
+        assert_eq!(trajectory_update(&mut qt, &trajectory), 3);
+        // α δ γλ = 0.1 * 1.0 * 0.95 * 0.8
+        assert!((qt.get_q_value(1, Action::ConsultPeer) - 0.076).abs() < 1e-6);
+        assert!((qt.get_q_value(2, Action::ContextAnswer) - 0.1).abs() < 1e-6);
+        assert_eq!(qt.entry(1, Action::ConsultPeer).unwrap().visit_count, 1);
+    }
+
+    #[test]
+    fn test_q_lambda_cuts_trace_after_exploration() {
+        let trajectory = consult_then_answer(TrajectoryOutcome::Success);
+        let mut qt = table(1, 0.8);
+        // ContextAnswer is not greedy in state 2
+        qt.set_q_value(2, Action::DirectAnswer, 0.5);
+
+        q_lambda_update(&mut qt, &trajectory, 0.8);
+
+        // Only the one-step update reached state 1: α γ max Q(2,·)
+        assert!((qt.get_q_value(1, Action::ConsultPeer) - 0.1 * 0.95 * 0.5).abs() < 1e-6);
+    }
+}
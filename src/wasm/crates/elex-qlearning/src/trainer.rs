@@ -0,0 +1,307 @@
+//! Replay Training Loop
+//!
+//! Drains a `PrioritizedBuffer` into a Q-table in small, bounded chunks so
+//! training can run in idle time of the wasm event loop without blocking
+//! query handling. Each step samples a mini-batch, applies
+//! importance-weighted updates through the agent's `Learner`, writes the
+//! new TD errors back as priorities and advances the β schedule.
+//!
+//! # Weighted Update
+//! ```text
+//! δ_i = r_i + γ V(s'_i) - Q(s_i,a_i)      V chosen by the learner
+//! Q(s_i,a_i) <- Q(s_i,a_i) + α w_i δ_i
+//! ```
+//!
+//! Replaying through the learner keeps replay consistent with online
+//! updates: Expected SARSA bootstraps from its expectation, Double
+//! Q-learning updates its estimators and the linear approximator its
+//! weights.
+
+use crate::learner::Learner;
+use crate::qtable::QTable;
+use crate::replay::{PrioritizedBuffer, Transition};
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Replay trainer configuration
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayConfig {
+    /// Replay buffer capacity
+    pub capacity: usize,
+    /// Prioritization alpha (0.0 = uniform)
+    pub alpha: f32,
+    /// Transitions per mini-batch
+    pub batch_size: usize,
+    /// Transitions required before training starts
+    pub min_samples: usize,
+    /// Initial importance sampling beta
+    pub beta_start: f32,
+    /// Training steps over which beta anneals to 1.0
+    pub beta_steps: u64,
+}
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 1000,
+            alpha: 0.6,
+            batch_size: 32,
+            min_samples: 64,
+            beta_start: 0.4,
+            beta_steps: 10_000,
+        }
+    }
+}
+
+// ============================================================================
+// Replay Trainer
+// ============================================================================
+
+/// Outcome of a bounded training run
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplayReport {
+    /// Mini-batches applied
+    pub steps: u32,
+    /// Transitions updated across all mini-batches
+    pub samples: u32,
+    /// Mean |TD error| before the updates
+    pub mean_td_error: f32,
+    /// Beta after the run
+    pub beta: f32,
+}
+
+/// Prioritized replay training loop
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ReplayTrainer {
+    /// Experience storage
+    buffer: PrioritizedBuffer,
+    /// Configuration
+    config: ReplayConfig,
+    /// Training steps taken (drives beta annealing)
+    steps: u64,
+    /// Largest |TD error| seen, assigned to new transitions
+    max_td_error: f32,
+}
+
+impl ReplayTrainer {
+    /// Create a trainer with an empty buffer
+    pub fn new(config: ReplayConfig) -> Self {
+        let mut buffer = PrioritizedBuffer::new(config.capacity, config.alpha);
+        buffer.set_beta(config.beta_start);
+        Self {
+            buffer,
+            config,
+            steps: 0,
+            max_td_error: 1.0,
+        }
+    }
+
+    /// Create with default ELEX settings
+    pub fn elex_default() -> Self {
+        Self::new(ReplayConfig::default())
+    }
+
+    /// Store a transition for replay
+    ///
+    /// New transitions get the largest priority seen so far, so every
+    /// transition is replayed at least once before its TD error is known.
+    pub fn record(&mut self, mut transition: Transition) {
+        let td_error = transition.td_error.abs().max(self.max_td_error);
+        transition.td_error = td_error;
+        self.buffer.push(transition);
+    }
+
+    /// Check if enough transitions are stored to train
+    pub fn is_ready(&self) -> bool {
+        self.buffer.len() >= self.config.min_samples.max(1)
+    }
+
+    /// Train for at most `budget` mini-batches
+    ///
+    /// Returns immediately with an empty report while the buffer holds
+    /// fewer than `min_samples` transitions.
+    pub fn run(&mut self, table: &mut QTable, learner: &mut dyn Learner, budget: u32) -> ReplayReport {
+        let mut report = ReplayReport::default();
+        let mut td_error_sum = 0.0;
+
+        while report.steps < budget && self.is_ready() {
+            let (samples, td_error) = self.step(table, learner);
+            report.steps += 1;
+            report.samples += samples as u32;
+            td_error_sum += td_error;
+        }
+
+        if report.samples > 0 {
+            report.mean_td_error = td_error_sum / report.samples as f32;
+        }
+        report.beta = self.buffer.beta();
+        report
+    }
+
+    /// Apply one mini-batch; returns the sample count and the |TD error| sum
+    fn step(&mut self, table: &mut QTable, learner: &mut dyn Learner) -> (usize, f32) {
+        let batch = self.buffer.sample_indices(self.config.batch_size);
+
+        let mut td_error_sum = 0.0;
+        for &(index, weight) in &batch {
+            let t = self.buffer.get(index).expect("sampled index in buffer");
+            let (state, action, reward, next_state) = (t.state, t.action, t.reward, t.next_state);
+            let td_error = learner.update_weighted(table, state, action, reward, next_state, weight);
+
+            self.buffer.update_priority(index, td_error);
+            self.max_td_error = self.max_td_error.max(td_error.abs());
+            td_error_sum += td_error.abs();
+        }
+
+        self.steps += 1;
+        self.buffer.set_beta(self.beta_at(self.steps));
+        (batch.len(), td_error_sum)
+    }
+
+    /// Linear beta schedule from `beta_start` to 1.0
+    fn beta_at(&self, steps: u64) -> f32 {
+        let progress = (steps as f32 / self.config.beta_steps.max(1) as f32).min(1.0);
+        self.config.beta_start + (1.0 - self.config.beta_start) * progress
+    }
+
+    /// Current importance sampling beta
+    pub fn beta(&self) -> f32 {
+        self.buffer.beta()
+    }
+
+    /// Training steps taken
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Get the replay buffer
+    pub fn buffer(&self) -> &PrioritizedBuffer {
+        &self.buffer
+    }
+
+    /// Get configuration
+    pub fn config(&self) -> &ReplayConfig {
+        &self.config
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::learner::{DoubleQLearning, ExpectedSarsa, QLearning};
+    use crate::policy::Action;
+    use crate::qtable::QLearningConfig;
+
+    fn trainer(batch_size: usize, beta_steps: u64) -> ReplayTrainer {
+        ReplayTrainer::new(ReplayConfig {
+            batch_size,
+            min_samples: 4,
+            beta_steps,
+            ..ReplayConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_waits_for_min_samples() {
+        let mut trainer = trainer(2, 100);
+        let mut table = QTable::new(QLearningConfig::elex_default());
+        for _ in 0..3 {
+            trainer.record(Transition::new(1, Action::DirectAnswer, 1.0, 2, 0.0));
+        }
+
+        assert!(!trainer.is_ready());
+        assert_eq!(trainer.run(&mut table, &mut QLearning, 10), ReplayReport { beta: 0.4, ..Default::default() });
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn test_run_respects_budget_and_anneals_beta() {
+        let mut trainer = trainer(2, 10);
+        let mut table = QTable::new(QLearningConfig::elex_default());
+        for i in 0..8 {
+            trainer.record(Transition::new(i, Action::DirectAnswer, 1.0, 100, 0.0));
+        }
+
+        let report = trainer.run(&mut table, &mut QLearning, 5);
+        assert_eq!(report.steps, 5);
+        assert_eq!(report.samples, 10);
+        assert!((report.beta - 0.7).abs() < 1e-6);
+
+        trainer.run(&mut table, &mut QLearning, 100);
+        assert_eq!(trainer.steps(), 105);
+        assert_eq!(trainer.beta(), 1.0);
+    }
+
+    #[test]
+    fn test_replay_converges_and_lowers_priorities() {
+        let mut trainer = trainer(4, 100);
+        let mut table = QTable::new(QLearningConfig::elex_default());
+        for state in 0..4 {
+            // Terminal-like next state with no value
+            trainer.record(Transition::new(state, Action::ConsultPeer, 0.5, 1000, 0.0));
+        }
+
+        let first = trainer.run(&mut table, &mut QLearning, 1);
+        trainer.run(&mut table, &mut QLearning, 200);
+        let last = trainer.run(&mut table, &mut QLearning, 1);
+
+        for state in 0..4 {
+            assert!((table.get_q_value(state, Action::ConsultPeer) - 0.5).abs() < 0.05);
+        }
+        assert!(last.mean_td_error < first.mean_td_error);
+    }
+
+    #[test]
+    fn test_replay_uses_learner_target() {
+        let mut table = QTable::new(QLearningConfig::elex_default());
+        table.set_q_value(100, Action::DirectAnswer, 1.0);
+        let mut q_table = table.clone();
+
+        let mut sarsa_trainer = trainer(4, 100);
+        let mut q_trainer = trainer(4, 100);
+        for state in 0..4 {
+            sarsa_trainer.record(Transition::new(state, Action::ConsultPeer, 0.0, 100, 0.0));
+            q_trainer.record(Transition::new(state, Action::ConsultPeer, 0.0, 100, 0.0));
+        }
+
+        // Uniform exploration bootstraps from the mean, not the max, of Q(s',·)
+        sarsa_trainer.run(&mut table, &mut ExpectedSarsa::new(1.0), 300);
+        q_trainer.run(&mut q_table, &mut QLearning, 300);
+        assert!((table.get_q_value(0, Action::ConsultPeer) - 0.95 * 0.2).abs() < 0.02);
+        assert!((q_table.get_q_value(0, Action::ConsultPeer) - 0.95).abs() < 0.02);
+
+        // Double Q-learning replays into its estimators
+        let mut table = QTable::new(QLearningConfig::elex_default());
+        let mut double_q = DoubleQLearning::new(table.config(), 5);
+        let mut trainer = trainer(4, 100);
+        for state in 0..4 {
+            trainer.record(Transition::new(state, Action::ConsultPeer, 1.0, 1000, 0.0));
+        }
+        trainer.run(&mut table, &mut double_q, 10);
+        let (a, b) = double_q.estimators();
+        assert!(a.len() + b.len() > 0);
+    }
+
+    #[test]
+    fn test_importance_weights_scale_updates() {
+        let mut buffer = PrioritizedBuffer::new(10, 0.6);
+        buffer.push(Transition::new(1, Action::DirectAnswer, 0.0, 2, 0.1));
+        buffer.push(Transition::new(2, Action::DirectAnswer, 0.0, 2, 2.0));
+        buffer.set_beta(1.0);
+
+        for (index, weight) in buffer.sample_indices(16) {
+            assert!(weight > 0.0 && weight <= 1.0);
+            if index == 0 {
+                // Lowest priority has the largest weight
+                assert!((weight - 1.0).abs() < 1e-6);
+            }
+        }
+    }
+}
@@ -89,6 +89,17 @@ impl AgentTrajectory {
         self.transitions.push(transition);
     }
 
+    /// Set the reward of the last transition (delayed feedback)
+    ///
+    /// Earlier transitions keep their rewards; multi-step updates carry
+    /// the final reward back to them.
+    pub fn set_final_reward(&mut self, reward: f32) {
+        if let Some(last) = self.transitions.last_mut() {
+            self.total_reward += reward - last.reward;
+            last.reward = reward;
+        }
+    }
+
     /// Mark trajectory as completed
     pub fn complete(&mut self, outcome: TrajectoryOutcome) {
         self.end_time = Some(now());
@@ -365,6 +376,11 @@ impl AgentTrajectoryBuffer {
         self.trajectories.is_empty()
     }
 
+    /// Iterate over stored trajectories
+    pub fn iter(&self) -> impl Iterator<Item = &AgentTrajectory> {
+        self.trajectories.iter()
+    }
+
     /// Clear all trajectories
     pub fn clear(&mut self) {
         self.trajectories.clear();
@@ -0,0 +1,134 @@
+//! Property-Based Tests for Exploration Policies
+//!
+//! Uses proptest to verify invariants shared by every policy:
+//! - Selections stay within the available actions
+//! - Reported behaviour probabilities are valid probabilities
+//! - Equal seeds give equal action sequences
+//! - Boltzmann probabilities form a distribution ordered by Q-value
+//! - Temperature schedules never cool below their floor
+
+use proptest::prelude::*;
+use elex_qlearning::exploration::{create_policy, BoltzmannPolicy, PolicyConfig};
+use elex_qlearning::policy::{Action, Policy};
+use elex_qlearning::qtable::{QEntry, QLearningConfig, QTable};
+
+const STATE: u64 = 42;
+
+fn policy_config() -> impl Strategy<Value = PolicyConfig> {
+    prop_oneof![
+        (0.0f32..1.0, 0.0f32..0.1, 0.9f32..1.0).prop_map(|(epsilon, epsilon_min, decay)| {
+            PolicyConfig::EpsilonGreedy { epsilon, epsilon_min, decay }
+        }),
+        (0.0f32..3.0).prop_map(|c| PolicyConfig::Ucb { c }),
+        (0.01f32..10.0, 0.9f32..1.0).prop_map(|(temperature, cooling)| PolicyConfig::Boltzmann {
+            temperature,
+            temperature_min: 0.01,
+            cooling,
+        }),
+        Just(PolicyConfig::Thompson),
+    ]
+}
+
+/// Q-table with one entry per action: (value, successes, failures)
+fn q_table(entries: &[(f32, u32, u32)]) -> QTable {
+    let mut qt = QTable::new(QLearningConfig::elex_default());
+    for (action, &(value, successes, failures)) in Action::all().iter().zip(entries) {
+        qt.set_entry(STATE, *action, QEntry { successes, failures, ..QEntry::new(value) });
+    }
+    qt
+}
+
+fn entries() -> impl Strategy<Value = Vec<(f32, u32, u32)>> {
+    prop::collection::vec((-5.0f32..5.0, 0u32..50, 0u32..50), 5)
+}
+
+fn actions() -> impl Strategy<Value = Vec<Action>> {
+    prop::sample::subsequence(Action::all().to_vec(), 1..=5)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(200))]
+
+    /// Every policy selects one of the available actions
+    #[test]
+    fn prop_selects_available_action(
+        config in policy_config(),
+        entries in entries(),
+        actions in actions(),
+        seed in any::<u64>(),
+    ) {
+        let qt = q_table(&entries);
+        let mut policy = create_policy(&config, seed);
+
+        for _ in 0..10 {
+            let selection = policy.select_action(&qt, STATE, &actions);
+            prop_assert!(actions.contains(&selection.action));
+            prop_assert_eq!(selection.q_value, qt.get_q_value(STATE, selection.action));
+            if let Some(p) = selection.probability {
+                prop_assert!(p > 0.0 && p <= 1.0);
+            }
+            policy.decay();
+        }
+    }
+
+    /// Policies created with the same seed make the same choices
+    #[test]
+    fn prop_seed_reproducible(
+        config in policy_config(),
+        entries in entries(),
+        seed in any::<u64>(),
+    ) {
+        let qt = q_table(&entries);
+        let mut a = create_policy(&config, seed);
+        let mut b = create_policy(&config, seed);
+
+        for _ in 0..20 {
+            prop_assert_eq!(
+                a.select_action(&qt, STATE, Action::all()).action,
+                b.select_action(&qt, STATE, Action::all()).action
+            );
+        }
+    }
+
+    /// Boltzmann probabilities sum to 1 and follow the Q-value order
+    #[test]
+    fn prop_boltzmann_distribution(
+        entries in entries(),
+        temperature in 0.05f32..20.0,
+    ) {
+        let qt = q_table(&entries);
+        let policy = BoltzmannPolicy::new(0, temperature);
+        let probabilities = policy.probabilities(&qt, STATE, Action::all());
+
+        let total: f32 = probabilities.iter().map(|(_, p)| p).sum();
+        prop_assert!((total - 1.0).abs() < 1e-4);
+
+        for &(a, pa) in &probabilities {
+            prop_assert!((0.0..=1.0).contains(&pa));
+            for &(b, pb) in &probabilities {
+                if qt.get_q_value(STATE, a) > qt.get_q_value(STATE, b) {
+                    prop_assert!(pa >= pb);
+                }
+            }
+        }
+    }
+
+    /// Cooling is monotone and bounded by the temperature floor
+    #[test]
+    fn prop_boltzmann_cooling_bounded(
+        temperature in 0.1f32..10.0,
+        temperature_min in 0.01f32..0.1,
+        cooling in 0.5f32..1.0,
+        steps in 0usize..500,
+    ) {
+        let mut policy = BoltzmannPolicy::new(0, temperature).with_cooling(temperature_min, cooling);
+        let mut previous = policy.temperature();
+
+        for _ in 0..steps {
+            policy.decay();
+            prop_assert!(policy.temperature() <= previous);
+            prop_assert!(policy.temperature() >= temperature_min);
+            previous = policy.temperature();
+        }
+    }
+}
@@ -32,7 +32,8 @@
 //! ```
 
 use elex_core::{ElexError, Result};
-use elex_qlearning::{QTable, QEntry};
+use elex_qlearning::{QTable, QEntry, StateHash};
+use elex_qlearning::policy::Action;
 use hashbrown::HashSet;
 use serde::{Serialize, Deserialize};
 
@@ -235,20 +236,20 @@ impl FederatedMerger {
         let all_keys: HashSet<_> = local_keys.union(&peer_keys).cloned().collect();
 
         for key in all_keys {
-            let local_entry = local.get_entry_by_key(&key);
-            let peer_entry = peer.get_entry_by_key(&key);
+            let local_entry = local.get_entry_by_key(key);
+            let peer_entry = peer.get_entry_by_key(key);
 
             match (local_entry, peer_entry) {
                 (Some(local_ent), Some(peer_ent)) => {
                     // Both exist - merge them
                     let merged_entry = self.merge_entries(
-                        Some(local_ent),
-                        Some(peer_ent),
+                        Some(&local_ent),
+                        Some(&peer_ent),
                         &mut stats,
                     )?;
 
                     // Update the merged table with the merged entry
-                    merged.update_entry_from_key(&key, &merged_entry);
+                    merged.update_entry_from_key(key, &merged_entry);
                 }
                 (Some(local_ent), None) => {
                     // Only local exists - keep it
@@ -258,7 +259,7 @@ impl FederatedMerger {
                     // Only peer exists - add it
                     stats.total_visits += peer_ent.visit_count;
                     stats.new_peer_entries += 1;
-                    merged.insert_entry(peer_ent.clone());
+                    merged.insert_entry(key, peer_ent);
                 }
                 (None, None) => {
                     // Should not happen
@@ -366,13 +367,13 @@ impl FederatedMerger {
             (Some(entry), None) => {
                 // Only local exists
                 stats.total_visits += entry.visit_count;
-                Ok(entry.clone())
+                Ok(*entry)
             }
             (None, Some(entry)) => {
                 // Only peer exists
                 stats.total_visits += entry.visit_count;
                 stats.new_peer_entries += 1;
-                Ok(entry.clone())
+                Ok(*entry)
             }
             (None, None) => {
                 // Neither exists - should not happen
@@ -396,7 +397,6 @@ impl FederatedMerger {
         if total_visits == 0 {
             // No visits - use average of values
             return QEntry {
-                state_action_key: local.state_action_key.clone(),
                 value: (local.value + peer.value) / 2.0,
                 visit_count: 0,
                 last_updated: std::cmp::max(local.last_updated, peer.last_updated),
@@ -419,7 +419,6 @@ impl FederatedMerger {
             / total_visits as f32;
 
         QEntry {
-            state_action_key: local.state_action_key.clone(),
             value: merged_value,
             visit_count: total_visits,
             last_updated: std::cmp::max(local.last_updated, peer.last_updated),
@@ -435,12 +434,12 @@ impl FederatedMerger {
         if local.value >= peer.value {
             QEntry {
                 visit_count: local.visit_count + peer.visit_count,
-                ..local.clone()
+                ..*local
             }
         } else {
             QEntry {
                 visit_count: local.visit_count + peer.visit_count,
-                ..peer.clone()
+                ..*peer
             }
         }
     }
@@ -452,12 +451,12 @@ impl FederatedMerger {
         if local.value <= peer.value {
             QEntry {
                 visit_count: local.visit_count + peer.visit_count,
-                ..local.clone()
+                ..*local
             }
         } else {
             QEntry {
                 visit_count: local.visit_count + peer.visit_count,
-                ..peer.clone()
+                ..*peer
             }
         }
     }
@@ -503,36 +502,39 @@ impl MergeResult {
 // QTable Extensions
 // ============================================================================
 
+/// State-action key of a Q-table entry
+pub type EntryKey = (StateHash, Action);
+
 /// Extension methods for QTable to support federated operations
 pub trait QTableFederatedExt {
     /// Get all entry keys
-    fn keys(&self) -> Vec<String>;
+    fn keys(&self) -> Vec<EntryKey>;
 
     /// Get entry by key
-    fn get_entry_by_key(&self, key: &str) -> Option<&QEntry>;
+    fn get_entry_by_key(&self, key: EntryKey) -> Option<QEntry>;
 
     /// Insert entry
-    fn insert_entry(&mut self, entry: QEntry);
+    fn insert_entry(&mut self, key: EntryKey, entry: QEntry);
 
     /// Update entry from key
-    fn update_entry_from_key(&mut self, key: &str, entry: &QEntry);
+    fn update_entry_from_key(&mut self, key: EntryKey, entry: &QEntry);
 }
 
 impl QTableFederatedExt for QTable {
-    fn keys(&self) -> Vec<String> {
-        self.entries.keys().cloned().collect()
+    fn keys(&self) -> Vec<EntryKey> {
+        self.iter().map(|(state, action, _)| (state, action)).collect()
     }
 
-    fn get_entry_by_key(&self, key: &str) -> Option<&QEntry> {
-        self.entries.get(key)
+    fn get_entry_by_key(&self, key: EntryKey) -> Option<QEntry> {
+        self.entry(key.0, key.1)
     }
 
-    fn insert_entry(&mut self, entry: QEntry) {
-        self.entries.insert(entry.state_action_key.clone(), entry);
+    fn insert_entry(&mut self, key: EntryKey, entry: QEntry) {
+        self.set_entry(key.0, key.1, entry);
     }
 
-    fn update_entry_from_key(&mut self, key: &str, entry: &QEntry) {
-        self.entries.insert(key.to_string(), entry.clone());
+    fn update_entry_from_key(&mut self, key: EntryKey, entry: &QEntry) {
+        self.set_entry(key.0, key.1, *entry);
     }
 }
 
@@ -544,11 +546,9 @@ impl QTableFederatedExt for QTable {
 mod tests {
     use super::*;
     use elex_qlearning::{QLearningConfig, State, Reward};
-    use elex_qlearning::policy::Action;
 
-    fn create_test_entry(key: String, value: f32, visits: u32) -> QEntry {
+    fn create_test_entry(value: f32, visits: u32) -> QEntry {
         QEntry {
-            state_action_key: key,
             value,
             visit_count: visits,
             last_updated: 1000,
@@ -569,8 +569,8 @@ mod tests {
     fn test_weighted_average_merge() {
         let merger = FederatedMerger::new(MergeStrategy::WeightedAverage);
 
-        let local = create_test_entry("test::0".into(), 0.5, 10);
-        let peer = create_test_entry("test::0".into(), 0.8, 20);
+        let local = create_test_entry(0.5, 10);
+        let peer = create_test_entry(0.8, 20);
 
         let mut stats = MergeStats::default();
         let merged = merger.merge_entries(Some(&local), Some(&peer), &mut stats).unwrap();
@@ -585,8 +585,8 @@ mod tests {
     fn test_max_merge() {
         let merger = FederatedMerger::new(MergeStrategy::Maximum);
 
-        let local = create_test_entry("test::0".into(), 0.5, 10);
-        let peer = create_test_entry("test::0".into(), 0.8, 20);
+        let local = create_test_entry(0.5, 10);
+        let peer = create_test_entry(0.8, 20);
 
         let mut stats = MergeStats::default();
         let merged = merger.merge_entries(Some(&local), Some(&peer), &mut stats).unwrap();
@@ -600,8 +600,8 @@ mod tests {
     fn test_min_merge() {
         let merger = FederatedMerger::new(MergeStrategy::Minimum);
 
-        let local = create_test_entry("test::0".into(), 0.5, 10);
-        let peer = create_test_entry("test::0".into(), 0.8, 20);
+        let local = create_test_entry(0.5, 10);
+        let peer = create_test_entry(0.8, 20);
 
         let mut stats = MergeStats::default();
         let merged = merger.merge_entries(Some(&local), Some(&peer), &mut stats).unwrap();
@@ -615,7 +615,7 @@ mod tests {
     fn test_merge_single_sided() {
         let merger = FederatedMerger::new(MergeStrategy::WeightedAverage);
 
-        let local = create_test_entry("test::0".into(), 0.5, 10);
+        let local = create_test_entry(0.5, 10);
 
         let mut stats = MergeStats::default();
         let merged = merger.merge_entries(Some(&local), None, &mut stats).unwrap();
@@ -9,13 +9,23 @@ pub mod router;
 pub mod raft;
 pub mod raft_log;
 pub mod raft_state;
+pub mod raft_storage;
+pub mod raft_sim;
+pub mod transport;
 
 // Re-export main types
 pub use federation::{
     FederatedMerger, MergeStrategy, MergeStats, MergeResult, QTableFederatedExt,
 };
 pub use gossip::{GossipProtocol, GossipMessage, GossipEntry, GossipResponse, StateKey, QValue, GossipStats};
-pub use router::{SemanticRouter, RouteResult};
+pub use router::{SemanticRouter, RouteResult, RouteDecision, RouterConfig};
 pub use raft::{RaftNode, RaftConfig, RaftMessage, Role, RaftCluster};
-pub use raft_log::{RaftLog, RaftLogEntry, RaftCommand, AgentMetadata, LogSnapshot};
+pub use raft_log::{RaftLog, RaftLogEntry, RaftCommand, AgentMetadata, LogSnapshot, Membership};
 pub use raft_state::{RaftStateMachine, ClusterConfig};
+pub use raft_storage::{RaftStorage, HardState, PersistedState, MemoryStorage};
+#[cfg(not(target_arch = "wasm32"))]
+pub use raft_storage::FileStorage;
+#[cfg(target_arch = "wasm32")]
+pub use raft_storage::IndexedDbStorage;
+pub use raft_sim::{ClusterSim, SimConfig, SimNetwork, NetworkConfig, SafetyChecker, SimReport, Violation};
+pub use transport::{Transport, Envelope, Outbox};
@@ -4,12 +4,26 @@
 //! Provides leader election, log replication, and strong consistency
 //! for the routing index across 3-5 coordinator nodes.
 //!
+//! A node built `with_storage` persists its term, vote, log and snapshot
+//! and makes them durable before `step` or `tick` send any message, so it
+//! can crash and rejoin without breaking its promises to the cluster.
+//!
+//! Membership changes go through joint consensus: new nodes join as
+//! learners, are promoted once caught up, and the switch to a new voter
+//! set passes through a configuration that needs majorities of both the
+//! old and the new voters. A leader can hand over leadership, which it
+//! does on its own when it is removed.
+//!
 //! Based on the Raft paper: https://raft.github.io/
 
 use elex_core::types::AgentId;
 use elex_core::{ElexError, Result};
-use crate::raft_log::{RaftLog, RaftLogEntry, RaftCommand};
+use crate::raft_log::{LogSnapshot, Membership, RaftLog, RaftLogEntry, RaftCommand};
 use crate::raft_state::RaftStateMachine;
+use crate::raft_storage::{HardState, RaftStorage};
+use crate::transport::{Envelope, Outbox, Transport};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::time::Duration;
@@ -67,6 +81,60 @@ pub struct RaftNode {
 
     /// Last heartbeat time
     last_heartbeat: Option<std::time::Instant>,
+
+    /// Logical clock of the driver (ms), see `tick`/`step`
+    now_ms: u64,
+
+    /// Logical time at which a follower or candidate starts an election
+    election_deadline_ms: u64,
+
+    /// Logical time of the leader's next heartbeat round
+    heartbeat_due_ms: u64,
+
+    /// Source of election timeout jitter
+    timer_rng: ChaCha8Rng,
+
+    /// When to snapshot the state machine and how to ship snapshots
+    snapshot_config: SnapshotConfig,
+
+    /// Snapshot transfers in progress (leader only): peer -> (snapshot index, bytes acknowledged)
+    snapshot_progress: HashMap<AgentId, (u64, u64)>,
+
+    /// Snapshot being received (follower only)
+    incoming_snapshot: Option<IncomingSnapshot>,
+
+    /// Durable storage; None keeps all state in memory
+    storage: Option<Box<dyn RaftStorage>>,
+
+    /// Hard state last written to storage
+    persisted: HardState,
+
+    /// Lowest log index changed since the last write to storage
+    log_dirty_from: Option<u64>,
+
+    /// Whether the snapshot changed since the last write to storage
+    snapshot_dirty: bool,
+
+    /// Consecutive automatic snapshot failures and the applied index
+    /// before which the next attempt is skipped
+    snapshot_backoff: Option<(u32, u64)>,
+
+    /// Messages waiting for their state to become durable
+    held: Vec<Envelope>,
+
+    /// Latest configuration in the log or snapshot and the index of its
+    /// entry; None before the first one, while the voters are this node
+    /// and `config.peers`
+    membership: Option<(u64, Membership)>,
+
+    /// Configuration in force before the first configuration entry
+    initial_membership: Option<Membership>,
+
+    /// Leadership handover in progress (leader only)
+    transfer: Option<LeadershipTransfer>,
+
+    /// Logical time this node last heard from a current leader
+    leader_contact_ms: Option<u64>,
 }
 
 /// Node role in Raft
@@ -100,6 +168,48 @@ impl Default for RaftConfig {
     }
 }
 
+/// Snapshot settings
+///
+/// A snapshot replaces the applied prefix of the log with the serialized
+/// state machine. Followers that fall behind the snapshot receive it in
+/// chunks through InstallSnapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotConfig {
+    /// Snapshot once the log holds more entries than this (0 = never)
+    pub max_log_entries: usize,
+    /// Snapshot once this many entries were applied since the last snapshot (0 = never)
+    pub applied_threshold: u64,
+    /// Bytes of snapshot data per InstallSnapshot message
+    pub chunk_size: usize,
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self {
+            max_log_entries: 1000,
+            applied_threshold: 0,
+            chunk_size: 64 * 1024,
+        }
+    }
+}
+
+/// Snapshot being assembled from InstallSnapshot chunks
+#[derive(Debug, Clone)]
+struct IncomingSnapshot {
+    last_included_index: u64,
+    last_included_term: u64,
+    data: Vec<u8>,
+}
+
+/// Leadership handover in progress
+#[derive(Debug, Clone)]
+struct LeadershipTransfer {
+    /// Node taking over
+    target: AgentId,
+    /// Logical time at which the transfer is abandoned
+    deadline_ms: u64,
+}
+
 /// Leader-specific state
 #[derive(Debug, Clone)]
 pub struct LeaderState {
@@ -132,6 +242,10 @@ pub struct RaftStats {
     pub elections_lost: u64,
     pub log_entries: u64,
     pub commits: u64,
+    pub snapshots_taken: u64,
+    pub snapshots_installed: u64,
+    /// Automatic snapshots that failed (retried with backoff)
+    pub snapshot_failures: u64,
 }
 
 // ============================================================================
@@ -147,6 +261,7 @@ pub enum RaftMessage {
     AppendEntriesResponse(AppendEntriesResponse),
     InstallSnapshot(InstallSnapshotRequest),
     InstallSnapshotResponse(InstallSnapshotResponse),
+    TimeoutNow(TimeoutNowRequest),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -155,6 +270,10 @@ pub struct RequestVoteRequest {
     pub candidate_id: AgentId,
     pub last_log_index: u64,
     pub last_log_term: u64,
+    /// Set when campaigning on behalf of a leadership transfer; voters then
+    /// grant their vote although they heard from a leader recently
+    #[serde(default)]
+    pub leadership_transfer: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -180,18 +299,45 @@ pub struct AppendEntriesResponse {
     pub match_index: Option<u64>,
 }
 
+/// One chunk of a snapshot
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InstallSnapshotRequest {
     pub term: u64,
     pub leader_id: AgentId,
     pub last_included_index: u64,
     pub last_included_term: u64,
+    /// Byte offset of `data` within the snapshot
+    #[serde(default)]
+    pub offset: u64,
     pub data: Vec<u8>,
+    /// Whether this is the last chunk
+    #[serde(default = "default_true")]
+    pub done: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InstallSnapshotResponse {
     pub term: u64,
+    /// Snapshot the response refers to
+    #[serde(default)]
+    pub last_included_index: u64,
+    /// Offset of the next chunk the follower expects
+    #[serde(default)]
+    pub next_offset: u64,
+    /// Whether the follower holds the snapshot's state
+    #[serde(default)]
+    pub done: bool,
+}
+
+/// Asks the target of a leadership transfer to campaign at once
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeoutNowRequest {
+    pub term: u64,
+    pub leader_id: AgentId,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 // ============================================================================
@@ -201,12 +347,12 @@ pub struct InstallSnapshotResponse {
 impl RaftNode {
     /// Create new Raft node
     pub fn new(id: AgentId, config: RaftConfig) -> Self {
-        Self {
+        let mut node = Self {
             id,
             role: Role::Follower,
             current_term: 0,
             voted_for: None,
-            log: RaftLog::new(),
+            log: RaftLog::unbounded(),
             commit_index: 0,
             last_applied: 0,
             config,
@@ -217,7 +363,89 @@ impl RaftNode {
             stats: RaftStats::default(),
             votes_received: HashSet::new(),
             last_heartbeat: Some(std::time::Instant::now()),
+            now_ms: 0,
+            election_deadline_ms: 0,
+            heartbeat_due_ms: 0,
+            timer_rng: ChaCha8Rng::seed_from_u64(seed_from_id(&id)),
+            snapshot_config: SnapshotConfig::default(),
+            snapshot_progress: HashMap::new(),
+            incoming_snapshot: None,
+            storage: None,
+            persisted: HardState::default(),
+            log_dirty_from: None,
+            snapshot_dirty: false,
+            snapshot_backoff: None,
+            held: Vec::new(),
+            membership: None,
+            initial_membership: None,
+            transfer: None,
+            leader_contact_ms: None,
+        };
+        node.reset_election_timer();
+        node
+    }
+
+    /// Set when snapshots are taken and how they are shipped
+    pub fn with_snapshot_config(mut self, config: SnapshotConfig) -> Self {
+        self.snapshot_config = config;
+        self
+    }
+
+    /// Reseed the election timeout jitter
+    ///
+    /// Nodes seed from their id by default; simulations reseed them from
+    /// their own RNG so that a run is reproducible from one seed.
+    pub fn with_timer_seed(mut self, seed: u64) -> Self {
+        self.timer_rng = ChaCha8Rng::seed_from_u64(seed);
+        self.reset_election_timer();
+        self
+    }
+
+    /// Start the logical clock at `now_ms`, e.g. for a node restarted
+    /// while the cluster runs
+    pub fn with_clock(mut self, now_ms: u64) -> Self {
+        self.now_ms = now_ms;
+        self.reset_election_timer();
+        self
+    }
+
+    /// Start from `membership` instead of this node and `config.peers`
+    ///
+    /// A node joining a running cluster starts as a learner of the current
+    /// voters so that it never campaigns before the leader promotes it.
+    /// Configuration entries in the log or snapshot take precedence.
+    pub fn with_membership(mut self, membership: Membership) -> Self {
+        self.initial_membership = Some(membership);
+        self.refresh_membership();
+        self
+    }
+
+    /// Persist state to `storage`, recovering what it already holds
+    ///
+    /// Term and vote are restored, the state machine is rebuilt from the
+    /// snapshot, and committed log entries after it are applied again.
+    pub fn with_storage(mut self, mut storage: Box<dyn RaftStorage>) -> Result<Self> {
+        let state = storage.load()?;
+
+        if let Some(snapshot) = state.snapshot {
+            self.state_machine = RaftStateMachine::restore(&snapshot.data)?;
+            self.commit_index = snapshot.last_included_index;
+            self.last_applied = snapshot.last_included_index;
+            self.log.compact_to(snapshot);
         }
+        self.log.append_from(self.log.snapshot_index(), state.entries);
+
+        self.current_term = state.hard_state.current_term;
+        self.voted_for = state.hard_state.voted_for;
+        self.commit_index = self
+            .commit_index
+            .max(state.hard_state.commit_index.min(self.log.last_index()));
+        self.persisted = state.hard_state;
+        self.storage = Some(storage);
+
+        self.refresh_membership();
+        self.apply_committed_entries();
+        Ok(self)
     }
 
     // ========================================================================
@@ -225,14 +453,31 @@ impl RaftNode {
     // ========================================================================
 
     /// Become follower
+    ///
+    /// The vote is only forgotten when the term advances; a node that steps
+    /// down within a term must not vote a second time in it.
     fn become_follower(&mut self, term: u64) {
         self.role = Role::Follower;
-        self.current_term = term;
-        self.voted_for = None;
+        if term > self.current_term {
+            self.current_term = term;
+            self.voted_for = None;
+        }
         self.leader_state = None;
         self.candidate_state = None;
         self.follower_state = Some(FollowerState { leader_id: None });
         self.votes_received.clear();
+        self.transfer = None;
+        self.reset_election_timer();
+    }
+
+    /// Push the election deadline out by a randomized timeout
+    ///
+    /// Also refreshes the wall-clock heartbeat used by
+    /// `election_timeout_expired`.
+    fn reset_election_timer(&mut self) {
+        let timeout = self.config.election_timeout.as_millis() as u64;
+        let jitter = self.timer_rng.gen_range(0..=timeout);
+        self.election_deadline_ms = self.now_ms + timeout + jitter;
         self.last_heartbeat = Some(std::time::Instant::now());
     }
 
@@ -241,7 +486,7 @@ impl RaftNode {
         self.role = Role::Candidate;
         self.current_term += 1;
         self.voted_for = Some(self.id);
-        self.stats.elections_won += 1; // Optimistic
+        self.stats.terms += 1;
 
         // Initialize candidate state
         self.candidate_state = Some(CandidateState {
@@ -254,8 +499,23 @@ impl RaftNode {
 
         self.leader_state = None;
         self.follower_state = None;
+        self.leader_contact_ms = None;
+        self.reset_election_timer();
+
+        // A single-node cluster elects itself
+        if self.has_quorum(|id| self.votes_received.contains(id)) {
+            self.become_leader();
+        }
 
-        Ok(self.config.peers.clone())
+        // Learners are not asked for their vote
+        let membership = self.membership();
+        Ok(self
+            .config
+            .peers
+            .iter()
+            .copied()
+            .filter(|peer| membership.is_voter(peer))
+            .collect())
     }
 
     /// Become leader
@@ -273,9 +533,49 @@ impl RaftNode {
 
         self.candidate_state = None;
         self.follower_state = None;
+        self.snapshot_progress.clear();
+        self.transfer = None;
+        self.stats.elections_won += 1;
 
         // Leaders don't set last_heartbeat - they send heartbeats
         self.last_heartbeat = None;
+        self.heartbeat_due_ms = self.now_ms;
+
+        // Commit a no-op so entries of earlier terms commit promptly (§8)
+        self.append_local(RaftCommand::Noop);
+        self.try_commit_entries();
+    }
+
+    /// Whether the nodes for which `granted` holds form a quorum of the
+    /// current configuration
+    fn has_quorum(&self, granted: impl Fn(&AgentId) -> bool) -> bool {
+        match &self.membership {
+            Some((_, membership)) => membership.has_quorum(granted),
+            None => {
+                let count = std::iter::once(&self.id)
+                    .chain(&self.config.peers)
+                    .filter(|id| granted(id))
+                    .count();
+                2 * count > 1 + self.config.peers.len()
+            }
+        }
+    }
+
+    /// Whether this node votes in its current configuration
+    fn is_voting_member(&self) -> bool {
+        self.membership
+            .as_ref()
+            .is_none_or(|(_, membership)| membership.is_voter(&self.id))
+    }
+
+    /// Whether this node leads or heard from a leader within the minimum
+    /// election timeout
+    fn has_live_leader(&self) -> bool {
+        let timeout = self.config.election_timeout.as_millis() as u64;
+        self.role == Role::Leader
+            || self
+                .leader_contact_ms
+                .is_some_and(|contact_ms| self.now_ms < contact_ms + timeout)
     }
 
     // ========================================================================
@@ -284,6 +584,23 @@ impl RaftNode {
 
     /// Handle RequestVote RPC
     pub fn handle_request_vote(&mut self, req: RequestVoteRequest) -> RequestVoteResponse {
+        // Reject candidates from earlier terms
+        if req.term < self.current_term {
+            return RequestVoteResponse {
+                term: self.current_term,
+                vote_granted: false,
+            };
+        }
+
+        // While a leader is alive, ignore candidates that did not time out
+        // on it, such as nodes removed from the cluster (Raft thesis §4.2.3)
+        if !req.leadership_transfer && self.has_live_leader() {
+            return RequestVoteResponse {
+                term: self.current_term,
+                vote_granted: false,
+            };
+        }
+
         // Update term if we're behind
         if req.term > self.current_term {
             self.become_follower(req.term);
@@ -304,7 +621,7 @@ impl RaftNode {
 
         if vote_granted {
             self.voted_for = Some(req.candidate_id);
-            self.last_heartbeat = Some(std::time::Instant::now());
+            self.reset_election_timer();
         }
 
         RequestVoteResponse {
@@ -324,7 +641,8 @@ impl RaftNode {
             return Ok(None);
         }
 
-        if self.role != Role::Candidate {
+        // Ignore votes from earlier elections
+        if self.role != Role::Candidate || resp.term < self.current_term {
             return Ok(None);
         }
 
@@ -332,11 +650,7 @@ impl RaftNode {
             self.votes_received.insert(from);
 
             // Check if we won the election
-            // Majority = (self + peers) / 2 + 1
-            let total_nodes = 1 + self.config.peers.len() as u64;
-            let majority = (total_nodes / 2) + 1;
-
-            if self.votes_received.len() as u64 >= majority {
+            if self.has_quorum(|id| self.votes_received.contains(id)) {
                 self.become_leader();
                 return Ok(self.leader_state.clone());
             }
@@ -349,6 +663,15 @@ impl RaftNode {
 
     /// Handle AppendEntries (heartbeat or log replication)
     pub fn handle_append_entries(&mut self, req: AppendEntriesRequest) -> AppendEntriesResponse {
+        // Reject leaders from earlier terms
+        if req.term < self.current_term {
+            return AppendEntriesResponse {
+                term: self.current_term,
+                success: false,
+                match_index: None,
+            };
+        }
+
         // Update term if needed
         if req.term > self.current_term {
             self.become_follower(req.term);
@@ -358,7 +681,7 @@ impl RaftNode {
         if let Some(ref mut follower) = &mut self.follower_state {
             follower.leader_id = Some(req.leader_id);
         }
-        self.last_heartbeat = Some(std::time::Instant::now());
+        self.reset_election_timer();
 
         // If we're candidate or leader, step down
         if self.role == Role::Candidate || self.role == Role::Leader {
@@ -367,6 +690,7 @@ impl RaftNode {
                 follower.leader_id = Some(req.leader_id);
             }
         }
+        self.leader_contact_ms = Some(self.now_ms);
 
         // Check log consistency
         let log_ok = self.log.match_prefix(req.prev_log_index, req.prev_log_term);
@@ -379,21 +703,27 @@ impl RaftNode {
             };
         }
 
+        // Only the entries in this request are known to match the leader;
+        // anything past them may be left over from an earlier term
+        let last_new_index = req.prev_log_index + req.entries.len() as u64;
+
         // Append new entries
-        if !req.entries.is_empty() {
-            self.log.append_from(req.prev_log_index, req.entries);
+        if let Some(first_written) = self.log.append_from(req.prev_log_index, req.entries) {
+            self.mark_log_dirty(first_written);
+            self.refresh_membership();
         }
 
         // Update commit index
-        if req.leader_commit > self.commit_index {
-            self.commit_index = req.leader_commit.min(self.log.last_index());
+        let commit_index = req.leader_commit.min(last_new_index);
+        if commit_index > self.commit_index {
+            self.commit_index = commit_index;
             self.apply_committed_entries();
         }
 
         AppendEntriesResponse {
             term: self.current_term,
             success: true,
-            match_index: Some(self.log.last_index()),
+            match_index: Some(last_new_index),
         }
     }
 
@@ -408,7 +738,8 @@ impl RaftNode {
             return Ok(false);
         }
 
-        if self.role != Role::Leader {
+        // Ignore responses to requests from earlier terms
+        if self.role != Role::Leader || resp.term < self.current_term {
             return Ok(false);
         }
 
@@ -419,19 +750,16 @@ impl RaftNode {
                 reason: "Leader state not initialized".to_string(),
             })?;
 
-        // Find peer index
-        let peer_idx = self
-            .config
-            .peers
-            .iter()
-            .position(|p| p == &from)
-            .ok_or_else(|| ElexError::Consensus {
-                reason: "Unknown peer".to_string(),
-            })?;
+        // Responses from nodes removed from the configuration are stale
+        let Some(peer_idx) = self.config.peers.iter().position(|p| p == &from) else {
+            return Ok(false);
+        };
 
         if resp.success {
-            // Successful append: update match_index and next_index
+            // Successful append: update match_index and next_index.
+            // Responses may arrive out of order, so never move backwards.
             if let Some(match_idx) = resp.match_index {
+                let match_idx = match_idx.max(leader_state.match_index[peer_idx]);
                 leader_state.match_index[peer_idx] = match_idx;
                 leader_state.next_index[peer_idx] = match_idx + 1;
             }
@@ -440,7 +768,8 @@ impl RaftNode {
             self.try_commit_entries();
         } else {
             // Failed: decrement next_index to retry
-            if leader_state.next_index[peer_idx] > 1 {
+            let floor = leader_state.match_index[peer_idx] + 1;
+            if leader_state.next_index[peer_idx] > floor {
                 leader_state.next_index[peer_idx] -= 1;
             }
         }
@@ -453,21 +782,62 @@ impl RaftNode {
     // ========================================================================
 
     /// Propose new command (leader only)
-    pub fn propose(&mut self, command: RaftCommand) -> Result<()> {
-        if self.role != Role::Leader {
+    ///
+    /// Returns the log index of the new entry. The entry is replicated on
+    /// the next heartbeat (or `replicate`) and is committed once a quorum
+    /// stores it. With storage, the entry is written before returning.
+    /// Configuration entries are refused; see `change_membership`.
+    pub fn propose(&mut self, command: RaftCommand) -> Result<u64> {
+        if matches!(command, RaftCommand::UpdateConfiguration { .. }) {
             return Err(ElexError::Consensus {
-                reason: "Not the cluster leader".to_string(),
+                reason: "Membership changes go through add_learner and change_membership".to_string(),
             });
         }
+        self.propose_entry(command)
+    }
 
-        let entry = RaftLogEntry {
+    /// Append, persist and try to commit an entry (leader only)
+    fn propose_entry(&mut self, command: RaftCommand) -> Result<u64> {
+        self.check_leader()?;
+        if self.transfer.is_some() {
+            return Err(ElexError::Consensus {
+                reason: "Leadership transfer in progress".to_string(),
+            });
+        }
+
+        let index = self.append_local(command);
+        self.persist()?;
+
+        // A single-node cluster commits on its own
+        self.try_commit_entries();
+
+        Ok(index)
+    }
+
+    /// Append an entry of the current term to the local log
+    ///
+    /// A configuration entry takes effect right away.
+    fn append_local(&mut self, command: RaftCommand) -> u64 {
+        let is_configuration = matches!(command, RaftCommand::UpdateConfiguration { .. });
+        self.log.append(RaftLogEntry {
             term: self.current_term,
             command,
-        };
-
-        self.log.append(entry);
+        });
+        let index = self.log.last_index();
+        self.mark_log_dirty(index);
         self.stats.log_entries += 1;
+        if is_configuration {
+            self.refresh_membership();
+        }
+        index
+    }
 
+    fn check_leader(&self) -> Result<()> {
+        if self.role != Role::Leader {
+            return Err(ElexError::Consensus {
+                reason: "Not the cluster leader".to_string(),
+            });
+        }
         Ok(())
     }
 
@@ -478,35 +848,31 @@ impl RaftNode {
             None => return,
         };
 
-        // Find highest index replicated to majority
+        // Find highest index replicated to a quorum
         let mut new_commit_index = self.commit_index;
 
         for index in (self.commit_index + 1)..=self.log.last_index() {
-            let mut replicated_count = 1u64; // Leader
+            let replicated = self.has_quorum(|id| {
+                *id == self.id
+                    || self
+                        .config
+                        .peers
+                        .iter()
+                        .position(|p| p == id)
+                        .and_then(|i| leader_state.match_index.get(i))
+                        .is_some_and(|match_idx| *match_idx >= index)
+            });
 
-            for match_idx in &leader_state.match_index {
-                if *match_idx >= index {
-                    replicated_count += 1;
-                }
-            }
-
-            // Majority check
-            let total_nodes = 1 + self.config.peers.len() as u64;
-            let majority = (total_nodes / 2) + 1;
-
-            if replicated_count >= majority {
-                // Check if entry's term is current term
-                if let Some(entry) = self.log.get_entry(index) {
-                    if entry.term == self.current_term {
-                        new_commit_index = index;
-                    }
-                }
+            // Only entries of the current term commit by counting replicas
+            if replicated && self.log.get_term(index) == self.current_term {
+                new_commit_index = index;
             }
         }
 
         if new_commit_index > self.commit_index {
             self.commit_index = new_commit_index;
             self.apply_committed_entries();
+            self.advance_membership();
         }
     }
 
@@ -522,6 +888,489 @@ impl RaftNode {
                 }
             }
         }
+
+        self.maybe_snapshot();
+    }
+
+    // ========================================================================
+    // Membership Changes
+    // ========================================================================
+
+    /// Add a non-voting member (leader only)
+    ///
+    /// The learner receives the log, from a snapshot if it lags behind one,
+    /// but neither votes nor counts towards commitment until
+    /// `change_membership` promotes it. Returns the index of the
+    /// configuration entry.
+    pub fn add_learner(&mut self, id: AgentId) -> Result<u64> {
+        let mut membership = self.membership();
+        if membership.contains(&id) {
+            return Err(ElexError::Consensus {
+                reason: "Node is already a member".to_string(),
+            });
+        }
+        membership.learners.push(id);
+        self.propose_membership(membership)
+    }
+
+    /// Remove a non-voting member (leader only)
+    pub fn remove_learner(&mut self, id: AgentId) -> Result<u64> {
+        let mut membership = self.membership();
+        if !membership.is_learner(&id) {
+            return Err(ElexError::Consensus {
+                reason: "Node is not a learner".to_string(),
+            });
+        }
+        membership.learners.retain(|learner| *learner != id);
+        self.propose_membership(membership)
+    }
+
+    /// Replace the voting members through joint consensus (leader only)
+    ///
+    /// Appends a joint configuration under which elections and commitment
+    /// need majorities of both the current and the new voters. Once it
+    /// commits, the leader appends the new configuration by itself, and a
+    /// leader not among `voters` then hands leadership to one of them.
+    /// New voters must be learners that hold every committed entry.
+    /// Returns the index of the joint configuration entry.
+    pub fn change_membership(&mut self, voters: Vec<AgentId>) -> Result<u64> {
+        self.check_leader()?;
+        let current = self.membership();
+
+        let mut new_voters: Vec<AgentId> = Vec::with_capacity(voters.len());
+        for id in voters {
+            if !new_voters.contains(&id) {
+                new_voters.push(id);
+            }
+        }
+        if new_voters.is_empty() {
+            return Err(ElexError::Consensus {
+                reason: "A configuration needs at least one voter".to_string(),
+            });
+        }
+        for id in new_voters.iter().filter(|id| !current.voters.contains(id)) {
+            if !current.is_learner(id) {
+                return Err(ElexError::Consensus {
+                    reason: "Only learners can be promoted to voters".to_string(),
+                });
+            }
+            if !self.is_caught_up(id) {
+                return Err(ElexError::Consensus {
+                    reason: "Learner has not caught up with the leader".to_string(),
+                });
+            }
+        }
+
+        let joint = Membership {
+            learners: current
+                .learners
+                .iter()
+                .copied()
+                .filter(|id| !new_voters.contains(id))
+                .collect(),
+            outgoing: current.voters,
+            voters: new_voters,
+        };
+        self.propose_membership(joint)
+    }
+
+    /// Hand leadership to another voter (leader only)
+    ///
+    /// Proposals are refused while the transfer runs. Once `target` holds
+    /// the whole log it is told to campaign at once, and voters grant it
+    /// their vote although they heard from this leader recently. The
+    /// transfer is abandoned after an election timeout.
+    pub fn transfer_leadership(&mut self, target: AgentId) -> Result<()> {
+        self.check_leader()?;
+        if target == self.id || !self.membership().voters.contains(&target) {
+            return Err(ElexError::Consensus {
+                reason: "Leadership can only be transferred to another voter".to_string(),
+            });
+        }
+        self.start_transfer(target);
+        Ok(())
+    }
+
+    /// Whether the latest configuration is uncommitted or joint
+    ///
+    /// Only one membership change runs at a time.
+    pub fn membership_change_pending(&self) -> bool {
+        self.membership
+            .as_ref()
+            .is_some_and(|(index, membership)| *index > self.commit_index || membership.is_joint())
+    }
+
+    /// Whether a peer holds every committed entry (leader only)
+    pub fn is_caught_up(&self, peer: &AgentId) -> bool {
+        self.peer_match_index(peer)
+            .is_some_and(|match_idx| match_idx >= self.commit_index)
+    }
+
+    /// Node leadership is being handed to, if any
+    pub fn transfer_target(&self) -> Option<AgentId> {
+        self.transfer.as_ref().map(|transfer| transfer.target)
+    }
+
+    fn propose_membership(&mut self, membership: Membership) -> Result<u64> {
+        self.check_leader()?;
+        if self.membership_change_pending() {
+            return Err(ElexError::Consensus {
+                reason: "A membership change is in progress".to_string(),
+            });
+        }
+        self.propose_entry(membership.into())
+    }
+
+    /// Adopt the latest configuration in the log
+    ///
+    /// Falls back to the configuration in the snapshot, then to the
+    /// initial one. A configuration is used as soon as it is in the log,
+    /// committed or not, and given up if its entry is truncated.
+    fn refresh_membership(&mut self) {
+        let latest = self.log.latest_membership().or_else(|| {
+            let membership = self.state_machine.config().membership.clone()?;
+            Some((self.log.snapshot_index(), membership))
+        });
+        if latest.is_some() && self.initial_membership.is_none() {
+            self.initial_membership = Some(self.membership());
+        }
+
+        let membership = latest.or_else(|| self.initial_membership.clone().map(|m| (0, m)));
+        if membership == self.membership {
+            return;
+        }
+        if let Some((_, m)) = &membership {
+            let peers = m.members().into_iter().filter(|id| *id != self.id).collect();
+            self.set_peers(peers);
+        }
+        self.membership = membership;
+    }
+
+    /// Replicate to `peers`, keeping the progress of peers already known
+    fn set_peers(&mut self, peers: Vec<AgentId>) {
+        if let Some(leader_state) = self.leader_state.as_mut() {
+            let next_index = self.log.last_index() + 1;
+            let (next, matched) = peers
+                .iter()
+                .map(|peer| match self.config.peers.iter().position(|p| p == peer) {
+                    Some(i) => (leader_state.next_index[i], leader_state.match_index[i]),
+                    None => (next_index, 0),
+                })
+                .unzip();
+            leader_state.next_index = next;
+            leader_state.match_index = matched;
+        }
+        self.snapshot_progress.retain(|peer, _| peers.contains(peer));
+        self.config.peers = peers;
+    }
+
+    /// Leave a committed joint configuration; once the new one commits, a
+    /// leader that is no longer a voter hands over leadership
+    fn advance_membership(&mut self) {
+        let Some((index, membership)) = self.membership.clone() else {
+            return;
+        };
+        if self.role != Role::Leader || index > self.commit_index {
+            return;
+        }
+
+        if membership.is_joint() {
+            self.append_local(
+                Membership {
+                    outgoing: Vec::new(),
+                    ..membership
+                }
+                .into(),
+            );
+            self.try_commit_entries();
+        } else if !membership.is_voter(&self.id) && self.transfer.is_none() {
+            let target = membership
+                .voters
+                .iter()
+                .copied()
+                .max_by_key(|id| self.peer_match_index(id).unwrap_or(0));
+            match target {
+                Some(target) => self.start_transfer(target),
+                None => self.become_follower(self.current_term),
+            }
+        }
+    }
+
+    fn start_transfer(&mut self, target: AgentId) {
+        self.transfer = Some(LeadershipTransfer {
+            target,
+            deadline_ms: self.now_ms + self.config.election_timeout.as_millis() as u64,
+        });
+    }
+
+    /// Abandon an overdue transfer; a leader that is no longer a voter
+    /// steps down regardless
+    fn check_transfer(&mut self) {
+        if self.transfer.as_ref().is_some_and(|t| self.now_ms >= t.deadline_ms) {
+            self.transfer = None;
+            if !self.is_voting_member() {
+                self.become_follower(self.current_term);
+            }
+        }
+    }
+
+    /// Tell the transfer target to campaign once it holds the whole log
+    fn send_timeout_now(&self, transport: &mut dyn Transport) {
+        let Some(transfer) = &self.transfer else {
+            return;
+        };
+        if self
+            .peer_match_index(&transfer.target)
+            .is_some_and(|match_idx| match_idx >= self.log.last_index())
+        {
+            let request = TimeoutNowRequest {
+                term: self.current_term,
+                leader_id: self.id,
+            };
+            transport.send(self.id, transfer.target, RaftMessage::TimeoutNow(request));
+        }
+    }
+
+    /// Handle TimeoutNow: campaign at once on behalf of a leadership transfer
+    fn handle_timeout_now(&mut self, req: TimeoutNowRequest, transport: &mut dyn Transport) -> Result<()> {
+        if req.term < self.current_term || !self.is_voting_member() {
+            return Ok(());
+        }
+        if req.term > self.current_term {
+            self.become_follower(req.term);
+        }
+        if self.role != Role::Follower {
+            return Ok(());
+        }
+        self.campaign(true, transport)
+    }
+
+    fn peer_match_index(&self, peer: &AgentId) -> Option<u64> {
+        let peer_idx = self.config.peers.iter().position(|p| p == peer)?;
+        self.leader_state.as_ref()?.match_index.get(peer_idx).copied()
+    }
+
+    // ========================================================================
+    // Snapshots
+    // ========================================================================
+
+    /// Snapshot the state machine at the applied index and compact the log
+    pub fn take_snapshot(&mut self) -> Result<()> {
+        let index = self.last_applied;
+        if index <= self.log.snapshot_index() {
+            return Ok(());
+        }
+
+        let snapshot = LogSnapshot {
+            last_included_index: index,
+            last_included_term: self.log.get_term(index),
+            data: self.state_machine.snapshot()?,
+        };
+        self.log.compact_to(snapshot);
+        self.snapshot_dirty = true;
+        self.stats.snapshots_taken += 1;
+        Ok(())
+    }
+
+    /// Snapshot once the log size or applied-index threshold is crossed
+    ///
+    /// Failures are counted in `stats`; after each consecutive failure the
+    /// next attempt waits for twice as many newly applied entries.
+    fn maybe_snapshot(&mut self) {
+        let config = &self.snapshot_config;
+        let since_snapshot = self.last_applied.saturating_sub(self.log.snapshot_index());
+        let over_size = config.max_log_entries > 0 && self.log.entries.len() > config.max_log_entries;
+        let over_applied = config.applied_threshold > 0 && since_snapshot >= config.applied_threshold;
+
+        if since_snapshot == 0 || !(over_size || over_applied) {
+            return;
+        }
+        if let Some((_, retry_at)) = self.snapshot_backoff {
+            if self.last_applied < retry_at {
+                return;
+            }
+        }
+
+        match self.take_snapshot() {
+            Ok(()) => self.snapshot_backoff = None,
+            Err(_) => {
+                self.stats.snapshot_failures += 1;
+                let failures = self.snapshot_backoff.map_or(0, |(failures, _)| failures) + 1;
+                let wait = 1u64 << failures.min(16);
+                self.snapshot_backoff = Some((failures, self.last_applied + wait));
+            }
+        }
+    }
+
+    /// Handle InstallSnapshot chunk
+    ///
+    /// Chunks are buffered until the last one arrives; the state machine and
+    /// log are then replaced together, so a partial transfer never becomes
+    /// visible.
+    pub fn handle_install_snapshot(&mut self, req: InstallSnapshotRequest) -> Result<InstallSnapshotResponse> {
+        let mut resp = InstallSnapshotResponse {
+            term: self.current_term,
+            last_included_index: req.last_included_index,
+            next_offset: 0,
+            done: false,
+        };
+
+        // Reject leaders from earlier terms
+        if req.term < self.current_term {
+            return Ok(resp);
+        }
+
+        if req.term > self.current_term || self.role != Role::Follower {
+            self.become_follower(req.term);
+            resp.term = self.current_term;
+        }
+        if let Some(ref mut follower) = &mut self.follower_state {
+            follower.leader_id = Some(req.leader_id);
+        }
+        self.leader_contact_ms = Some(self.now_ms);
+        self.reset_election_timer();
+
+        // Everything the snapshot covers is already committed here
+        if req.last_included_index <= self.commit_index {
+            self.incoming_snapshot = None;
+            resp.next_offset = req.offset + req.data.len() as u64;
+            resp.done = true;
+            return Ok(resp);
+        }
+
+        if req.offset == 0 {
+            self.incoming_snapshot = Some(IncomingSnapshot {
+                last_included_index: req.last_included_index,
+                last_included_term: req.last_included_term,
+                data: Vec::new(),
+            });
+        }
+
+        // Only accept the chunk that continues the buffered prefix
+        let incoming = match self.incoming_snapshot.as_mut() {
+            Some(incoming)
+                if incoming.last_included_index == req.last_included_index
+                    && incoming.last_included_term == req.last_included_term =>
+            {
+                incoming
+            }
+            _ => return Ok(resp),
+        };
+        if incoming.data.len() as u64 != req.offset {
+            resp.next_offset = incoming.data.len() as u64;
+            return Ok(resp);
+        }
+        incoming.data.extend_from_slice(&req.data);
+        resp.next_offset = incoming.data.len() as u64;
+
+        if !req.done {
+            return Ok(resp);
+        }
+
+        let incoming = self.incoming_snapshot.take().unwrap();
+        let state_machine = RaftStateMachine::restore(&incoming.data)?;
+        self.log.compact_to(LogSnapshot {
+            last_included_index: incoming.last_included_index,
+            last_included_term: incoming.last_included_term,
+            data: incoming.data,
+        });
+        self.snapshot_dirty = true;
+        self.state_machine = state_machine;
+        self.commit_index = incoming.last_included_index;
+        self.last_applied = incoming.last_included_index;
+        self.stats.snapshots_installed += 1;
+        self.refresh_membership();
+
+        resp.done = true;
+        Ok(resp)
+    }
+
+    /// Handle InstallSnapshot response (leader only)
+    ///
+    /// Returns whether the transfer to `from` advanced, i.e. whether the
+    /// next chunk (or, once done, the following entries) should be sent.
+    pub fn handle_install_snapshot_response(
+        &mut self,
+        resp: InstallSnapshotResponse,
+        from: AgentId,
+    ) -> Result<bool> {
+        if resp.term > self.current_term {
+            self.become_follower(resp.term);
+            return Ok(false);
+        }
+
+        if self.role != Role::Leader || resp.term < self.current_term {
+            return Ok(false);
+        }
+
+        let Some(peer_idx) = self.config.peers.iter().position(|p| p == &from) else {
+            return Ok(false);
+        };
+
+        if resp.done {
+            let leader_state = self.leader_state.as_mut().ok_or_else(|| ElexError::Consensus {
+                reason: "Leader state not initialized".to_string(),
+            })?;
+            if resp.last_included_index <= leader_state.match_index[peer_idx] {
+                return Ok(false);
+            }
+            leader_state.match_index[peer_idx] = resp.last_included_index;
+            leader_state.next_index[peer_idx] = resp.last_included_index + 1;
+            self.snapshot_progress.remove(&from);
+            self.try_commit_entries();
+            return Ok(true);
+        }
+
+        let acked = self
+            .snapshot_progress
+            .get(&from)
+            .filter(|(index, _)| *index == resp.last_included_index)
+            .map(|(_, offset)| *offset);
+        if acked == Some(resp.next_offset) {
+            return Ok(false);
+        }
+        self.snapshot_progress
+            .insert(from, (resp.last_included_index, resp.next_offset));
+        Ok(true)
+    }
+
+    /// Build the next InstallSnapshot chunk for a peer
+    ///
+    /// Returns None unless the peer needs entries the log no longer holds.
+    pub fn build_install_snapshot(&self, peer_idx: usize) -> Option<InstallSnapshotRequest> {
+        let snapshot = self.log.snapshot()?;
+        let next_index = self.leader_state.as_ref()?.next_index[peer_idx];
+        if next_index > snapshot.last_included_index {
+            return None;
+        }
+
+        let peer = self.config.peers[peer_idx];
+        let offset = self
+            .snapshot_progress
+            .get(&peer)
+            .filter(|(index, _)| *index == snapshot.last_included_index)
+            .map(|(_, offset)| (*offset as usize).min(snapshot.data.len()))
+            .unwrap_or(0);
+        let end = (offset + self.snapshot_config.chunk_size.max(1)).min(snapshot.data.len());
+
+        Some(InstallSnapshotRequest {
+            term: self.current_term,
+            leader_id: self.id,
+            last_included_index: snapshot.last_included_index,
+            last_included_term: snapshot.last_included_term,
+            offset: offset as u64,
+            data: snapshot.data[offset..end].to_vec(),
+            done: end == snapshot.data.len(),
+        })
+    }
+
+    /// Next replication message for a peer: a snapshot chunk if it lags
+    /// behind the snapshot, AppendEntries otherwise
+    fn replication_message(&self, peer_idx: usize) -> RaftMessage {
+        match self.build_install_snapshot(peer_idx) {
+            Some(req) => RaftMessage::InstallSnapshot(req),
+            None => RaftMessage::AppendEntries(self.build_append_entries(peer_idx)),
+        }
     }
 
     // ========================================================================
@@ -566,6 +1415,219 @@ impl RaftNode {
         elapsed >= self.config.election_timeout
     }
 
+    // ========================================================================
+    // Driver
+    // ========================================================================
+
+    /// Advance the node's logical clock to `now_ms`
+    ///
+    /// Starts an election once the randomized election timeout has passed
+    /// without hearing from a leader, and sends heartbeats while leader.
+    pub fn tick(&mut self, now_ms: u64, transport: &mut dyn Transport) -> Result<()> {
+        self.now_ms = self.now_ms.max(now_ms);
+
+        let mut outbox = Outbox::new();
+        if self.role == Role::Leader {
+            self.check_transfer();
+        }
+        if self.role == Role::Leader {
+            if self.now_ms >= self.heartbeat_due_ms {
+                self.replicate(&mut outbox);
+            }
+        } else if self.now_ms >= self.election_deadline_ms {
+            // Learners and removed nodes never campaign
+            if self.is_voting_member() {
+                self.campaign(false, &mut outbox)?;
+            } else {
+                self.reset_election_timer();
+            }
+        }
+
+        self.send_durably(outbox, transport)
+    }
+
+    /// Start an election and ask the other voters for their votes
+    fn campaign(&mut self, leadership_transfer: bool, transport: &mut dyn Transport) -> Result<()> {
+        let peers = self.start_election()?;
+        if self.role == Role::Leader {
+            self.replicate(transport);
+            return Ok(());
+        }
+
+        let last_log_index = self.log.last_index();
+        let request = RequestVoteRequest {
+            term: self.current_term,
+            candidate_id: self.id,
+            last_log_index,
+            last_log_term: self.log.get_term(last_log_index),
+            leadership_transfer,
+        };
+        for peer in peers {
+            transport.send(self.id, peer, RaftMessage::RequestVote(request.clone()));
+        }
+        Ok(())
+    }
+
+    /// Handle a message from `from` at logical time `now_ms`
+    ///
+    /// Replies and follow-up requests are sent through `transport`.
+    pub fn step(
+        &mut self,
+        from: AgentId,
+        message: RaftMessage,
+        now_ms: u64,
+        transport: &mut dyn Transport,
+    ) -> Result<()> {
+        self.now_ms = self.now_ms.max(now_ms);
+
+        let mut outbox = Outbox::new();
+        self.handle_message(from, message, &mut outbox)?;
+        self.send_durably(outbox, transport)
+    }
+
+    /// Handle a message, queueing replies and follow-up requests
+    fn handle_message(&mut self, from: AgentId, message: RaftMessage, transport: &mut dyn Transport) -> Result<()> {
+        match message {
+            RaftMessage::RequestVote(req) => {
+                let resp = self.handle_request_vote(req);
+                transport.send(self.id, from, RaftMessage::RequestVoteResponse(resp));
+            }
+            RaftMessage::RequestVoteResponse(resp) => {
+                if self.handle_vote_response(resp, from)?.is_some() {
+                    self.replicate(transport);
+                }
+            }
+            RaftMessage::AppendEntries(req) => {
+                let resp = self.handle_append_entries(req);
+                transport.send(self.id, from, RaftMessage::AppendEntriesResponse(resp));
+            }
+            RaftMessage::AppendEntriesResponse(resp) => {
+                let success = resp.success;
+                if self.handle_append_entries_response(resp, from)? {
+                    if !success {
+                        // Retry right away with an earlier prefix
+                        self.send_replication(from, transport);
+                    } else if self.transfer_target() == Some(from) {
+                        self.send_timeout_now(transport);
+                    }
+                }
+            }
+            RaftMessage::InstallSnapshot(req) => {
+                let resp = self.handle_install_snapshot(req)?;
+                transport.send(self.id, from, RaftMessage::InstallSnapshotResponse(resp));
+            }
+            RaftMessage::InstallSnapshotResponse(resp) => {
+                if self.handle_install_snapshot_response(resp, from)? {
+                    self.send_replication(from, transport);
+                }
+            }
+            RaftMessage::TimeoutNow(req) => {
+                self.handle_timeout_now(req, transport)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Send AppendEntries to every peer (leader only)
+    ///
+    /// Doubles as the heartbeat; the next one is due a heartbeat interval
+    /// from now.
+    pub fn replicate(&mut self, transport: &mut dyn Transport) {
+        if self.role != Role::Leader {
+            return;
+        }
+
+        for (peer_idx, peer) in self.config.peers.iter().enumerate() {
+            transport.send(self.id, *peer, self.replication_message(peer_idx));
+        }
+        self.send_timeout_now(transport);
+        self.heartbeat_due_ms = self.now_ms + self.config.heartbeat_interval.as_millis() as u64;
+    }
+
+    /// Send the next replication message to one peer
+    fn send_replication(&self, peer: AgentId, transport: &mut dyn Transport) {
+        if let Some(peer_idx) = self.config.peers.iter().position(|p| p == &peer) {
+            transport.send(self.id, peer, self.replication_message(peer_idx));
+        }
+    }
+
+    // ========================================================================
+    // Persistence
+    // ========================================================================
+
+    /// Write changed state to storage and make it durable
+    ///
+    /// Called by `step`, `tick` and `propose`; callers driving the RPC
+    /// handlers directly call it before sending their responses. Returns
+    /// whether everything written is durable, which is always the case
+    /// without storage.
+    pub fn persist(&mut self) -> Result<bool> {
+        let hard_state = self.hard_state();
+        let Some(storage) = self.storage.as_mut() else {
+            return Ok(true);
+        };
+
+        // Snapshot before entries, as storage replaces the log with the
+        // entries following it
+        if self.snapshot_dirty {
+            if let Some(snapshot) = self.log.snapshot() {
+                let retained = self.log.entries_from(snapshot.last_included_index + 1);
+                storage.save_snapshot(snapshot, &retained)?;
+            }
+            self.snapshot_dirty = false;
+        }
+        if let Some(from) = self.log_dirty_from {
+            let from = from.max(self.log.snapshot_index() + 1);
+            storage.append(from, &self.log.entries_from(from))?;
+            self.log_dirty_from = None;
+        }
+        if hard_state != self.persisted {
+            storage.save_hard_state(&hard_state)?;
+            self.persisted = hard_state;
+        }
+
+        storage.sync()
+    }
+
+    /// Send held messages once the state they depend on is durable
+    ///
+    /// Only needed with storage that completes writes asynchronously; see
+    /// `raft_storage`.
+    pub fn release_held(&mut self, transport: &mut dyn Transport) -> Result<()> {
+        if self.persist()? {
+            for envelope in self.held.drain(..) {
+                transport.send(envelope.from, envelope.to, envelope.message);
+            }
+        }
+        Ok(())
+    }
+
+    /// Number of messages waiting for storage
+    pub fn held_messages(&self) -> usize {
+        self.held.len()
+    }
+
+    /// Persist, then send the queued messages
+    fn send_durably(&mut self, mut outbox: Outbox, transport: &mut dyn Transport) -> Result<()> {
+        self.held.extend(outbox.drain());
+        self.release_held(transport)
+    }
+
+    /// Current term, vote and commit index
+    fn hard_state(&self) -> HardState {
+        HardState {
+            current_term: self.current_term,
+            voted_for: self.voted_for,
+            commit_index: self.commit_index,
+        }
+    }
+
+    /// Record that the log changed from `index` on
+    fn mark_log_dirty(&mut self, index: u64) {
+        self.log_dirty_from = Some(self.log_dirty_from.map_or(index, |from| from.min(index)));
+    }
+
     // ========================================================================
     // Public Accessors
     // ========================================================================
@@ -595,6 +1657,11 @@ impl RaftNode {
         self.commit_index
     }
 
+    /// Get index of the last entry applied to the state machine
+    pub fn last_applied(&self) -> u64 {
+        self.last_applied
+    }
+
     /// Get state machine reference
     pub fn state_machine(&self) -> &RaftStateMachine {
         &self.state_machine
@@ -628,6 +1695,31 @@ impl RaftNode {
     pub fn config(&self) -> &RaftConfig {
         &self.config
     }
+
+    /// Get current cluster membership
+    ///
+    /// The latest configuration entry takes effect as soon as it is in the
+    /// log. Before the first one, the voters are this node and its peers.
+    pub fn membership(&self) -> Membership {
+        match &self.membership {
+            Some((_, membership)) => membership.clone(),
+            None => Membership::new(std::iter::once(self.id).chain(self.config.peers.iter().copied()).collect()),
+        }
+    }
+
+    /// Check if node is a non-voting member
+    pub fn is_learner(&self) -> bool {
+        self.membership
+            .as_ref()
+            .is_some_and(|(_, membership)| membership.is_learner(&self.id))
+    }
+}
+
+/// Default election jitter seed derived from a node id
+fn seed_from_id(id: &AgentId) -> u64 {
+    id.iter().fold(0xcbf29ce484222325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
 }
 
 // ============================================================================
@@ -691,6 +1783,7 @@ impl RaftCluster {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::raft_storage::MemoryStorage;
 
     fn make_agent_id(byte: u8) -> AgentId {
         let mut id = [0u8; 32];
@@ -732,6 +1825,7 @@ mod tests {
             candidate_id: cluster.nodes[0].id,
             last_log_index: 0,
             last_log_term: 0,
+            leadership_transfer: false,
         };
 
         let node1_id = cluster.nodes[1].id;
@@ -877,6 +1971,282 @@ mod tests {
         assert!(!node.is_leader());
     }
 
+    #[test]
+    fn test_stale_leader_rejected() {
+        let mut node = RaftNode::new(make_agent_id(0), make_config(vec![make_agent_id(1)]));
+        node.current_term = 3;
+
+        let append = AppendEntriesRequest {
+            term: 2,
+            leader_id: make_agent_id(1),
+            prev_log_index: 0,
+            prev_log_term: 0,
+            entries: vec![],
+            leader_commit: 0,
+        };
+        let resp = node.handle_append_entries(append);
+
+        assert!(!resp.success);
+        assert_eq!(resp.term, 3);
+        assert_eq!(node.current_term(), 3);
+        assert_eq!(node.leader_id(), None);
+    }
+
+    #[test]
+    fn test_single_vote_per_term() {
+        let mut node = RaftNode::new(
+            make_agent_id(0),
+            make_config(vec![make_agent_id(1), make_agent_id(2)]),
+        );
+        node.start_election().unwrap();
+
+        // Stepping down within the term keeps the vote for ourselves
+        node.handle_append_entries(AppendEntriesRequest {
+            term: 1,
+            leader_id: make_agent_id(1),
+            prev_log_index: 0,
+            prev_log_term: 0,
+            entries: vec![],
+            leader_commit: 0,
+        });
+        let resp = node.handle_request_vote(RequestVoteRequest {
+            term: 1,
+            candidate_id: make_agent_id(2),
+            last_log_index: 0,
+            last_log_term: 0,
+            leadership_transfer: false,
+        });
+        assert!(!resp.vote_granted);
+    }
+
+    #[test]
+    fn test_snapshot_shipped_in_chunks() {
+        let mut leader = RaftNode::new(make_agent_id(0), make_config(vec![make_agent_id(1)]))
+            .with_snapshot_config(SnapshotConfig {
+                max_log_entries: 0,
+                applied_threshold: 0,
+                chunk_size: 100,
+            });
+        let mut follower = RaftNode::new(make_agent_id(1), make_config(vec![make_agent_id(0)]));
+
+        // Leader commits alone while the follower is unreachable
+        leader.config.peers.clear();
+        leader.start_election().unwrap();
+        for i in 0..3 {
+            leader
+                .propose(RaftCommand::UpdateRoutingIndex {
+                    agent_id: make_agent_id(10 + i),
+                    embedding: vec![i as f32; 8],
+                })
+                .unwrap();
+        }
+        leader.take_snapshot().unwrap();
+        assert_eq!(leader.log().snapshot_index(), 4);
+        assert!(leader.log().entries.is_empty());
+
+        leader.config.peers.push(follower.id);
+        leader.leader_state = Some(LeaderState {
+            next_index: vec![1],
+            match_index: vec![0],
+        });
+
+        let mut chunks = 0;
+        while let Some(req) = leader.build_install_snapshot(0) {
+            let done = req.done;
+            let resp = follower.handle_install_snapshot(req).unwrap();
+            chunks += 1;
+            // Nothing is visible before the last chunk
+            assert_eq!(follower.state_machine().agent_count(), if done { 3 } else { 0 });
+            assert!(leader.handle_install_snapshot_response(resp, follower.id).unwrap());
+        }
+
+        assert!(chunks > 1);
+        assert_eq!(follower.commit_index(), 4);
+        assert_eq!(follower.log().snapshot_index(), 4);
+        assert_eq!(follower.stats().snapshots_installed, 1);
+        assert_eq!(
+            follower.state_machine().get_embedding(&make_agent_id(12)),
+            Some([2.0; 8].as_slice())
+        );
+        assert_eq!(leader.leader_state.as_ref().unwrap().match_index, vec![4]);
+    }
+
+    #[test]
+    fn test_snapshot_thresholds() {
+        let mut node = RaftNode::new(make_agent_id(0), make_config(vec![])).with_snapshot_config(
+            SnapshotConfig {
+                max_log_entries: 0,
+                applied_threshold: 5,
+                chunk_size: 1024,
+            },
+        );
+        node.start_election().unwrap();
+        for _ in 0..3 {
+            node.propose(RaftCommand::RemoveAgent { agent_id: make_agent_id(9) }).unwrap();
+        }
+        assert_eq!(node.log().snapshot_index(), 0);
+
+        node.propose(RaftCommand::RemoveAgent { agent_id: make_agent_id(9) }).unwrap();
+        assert_eq!(node.log().snapshot_index(), 5);
+        assert_eq!(node.stats().snapshots_taken, 1);
+        assert!(!node.log().snapshot().unwrap().data.is_empty());
+    }
+
+    #[test]
+    fn test_recovery_from_storage() {
+        let storage = MemoryStorage::new();
+        let snapshot_config = SnapshotConfig {
+            max_log_entries: 0,
+            applied_threshold: 3,
+            chunk_size: 1024,
+        };
+        let mut node = RaftNode::new(make_agent_id(0), make_config(vec![]))
+            .with_snapshot_config(snapshot_config.clone())
+            .with_storage(Box::new(storage.reopen()))
+            .unwrap();
+        node.start_election().unwrap();
+        for i in 0..5u8 {
+            node.propose(RaftCommand::UpdateRoutingIndex {
+                agent_id: make_agent_id(10 + i % 2),
+                embedding: vec![i as f32; 4],
+            })
+            .unwrap();
+        }
+        assert!(node.persist().unwrap());
+        drop(node);
+
+        let node = RaftNode::new(make_agent_id(0), make_config(vec![]))
+            .with_snapshot_config(snapshot_config)
+            .with_storage(Box::new(storage.reopen()))
+            .unwrap();
+        assert_eq!(node.role(), Role::Follower);
+        assert_eq!(node.current_term(), 1);
+        assert_eq!(node.log().snapshot_index(), 6);
+        assert_eq!(node.last_applied(), 6);
+        assert_eq!(node.state_machine().agent_count(), 2);
+        assert_eq!(node.state_machine().get_embedding(&make_agent_id(10)), Some([4.0; 4].as_slice()));
+    }
+
+    #[test]
+    fn test_vote_survives_restart() {
+        let storage = MemoryStorage::new();
+        let peers = vec![make_agent_id(1), make_agent_id(2)];
+        let mut node = RaftNode::new(make_agent_id(0), make_config(peers.clone()))
+            .with_storage(Box::new(storage.reopen()))
+            .unwrap();
+        let request = |candidate: u8| {
+            RaftMessage::RequestVote(RequestVoteRequest {
+                term: 3,
+                candidate_id: make_agent_id(candidate),
+                last_log_index: 0,
+                last_log_term: 0,
+                leadership_transfer: false,
+            })
+        };
+
+        let mut outbox = Outbox::new();
+        node.step(make_agent_id(1), request(1), 0, &mut outbox).unwrap();
+        drop(node);
+
+        let mut node = RaftNode::new(make_agent_id(0), make_config(peers))
+            .with_storage(Box::new(storage.reopen()))
+            .unwrap();
+        node.step(make_agent_id(2), request(2), 0, &mut outbox).unwrap();
+
+        let granted: Vec<bool> = outbox
+            .drain()
+            .into_iter()
+            .map(|e| match e.message {
+                RaftMessage::RequestVoteResponse(resp) => resp.vote_granted,
+                other => panic!("unexpected message {:?}", other),
+            })
+            .collect();
+        assert_eq!(granted, vec![true, false]);
+    }
+
+    #[test]
+    fn test_learner_promoted_through_joint_consensus() {
+        let (leader_id, learner_id) = (make_agent_id(0), make_agent_id(1));
+        let mut leader = RaftNode::new(leader_id, make_config(vec![]));
+        let mut learner = RaftNode::new(learner_id, make_config(vec![leader_id])).with_membership(Membership {
+            voters: vec![leader_id],
+            outgoing: vec![],
+            learners: vec![learner_id],
+        });
+        assert!(learner.is_learner());
+        leader.start_election().unwrap();
+
+        // Replacing the configuration in one step is refused
+        assert!(leader.propose(Membership::new(vec![leader_id, learner_id]).into()).is_err());
+
+        // The learner does not count towards commitment
+        let added = leader.add_learner(learner_id).unwrap();
+        assert_eq!(leader.commit_index(), added);
+        assert!(leader.change_membership(vec![leader_id, learner_id]).is_err());
+
+        let sync = |leader: &mut RaftNode, learner: &mut RaftNode| loop {
+            let resp = learner.handle_append_entries(leader.build_append_entries(0));
+            let success = resp.success;
+            leader.handle_append_entries_response(resp, learner_id).unwrap();
+            if success {
+                break;
+            }
+        };
+        sync(&mut leader, &mut learner);
+        assert!(leader.is_caught_up(&learner_id));
+
+        // The joint configuration needs a majority of the new voters too
+        let joint = leader.change_membership(vec![leader_id, learner_id]).unwrap();
+        assert!(leader.membership().is_joint());
+        assert!(leader.commit_index() < joint);
+        assert!(leader.change_membership(vec![leader_id]).is_err());
+
+        // Once it commits, the leader appends the new configuration
+        sync(&mut leader, &mut learner);
+        assert_eq!(leader.commit_index(), joint);
+        assert_eq!(leader.log().last_index(), joint + 1);
+
+        sync(&mut leader, &mut learner);
+        assert_eq!(leader.commit_index(), joint + 1);
+        assert!(!leader.membership_change_pending());
+        assert_eq!(leader.membership(), Membership::new(vec![leader_id, learner_id]));
+        assert!(!learner.is_learner());
+        assert_eq!(learner.membership(), leader.membership());
+    }
+
+    #[test]
+    fn test_live_leader_ignores_disruptive_candidate() {
+        let mut node = RaftNode::new(
+            make_agent_id(0),
+            make_config(vec![make_agent_id(1), make_agent_id(2)]),
+        );
+        node.handle_append_entries(AppendEntriesRequest {
+            term: 1,
+            leader_id: make_agent_id(1),
+            prev_log_index: 0,
+            prev_log_term: 0,
+            entries: vec![],
+            leader_commit: 0,
+        });
+
+        // A removed node campaigning at a higher term cannot depose the leader
+        let mut req = RequestVoteRequest {
+            term: 5,
+            candidate_id: make_agent_id(2),
+            last_log_index: 0,
+            last_log_term: 0,
+            leadership_transfer: false,
+        };
+        assert!(!node.handle_request_vote(req.clone()).vote_granted);
+        assert_eq!(node.current_term(), 1);
+        assert_eq!(node.leader_id(), Some(make_agent_id(1)));
+
+        // A leadership transfer target can
+        req.leadership_transfer = true;
+        assert!(node.handle_request_vote(req).vote_granted);
+        assert_eq!(node.current_term(), 5);
+    }
+
     #[test]
     fn test_request_vote_granting() {
         let mut node = RaftNode::new(make_agent_id(0), make_config(vec![make_agent_id(1)]));
@@ -886,6 +2256,7 @@ mod tests {
             candidate_id: make_agent_id(1),
             last_log_index: 0,
             last_log_term: 0,
+            leadership_transfer: false,
         };
 
         let resp = node.handle_request_vote(req);
@@ -914,6 +2285,7 @@ mod tests {
             candidate_id: make_agent_id(1),
             last_log_index: 0,
             last_log_term: 0,
+            leadership_transfer: false,
         };
 
         let resp = node.handle_request_vote(req);
@@ -942,6 +2314,7 @@ mod tests {
                 candidate_id: cluster.nodes[0].id,
                 last_log_index: 0,
                 last_log_term: 0,
+                leadership_transfer: false,
             };
 
             let resp = cluster.nodes[i].handle_request_vote(req.clone());
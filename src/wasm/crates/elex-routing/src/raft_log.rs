@@ -8,7 +8,7 @@ use elex_core::{ElexError, Result};
 use serde::{Deserialize, Serialize};
 
 /// Raft log entry
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RaftLogEntry {
     /// Term when entry was received by leader
     pub term: u64,
@@ -17,7 +17,7 @@ pub struct RaftLogEntry {
 }
 
 /// Raft command for routing index operations
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum RaftCommand {
     /// Update agent embedding in routing index
     UpdateRoutingIndex {
@@ -37,13 +37,25 @@ pub enum RaftCommand {
     },
 
     /// Update cluster configuration
+    ///
+    /// `peers` lists every voting member. The entry takes effect on each
+    /// node as soon as it is in its log; see `RaftNode::change_membership`.
     UpdateConfiguration {
         peers: Vec<AgentId>,
+        /// Previous voters while a joint-consensus change is in progress
+        #[serde(default)]
+        outgoing: Vec<AgentId>,
+        /// Non-voting members
+        #[serde(default)]
+        learners: Vec<AgentId>,
     },
+
+    /// No-op appended by a new leader to commit entries of earlier terms
+    Noop,
 }
 
 /// Agent metadata for registration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AgentMetadata {
     /// Agent feature code
     pub feature_code: String,
@@ -66,6 +78,106 @@ impl Default for AgentMetadata {
     }
 }
 
+impl RaftCommand {
+    /// Membership carried by a configuration entry
+    pub fn membership(&self) -> Option<Membership> {
+        match self {
+            RaftCommand::UpdateConfiguration {
+                peers,
+                outgoing,
+                learners,
+            } => Some(Membership {
+                voters: peers.clone(),
+                outgoing: outgoing.clone(),
+                learners: learners.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl From<Membership> for RaftCommand {
+    fn from(membership: Membership) -> Self {
+        RaftCommand::UpdateConfiguration {
+            peers: membership.voters,
+            outgoing: membership.outgoing,
+            learners: membership.learners,
+        }
+    }
+}
+
+/// Cluster membership
+///
+/// Elections and commitment need a majority of `voters`. During a
+/// joint-consensus change `outgoing` holds the previous voters and both
+/// sets must agree, so the old and new configurations can never elect
+/// separate leaders. Learners receive the log but neither vote nor count
+/// towards commitment.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Membership {
+    /// Voting members
+    pub voters: Vec<AgentId>,
+    /// Voters of the configuration being left; empty unless joint
+    #[serde(default)]
+    pub outgoing: Vec<AgentId>,
+    /// Non-voting members
+    #[serde(default)]
+    pub learners: Vec<AgentId>,
+}
+
+impl Membership {
+    /// Create a configuration of voters only
+    pub fn new(voters: Vec<AgentId>) -> Self {
+        Self {
+            voters,
+            ..Self::default()
+        }
+    }
+
+    /// Whether a joint-consensus change is in progress
+    pub fn is_joint(&self) -> bool {
+        !self.outgoing.is_empty()
+    }
+
+    /// Whether `id` votes in the new or the outgoing configuration
+    pub fn is_voter(&self, id: &AgentId) -> bool {
+        self.voters.contains(id) || self.outgoing.contains(id)
+    }
+
+    /// Whether `id` is a non-voting member
+    pub fn is_learner(&self, id: &AgentId) -> bool {
+        self.learners.contains(id) && !self.is_voter(id)
+    }
+
+    /// Whether `id` is a voter or learner
+    pub fn contains(&self, id: &AgentId) -> bool {
+        self.is_voter(id) || self.learners.contains(id)
+    }
+
+    /// All voters and learners, each once
+    pub fn members(&self) -> Vec<AgentId> {
+        let mut members: Vec<AgentId> = Vec::new();
+        for id in self.voters.iter().chain(&self.outgoing).chain(&self.learners) {
+            if !members.contains(id) {
+                members.push(*id);
+            }
+        }
+        members
+    }
+
+    /// Whether the members for which `granted` holds form a quorum
+    ///
+    /// A joint configuration needs a majority of both voter sets.
+    pub fn has_quorum(&self, granted: impl Fn(&AgentId) -> bool) -> bool {
+        is_majority(&self.voters, &granted) && (!self.is_joint() || is_majority(&self.outgoing, &granted))
+    }
+}
+
+/// Whether `granted` holds for more than half of `voters`
+fn is_majority(voters: &[AgentId], granted: &impl Fn(&AgentId) -> bool) -> bool {
+    voters.iter().filter(|id| granted(id)).count() > voters.len() / 2
+}
+
 /// Raft log storage
 ///
 /// Stores log entries with indices starting from 1.
@@ -120,9 +232,20 @@ impl RaftLog {
         }
     }
 
+    /// Get index of the last entry covered by the snapshot
+    ///
+    /// Returns 0 if there is no snapshot.
+    pub fn snapshot_index(&self) -> u64 {
+        self.snapshot
+            .as_ref()
+            .map(|s| s.last_included_index)
+            .unwrap_or(0)
+    }
+
     /// Get term at index
     ///
-    /// Returns 0 for index 0 or if index not found.
+    /// Returns 0 for index 0 or if index not found. Of the entries covered
+    /// by the snapshot only the last one has a known term.
     pub fn get_term(&self, index: u64) -> u64 {
         if index == 0 {
             return 0;
@@ -134,7 +257,7 @@ impl RaftLog {
                 return snapshot.last_included_term;
             }
             if index < snapshot.last_included_index {
-                return snapshot.last_included_term;
+                return 0;
             }
 
             // Adjust index for snapshot
@@ -172,8 +295,10 @@ impl RaftLog {
     /// Check if log matches prefix
     ///
     /// Returns true if the log at prev_log_index has term prev_log_term.
+    /// Entries covered by the snapshot are committed, so they match the log
+    /// of any current leader.
     pub fn match_prefix(&self, prev_log_index: u64, prev_log_term: u64) -> bool {
-        self.get_term(prev_log_index) == prev_log_term
+        prev_log_index < self.snapshot_index() || self.get_term(prev_log_index) == prev_log_term
     }
 
     /// Check if candidate log is at least as up-to-date as ours
@@ -200,42 +325,38 @@ impl RaftLog {
 
     /// Append multiple entries starting from index
     ///
-    /// If existing entries conflict, truncate and append.
-    pub fn append_from(&mut self, prev_index: u64, entries: Vec<RaftLogEntry>) {
-        if entries.is_empty() {
-            return;
-        }
-
-        // Calculate base index
-        let base_index = if let Some(snapshot) = &self.snapshot {
-            snapshot.last_included_index + 1
-        } else {
-            0
-        };
-
-        // Find where to start appending
-        let start_offset = if prev_index >= base_index {
-            (prev_index - base_index) as usize
-        } else {
-            0
-        };
+    /// Entries already present with the same term are kept, so repeated or
+    /// reordered requests are idempotent. At the first conflicting entry the
+    /// log is truncated and the rest appended.
+    ///
+    /// Returns the index of the first entry written, if any.
+    pub fn append_from(&mut self, prev_index: u64, entries: Vec<RaftLogEntry>) -> Option<u64> {
+        let snapshot_index = self
+            .snapshot
+            .as_ref()
+            .map(|s| s.last_included_index)
+            .unwrap_or(0);
+        let mut first_written = None;
+
+        for (i, entry) in entries.into_iter().enumerate() {
+            let index = prev_index + 1 + i as u64;
+            if index <= snapshot_index {
+                // Already compacted, hence committed
+                continue;
+            }
 
-        // Check for conflicts and truncate if needed
-        for (i, entry) in entries.iter().enumerate() {
-            let local_idx = start_offset + i + 1;
-            if let Some(existing) = self.entries.get(local_idx - 1) {
-                if existing.term != entry.term {
-                    // Conflict: truncate from here
-                    self.entries.truncate(local_idx - 1);
-                    break;
+            if index <= self.last_index() {
+                if self.get_term(index) == entry.term {
+                    continue;
                 }
+                // Conflict: truncate from here
+                self.truncate(index);
             }
-        }
-
-        // Append new entries
-        for entry in entries {
             self.entries.push(entry);
+            first_written.get_or_insert(index);
         }
+
+        first_written
     }
 
     /// Truncate log from index (exclusive)
@@ -276,7 +397,9 @@ impl RaftLog {
 
     /// Compact log by creating snapshot
     ///
-    /// Keeps last half of entries and creates snapshot of rest.
+    /// Keeps last half of entries and creates snapshot of rest. The log has
+    /// no state machine to capture, so the snapshot carries no data; logs
+    /// owned by a `RaftNode` are compacted by the node through `compact_to`.
     pub fn compact(&mut self) {
         if self.entries.len() <= self.max_log_size / 2 {
             return;
@@ -294,7 +417,7 @@ impl RaftLog {
                 split_point as u64
             },
             last_included_term: last_included.term,
-            data: Vec::new(), // TODO: Serialize actual state
+            data: Vec::new(),
         };
 
         self.entries.drain(0..split_point);
@@ -321,6 +444,43 @@ impl RaftLog {
         self.snapshot = Some(snapshot);
         self.entries.clear();
     }
+
+    /// Replace the log prefix up to the snapshot's last included entry
+    ///
+    /// Entries after the snapshot are kept if the log agrees with the
+    /// snapshot's last entry; otherwise the whole log is discarded (Raft §7).
+    /// Snapshots older than the current one are ignored.
+    pub fn compact_to(&mut self, snapshot: LogSnapshot) {
+        let index = snapshot.last_included_index;
+        let current = self.snapshot_index();
+        if index <= current && self.snapshot.is_some() {
+            return;
+        }
+
+        if index <= self.last_index() && self.get_term(index) == snapshot.last_included_term {
+            self.entries.drain(..(index - current) as usize);
+        } else {
+            self.entries.clear();
+        }
+        self.snapshot = Some(snapshot);
+    }
+
+    /// Latest configuration entry after the snapshot and its index
+    pub fn latest_membership(&self) -> Option<(u64, Membership)> {
+        let snapshot_index = self.snapshot_index();
+        self.entries
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(i, entry)| entry.command.membership().map(|m| (snapshot_index + 1 + i as u64, m)))
+    }
+
+    /// Create a log that never compacts on its own
+    ///
+    /// Used by owners that snapshot their state machine themselves.
+    pub fn unbounded() -> Self {
+        Self::with_max_size(usize::MAX)
+    }
 }
 
 impl Default for RaftLog {
@@ -460,6 +620,22 @@ mod tests {
         assert_eq!(log.get_term(4), 3);
     }
 
+    #[test]
+    fn test_append_from_is_idempotent() {
+        let mut log = RaftLog::new();
+        let id = make_agent_id(1);
+
+        let entries = vec![make_entry(1, id), make_entry(1, id), make_entry(2, id)];
+        log.append_from(0, entries.clone());
+        log.append_from(0, entries.clone());
+        assert_eq!(log.last_index(), 3);
+
+        // A delayed, shorter request must not drop later entries
+        log.append_from(0, entries[..1].to_vec());
+        assert_eq!(log.last_index(), 3);
+        assert_eq!(log.get_term(3), 2);
+    }
+
     #[test]
     fn test_compaction() {
         let mut log = RaftLog::with_max_size(10);
@@ -474,6 +650,55 @@ mod tests {
         assert!(log.entries.len() <= 10);
     }
 
+    #[test]
+    fn test_compact_to_keeps_matching_suffix() {
+        let mut log = RaftLog::unbounded();
+        let id = make_agent_id(1);
+        for term in [1, 1, 2, 2, 3] {
+            log.append(make_entry(term, id));
+        }
+
+        log.compact_to(LogSnapshot {
+            last_included_index: 3,
+            last_included_term: 2,
+            data: vec![1],
+        });
+        assert_eq!(log.snapshot_index(), 3);
+        assert_eq!(log.last_index(), 5);
+        assert_eq!(log.get_term(3), 2);
+        assert_eq!(log.get_term(2), 0);
+        assert!(log.get_entry(3).is_none());
+        assert_eq!(log.get_entry(5).unwrap().term, 3);
+
+        // Compacted entries are committed and match any leader
+        assert!(log.match_prefix(1, 7));
+
+        // Older snapshots are ignored
+        log.compact_to(LogSnapshot {
+            last_included_index: 2,
+            last_included_term: 1,
+            data: vec![],
+        });
+        assert_eq!(log.snapshot_index(), 3);
+    }
+
+    #[test]
+    fn test_compact_to_discards_conflicting_log() {
+        let mut log = RaftLog::unbounded();
+        let id = make_agent_id(1);
+        for term in [1, 1, 2] {
+            log.append(make_entry(term, id));
+        }
+
+        log.compact_to(LogSnapshot {
+            last_included_index: 2,
+            last_included_term: 4,
+            data: vec![],
+        });
+        assert_eq!(log.last_index(), 2);
+        assert!(log.entries.is_empty());
+    }
+
     #[test]
     fn test_entries_from() {
         let mut log = RaftLog::new();
@@ -504,4 +729,39 @@ mod tests {
         assert!(log.get_entry(0).is_none());
         assert!(log.get_entry(10).is_none());
     }
+
+    #[test]
+    fn test_joint_quorum_needs_both_majorities() {
+        let (a, b, c, d) = (make_agent_id(1), make_agent_id(2), make_agent_id(3), make_agent_id(4));
+        let joint = Membership {
+            voters: vec![b, c, d],
+            outgoing: vec![a, b, c],
+            learners: vec![],
+        };
+
+        // {c, d} is a majority of the new voters only, {a, b} of the old only
+        assert!(!joint.has_quorum(|id| [c, d].contains(id)));
+        assert!(!joint.has_quorum(|id| [a, b].contains(id)));
+        assert!(joint.has_quorum(|id| [b, c].contains(id)));
+        assert_eq!(joint.members(), vec![b, c, d, a]);
+    }
+
+    #[test]
+    fn test_latest_membership() {
+        let mut log = RaftLog::new();
+        log.append(make_entry(1, make_agent_id(1)));
+        assert_eq!(log.latest_membership(), None);
+
+        let mut membership = Membership::new(vec![make_agent_id(1)]);
+        membership.learners.push(make_agent_id(2));
+        log.append(RaftLogEntry {
+            term: 1,
+            command: membership.clone().into(),
+        });
+        log.append(make_entry(1, make_agent_id(1)));
+        assert_eq!(log.latest_membership(), Some((2, membership)));
+
+        log.truncate(2);
+        assert_eq!(log.latest_membership(), None);
+    }
 }
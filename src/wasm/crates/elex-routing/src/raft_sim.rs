@@ -0,0 +1,1508 @@
+//! Deterministic Raft Cluster Simulator
+//!
+//! Runs a cluster of `RaftNode`s in-process on a logical millisecond clock
+//! over `SimNetwork`, a `Transport` that loses, delays, reorders and
+//! duplicates messages and honours partitions. Every random choice comes
+//! from one seeded RNG, so a failing run replays exactly from its seed.
+//!
+//! While the cluster runs, clients write and read the routing index through
+//! whichever nodes believe they lead, and the `SafetyChecker` verifies:
+//!
+//! - Election safety: at most one leader per term
+//! - Log matching: logs that agree on an entry agree on everything before it
+//! - State machine safety: a committed entry never changes, and every node's
+//!   routing index equals the committed log replayed up to its applied index
+//! - Linearizability of the client history against the routing index
+//!
+//! Reads go through the log: the serving leader appends a `Noop` and answers
+//! from its state machine once that entry is applied.
+//!
+//! Nodes persist to `MemoryStorage` and may crash: a crashed node loses all
+//! unsynced state, is rebuilt from its storage and stays down for a while
+//! before rejoining.
+//!
+//! Membership churn removes voters, the leader included, and brings them
+//! back as learners that are promoted once caught up. Removed nodes keep
+//! running and must not disturb the cluster.
+
+use crate::raft::{RaftConfig, RaftMessage, RaftNode, SnapshotConfig};
+use crate::raft_log::{Membership, RaftCommand, RaftLogEntry};
+use crate::raft_storage::MemoryStorage;
+use crate::transport::{Envelope, Transport};
+use elex_core::types::AgentId;
+use elex_core::Result;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Routing index modelled by the checker: agent -> embedding
+type RoutingState = BTreeMap<AgentId, Vec<f32>>;
+
+/// Most violations kept; a broken run would otherwise report every tick
+const MAX_VIOLATIONS: usize = 100;
+
+// ============================================================================
+// Simulated Network
+// ============================================================================
+
+/// Fault injection settings of the simulated network
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkConfig {
+    /// Probability that a message is lost
+    pub loss_rate: f64,
+    /// Probability that a message is delivered twice
+    pub duplicate_rate: f64,
+    /// Probability that a message is held back by `reorder_delay_ms`
+    pub reorder_rate: f64,
+    /// Minimum delivery delay (ms)
+    pub min_delay_ms: u64,
+    /// Maximum delivery delay (ms)
+    pub max_delay_ms: u64,
+    /// Extra delay of held-back messages (ms)
+    pub reorder_delay_ms: u64,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            loss_rate: 0.0,
+            duplicate_rate: 0.0,
+            reorder_rate: 0.0,
+            min_delay_ms: 1,
+            max_delay_ms: 5,
+            reorder_delay_ms: 0,
+        }
+    }
+}
+
+impl NetworkConfig {
+    /// Lossy network with jitter, duplicates and reordering
+    pub fn faulty() -> Self {
+        Self {
+            loss_rate: 0.05,
+            duplicate_rate: 0.05,
+            reorder_rate: 0.1,
+            min_delay_ms: 1,
+            max_delay_ms: 20,
+            reorder_delay_ms: 60,
+        }
+    }
+}
+
+/// Message counters of the simulated network
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkStats {
+    /// Messages handed to the network
+    pub sent: u64,
+    /// Messages delivered (duplicates included)
+    pub delivered: u64,
+    /// Messages lost
+    pub dropped: u64,
+    /// Extra copies delivered
+    pub duplicated: u64,
+    /// Messages held back behind later ones
+    pub reordered: u64,
+    /// Messages discarded by a partition
+    pub partitioned: u64,
+}
+
+/// In-process network with seeded fault injection
+pub struct SimNetwork {
+    /// Fault settings
+    config: NetworkConfig,
+    /// Source of all fault decisions
+    rng: ChaCha8Rng,
+    /// Logical time (ms)
+    now_ms: u64,
+    /// Tie-breaker keeping delivery order deterministic
+    seq: u64,
+    /// Messages in flight by (delivery time, sequence)
+    in_flight: BTreeMap<(u64, u64), Envelope>,
+    /// Partition group of each node; empty when fully connected
+    groups: BTreeMap<AgentId, usize>,
+    /// Counters
+    stats: NetworkStats,
+}
+
+impl SimNetwork {
+    /// Create network with fault settings and seed
+    pub fn new(config: NetworkConfig, seed: u64) -> Self {
+        Self {
+            config,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            now_ms: 0,
+            seq: 0,
+            in_flight: BTreeMap::new(),
+            groups: BTreeMap::new(),
+            stats: NetworkStats::default(),
+        }
+    }
+
+    /// Set the logical time used for new messages
+    pub fn set_time(&mut self, now_ms: u64) {
+        self.now_ms = now_ms;
+    }
+
+    /// Split the network; nodes only reach nodes of their own group
+    ///
+    /// Nodes not listed in any group are isolated.
+    pub fn partition(&mut self, groups: &[Vec<AgentId>]) {
+        self.groups = groups
+            .iter()
+            .enumerate()
+            .flat_map(|(group, nodes)| nodes.iter().map(move |id| (*id, group)))
+            .collect();
+    }
+
+    /// Cut one node off from all others
+    pub fn isolate(&mut self, node: AgentId, all: &[AgentId]) {
+        let rest: Vec<_> = all.iter().copied().filter(|id| *id != node).collect();
+        self.partition(&[vec![node], rest]);
+    }
+
+    /// Reconnect all nodes
+    pub fn heal(&mut self) {
+        self.groups.clear();
+    }
+
+    /// Whether `from` can currently reach `to`
+    pub fn connected(&self, from: &AgentId, to: &AgentId) -> bool {
+        if self.groups.is_empty() {
+            return true;
+        }
+        match (self.groups.get(from), self.groups.get(to)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Whether the network is currently partitioned
+    pub fn is_partitioned(&self) -> bool {
+        !self.groups.is_empty()
+    }
+
+    /// Take the messages due at `now_ms`, in delivery order
+    ///
+    /// Messages whose link was cut while in flight are discarded.
+    pub fn deliver_due(&mut self, now_ms: u64) -> Vec<Envelope> {
+        let later = self.in_flight.split_off(&(now_ms + 1, 0));
+        let due = std::mem::replace(&mut self.in_flight, later);
+
+        let mut delivered = Vec::with_capacity(due.len());
+        for envelope in due.into_values() {
+            if self.connected(&envelope.from, &envelope.to) {
+                self.stats.delivered += 1;
+                delivered.push(envelope);
+            } else {
+                self.stats.partitioned += 1;
+            }
+        }
+        delivered
+    }
+
+    /// Number of messages in flight
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Get counters
+    pub fn stats(&self) -> &NetworkStats {
+        &self.stats
+    }
+
+    fn enqueue(&mut self, envelope: Envelope) {
+        let mut delay = self
+            .rng
+            .gen_range(self.config.min_delay_ms..=self.config.max_delay_ms.max(self.config.min_delay_ms));
+        if self.rng.gen_bool(self.config.reorder_rate) {
+            delay += self.config.reorder_delay_ms;
+            self.stats.reordered += 1;
+        }
+        self.seq += 1;
+        self.in_flight.insert((self.now_ms + delay.max(1), self.seq), envelope);
+    }
+}
+
+impl Transport for SimNetwork {
+    fn send(&mut self, from: AgentId, to: AgentId, message: RaftMessage) {
+        self.stats.sent += 1;
+        if !self.connected(&from, &to) {
+            self.stats.partitioned += 1;
+            return;
+        }
+        if self.rng.gen_bool(self.config.loss_rate) {
+            self.stats.dropped += 1;
+            return;
+        }
+
+        let envelope = Envelope { from, to, message };
+        if self.rng.gen_bool(self.config.duplicate_rate) {
+            self.stats.duplicated += 1;
+            self.enqueue(envelope.clone());
+        }
+        self.enqueue(envelope);
+    }
+}
+
+// ============================================================================
+// Client History
+// ============================================================================
+
+/// Client operation on the routing index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Operation {
+    /// Set (Some) or remove (None) an agent's embedding
+    Write {
+        agent_id: AgentId,
+        embedding: Option<Vec<f32>>,
+    },
+    /// Read an agent's embedding
+    Read { agent_id: AgentId },
+}
+
+impl Operation {
+    /// Log command carrying the operation
+    fn command(&self) -> RaftCommand {
+        match self {
+            Operation::Write {
+                agent_id,
+                embedding: Some(embedding),
+            } => RaftCommand::UpdateRoutingIndex {
+                agent_id: *agent_id,
+                embedding: embedding.clone(),
+            },
+            Operation::Write {
+                agent_id,
+                embedding: None,
+            } => RaftCommand::RemoveAgent { agent_id: *agent_id },
+            Operation::Read { .. } => RaftCommand::Noop,
+        }
+    }
+}
+
+/// One client operation and its outcome
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClientOp {
+    /// Operation id (position in the history)
+    pub id: usize,
+    /// Operation
+    pub op: Operation,
+    /// Invocation time (ms)
+    pub invoked_ms: u64,
+    /// Completion time (ms); None while pending or if the outcome is unknown
+    pub completed_ms: Option<u64>,
+    /// Writes: log index of the entry. Reads: applied index observed.
+    pub index: Option<u64>,
+    /// Term of the proposed entry
+    pub term: u64,
+    /// Value returned by a read
+    pub observed: Option<Vec<f32>>,
+}
+
+/// Operation waiting for its entry to commit on the serving node
+#[derive(Debug, Clone)]
+struct PendingOp {
+    op: usize,
+    node: usize,
+    index: u64,
+    term: u64,
+}
+
+// ============================================================================
+// Safety Checker
+// ============================================================================
+
+/// Broken Raft or linearizability invariant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Violation {
+    /// Two nodes led the same term
+    TwoLeaders {
+        term: u64,
+        first: AgentId,
+        second: AgentId,
+    },
+    /// A node's committed entry differs from the one committed before
+    CommittedEntryChanged { node: AgentId, index: u64 },
+    /// Two logs agree on an entry but not on an earlier one
+    LogMismatch { first: AgentId, second: AgentId, index: u64 },
+    /// A node's routing index differs from the replayed committed log
+    StateMachineDiverged { node: AgentId, applied: u64 },
+    /// An acknowledged write is not in the committed log
+    LostWrite { op: usize, index: u64 },
+    /// A read returned a value the routing index never had at its index
+    ReadMismatch { op: usize, index: u64 },
+    /// A read missed a write acknowledged before it started
+    StaleRead { read: usize, write: usize },
+    /// A read saw a write that started after it finished
+    FutureRead { read: usize, write: usize },
+    /// A later read observed an older state than an earlier one
+    NonMonotonicRead { first: usize, second: usize },
+    /// A later write was ordered before an acknowledged earlier one
+    WriteReordered { first: usize, second: usize },
+}
+
+/// Checks Raft safety properties and client linearizability
+#[derive(Debug, Default)]
+pub struct SafetyChecker {
+    /// Leader of each term seen so far
+    leaders: BTreeMap<u64, AgentId>,
+    /// Committed log; entry `i` has index `i + 1`
+    committed: Vec<RaftLogEntry>,
+    /// Uncommitted entries seen in any log: (index, term) -> (term at index - 1, entry)
+    seen: BTreeMap<(u64, u64), (u64, RaftLogEntry)>,
+    /// Routing index after each committed prefix; `states[0]` is empty
+    states: Vec<RoutingState>,
+    /// Applied index of each node at its last state machine check
+    applied: BTreeMap<AgentId, u64>,
+    /// Violations found
+    violations: Vec<Violation>,
+}
+
+impl SafetyChecker {
+    /// Create empty checker
+    pub fn new() -> Self {
+        Self {
+            states: vec![RoutingState::new()],
+            ..Self::default()
+        }
+    }
+
+    /// Check the Raft invariants against the current node states
+    pub fn observe(&mut self, nodes: &[RaftNode]) {
+        for node in nodes {
+            self.check_leader(node);
+            self.record_entries(node);
+        }
+        for node in nodes {
+            self.check_committed(node);
+        }
+        for (i, first) in nodes.iter().enumerate() {
+            for second in &nodes[i + 1..] {
+                self.check_log_matching(first, second);
+            }
+        }
+        for node in nodes {
+            self.check_state_machine(node);
+        }
+    }
+
+    /// Check a client history against the committed log
+    pub fn check_history(&mut self, history: &[ClientOp]) {
+        // A write took effect if its entry is committed at its index
+        let effective: Vec<Option<u64>> = history
+            .iter()
+            .map(|op| match (&op.op, op.index) {
+                (Operation::Write { .. }, Some(index)) => self
+                    .committed
+                    .get(index as usize - 1)
+                    .filter(|e| e.term == op.term && e.command == op.op.command())
+                    .map(|_| index),
+                _ => None,
+            })
+            .collect();
+
+        for (write, op) in history.iter().enumerate() {
+            if let (Operation::Write { .. }, Some(_), Some(index)) = (&op.op, op.completed_ms, op.index) {
+                if effective[write].is_none() {
+                    self.report(Violation::LostWrite { op: op.id, index });
+                }
+            }
+        }
+
+        let reads: Vec<_> = history
+            .iter()
+            .filter(|op| matches!(op.op, Operation::Read { .. }) && op.completed_ms.is_some())
+            .collect();
+
+        for read in &reads {
+            let (Operation::Read { agent_id }, Some(completed), Some(k)) = (&read.op, read.completed_ms, read.index)
+            else {
+                continue;
+            };
+
+            let expected = self.states.get(k as usize).map(|s| s.get(agent_id).cloned());
+            if expected != Some(read.observed.clone()) {
+                self.report(Violation::ReadMismatch { op: read.id, index: k });
+            }
+
+            for (write, op) in history.iter().enumerate() {
+                let Some(index) = effective[write] else { continue };
+                if op.completed_ms.is_some_and(|t| t < read.invoked_ms) && index > k {
+                    self.report(Violation::StaleRead { read: read.id, write: op.id });
+                }
+                if op.invoked_ms > completed && index <= k {
+                    self.report(Violation::FutureRead { read: read.id, write: op.id });
+                }
+            }
+        }
+
+        for first in &reads {
+            for second in &reads {
+                if first.completed_ms.is_some_and(|t| t < second.invoked_ms) && first.index > second.index {
+                    self.report(Violation::NonMonotonicRead { first: first.id, second: second.id });
+                }
+            }
+        }
+
+        for (i, first) in history.iter().enumerate() {
+            let (Some(first_index), Some(completed)) = (effective[i], first.completed_ms) else {
+                continue;
+            };
+            for (j, second) in history.iter().enumerate() {
+                if effective[j].is_some_and(|index| completed < second.invoked_ms && index <= first_index) {
+                    self.report(Violation::WriteReordered { first: first.id, second: second.id });
+                }
+            }
+        }
+    }
+
+    /// Violations found so far
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    /// Length of the committed log
+    pub fn committed_len(&self) -> u64 {
+        self.committed.len() as u64
+    }
+
+    /// Term of the committed entry at `index`, if known yet
+    pub fn committed_term(&self, index: u64) -> Option<u64> {
+        let i = (index as usize).checked_sub(1)?;
+        self.committed.get(i).map(|e| e.term)
+    }
+
+    /// Number of terms that had a leader
+    pub fn leader_terms(&self) -> usize {
+        self.leaders.len()
+    }
+
+    fn check_leader(&mut self, node: &RaftNode) {
+        if !node.is_leader() {
+            return;
+        }
+        match self.leaders.entry(node.current_term()) {
+            Entry::Vacant(slot) => {
+                slot.insert(node.id);
+            }
+            Entry::Occupied(slot) if *slot.get() != node.id => {
+                let violation = Violation::TwoLeaders {
+                    term: node.current_term(),
+                    first: *slot.get(),
+                    second: node.id,
+                };
+                self.report(violation);
+            }
+            Entry::Occupied(_) => {}
+        }
+    }
+
+    /// Remember the node's uncommitted entries
+    ///
+    /// Nodes may compact an entry in the same step that commits it, so the
+    /// committed log is rebuilt from entries seen while still uncommitted.
+    fn record_entries(&mut self, node: &RaftNode) {
+        let log = node.log();
+        let from = (log.snapshot_index() + 1).max(self.committed.len() as u64 + 1);
+        for index in from..=log.last_index() {
+            if let Some(entry) = log.get_entry(index) {
+                self.seen
+                    .entry((index, entry.term))
+                    .or_insert_with(|| (log.get_term(index - 1), entry.clone()));
+            }
+        }
+    }
+
+    /// Extend the committed log and verify the node's committed prefix
+    ///
+    /// Known entries are compared by term only: with election safety, an
+    /// index and term identify one entry, and with log matching they also
+    /// identify the prefix before it. New entries are found by following
+    /// that chain back from the node's commit index.
+    fn check_committed(&mut self, node: &RaftNode) {
+        let log = node.log();
+        let known = self.committed.len() as u64;
+
+        for index in log.snapshot_index().max(1)..=node.commit_index().min(known) {
+            if self.committed[index as usize - 1].term != log.get_term(index) {
+                self.report(Violation::CommittedEntryChanged { node: node.id, index });
+                return;
+            }
+        }
+
+        let commit_index = node.commit_index();
+        if commit_index <= known {
+            return;
+        }
+
+        let mut chain = Vec::new();
+        let mut term = log.get_term(commit_index);
+        for index in (known + 1..=commit_index).rev() {
+            // Not seen yet; retry once another node reports it
+            let Some((prev_term, entry)) = self.seen.get(&(index, term)) else { return };
+            chain.push(entry.clone());
+            term = *prev_term;
+        }
+        if self.committed_term(known).is_some_and(|t| t != term) {
+            self.report(Violation::CommittedEntryChanged { node: node.id, index: known });
+            return;
+        }
+
+        for entry in chain.into_iter().rev() {
+            let mut state = self.states[self.committed.len()].clone();
+            apply_to_model(&mut state, &entry.command);
+            self.states.push(state);
+            self.committed.push(entry);
+        }
+        self.seen = self.seen.split_off(&(commit_index + 1, 0));
+    }
+
+    /// Compares terms from the later of the two snapshots on, as terms of
+    /// compacted entries are unknown
+    fn check_log_matching(&mut self, first: &RaftNode, second: &RaftNode) {
+        let from = first.log().snapshot_index().max(second.log().snapshot_index()).max(1);
+        let common = first.log().last_index().min(second.log().last_index());
+        let Some(agreed) = (from..=common)
+            .rev()
+            .find(|&i| first.log().get_term(i) == second.log().get_term(i))
+        else {
+            return;
+        };
+
+        if let Some(index) = (from..agreed).find(|&i| first.log().get_term(i) != second.log().get_term(i)) {
+            self.report(Violation::LogMismatch {
+                first: first.id,
+                second: second.id,
+                index,
+            });
+        }
+    }
+
+    fn check_state_machine(&mut self, node: &RaftNode) {
+        let sm = node.state_machine();
+        let applied = sm.last_applied();
+        if self.applied.get(&node.id) == Some(&applied) {
+            return;
+        }
+        // Wait until the committed log reaches the applied index
+        let Some(state) = self.states.get(applied as usize) else { return };
+        self.applied.insert(node.id, applied);
+
+        let matches = sm.agent_count() == state.len()
+            && state
+                .iter()
+                .all(|(agent_id, embedding)| sm.get_embedding(agent_id) == Some(embedding.as_slice()));
+        if !matches {
+            self.report(Violation::StateMachineDiverged { node: node.id, applied });
+        }
+    }
+
+    fn report(&mut self, violation: Violation) {
+        if self.violations.len() < MAX_VIOLATIONS {
+            self.violations.push(violation);
+        }
+    }
+}
+
+/// Apply a command to the modelled routing index
+fn apply_to_model(state: &mut RoutingState, command: &RaftCommand) {
+    match command {
+        RaftCommand::UpdateRoutingIndex { agent_id, embedding } => {
+            state.insert(*agent_id, embedding.clone());
+        }
+        RaftCommand::RemoveAgent { agent_id } => {
+            state.remove(agent_id);
+        }
+        RaftCommand::RegisterAgent { .. }
+        | RaftCommand::UpdateConfiguration { .. }
+        | RaftCommand::Noop => {}
+    }
+}
+
+// ============================================================================
+// Cluster Simulation
+// ============================================================================
+
+/// Randomized scenario settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimConfig {
+    /// Cluster size
+    pub nodes: usize,
+    /// Seed of every random choice in the run
+    pub seed: u64,
+    /// Network fault settings
+    pub network: NetworkConfig,
+    /// Base election timeout (ms); randomized up to twice this
+    pub election_timeout_ms: u64,
+    /// Leader heartbeat interval (ms)
+    pub heartbeat_interval_ms: u64,
+    /// Probability of a client operation per ms
+    pub client_rate: f64,
+    /// Share of client operations that are reads
+    pub read_ratio: f64,
+    /// Share of writes that remove the agent
+    pub remove_ratio: f64,
+    /// Number of distinct agents written
+    pub keys: u8,
+    /// Probability per ms of a new random partition
+    pub partition_rate: f64,
+    /// Probability per ms of healing a partition
+    pub heal_rate: f64,
+    /// Snapshot thresholds of every node
+    pub snapshot: SnapshotConfig,
+    /// Probability per ms of crashing a random node
+    pub crash_rate: f64,
+    /// Time a crashed node stays down (ms)
+    pub restart_after_ms: u64,
+    /// Probability per ms of a membership change step
+    pub membership_rate: f64,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            nodes: 5,
+            seed: 0,
+            network: NetworkConfig::default(),
+            election_timeout_ms: 150,
+            heartbeat_interval_ms: 50,
+            client_rate: 0.05,
+            read_ratio: 0.3,
+            remove_ratio: 0.1,
+            keys: 4,
+            partition_rate: 0.0,
+            heal_rate: 0.0,
+            snapshot: SnapshotConfig::default(),
+            crash_rate: 0.0,
+            restart_after_ms: 300,
+            membership_rate: 0.0,
+        }
+    }
+}
+
+impl SimConfig {
+    /// Faulty network with partitions coming and going, crashing nodes,
+    /// and small snapshots shipped in several chunks
+    pub fn chaos(seed: u64) -> Self {
+        Self {
+            seed,
+            network: NetworkConfig::faulty(),
+            partition_rate: 0.002,
+            heal_rate: 0.004,
+            snapshot: SnapshotConfig {
+                max_log_entries: 16,
+                applied_threshold: 0,
+                chunk_size: 256,
+            },
+            crash_rate: 0.001,
+            ..Self::default()
+        }
+    }
+
+    /// Chaos with voters leaving and rejoining the cluster
+    pub fn churn(seed: u64) -> Self {
+        Self {
+            membership_rate: 0.004,
+            ..Self::chaos(seed)
+        }
+    }
+}
+
+/// Outcome of a simulation run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimReport {
+    /// Seed of the run
+    pub seed: u64,
+    /// Logical time simulated (ms)
+    pub elapsed_ms: u64,
+    /// Terms that had a leader
+    pub leader_terms: usize,
+    /// Committed log length
+    pub committed: u64,
+    /// Writes acknowledged to clients
+    pub acked_writes: usize,
+    /// Reads answered
+    pub completed_reads: usize,
+    /// Snapshots installed from a leader, over all nodes
+    pub snapshots_installed: u64,
+    /// Node crashes
+    pub crashes: usize,
+    /// Membership changes started
+    pub membership_changes: usize,
+    /// Network counters
+    pub network: NetworkStats,
+    /// Invariants broken during the run
+    pub violations: Vec<Violation>,
+}
+
+impl SimReport {
+    /// Whether no invariant was broken
+    pub fn is_safe(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Deterministic in-process Raft cluster
+pub struct ClusterSim {
+    /// Scenario settings
+    config: SimConfig,
+    /// Cluster nodes
+    nodes: Vec<RaftNode>,
+    /// Durable state of each node, kept across crashes
+    storage: Vec<MemoryStorage>,
+    /// Membership each node starts from before it sees a configuration entry
+    initial: Vec<Membership>,
+    /// Membership changes started
+    membership_changes: usize,
+    /// Crashed nodes and when they restart (ms)
+    down: BTreeMap<usize, u64>,
+    /// Crashes so far
+    crashes: usize,
+    /// Simulated network
+    network: SimNetwork,
+    /// Source of scenario decisions
+    rng: ChaCha8Rng,
+    /// Logical time (ms)
+    now_ms: u64,
+    /// Invariant checker
+    checker: SafetyChecker,
+    /// Client history
+    history: Vec<ClientOp>,
+    /// Operations awaiting commit
+    pending: Vec<PendingOp>,
+}
+
+impl ClusterSim {
+    /// Create cluster from scenario settings
+    pub fn new(config: SimConfig) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(config.seed);
+        let ids: Vec<AgentId> = (0..config.nodes)
+            .map(|i| {
+                let mut id = [0u8; 32];
+                id[0] = i as u8;
+                id
+            })
+            .collect();
+
+        let storage: Vec<MemoryStorage> = ids.iter().map(|_| MemoryStorage::new()).collect();
+        let initial = vec![Membership::new(ids.clone()); ids.len()];
+        let nodes = ids
+            .iter()
+            .zip(&storage)
+            .zip(&initial)
+            .map(|((id, storage), membership)| Self::start_node(&config, membership, *id, storage, rng.gen(), 0))
+            .collect::<Result<_>>()
+            .expect("empty storage always loads");
+
+        let network = SimNetwork::new(config.network.clone(), rng.gen());
+
+        Self {
+            config,
+            nodes,
+            storage,
+            initial,
+            membership_changes: 0,
+            down: BTreeMap::new(),
+            crashes: 0,
+            network,
+            rng,
+            now_ms: 0,
+            checker: SafetyChecker::new(),
+            history: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Run the scenario for `duration_ms` of logical time
+    pub fn run(&mut self, duration_ms: u64) -> Result<()> {
+        for _ in 0..duration_ms {
+            self.advance()?;
+            self.inject_faults()?;
+            self.generate_client_ops()?;
+        }
+        Ok(())
+    }
+
+    /// Run without faults or new client operations until every node has
+    /// applied the whole committed log, or `max_ms` elapses
+    ///
+    /// Crashed nodes restart right away. Returns whether the cluster
+    /// converged.
+    pub fn settle(&mut self, max_ms: u64) -> Result<bool> {
+        self.network.heal();
+        for restart_ms in self.down.values_mut() {
+            *restart_ms = self.now_ms;
+        }
+        for _ in 0..max_ms {
+            self.advance()?;
+            if self.converged() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Advance the cluster by one millisecond
+    pub fn advance(&mut self) -> Result<()> {
+        self.now_ms += 1;
+        self.network.set_time(self.now_ms);
+        let now_ms = self.now_ms;
+        self.down.retain(|_, restart_ms| *restart_ms > now_ms);
+
+        // Messages to crashed nodes are lost
+        for envelope in self.network.deliver_due(self.now_ms) {
+            if let Some(i) = self.nodes.iter().position(|n| n.id == envelope.to) {
+                if !self.down.contains_key(&i) {
+                    self.nodes[i].step(envelope.from, envelope.message, self.now_ms, &mut self.network)?;
+                }
+            }
+        }
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            if !self.down.contains_key(&i) {
+                node.tick(self.now_ms, &mut self.network)?;
+            }
+        }
+
+        self.checker.observe(&self.nodes);
+        self.resolve_pending();
+        Ok(())
+    }
+
+    /// Submit a write through a random node that believes it leads
+    ///
+    /// Returns false if no node believes it leads.
+    pub fn write(&mut self, agent_id: AgentId, embedding: Option<Vec<f32>>) -> Result<bool> {
+        self.submit(Operation::Write { agent_id, embedding })
+    }
+
+    /// Submit a read through a random node that believes it leads
+    pub fn read(&mut self, agent_id: AgentId) -> Result<bool> {
+        self.submit(Operation::Read { agent_id })
+    }
+
+    /// Split the cluster into groups of node positions
+    pub fn partition(&mut self, groups: &[Vec<usize>]) {
+        let groups: Vec<Vec<AgentId>> = groups
+            .iter()
+            .map(|group| group.iter().map(|&i| self.nodes[i].id).collect())
+            .collect();
+        self.network.partition(&groups);
+    }
+
+    /// Cut one node off from the rest of the cluster
+    pub fn isolate(&mut self, node: usize) {
+        let ids: Vec<_> = self.nodes.iter().map(|n| n.id).collect();
+        self.network.isolate(ids[node], &ids);
+    }
+
+    /// Reconnect all nodes
+    pub fn heal(&mut self) {
+        self.network.heal();
+    }
+
+    /// Crash a node: it loses everything not synced to its storage, is
+    /// recovered from storage and stays down for `restart_after_ms`
+    ///
+    /// Operations it was serving are abandoned with an unknown outcome.
+    pub fn crash(&mut self, node: usize) -> Result<()> {
+        let id = self.nodes[node].id;
+        let restart_ms = self.now_ms + self.config.restart_after_ms;
+        let seed = self.rng.gen();
+
+        self.nodes[node] = Self::start_node(
+            &self.config,
+            &self.initial[node],
+            id,
+            &self.storage[node],
+            seed,
+            restart_ms,
+        )?;
+        self.down.insert(node, restart_ms);
+        self.pending.retain(|p| p.node != node);
+        self.crashes += 1;
+        Ok(())
+    }
+
+    /// Start a new node as a learner of the current voters
+    ///
+    /// The node joins once the leader adds it with `RaftNode::add_learner`.
+    /// Returns its position.
+    pub fn add_node(&mut self) -> Result<usize> {
+        let position = self.nodes.len();
+        let mut id = [0u8; 32];
+        id[0] = position as u8;
+
+        let voters = self.nodes[self.leader().unwrap_or(0)].membership().voters;
+        let membership = Membership {
+            voters,
+            outgoing: Vec::new(),
+            learners: vec![id],
+        };
+        let storage = MemoryStorage::new();
+        let seed = self.rng.gen();
+        let node = Self::start_node(&self.config, &membership, id, &storage, seed, self.now_ms)?;
+
+        self.nodes.push(node);
+        self.storage.push(storage);
+        self.initial.push(membership);
+        Ok(position)
+    }
+
+    /// Whether a node is crashed and not yet restarted
+    pub fn is_down(&self, node: usize) -> bool {
+        self.down.contains_key(&node)
+    }
+
+    /// Position of the leader with the highest term, if any
+    pub fn leader(&self) -> Option<usize> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| n.is_leader())
+            .max_by_key(|(_, n)| n.current_term())
+            .map(|(i, _)| i)
+    }
+
+    /// Check the client history and summarize the run
+    pub fn report(&mut self) -> SimReport {
+        self.checker.check_history(&self.history);
+
+        let completed = |read: bool| {
+            self.history
+                .iter()
+                .filter(|op| op.completed_ms.is_some() && matches!(op.op, Operation::Read { .. }) == read)
+                .count()
+        };
+
+        SimReport {
+            seed: self.config.seed,
+            elapsed_ms: self.now_ms,
+            leader_terms: self.checker.leader_terms(),
+            committed: self.checker.committed_len(),
+            acked_writes: completed(false),
+            completed_reads: completed(true),
+            snapshots_installed: self.nodes.iter().map(|n| n.stats().snapshots_installed).sum(),
+            crashes: self.crashes,
+            membership_changes: self.membership_changes,
+            network: self.network.stats().clone(),
+            violations: self.checker.violations().to_vec(),
+        }
+    }
+
+    /// Cluster nodes
+    pub fn nodes(&self) -> &[RaftNode] {
+        &self.nodes
+    }
+
+    /// Cluster node, e.g. to change membership through the leader
+    pub fn node_mut(&mut self, node: usize) -> &mut RaftNode {
+        &mut self.nodes[node]
+    }
+
+    /// Simulated network
+    pub fn network(&self) -> &SimNetwork {
+        &self.network
+    }
+
+    /// Client history so far
+    pub fn history(&self) -> &[ClientOp] {
+        &self.history
+    }
+
+    /// Logical time (ms)
+    pub fn now_ms(&self) -> u64 {
+        self.now_ms
+    }
+
+    /// Build a node on its storage, with its clock at `now_ms`
+    fn start_node(
+        config: &SimConfig,
+        membership: &Membership,
+        id: AgentId,
+        storage: &MemoryStorage,
+        seed: u64,
+        now_ms: u64,
+    ) -> Result<RaftNode> {
+        let raft_config = RaftConfig {
+            election_timeout: Duration::from_millis(config.election_timeout_ms),
+            heartbeat_interval: Duration::from_millis(config.heartbeat_interval_ms),
+            peers: membership.members().into_iter().filter(|p| *p != id).collect(),
+        };
+        Ok(RaftNode::new(id, raft_config)
+            .with_membership(membership.clone())
+            .with_snapshot_config(config.snapshot.clone())
+            .with_storage(Box::new(storage.reopen()))?
+            .with_timer_seed(seed)
+            .with_clock(now_ms))
+    }
+
+    /// Whether a leader runs and every member applied the committed log;
+    /// removed nodes no longer hear from the leader
+    fn converged(&self) -> bool {
+        let committed = self.checker.committed_len();
+        let Some(leader) = self.leader() else {
+            return false;
+        };
+        let membership = self.nodes[leader].membership();
+        committed > 0
+            && !self.nodes[leader].membership_change_pending()
+            && self
+                .nodes
+                .iter()
+                .filter(|n| membership.contains(&n.id))
+                .all(|n| n.state_machine().last_applied() == committed)
+    }
+
+    fn submit(&mut self, op: Operation) -> Result<bool> {
+        // Leaders handing over leadership refuse proposals
+        let leaders: Vec<usize> = (0..self.nodes.len())
+            .filter(|&i| self.nodes[i].is_leader() && self.nodes[i].transfer_target().is_none())
+            .collect();
+        let Some(&node) = leaders.choose(&mut self.rng) else {
+            return Ok(false);
+        };
+
+        let index = self.nodes[node].propose(op.command())?;
+        let term = self.nodes[node].current_term();
+        let id = self.history.len();
+
+        self.history.push(ClientOp {
+            id,
+            index: matches!(op, Operation::Write { .. }).then_some(index),
+            op,
+            invoked_ms: self.now_ms,
+            completed_ms: None,
+            term,
+            observed: None,
+        });
+        self.pending.push(PendingOp { op: id, node, index, term });
+        Ok(true)
+    }
+
+    /// Complete operations whose entry is applied on the serving node and
+    /// abandon those whose entry was overwritten (outcome unknown)
+    fn resolve_pending(&mut self) {
+        let now_ms = self.now_ms;
+        let nodes = &self.nodes;
+        let checker = &self.checker;
+        let history = &mut self.history;
+
+        self.pending.retain(|p| {
+            let node = &nodes[p.node];
+            let sm = node.state_machine();
+            if sm.last_applied() < p.index {
+                return node.log().get_term(p.index) == p.term;
+            }
+            // The serving node may already have compacted the entry
+            match checker.committed_term(p.index) {
+                None => return true,
+                Some(term) if term != p.term => return false,
+                Some(_) => {}
+            }
+
+            let op = &mut history[p.op];
+            op.completed_ms = Some(now_ms);
+            if let Operation::Read { agent_id } = &op.op {
+                op.index = Some(sm.last_applied());
+                op.observed = sm.get_embedding(agent_id).map(|e| e.to_vec());
+            }
+            false
+        });
+    }
+
+    fn inject_faults(&mut self) -> Result<()> {
+        // Only draw when enabled, so that other scenarios replay unchanged
+        if self.config.membership_rate > 0.0 && self.rng.gen_bool(self.config.membership_rate) {
+            self.churn_membership();
+        }
+
+        if self.rng.gen_bool(self.config.crash_rate) {
+            let node = self.rng.gen_range(0..self.nodes.len());
+            if !self.is_down(node) {
+                self.crash(node)?;
+            }
+        }
+
+        if self.network.is_partitioned() {
+            if self.rng.gen_bool(self.config.heal_rate) {
+                self.network.heal();
+            }
+        } else if self.rng.gen_bool(self.config.partition_rate) {
+            let mut order: Vec<usize> = (0..self.nodes.len()).collect();
+            order.shuffle(&mut self.rng);
+            let split = self.rng.gen_range(1..order.len().max(2));
+            let (a, b) = order.split_at(split.min(order.len()));
+            self.partition(&[a.to_vec(), b.to_vec()]);
+        }
+        Ok(())
+    }
+
+    /// Take one membership step through the leader: promote a caught-up
+    /// learner, add a node outside the cluster as a learner, or remove a
+    /// random voter, possibly the leader itself
+    fn churn_membership(&mut self) {
+        let Some(leader) = self.leader() else { return };
+        let membership = self.nodes[leader].membership();
+        let outside: Vec<AgentId> = self
+            .nodes
+            .iter()
+            .map(|n| n.id)
+            .filter(|id| !membership.contains(id))
+            .collect();
+
+        let node = &mut self.nodes[leader];
+        if node.membership_change_pending() || node.transfer_target().is_some() {
+            return;
+        }
+        let started = if let Some(learner) = membership.learners.iter().find(|id| node.is_caught_up(id)) {
+            let mut voters = membership.voters.clone();
+            voters.push(*learner);
+            node.change_membership(voters).is_ok()
+        } else if let Some(id) = outside.choose(&mut self.rng) {
+            node.add_learner(*id).is_ok()
+        } else if membership.voters.len() > 3 {
+            let removed = *membership.voters.choose(&mut self.rng).unwrap();
+            let voters = membership.voters.iter().copied().filter(|id| *id != removed).collect();
+            node.change_membership(voters).is_ok()
+        } else {
+            false
+        };
+        if started {
+            self.membership_changes += 1;
+        }
+    }
+
+    fn generate_client_ops(&mut self) -> Result<()> {
+        if !self.rng.gen_bool(self.config.client_rate) {
+            return Ok(());
+        }
+
+        let mut agent_id = [0u8; 32];
+        agent_id[0] = 0x80 | self.rng.gen_range(0..self.config.keys.max(1));
+
+        if self.rng.gen_bool(self.config.read_ratio) {
+            self.read(agent_id)?;
+        } else if self.rng.gen_bool(self.config.remove_ratio) {
+            self.write(agent_id, None)?;
+        } else {
+            // Unique values make every read attributable to one write
+            let value = self.history.len() as f32;
+            self.write(agent_id, Some(vec![value; 4]))?;
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raft::Role;
+
+    fn make_agent_id(byte: u8) -> AgentId {
+        let mut id = [0u8; 32];
+        id[0] = byte;
+        id
+    }
+
+    fn heartbeat() -> RaftMessage {
+        vote(1)
+    }
+
+    fn vote(term: u64) -> RaftMessage {
+        RaftMessage::RequestVoteResponse(crate::raft::RequestVoteResponse {
+            term,
+            vote_granted: false,
+        })
+    }
+
+    #[test]
+    fn test_network_partition_and_heal() {
+        let (a, b, c) = (make_agent_id(0), make_agent_id(1), make_agent_id(2));
+        let mut network = SimNetwork::new(NetworkConfig::default(), 1);
+
+        network.partition(&[vec![a, b], vec![c]]);
+        network.send(a, b, heartbeat());
+        network.send(a, c, heartbeat());
+        assert_eq!(network.stats().partitioned, 1);
+
+        // Links cut while a message is in flight drop it too
+        network.isolate(b, &[a, b, c]);
+        assert!(network.deliver_due(10).is_empty());
+        assert_eq!(network.stats().partitioned, 2);
+
+        network.heal();
+        network.send(c, a, heartbeat());
+        let delivered = network.deliver_due(10);
+        assert_eq!(delivered.len(), 1);
+        assert_eq!(delivered[0].to, a);
+    }
+
+    #[test]
+    fn test_network_faults_are_seeded() {
+        let run = |seed| {
+            let mut network = SimNetwork::new(NetworkConfig::faulty(), seed);
+            for term in 0..500u64 {
+                network.set_time(term);
+                network.send(make_agent_id(0), make_agent_id(1), vote(term));
+            }
+            let order: Vec<u64> = network
+                .deliver_due(1_000)
+                .into_iter()
+                .map(|e| match e.message {
+                    RaftMessage::RequestVoteResponse(resp) => resp.term,
+                    _ => unreachable!(),
+                })
+                .collect();
+            (network.stats().clone(), order)
+        };
+
+        let (stats, order) = run(7);
+        assert_eq!((stats.clone(), order.clone()), run(7));
+        assert_ne!(order, run(8).1);
+        assert!(stats.dropped > 0 && stats.duplicated > 0 && stats.reordered > 0);
+        assert_eq!(stats.sent - stats.dropped + stats.duplicated, stats.delivered);
+        assert!(order.windows(2).any(|w| w[0] > w[1]));
+    }
+
+    #[test]
+    fn test_reliable_cluster_is_linearizable() {
+        let mut sim = ClusterSim::new(SimConfig { seed: 3, ..SimConfig::default() });
+        sim.run(3_000).unwrap();
+        assert!(sim.settle(2_000).unwrap());
+
+        let report = sim.report();
+        assert!(report.is_safe(), "{:?}", report.violations);
+        assert!(report.acked_writes > 50);
+        assert!(report.completed_reads > 20);
+        assert!(sim.nodes().iter().all(|n| n.commit_index() == report.committed));
+    }
+
+    #[test]
+    fn test_randomized_chaos_scenarios_are_safe() {
+        let mut snapshots_installed = 0;
+        let mut crashes = 0;
+        for seed in 0..16 {
+            let mut sim = ClusterSim::new(SimConfig::chaos(seed));
+            sim.run(4_000).unwrap();
+            assert!(sim.settle(5_000).unwrap(), "seed {} did not converge", seed);
+
+            let report = sim.report();
+            assert!(report.is_safe(), "seed {}: {:?}", seed, report.violations);
+            assert!(report.acked_writes > 0, "seed {} made no progress", seed);
+            snapshots_installed += report.snapshots_installed;
+            crashes += report.crashes;
+        }
+        assert!(snapshots_installed > 0);
+        assert!(crashes > 0);
+    }
+
+    #[test]
+    fn test_membership_churn_is_safe() {
+        let mut membership_changes = 0;
+        for seed in 0..8 {
+            let mut sim = ClusterSim::new(SimConfig::churn(seed));
+            sim.run(4_000).unwrap();
+            assert!(sim.settle(5_000).unwrap(), "seed {} did not converge", seed);
+
+            let report = sim.report();
+            assert!(report.is_safe(), "seed {}: {:?}", seed, report.violations);
+            assert!(report.acked_writes > 0, "seed {} made no progress", seed);
+            membership_changes += report.membership_changes;
+
+            // Every member ends up with the leader's configuration
+            let membership = sim.nodes()[sim.leader().unwrap()].membership();
+            assert!(!membership.is_joint());
+            assert!(membership.voters.len() >= 3);
+            for node in sim.nodes().iter().filter(|n| membership.contains(&n.id)) {
+                assert_eq!(node.membership(), membership, "seed {}", seed);
+            }
+        }
+        assert!(membership_changes > 8);
+    }
+
+    #[test]
+    fn test_lagging_follower_catches_up_from_snapshot() {
+        let mut sim = ClusterSim::new(SimConfig {
+            client_rate: 0.0,
+            seed: 9,
+            snapshot: SnapshotConfig {
+                max_log_entries: 0,
+                applied_threshold: 8,
+                chunk_size: 64,
+            },
+            ..SimConfig::default()
+        });
+        sim.run(1_000).unwrap();
+        let leader = sim.leader().unwrap();
+        let lagging = (leader + 1) % sim.nodes().len();
+
+        sim.isolate(lagging);
+        for i in 0..40u8 {
+            sim.write(make_agent_id(0x80 | (i % 4)), Some(vec![i as f32; 4])).unwrap();
+            sim.run(20).unwrap();
+        }
+        assert!(sim.nodes()[leader].log().snapshot_index() > sim.nodes()[lagging].commit_index());
+
+        assert!(sim.settle(3_000).unwrap());
+        let follower = &sim.nodes()[lagging];
+        assert_eq!(follower.stats().snapshots_installed, 1);
+        assert_eq!(follower.state_machine().agent_count(), 4);
+        assert_eq!(
+            follower.state_machine().get_embedding(&make_agent_id(0x83)),
+            Some([39.0; 4].as_slice())
+        );
+
+        let report = sim.report();
+        assert!(report.is_safe(), "{:?}", report.violations);
+        assert_eq!(report.acked_writes, 40);
+    }
+
+    #[test]
+    fn test_crashed_leader_keeps_committed_writes() {
+        let mut sim = ClusterSim::new(SimConfig {
+            client_rate: 0.0,
+            seed: 13,
+            ..SimConfig::default()
+        });
+        sim.run(1_000).unwrap();
+        let leader = sim.leader().unwrap();
+        for i in 0..10u8 {
+            sim.write(make_agent_id(0x80 | (i % 4)), Some(vec![i as f32; 4])).unwrap();
+            sim.run(20).unwrap();
+        }
+        sim.run(200).unwrap();
+        assert_eq!(sim.history().iter().filter(|op| op.completed_ms.is_some()).count(), 10);
+        let committed = sim.nodes()[leader].commit_index();
+
+        sim.crash(leader).unwrap();
+        assert!(sim.is_down(leader));
+        assert!(sim.nodes()[leader].log().last_index() >= committed);
+        assert_eq!(sim.nodes()[leader].state_machine().agent_count(), 4);
+
+        sim.run(1_000).unwrap();
+        assert!(!sim.is_down(leader));
+        assert!(sim.settle(2_000).unwrap());
+
+        let report = sim.report();
+        assert!(report.is_safe(), "{:?}", report.violations);
+        assert_eq!(report.acked_writes, 10);
+        assert_eq!(report.crashes, 1);
+        assert_eq!(
+            sim.nodes()[leader].state_machine().get_embedding(&make_agent_id(0x81)),
+            Some([9.0; 4].as_slice())
+        );
+    }
+
+    #[test]
+    fn test_runs_replay_from_seed() {
+        let run = || {
+            let mut sim = ClusterSim::new(SimConfig::chaos(11));
+            sim.run(2_000).unwrap();
+            (sim.history().to_vec(), sim.network().stats().clone())
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn test_isolated_leader_loses_uncommitted_writes() {
+        let mut sim = ClusterSim::new(SimConfig {
+            client_rate: 0.0,
+            seed: 5,
+            ..SimConfig::default()
+        });
+        sim.run(1_000).unwrap();
+        let old = sim.leader().unwrap();
+        let old_term = sim.nodes()[old].current_term();
+
+        // The isolated leader accepts a write it can never commit
+        sim.isolate(old);
+        assert!(sim.write(make_agent_id(0x80), Some(vec![1.0; 4])).unwrap());
+        sim.run(1_000).unwrap();
+
+        let new = sim.leader().unwrap();
+        assert_ne!(new, old);
+        assert!(sim.nodes()[new].current_term() > old_term);
+        assert!(sim.nodes()[old].is_leader());
+
+        // Once healed, the old leader steps down and its entry is replaced
+        assert!(sim.settle(2_000).unwrap());
+        assert!(!sim.nodes()[old].is_leader());
+        assert_eq!(sim.history()[0].completed_ms, None);
+        assert!(sim.nodes().iter().all(|n| n.state_machine().agent_count() == 0));
+
+        let report = sim.report();
+        assert!(report.is_safe(), "{:?}", report.violations);
+    }
+
+    #[test]
+    fn test_checker_flags_two_leaders() {
+        let config = |peer| RaftConfig {
+            peers: vec![make_agent_id(peer)],
+            ..RaftConfig::default()
+        };
+        let mut nodes = vec![
+            RaftNode::new(make_agent_id(0), config(1)),
+            RaftNode::new(make_agent_id(1), config(0)),
+        ];
+        for node in &mut nodes {
+            node.current_term = 1;
+            node.role = Role::Leader;
+        }
+
+        let mut checker = SafetyChecker::new();
+        checker.observe(&nodes);
+        assert!(matches!(checker.violations(), [Violation::TwoLeaders { term: 1, .. }]));
+    }
+
+    #[test]
+    fn test_checker_flags_stale_read() {
+        let agent_id = make_agent_id(0x80);
+        let mut node = RaftNode::new(make_agent_id(0), RaftConfig::default());
+        node.start_election().unwrap();
+        let index = node
+            .propose(RaftCommand::UpdateRoutingIndex {
+                agent_id,
+                embedding: vec![1.0; 4],
+            })
+            .unwrap();
+
+        let mut checker = SafetyChecker::new();
+        checker.observe(std::slice::from_ref(&node));
+
+        // The read started after the write was acknowledged but observed
+        // the state before it
+        let history = vec![
+            ClientOp {
+                id: 0,
+                op: Operation::Write {
+                    agent_id,
+                    embedding: Some(vec![1.0; 4]),
+                },
+                invoked_ms: 0,
+                completed_ms: Some(1),
+                index: Some(index),
+                term: 1,
+                observed: None,
+            },
+            ClientOp {
+                id: 1,
+                op: Operation::Read { agent_id },
+                invoked_ms: 2,
+                completed_ms: Some(3),
+                index: Some(index - 1),
+                term: 1,
+                observed: None,
+            },
+        ];
+        checker.check_history(&history);
+        assert_eq!(checker.violations(), &[Violation::StaleRead { read: 1, write: 0 }]);
+    }
+}
@@ -5,7 +5,7 @@
 
 use elex_core::types::AgentId;
 use elex_core::{ElexError, Result};
-use crate::raft_log::{RaftCommand, RaftLogEntry, AgentMetadata};
+use crate::raft_log::{RaftCommand, RaftLogEntry, AgentMetadata, Membership};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -33,12 +33,17 @@ pub struct RaftStateMachine {
 pub struct ClusterConfig {
     /// Cluster nodes
     pub nodes: Vec<String>,
+
+    /// Membership of the last applied configuration entry
+    #[serde(default)]
+    pub membership: Option<Membership>,
 }
 
 impl Default for ClusterConfig {
     fn default() -> Self {
         Self {
             nodes: Vec::new(),
+            membership: None,
         }
     }
 }
@@ -82,14 +87,37 @@ impl RaftStateMachine {
                 Ok(())
             }
 
-            RaftCommand::UpdateConfiguration { peers } => {
+            RaftCommand::UpdateConfiguration { peers, .. } => {
                 self.config.nodes = peers.iter().map(hex_id).collect();
+                self.config.membership = entry.command.membership();
+                self.last_applied += 1;
+                Ok(())
+            }
+
+            RaftCommand::Noop => {
                 self.last_applied += 1;
                 Ok(())
             }
         }
     }
 
+    /// Serialize the full state for a Raft snapshot
+    ///
+    /// Covers the routing index, agent registry, cluster configuration and
+    /// the applied index.
+    pub fn snapshot(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).map_err(|e| ElexError::Consensus {
+            reason: format!("Failed to serialize state machine snapshot: {}", e),
+        })
+    }
+
+    /// Rebuild a state machine from `snapshot` data
+    pub fn restore(data: &[u8]) -> Result<Self> {
+        serde_json::from_slice(data).map_err(|e| ElexError::Consensus {
+            reason: format!("Failed to restore state machine snapshot: {}", e),
+        })
+    }
+
     /// Get embedding for agent
     pub fn get_embedding(&self, agent_id: &AgentId) -> Option<&[f32]> {
         self.routing_index.get(&hex_id(agent_id)).map(|v| v.as_slice())
@@ -276,11 +304,14 @@ mod tests {
 
         let entry = make_entry(RaftCommand::UpdateConfiguration {
             peers: vec![peer1, peer2],
+            outgoing: vec![peer1],
+            learners: vec![],
         });
 
         sm.apply(&entry).unwrap();
 
         assert_eq!(sm.config().nodes.len(), 2);
+        assert!(sm.config().membership.as_ref().unwrap().is_joint());
     }
 
     #[test]
@@ -384,4 +415,35 @@ mod tests {
         let agents = sm.get_agents();
         assert_eq!(agents.len(), 2);
     }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let mut sm = RaftStateMachine::new();
+        let id = make_agent_id(1);
+        let peers = vec![make_agent_id(2), make_agent_id(3)];
+
+        sm.apply(&make_entry(RaftCommand::UpdateRoutingIndex {
+            agent_id: id,
+            embedding: vec![0.25; 128],
+        }))
+        .unwrap();
+        sm.apply(&make_entry(RaftCommand::RegisterAgent {
+            agent_id: id,
+            metadata: AgentMetadata {
+                feature_code: "FAJ 121 3094".to_string(),
+                ..Default::default()
+            },
+        }))
+        .unwrap();
+        sm.apply(&make_entry(Membership::new(peers.clone()).into())).unwrap();
+
+        let restored = RaftStateMachine::restore(&sm.snapshot().unwrap()).unwrap();
+        assert_eq!(restored.last_applied(), 3);
+        assert_eq!(restored.get_embedding(&id), Some([0.25; 128].as_slice()));
+        assert_eq!(restored.get_metadata(&id).unwrap().feature_code, "FAJ 121 3094");
+        assert_eq!(restored.config().nodes.len(), 2);
+        assert_eq!(restored.config().membership, Some(Membership::new(peers)));
+
+        assert!(RaftStateMachine::restore(b"not a snapshot").is_err());
+    }
 }
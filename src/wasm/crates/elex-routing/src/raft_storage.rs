@@ -0,0 +1,754 @@
+//! Durable Raft Storage
+//!
+//! Raft's safety argument assumes a node never forgets its term, its vote
+//! or a log entry it acknowledged. `RaftStorage` persists that state; a
+//! `RaftNode` built with `RaftNode::recover` writes every change through it
+//! and syncs before any message leaves `step` or `tick`.
+//!
+//! Backends:
+//! - `MemoryStorage`: survives dropping the node, for tests and simulation
+//! - `FileStorage`: a directory with fsync'd files (native builds)
+//! - `IndexedDbStorage`: `elex_memory::storage::IndexedDBBackend` (WASM)
+//!
+//! IndexedDB only completes writes asynchronously. Its `sync` reports
+//! whether staged writes are durable; until they are, the node holds its
+//! outgoing messages and the driver releases them after awaiting
+//! `IndexedDbStorage::flush`:
+//!
+//! ```ignore
+//! node.step(from, message, now_ms, &mut transport)?;
+//! storage.flush().await?;
+//! node.release_held(&mut transport)?;
+//! ```
+
+use crate::raft_log::{LogSnapshot, RaftLogEntry};
+use elex_core::types::AgentId;
+use elex_core::{ElexError, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+// ============================================================================
+// Persistent State
+// ============================================================================
+
+/// Raft state that must survive a restart
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HardState {
+    /// Latest term the node has seen
+    pub current_term: u64,
+    /// Candidate voted for in `current_term`
+    pub voted_for: Option<AgentId>,
+    /// Highest index known committed; lets recovery replay the log
+    pub commit_index: u64,
+}
+
+/// Everything a storage backend holds
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PersistedState {
+    /// Term, vote and commit index
+    pub hard_state: HardState,
+    /// Latest snapshot
+    pub snapshot: Option<LogSnapshot>,
+    /// Log entries following the snapshot
+    pub entries: Vec<RaftLogEntry>,
+}
+
+impl PersistedState {
+    /// Index of the last entry covered by the snapshot
+    pub fn snapshot_index(&self) -> u64 {
+        self.snapshot
+            .as_ref()
+            .map(|s| s.last_included_index)
+            .unwrap_or(0)
+    }
+
+    /// Index of the last log entry
+    pub fn last_index(&self) -> u64 {
+        self.snapshot_index() + self.entries.len() as u64
+    }
+
+    /// Replace the log from `first_index` on with `entries`
+    ///
+    /// Entries covered by the snapshot are skipped.
+    pub fn append(&mut self, first_index: u64, entries: &[RaftLogEntry]) {
+        let snapshot_index = self.snapshot_index();
+        let skip = (snapshot_index + 1).saturating_sub(first_index) as usize;
+        let first_index = first_index.max(snapshot_index + 1);
+
+        self.entries.truncate((first_index - snapshot_index - 1) as usize);
+        self.entries.extend(entries.iter().skip(skip).cloned());
+    }
+
+    /// Replace the log prefix with a snapshot, as `RaftLog::compact_to`
+    pub fn install_snapshot(&mut self, snapshot: &LogSnapshot) {
+        let current = self.snapshot_index();
+        let index = snapshot.last_included_index;
+        if index <= current && self.snapshot.is_some() {
+            return;
+        }
+
+        let matches = index > current
+            && index <= self.last_index()
+            && self
+                .entries
+                .get((index - current) as usize - 1)
+                .is_some_and(|e| e.term == snapshot.last_included_term);
+        if matches {
+            self.entries.drain(..(index - current) as usize);
+        } else {
+            self.entries.clear();
+        }
+        self.snapshot = Some(snapshot.clone());
+    }
+}
+
+// ============================================================================
+// Storage Trait
+// ============================================================================
+
+/// Durable store for a node's hard state, log and snapshot
+///
+/// Writes may be buffered until `sync`; only synced writes are guaranteed
+/// to survive a crash.
+pub trait RaftStorage {
+    /// Load the state written by an earlier run
+    fn load(&mut self) -> Result<PersistedState>;
+
+    /// Write term, vote and commit index
+    fn save_hard_state(&mut self, state: &HardState) -> Result<()>;
+
+    /// Replace the log from `first_index` on with `entries`
+    fn append(&mut self, first_index: u64, entries: &[RaftLogEntry]) -> Result<()>;
+
+    /// Write a snapshot and replace the log with the entries following it
+    ///
+    /// `entries` are the caller's in-memory entries after
+    /// `snapshot.last_included_index`, so backends never read the log back.
+    fn save_snapshot(&mut self, snapshot: &LogSnapshot, entries: &[RaftLogEntry]) -> Result<()>;
+
+    /// Make all earlier writes durable
+    ///
+    /// Returns false if the backend only completes them asynchronously;
+    /// see the module docs.
+    fn sync(&mut self) -> Result<bool>;
+}
+
+fn storage_error(action: &str, e: impl std::fmt::Display) -> ElexError {
+    ElexError::Consensus {
+        reason: format!("Raft storage failed to {}: {}", action, e),
+    }
+}
+
+// ============================================================================
+// Memory Storage
+// ============================================================================
+
+/// Staged write of a storage handle
+#[derive(Debug, Clone)]
+enum Write {
+    HardState(HardState),
+    Append(u64, Vec<RaftLogEntry>),
+    Snapshot(LogSnapshot, Vec<RaftLogEntry>),
+}
+
+impl Write {
+    fn apply(self, state: &mut PersistedState) {
+        match self {
+            Write::HardState(hard_state) => state.hard_state = hard_state,
+            Write::Append(first_index, entries) => state.append(first_index, &entries),
+            Write::Snapshot(snapshot, entries) => {
+                state.snapshot = Some(snapshot);
+                state.entries = entries;
+            }
+        }
+    }
+}
+
+/// In-memory storage that outlives the node using it
+///
+/// Writes are staged per handle and become durable on `sync`. Dropping a
+/// handle loses its unsynced writes, like a crash; `reopen` gives a fresh
+/// handle on the durable state.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    /// Durable state shared by all handles
+    durable: Arc<Mutex<PersistedState>>,
+    /// Writes not yet synced
+    staged: Vec<Write>,
+}
+
+impl MemoryStorage {
+    /// Create empty storage
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// New handle on the same durable state, without staged writes
+    pub fn reopen(&self) -> Self {
+        Self {
+            durable: Arc::clone(&self.durable),
+            staged: Vec::new(),
+        }
+    }
+
+    /// Copy of the durable state
+    pub fn durable_state(&self) -> PersistedState {
+        self.durable.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+}
+
+impl RaftStorage for MemoryStorage {
+    fn load(&mut self) -> Result<PersistedState> {
+        Ok(self.durable_state())
+    }
+
+    fn save_hard_state(&mut self, state: &HardState) -> Result<()> {
+        self.staged.push(Write::HardState(state.clone()));
+        Ok(())
+    }
+
+    fn append(&mut self, first_index: u64, entries: &[RaftLogEntry]) -> Result<()> {
+        self.staged.push(Write::Append(first_index, entries.to_vec()));
+        Ok(())
+    }
+
+    fn save_snapshot(&mut self, snapshot: &LogSnapshot, entries: &[RaftLogEntry]) -> Result<()> {
+        self.staged.push(Write::Snapshot(snapshot.clone(), entries.to_vec()));
+        Ok(())
+    }
+
+    fn sync(&mut self) -> Result<bool> {
+        let mut durable = self.durable.lock().map_err(|e| storage_error("sync", e))?;
+        for write in self.staged.drain(..) {
+            write.apply(&mut durable);
+        }
+        Ok(true)
+    }
+}
+
+// ============================================================================
+// File Storage
+// ============================================================================
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use file::FileStorage;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod file {
+    use super::*;
+    use std::fs::{self, File, OpenOptions};
+    use std::io::{Read, Write as _};
+    use std::path::{Path, PathBuf};
+
+    const HARD_STATE_FILE: &str = "hard_state.json";
+    const SNAPSHOT_FILE: &str = "snapshot.json";
+    const LOG_FILE: &str = "log";
+
+    /// Record header: payload length and CRC-32, little-endian
+    const RECORD_HEADER_LEN: usize = 8;
+
+    /// One log record: replace the log from `first_index` on
+    #[derive(Serialize, Deserialize)]
+    struct LogRecord {
+        first_index: u64,
+        entries: Vec<RaftLogEntry>,
+    }
+
+    /// Directory-backed storage for native builds
+    ///
+    /// Hard state and snapshot are replaced atomically (write, fsync,
+    /// rename). Log changes are appended as checksummed records; a torn
+    /// record at the tail left by a crash is discarded on open. Writing a
+    /// snapshot rewrites the log without the entries it covers.
+    pub struct FileStorage {
+        /// Storage directory
+        dir: PathBuf,
+        /// Log file opened for appending
+        log: File,
+        /// Directory entries changed since the last sync
+        dir_dirty: bool,
+    }
+
+    impl FileStorage {
+        /// Open or create storage in `dir`
+        pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+            let dir = dir.as_ref().to_path_buf();
+            fs::create_dir_all(&dir).map_err(|e| storage_error("create directory", e))?;
+
+            // Drop a torn tail so new records follow valid ones
+            let path = dir.join(LOG_FILE);
+            let valid_len = match fs::read(&path) {
+                Ok(data) => Some(read_records(&data).1),
+                Err(_) => None,
+            };
+            let log = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .map_err(|e| storage_error("open log", e))?;
+            if let Some(len) = valid_len {
+                log.set_len(len as u64).map_err(|e| storage_error("truncate log", e))?;
+            }
+
+            Ok(Self {
+                dir,
+                log,
+                dir_dirty: true,
+            })
+        }
+
+        /// Storage directory
+        pub fn dir(&self) -> &Path {
+            &self.dir
+        }
+
+        /// Write `data` to `name` atomically
+        fn replace_file(&mut self, name: &str, data: &[u8]) -> Result<()> {
+            let tmp = self.dir.join(format!("{}.tmp", name));
+            let mut file = File::create(&tmp).map_err(|e| storage_error("create file", e))?;
+            file.write_all(data).map_err(|e| storage_error("write file", e))?;
+            file.sync_all().map_err(|e| storage_error("sync file", e))?;
+            fs::rename(&tmp, self.dir.join(name)).map_err(|e| storage_error("rename file", e))?;
+            self.dir_dirty = true;
+            Ok(())
+        }
+
+        fn read_json<T: for<'de> Deserialize<'de>>(&self, name: &str) -> Result<Option<T>> {
+            let mut file = match File::open(self.dir.join(name)) {
+                Ok(file) => file,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                Err(e) => return Err(storage_error("open file", e)),
+            };
+            let mut data = Vec::new();
+            file.read_to_end(&mut data).map_err(|e| storage_error("read file", e))?;
+            serde_json::from_slice(&data)
+                .map(Some)
+                .map_err(|e| storage_error("decode file", e))
+        }
+    }
+
+    impl RaftStorage for FileStorage {
+        fn load(&mut self) -> Result<PersistedState> {
+            let mut state = PersistedState {
+                hard_state: self.read_json(HARD_STATE_FILE)?.unwrap_or_default(),
+                ..PersistedState::default()
+            };
+            if let Some(snapshot) = self.read_json::<LogSnapshot>(SNAPSHOT_FILE)? {
+                state.install_snapshot(&snapshot);
+            }
+
+            let data = fs::read(self.dir.join(LOG_FILE)).map_err(|e| storage_error("read log", e))?;
+            for record in read_records(&data).0 {
+                state.append(record.first_index, &record.entries);
+            }
+            Ok(state)
+        }
+
+        fn save_hard_state(&mut self, state: &HardState) -> Result<()> {
+            // The commit index must not become durable before the entries it covers
+            self.log.sync_data().map_err(|e| storage_error("sync log", e))?;
+            let data = serde_json::to_vec(state).map_err(|e| storage_error("encode hard state", e))?;
+            self.replace_file(HARD_STATE_FILE, &data)
+        }
+
+        fn append(&mut self, first_index: u64, entries: &[RaftLogEntry]) -> Result<()> {
+            let record = encode_record(first_index, entries)?;
+            self.log.write_all(&record).map_err(|e| storage_error("append log", e))
+        }
+
+        fn save_snapshot(&mut self, snapshot: &LogSnapshot, entries: &[RaftLogEntry]) -> Result<()> {
+            let data = serde_json::to_vec(snapshot).map_err(|e| storage_error("encode snapshot", e))?;
+            self.replace_file(SNAPSHOT_FILE, &data)?;
+
+            // Rewrite the log with only the entries after the snapshot
+            let log = encode_record(snapshot.last_included_index + 1, entries)?;
+            self.replace_file(LOG_FILE, &log)?;
+            self.log = OpenOptions::new()
+                .append(true)
+                .open(self.dir.join(LOG_FILE))
+                .map_err(|e| storage_error("open log", e))?;
+            Ok(())
+        }
+
+        fn sync(&mut self) -> Result<bool> {
+            self.log.sync_data().map_err(|e| storage_error("sync log", e))?;
+            if self.dir_dirty {
+                // Make renames durable; not supported on every platform
+                #[cfg(unix)]
+                File::open(&self.dir)
+                    .and_then(|d| d.sync_all())
+                    .map_err(|e| storage_error("sync directory", e))?;
+                self.dir_dirty = false;
+            }
+            Ok(true)
+        }
+    }
+
+    fn encode_record(first_index: u64, entries: &[RaftLogEntry]) -> Result<Vec<u8>> {
+        let payload = serde_json::to_vec(&LogRecord {
+            first_index,
+            entries: entries.to_vec(),
+        })
+        .map_err(|e| storage_error("encode log record", e))?;
+
+        let mut record = Vec::with_capacity(RECORD_HEADER_LEN + payload.len());
+        record.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        record.extend_from_slice(&crc32(&payload).to_le_bytes());
+        record.extend_from_slice(&payload);
+        Ok(record)
+    }
+
+    /// Decode records up to the first torn or corrupt one
+    ///
+    /// Returns the records and the length of the valid prefix.
+    fn read_records(data: &[u8]) -> (Vec<LogRecord>, usize) {
+        let mut records = Vec::new();
+        let mut offset = 0;
+        while data.len() >= offset + RECORD_HEADER_LEN {
+            let len = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()) as usize;
+            let crc = u32::from_le_bytes(data[offset + 4..offset + 8].try_into().unwrap());
+            let start = offset + RECORD_HEADER_LEN;
+            let Some(payload) = data.get(start..start + len) else { break };
+            if crc32(payload) != crc {
+                break;
+            }
+            let Ok(record) = serde_json::from_slice(payload) else { break };
+            records.push(record);
+            offset = start + len;
+        }
+        (records, offset)
+    }
+
+    /// CRC-32 (IEEE 802.3)
+    fn crc32(data: &[u8]) -> u32 {
+        let mut crc = !0u32;
+        for &byte in data {
+            crc ^= byte as u32;
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 };
+            }
+        }
+        !crc
+    }
+}
+
+// ============================================================================
+// IndexedDB Storage
+// ============================================================================
+
+#[cfg(target_arch = "wasm32")]
+pub use indexeddb::IndexedDbStorage;
+
+#[cfg(target_arch = "wasm32")]
+mod indexeddb {
+    use super::*;
+    use elex_memory::storage::IndexedDBBackend;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Storage in IndexedDB for browser and edge builds
+    ///
+    /// The durable state is mirrored in memory; writes are applied to the
+    /// mirror and queued, and `flush` writes the queued keys. Clones share
+    /// the mirror and queue, so the driver keeps one to flush while the
+    /// node owns another.
+    #[derive(Clone)]
+    pub struct IndexedDbStorage {
+        backend: Rc<IndexedDBBackend>,
+        /// Key prefix separating nodes that share a database
+        prefix: String,
+        inner: Rc<RefCell<Inner>>,
+    }
+
+    struct Inner {
+        /// State as of the last write
+        state: PersistedState,
+        /// Keys changed since the last flush, with their values (None = delete)
+        queued: Vec<(String, Option<Vec<u8>>)>,
+        /// Entry range stored under entry keys once the queue is written
+        stored_range: (u64, u64),
+    }
+
+    /// Entry range stored under entry keys
+    #[derive(Serialize, Deserialize)]
+    struct EntryRange {
+        first: u64,
+        last: u64,
+    }
+
+    impl IndexedDbStorage {
+        /// Open storage for one node
+        pub async fn open(db_name: &str, prefix: &str) -> Result<Self> {
+            let backend = IndexedDBBackend::open(db_name)
+                .await
+                .map_err(|e| storage_error("open IndexedDB", e))?;
+            let storage = Self {
+                backend: Rc::new(backend),
+                prefix: prefix.to_string(),
+                inner: Rc::new(RefCell::new(Inner {
+                    state: PersistedState::default(),
+                    queued: Vec::new(),
+                    stored_range: (1, 0),
+                })),
+            };
+
+            let mut state = PersistedState::default();
+            if let Some(hard_state) = storage.get_json::<HardState>(&storage.key("hard_state")).await? {
+                state.hard_state = hard_state;
+            }
+            if let Some(snapshot) = storage.get_json::<LogSnapshot>(&storage.key("snapshot")).await? {
+                state.install_snapshot(&snapshot);
+            }
+            let range: Option<EntryRange> = storage.get_json(&storage.key("entries")).await?;
+            let (first, last) = range.map(|r| (r.first, r.last)).unwrap_or((1, 0));
+            let mut entries = Vec::new();
+            for index in first..=last {
+                match storage.get_json::<RaftLogEntry>(&storage.entry_key(index)).await? {
+                    Some(entry) => entries.push(entry),
+                    None => break,
+                }
+            }
+            state.append(first, &entries);
+
+            {
+                let mut inner = storage.inner.borrow_mut();
+                inner.state = state;
+                inner.stored_range = (first, last);
+            }
+            Ok(storage)
+        }
+
+        /// Write all queued changes
+        pub async fn flush(&self) -> Result<()> {
+            let mut queued = std::mem::take(&mut self.inner.borrow_mut().queued);
+            let mut written = 0;
+            while let Some((key, value)) = queued.get(written) {
+                let result = match value {
+                    Some(data) => self.backend.put(key, data).await,
+                    None => self.backend.delete(key).await,
+                };
+                if let Err(e) = result {
+                    // Requeue the unwritten rest ahead of newer writes
+                    let mut inner = self.inner.borrow_mut();
+                    let newer = std::mem::take(&mut inner.queued);
+                    inner.queued = queued.split_off(written);
+                    inner.queued.extend(newer);
+                    return Err(storage_error("write IndexedDB", e));
+                }
+                written += 1;
+            }
+            Ok(())
+        }
+
+        fn key(&self, name: &str) -> String {
+            format!("raft/{}/{}", self.prefix, name)
+        }
+
+        fn entry_key(&self, index: u64) -> String {
+            format!("raft/{}/entry/{:020}", self.prefix, index)
+        }
+
+        async fn get_json<T: for<'de> Deserialize<'de>>(&self, key: &str) -> Result<Option<T>> {
+            let Some(data) = self
+                .backend
+                .get(key)
+                .await
+                .map_err(|e| storage_error("read IndexedDB", e))?
+            else {
+                return Ok(None);
+            };
+            serde_json::from_slice(&data)
+                .map(Some)
+                .map_err(|e| storage_error("decode IndexedDB value", e))
+        }
+
+        fn queue_json<T: Serialize>(&self, key: String, value: &T) -> Result<()> {
+            let data = serde_json::to_vec(value).map_err(|e| storage_error("encode IndexedDB value", e))?;
+            self.inner.borrow_mut().queued.push((key, Some(data)));
+            Ok(())
+        }
+
+        /// Queue entry keys for the mirror's log, deleting keys it dropped
+        fn queue_entries(&self, changed_from: u64) -> Result<()> {
+            let (first, last, entries, (old_first, old_last)) = {
+                let inner = self.inner.borrow();
+                let first = inner.state.snapshot_index() + 1;
+                (first, inner.state.last_index(), inner.state.entries.clone(), inner.stored_range)
+            };
+
+            for index in old_first..=old_last {
+                if index < first || index > last {
+                    self.inner.borrow_mut().queued.push((self.entry_key(index), None));
+                }
+            }
+            for (i, entry) in entries.iter().enumerate() {
+                let index = first + i as u64;
+                if index >= changed_from {
+                    self.queue_json(self.entry_key(index), entry)?;
+                }
+            }
+            self.queue_json(self.key("entries"), &EntryRange { first, last })?;
+            self.inner.borrow_mut().stored_range = (first, last);
+            Ok(())
+        }
+    }
+
+    impl RaftStorage for IndexedDbStorage {
+        fn load(&mut self) -> Result<PersistedState> {
+            Ok(self.inner.borrow().state.clone())
+        }
+
+        fn save_hard_state(&mut self, state: &HardState) -> Result<()> {
+            self.inner.borrow_mut().state.hard_state = state.clone();
+            self.queue_json(self.key("hard_state"), state)
+        }
+
+        fn append(&mut self, first_index: u64, entries: &[RaftLogEntry]) -> Result<()> {
+            self.inner.borrow_mut().state.append(first_index, entries);
+            self.queue_entries(first_index)
+        }
+
+        fn save_snapshot(&mut self, snapshot: &LogSnapshot, entries: &[RaftLogEntry]) -> Result<()> {
+            {
+                let mut inner = self.inner.borrow_mut();
+                inner.state.snapshot = Some(snapshot.clone());
+                inner.state.entries = entries.to_vec();
+            }
+            self.queue_json(self.key("snapshot"), snapshot)?;
+            self.queue_entries(snapshot.last_included_index + 1)
+        }
+
+        fn sync(&mut self) -> Result<bool> {
+            Ok(self.inner.borrow().queued.is_empty())
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raft_log::RaftCommand;
+
+    fn entry(term: u64) -> RaftLogEntry {
+        RaftLogEntry {
+            term,
+            command: RaftCommand::Noop,
+        }
+    }
+
+    fn snapshot(index: u64, term: u64) -> LogSnapshot {
+        LogSnapshot {
+            last_included_index: index,
+            last_included_term: term,
+            data: vec![index as u8],
+        }
+    }
+
+    fn terms(state: &PersistedState) -> Vec<u64> {
+        state.entries.iter().map(|e| e.term).collect()
+    }
+
+    #[test]
+    fn test_persisted_state_append_and_snapshot() {
+        let mut state = PersistedState::default();
+        state.append(1, &[entry(1), entry(1), entry(2)]);
+        state.append(3, &[entry(3), entry(3)]);
+        assert_eq!(terms(&state), vec![1, 1, 3, 3]);
+
+        state.install_snapshot(&snapshot(2, 1));
+        assert_eq!(state.snapshot_index(), 2);
+        assert_eq!(terms(&state), vec![3, 3]);
+
+        // Entries covered by the snapshot are skipped
+        state.append(2, &[entry(1), entry(3), entry(4)]);
+        assert_eq!(terms(&state), vec![3, 4]);
+        assert_eq!(state.last_index(), 4);
+    }
+
+    #[test]
+    fn test_memory_storage_loses_unsynced_writes() {
+        let mut storage = MemoryStorage::new();
+        let hard_state = HardState {
+            current_term: 2,
+            voted_for: Some([1u8; 32]),
+            commit_index: 1,
+        };
+        storage.save_hard_state(&hard_state).unwrap();
+        storage.append(1, &[entry(1), entry(2)]).unwrap();
+        assert!(storage.sync().unwrap());
+
+        storage.append(3, &[entry(2)]).unwrap();
+        let mut reopened = storage.reopen();
+        drop(storage);
+
+        let state = reopened.load().unwrap();
+        assert_eq!(state.hard_state, hard_state);
+        assert_eq!(terms(&state), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_file_storage_round_trip() {
+        let dir = std::env::temp_dir().join(format!("elex-raft-storage-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        {
+            let mut storage = FileStorage::open(&dir).unwrap();
+            storage
+                .save_hard_state(&HardState {
+                    current_term: 3,
+                    voted_for: None,
+                    commit_index: 4,
+                })
+                .unwrap();
+            storage.append(1, &[entry(1), entry(1), entry(2)]).unwrap();
+            storage.append(3, &[entry(3), entry(3)]).unwrap();
+            storage.save_snapshot(&snapshot(2, 1), &[entry(3), entry(3)]).unwrap();
+            storage.append(5, &[entry(3)]).unwrap();
+            assert!(storage.sync().unwrap());
+        }
+
+        // A torn record at the tail is dropped on open
+        let log = dir.join("log");
+        let mut data = std::fs::read(&log).unwrap();
+        data.extend_from_slice(&[200, 0, 0, 0, 1, 2, 3, 4, b'{']);
+        std::fs::write(&log, &data).unwrap();
+
+        let mut storage = FileStorage::open(&dir).unwrap();
+        let state = storage.load().unwrap();
+        assert_eq!(state.hard_state.current_term, 3);
+        assert_eq!(state.snapshot.as_ref().unwrap().data, vec![2]);
+        assert_eq!(state.snapshot_index(), 2);
+        assert_eq!(terms(&state), vec![3, 3, 3]);
+
+        storage.append(6, &[entry(4)]).unwrap();
+        storage.sync().unwrap();
+        assert_eq!(terms(&FileStorage::open(&dir).unwrap().load().unwrap()), vec![3, 3, 3, 4]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_storage_snapshot_keeps_caller_entries() {
+        let dir = std::env::temp_dir().join(format!("elex-raft-snapshot-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut storage = FileStorage::open(&dir).unwrap();
+        storage.append(1, &[entry(1), entry(1), entry(2)]).unwrap();
+        storage.sync().unwrap();
+
+        // The retained entries come from the caller, not the log on disk
+        std::fs::write(dir.join("log"), b"").unwrap();
+        storage.save_snapshot(&snapshot(1, 1), &[entry(1), entry(2)]).unwrap();
+        storage.sync().unwrap();
+
+        let state = FileStorage::open(&dir).unwrap().load().unwrap();
+        assert_eq!(state.snapshot_index(), 1);
+        assert_eq!(terms(&state), vec![1, 2]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
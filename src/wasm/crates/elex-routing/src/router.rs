@@ -1,48 +1,287 @@
 //! Semantic Router using HNSW for query routing
+//!
+//! Agents register an expertise embedding under their id; the router keeps
+//! the id↔agent mapping next to the HNSW index so hits resolve to the
+//! registered agent and its feature code.
+//!
+//! # Routing
+//! - Hits below `min_similarity` never route; if nothing clears it the
+//!   decision is `RouteDecision::NoConfidentRoute`
+//! - Agents scoring within `fanout_margin` of the best hit are returned as
+//!   fan-out targets alongside the primary
+//! - Category filters (`FeatureCode::category()`, e.g. 121 in "FAJ 121 3094")
+//!   restrict candidates before scoring, with an exact scan of the category
+//!
+//! HNSW has no deletion, so re-registered and removed agents leave stale
+//! nodes behind that searches skip. The index is rebuilt once stale nodes
+//! exceed `compaction_ratio` of the live ones.
 
 use elex_core::types::FeatureCode;
-use elex_core::Result;
-use elex_memory::HnswIndex;
+use elex_memory::{HnswConfig, HnswIndex};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// Embedding dimension of registered agents
+pub const EMBEDDING_DIM: usize = 128;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Routing thresholds
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RouterConfig {
+    /// Minimum similarity for a confident route
+    pub min_similarity: f32,
+    /// Agents within this similarity of the best hit are fanned out to
+    pub fanout_margin: f32,
+    /// Stale-to-live node ratio that triggers an index rebuild
+    pub compaction_ratio: f32,
+}
+
+impl Default for RouterConfig {
+    fn default() -> Self {
+        Self {
+            min_similarity: 0.5,
+            fanout_margin: 0.05,
+            compaction_ratio: 0.5,
+        }
+    }
+}
+
+// ============================================================================
+// Routing Results
+// ============================================================================
 
 /// Routing result
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct RouteResult {
+    /// Registered agent id
+    pub agent_id: u64,
+    /// Feature code of the agent
     pub feature_code: FeatureCode,
+    /// Similarity of the query to the agent's expertise
     pub confidence: f32,
+    /// Similarity gap to the best hit (0.0 for the best)
+    pub margin: f32,
+}
+
+/// Outcome of routing a query
+#[derive(Clone, Debug, PartialEq)]
+pub enum RouteDecision {
+    /// A confident route, with agents close enough to fan out to
+    Routed {
+        /// Best-matching agent
+        primary: RouteResult,
+        /// Other agents within `fanout_margin` of the primary
+        fanout: Vec<RouteResult>,
+    },
+    /// No agent cleared `min_similarity`
+    NoConfidentRoute {
+        /// Best hit below the threshold, if any agent was scored
+        best: Option<RouteResult>,
+    },
+}
+
+impl RouteDecision {
+    /// Primary agent, if the route is confident
+    pub fn primary(&self) -> Option<&RouteResult> {
+        match self {
+            RouteDecision::Routed { primary, .. } => Some(primary),
+            RouteDecision::NoConfidentRoute { .. } => None,
+        }
+    }
+
+    /// Check if the route is confident
+    pub fn is_confident(&self) -> bool {
+        matches!(self, RouteDecision::Routed { .. })
+    }
+}
+
+/// Registered agent
+#[derive(Clone, Debug)]
+struct RegisteredAgent {
+    feature_code: FeatureCode,
+    embedding: [f32; EMBEDDING_DIM],
+    node: u32,
 }
 
+// ============================================================================
+// Semantic Router
+// ============================================================================
+
 /// Semantic router for query-agent matching
 pub struct SemanticRouter {
+    config: RouterConfig,
     index: HnswIndex,
+    /// Agent id -> registration
+    agents: HashMap<u64, RegisteredAgent>,
+    /// Live HNSW node -> agent id
+    nodes: HashMap<u32, u64>,
+    /// Nodes left behind by re-registration and removal
+    stale: usize,
 }
 
 impl SemanticRouter {
     pub fn new() -> Self {
+        Self::with_config(RouterConfig::default())
+    }
+
+    /// Create with routing thresholds
+    pub fn with_config(config: RouterConfig) -> Self {
         Self {
+            config,
             index: HnswIndex::default(),
+            agents: HashMap::new(),
+            nodes: HashMap::new(),
+            stale: 0,
         }
     }
 
-    pub fn register_agent(&mut self, _id: u64, embedding: [f32; 128]) {
-        // Store the ID for later mapping, insert returns the node ID
-        self.index.insert(&embedding);
+    /// Get configuration
+    pub fn config(&self) -> &RouterConfig {
+        &self.config
+    }
+
+    /// Register an agent, replacing an earlier registration of the same id
+    ///
+    /// Returns the feature code of the replaced registration.
+    pub fn register_agent(
+        &mut self,
+        id: u64,
+        feature_code: FeatureCode,
+        embedding: [f32; EMBEDDING_DIM],
+    ) -> Option<FeatureCode> {
+        let previous = self.unlink(id);
+        let node = self.index.insert(&embedding);
+        self.nodes.insert(node, id);
+        self.agents.insert(id, RegisteredAgent { feature_code, embedding, node });
+        self.maybe_compact();
+        previous
+    }
+
+    /// Remove an agent; returns its feature code if it was registered
+    pub fn remove_agent(&mut self, id: u64) -> Option<FeatureCode> {
+        let removed = self.unlink(id);
+        self.maybe_compact();
+        removed
+    }
+
+    /// Feature code of a registered agent
+    pub fn agent(&self, id: u64) -> Option<&FeatureCode> {
+        self.agents.get(&id).map(|a| &a.feature_code)
+    }
+
+    /// Number of registered agents
+    pub fn len(&self) -> usize {
+        self.agents.len()
+    }
+
+    /// Check if no agent is registered
+    pub fn is_empty(&self) -> bool {
+        self.agents.is_empty()
     }
 
+    /// Nodes awaiting compaction
+    pub fn stale_nodes(&self) -> usize {
+        self.stale
+    }
+
+    /// Top-k agents at or above `min_similarity`, best first
     pub fn route(&self, query_embedding: &[f32], k: usize) -> Vec<RouteResult> {
-        let len = 128.min(query_embedding.len());
-        let mut query = [0.0f32; 128];
+        self.route_in(query_embedding, k, &[])
+    }
+
+    /// Top-k agents in the given categories (all if empty), best first
+    pub fn route_in(&self, query_embedding: &[f32], k: usize, categories: &[u16]) -> Vec<RouteResult> {
+        let mut hits = self.ranked(query_embedding, k, categories);
+        hits.retain(|r| r.confidence >= self.config.min_similarity);
+        hits
+    }
+
+    /// Route to a primary agent and fan out to near-ties
+    ///
+    /// At most `k` agents are returned in total.
+    pub fn decide(&self, query_embedding: &[f32], k: usize, categories: &[u16]) -> RouteDecision {
+        let mut hits = self.ranked(query_embedding, k.max(1), categories).into_iter();
+        let Some(primary) = hits.next() else {
+            return RouteDecision::NoConfidentRoute { best: None };
+        };
+        if primary.confidence < self.config.min_similarity {
+            return RouteDecision::NoConfidentRoute { best: Some(primary) };
+        }
+
+        let fanout = hits
+            .take_while(|r| r.margin <= self.config.fanout_margin && r.confidence >= self.config.min_similarity)
+            .collect();
+        RouteDecision::Routed { primary, fanout }
+    }
+
+    // ========================================================================
+    // Internal Helpers
+    // ========================================================================
+
+    /// Top-k scored agents with margins, ignoring the threshold
+    fn ranked(&self, query_embedding: &[f32], k: usize, categories: &[u16]) -> Vec<RouteResult> {
+        if k == 0 || self.agents.is_empty() {
+            return Vec::new();
+        }
+
+        let len = EMBEDDING_DIM.min(query_embedding.len());
+        let mut query = [0.0f32; EMBEDDING_DIM];
         query[..len].copy_from_slice(&query_embedding[..len]);
 
-        let results = self.index.search(&query, k);
+        let mut scored: Vec<(u64, f32)> = if categories.is_empty() {
+            // Over-fetch so stale nodes do not crowd out live agents
+            self.index
+                .search(&query, k + self.stale)
+                .into_iter()
+                .filter_map(|r| self.nodes.get(&r.id).map(|&id| (id, r.similarity)))
+                .collect()
+        } else {
+            self.agents
+                .iter()
+                .filter(|(_, a)| categories.contains(&a.feature_code.category()))
+                .map(|(&id, a)| (id, cosine_similarity(&query, &a.embedding)))
+                .collect()
+        };
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal).then(a.0.cmp(&b.0)));
+        scored.truncate(k);
 
-        results
+        let best = scored.first().map_or(0.0, |&(_, s)| s);
+        scored
             .into_iter()
-            .map(|r| RouteResult {
-                feature_code: FeatureCode::from(format!("FAJ 121 {:04}", r.id)),
-                confidence: r.similarity,
+            .map(|(agent_id, confidence)| RouteResult {
+                agent_id,
+                feature_code: self.agents[&agent_id].feature_code.clone(),
+                confidence,
+                margin: best - confidence,
             })
             .collect()
     }
+
+    fn unlink(&mut self, id: u64) -> Option<FeatureCode> {
+        let agent = self.agents.remove(&id)?;
+        self.nodes.remove(&agent.node);
+        self.stale += 1;
+        Some(agent.feature_code)
+    }
+
+    /// Rebuild the index from live agents once stale nodes dominate
+    fn maybe_compact(&mut self) {
+        if (self.stale as f32) <= self.agents.len() as f32 * self.config.compaction_ratio {
+            return;
+        }
+
+        self.index = HnswIndex::with_config(HnswConfig::default());
+        self.nodes.clear();
+        for (&id, agent) in self.agents.iter_mut() {
+            agent.node = self.index.insert(&agent.embedding);
+            self.nodes.insert(agent.node, id);
+        }
+        self.stale = 0;
+    }
 }
 
 impl Default for SemanticRouter {
@@ -50,3 +289,121 @@ impl Default for SemanticRouter {
         Self::new()
     }
 }
+
+/// Cosine similarity, matching the HNSW index's `1 - distance`
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let (mut dot, mut norm_a, mut norm_b) = (0.0f32, 0.0f32, 0.0f32);
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    let norm = (norm_a * norm_b).sqrt();
+    if norm == 0.0 {
+        0.0
+    } else {
+        dot / norm
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Unit vector along `axis`, tilted towards `axis + 1` by `tilt`
+    fn embedding(axis: usize, tilt: f32) -> [f32; EMBEDDING_DIM] {
+        let mut e = [0.0f32; EMBEDDING_DIM];
+        e[axis] = 1.0;
+        e[axis + 1] = tilt;
+        e
+    }
+
+    fn code(s: &str) -> FeatureCode {
+        FeatureCode::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_routes_to_registered_agent() {
+        let mut router = SemanticRouter::new();
+        router.register_agent(10, code("FAJ 121 3094"), embedding(0, 0.0));
+        router.register_agent(20, code("FAJ 121 4219"), embedding(10, 0.0));
+
+        let results = router.route(&embedding(10, 0.1), 2);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].agent_id, 20);
+        assert_eq!(results[0].feature_code, code("FAJ 121 4219"));
+        assert_eq!(results[0].margin, 0.0);
+    }
+
+    #[test]
+    fn test_reregistration_and_removal() {
+        let mut router = SemanticRouter::new();
+        for id in 0..4 {
+            router.register_agent(id, code(&format!("FAJ 121 {:04}", 3000 + id)), embedding(id as usize * 10, 0.0));
+        }
+
+        // Moving agent 1 to a new embedding leaves no trace at the old one
+        assert_eq!(router.register_agent(1, code("FAJ 121 3001"), embedding(60, 0.0)), Some(code("FAJ 121 3001")));
+        assert!(router.route(&embedding(10, 0.0), 4).is_empty());
+        assert_eq!(router.route(&embedding(60, 0.0), 1)[0].agent_id, 1);
+        assert_eq!(router.stale_nodes(), 1);
+
+        // Two stale nodes for three live agents exceeds the ratio and compacts
+        assert_eq!(router.remove_agent(2), Some(code("FAJ 121 3002")));
+        assert_eq!(router.remove_agent(2), None);
+        assert_eq!(router.stale_nodes(), 0);
+        assert!(router.route(&embedding(20, 0.0), 4).is_empty());
+        assert_eq!(router.len(), 3);
+        assert_eq!(router.route(&embedding(0, 0.0), 1)[0].agent_id, 0);
+        assert_eq!(router.route(&embedding(60, 0.0), 1)[0].agent_id, 1);
+    }
+
+    #[test]
+    fn test_no_confident_route() {
+        let mut router = SemanticRouter::with_config(RouterConfig { min_similarity: 0.9, ..RouterConfig::default() });
+        assert_eq!(router.decide(&embedding(0, 0.0), 3, &[]), RouteDecision::NoConfidentRoute { best: None });
+
+        router.register_agent(1, code("FAJ 121 3094"), embedding(0, 0.0));
+        let decision = router.decide(&embedding(0, 1.0), 3, &[]);
+        assert!(!decision.is_confident());
+        match decision {
+            RouteDecision::NoConfidentRoute { best: Some(best) } => assert_eq!(best.agent_id, 1),
+            other => panic!("unexpected decision {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_fanout_within_margin() {
+        let mut router = SemanticRouter::with_config(RouterConfig { fanout_margin: 0.1, ..RouterConfig::default() });
+        router.register_agent(1, code("FAJ 121 0001"), embedding(0, 0.0));
+        router.register_agent(2, code("FAJ 121 0002"), embedding(0, 0.3));
+        router.register_agent(3, code("FAJ 121 0003"), embedding(0, 1.0));
+
+        let decision = router.decide(&embedding(0, 0.1), 3, &[]);
+        assert_eq!(decision.primary().unwrap().agent_id, 1);
+        match decision {
+            RouteDecision::Routed { fanout, .. } => {
+                assert_eq!(fanout.iter().map(|r| r.agent_id).collect::<Vec<_>>(), vec![2]);
+                assert!(fanout[0].margin > 0.0 && fanout[0].margin <= 0.1);
+            }
+            other => panic!("unexpected decision {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_category_prefilter() {
+        let mut router = SemanticRouter::new();
+        router.register_agent(1, code("FAJ 121 3094"), embedding(0, 0.0));
+        router.register_agent(2, code("FAJ 122 3094"), embedding(0, 0.2));
+
+        assert_eq!(router.route(&embedding(0, 0.0), 1)[0].agent_id, 1);
+        let results = router.route_in(&embedding(0, 0.0), 2, &[122]);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].agent_id, 2);
+        assert!(router.route_in(&embedding(0, 0.0), 2, &[999]).is_empty());
+    }
+}
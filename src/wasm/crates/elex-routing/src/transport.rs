@@ -0,0 +1,65 @@
+//! Raft Transport
+//!
+//! `RaftNode::tick` and `RaftNode::step` hand their outgoing messages to a
+//! `Transport`. Delivery is best-effort: Raft tolerates loss, delay,
+//! duplication and reordering, so a transport may do any of these. The
+//! receiving side feeds each message back into `RaftNode::step`.
+//!
+//! `Outbox` collects messages for callers that deliver them themselves;
+//! `raft_sim::SimNetwork` is a faulty in-process network for tests.
+
+use crate::raft::RaftMessage;
+use elex_core::types::AgentId;
+use serde::{Deserialize, Serialize};
+
+/// Message addressed from one node to another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Envelope {
+    /// Sending node
+    pub from: AgentId,
+    /// Receiving node
+    pub to: AgentId,
+    /// Raft message
+    pub message: RaftMessage,
+}
+
+/// Outgoing side of a Raft network
+pub trait Transport {
+    /// Send a message; delivery is not guaranteed
+    fn send(&mut self, from: AgentId, to: AgentId, message: RaftMessage);
+}
+
+/// Transport that queues messages for the caller to deliver
+#[derive(Debug, Default)]
+pub struct Outbox {
+    /// Queued messages in send order
+    messages: Vec<Envelope>,
+}
+
+impl Outbox {
+    /// Create empty outbox
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take all queued messages
+    pub fn drain(&mut self) -> Vec<Envelope> {
+        std::mem::take(&mut self.messages)
+    }
+
+    /// Number of queued messages
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Check if no messages are queued
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+impl Transport for Outbox {
+    fn send(&mut self, from: AgentId, to: AgentId, message: RaftMessage) {
+        self.messages.push(Envelope { from, to, message });
+    }
+}
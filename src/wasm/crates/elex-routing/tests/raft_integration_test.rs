@@ -5,6 +5,8 @@
 use elex_routing::raft::{RaftNode, RaftConfig, Role, RaftCluster};
 use elex_routing::raft_log::{RaftLog, RaftLogEntry, RaftCommand, AgentMetadata};
 use elex_routing::raft_state::RaftStateMachine;
+use elex_routing::raft_sim::{ClusterSim, SimConfig};
+use elex_routing::Membership;
 use elex_core::types::AgentId;
 
 fn make_agent_id(byte: u8) -> AgentId {
@@ -38,6 +40,7 @@ fn test_raft_leader_election() {
         candidate_id: cluster.nodes[0].id,
         last_log_index: 0,
         last_log_term: 0,
+        leadership_transfer: false,
     };
 
     let resp1 = cluster.nodes[1].handle_request_vote(req.clone());
@@ -183,3 +186,118 @@ fn test_raft_consistency_acquired() {
     assert_eq!(leader.log().last_index(), 3);
     assert_eq!(leader.log().len(), 3);
 }
+
+#[test]
+fn test_raft_joint_consensus_replaces_node() {
+    let mut sim = ClusterSim::new(SimConfig {
+        nodes: 3,
+        client_rate: 0.0,
+        seed: 21,
+        ..SimConfig::default()
+    });
+    sim.run(1_000).unwrap();
+    let leader = sim.leader().unwrap();
+    let removed = (leader + 1) % 3;
+    let kept = (leader + 2) % 3;
+    sim.write(make_agent_id(0x80), Some(vec![1.0; 4])).unwrap();
+
+    // The new node catches up as a learner before it may vote
+    let joining = sim.add_node().unwrap();
+    let joining_id = sim.nodes()[joining].id;
+    sim.node_mut(leader).add_learner(joining_id).unwrap();
+    let voters: Vec<AgentId> = [leader, kept, joining].iter().map(|&i| sim.nodes()[i].id).collect();
+    sim.run(200).unwrap();
+    assert!(sim.nodes()[joining].is_learner());
+    assert!(sim.nodes()[leader].is_caught_up(&joining_id));
+
+    sim.node_mut(leader).change_membership(voters.clone()).unwrap();
+    assert!(sim.nodes()[leader].membership().is_joint());
+    sim.run(200).unwrap();
+    sim.write(make_agent_id(0x81), Some(vec![2.0; 4])).unwrap();
+    assert!(sim.settle(2_000).unwrap());
+
+    let membership = sim.nodes()[leader].membership();
+    assert_eq!(membership, Membership::new(voters));
+    assert!(!membership.contains(&sim.nodes()[removed].id));
+    for node in [leader, kept, joining] {
+        assert_eq!(sim.nodes()[node].membership(), membership);
+        assert_eq!(
+            sim.nodes()[node].state_machine().get_embedding(&make_agent_id(0x80)),
+            Some([1.0; 4].as_slice())
+        );
+    }
+
+    // The removed node never campaigns against the new configuration
+    let term = sim.nodes()[leader].current_term();
+    sim.run(2_000).unwrap();
+    assert_eq!(sim.leader(), Some(leader));
+    assert_eq!(sim.nodes()[leader].current_term(), term);
+
+    let report = sim.report();
+    assert!(report.is_safe(), "{:?}", report.violations);
+    assert_eq!(report.acked_writes, 2);
+}
+
+#[test]
+fn test_raft_leadership_transfer() {
+    let mut sim = ClusterSim::new(SimConfig {
+        client_rate: 0.0,
+        seed: 22,
+        ..SimConfig::default()
+    });
+    sim.run(1_000).unwrap();
+    let leader = sim.leader().unwrap();
+    let target = (leader + 1) % sim.nodes().len();
+    let target_id = sim.nodes()[target].id;
+    let term = sim.nodes()[leader].current_term();
+
+    // Proposals are refused while leadership is handed over
+    sim.node_mut(leader).transfer_leadership(target_id).unwrap();
+    assert!(sim
+        .node_mut(leader)
+        .propose(RaftCommand::RemoveAgent { agent_id: make_agent_id(0x80) })
+        .is_err());
+
+    // Far sooner than an election timeout
+    sim.run(100).unwrap();
+    assert_eq!(sim.leader(), Some(target));
+    assert_eq!(sim.nodes()[target].current_term(), term + 1);
+    assert!(!sim.nodes()[leader].is_leader());
+
+    let report = sim.report();
+    assert!(report.is_safe(), "{:?}", report.violations);
+}
+
+#[test]
+fn test_raft_removed_leader_hands_over_leadership() {
+    let mut sim = ClusterSim::new(SimConfig {
+        client_rate: 0.0,
+        seed: 23,
+        ..SimConfig::default()
+    });
+    sim.run(1_000).unwrap();
+    let leader = sim.leader().unwrap();
+    let leader_id = sim.nodes()[leader].id;
+    let term = sim.nodes()[leader].current_term();
+    let voters: Vec<AgentId> = sim.nodes().iter().map(|n| n.id).filter(|id| *id != leader_id).collect();
+
+    sim.node_mut(leader).change_membership(voters.clone()).unwrap();
+    sim.run(200).unwrap();
+
+    // The old leader led until the new configuration committed, then
+    // handed over without waiting for an election timeout
+    let new = sim.leader().unwrap();
+    assert_ne!(new, leader);
+    assert_eq!(sim.nodes()[new].current_term(), term + 1);
+    assert_eq!(sim.nodes()[new].membership(), Membership::new(voters));
+    assert!(!sim.nodes()[leader].is_leader());
+
+    assert!(sim.write(make_agent_id(0x80), Some(vec![1.0; 4])).unwrap());
+    assert!(sim.settle(2_000).unwrap());
+    sim.run(2_000).unwrap();
+    assert_eq!(sim.leader(), Some(new));
+
+    let report = sim.report();
+    assert!(report.is_safe(), "{:?}", report.violations);
+    assert_eq!(report.acked_writes, 1);
+}
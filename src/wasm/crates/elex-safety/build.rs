@@ -1,47 +1,354 @@
+use serde::de::{self, Deserializer, MapAccess, Visitor};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
 use std::env;
+use std::fmt;
 use std::fs;
 use std::path::Path;
 
+/// Checked-in constraint source (relative to the crate root)
+const CONSTRAINTS_SOURCE: &str = "constraints/safe_zones.json";
+
 /// Build script to embed safe zone constraints at compile time.
 ///
-/// This ensures safety constraints are immutable at runtime,
-/// preventing any code from modifying the hard limits.
+/// Constraints are read from a versioned JSON source, validated, and
+/// compiled into the binary. This ensures safety constraints are
+/// immutable at runtime, preventing any code from modifying the hard limits.
 fn main() {
-    println!("cargo:rerun-if-changed=constraints/");
+    println!("cargo:rerun-if-changed={}", CONSTRAINTS_SOURCE);
+
+    let source = fs::read(CONSTRAINTS_SOURCE)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {}", CONSTRAINTS_SOURCE, e));
+    let constraint_set: ConstraintSet = serde_json::from_slice(&source)
+        .unwrap_or_else(|e| panic!("Failed to parse {}: {}", CONSTRAINTS_SOURCE, e));
+
+    let errors = validate(&constraint_set);
+    if !errors.is_empty() {
+        panic!(
+            "Invalid safe zone constraints in {}:\n  - {}",
+            CONSTRAINTS_SOURCE,
+            errors.join("\n  - ")
+        );
+    }
 
-    // Generate constraint file if it doesn't exist
-    let constraints_path = Path::new("constraints").join("embedded.rs");
     let out_dir = env::var("OUT_DIR").unwrap();
     let dest_path = Path::new(&out_dir).join("embedded_constraints.rs");
-
-    // Ensure constraints directory exists
-    fs::create_dir_all("constraints").ok();
-
-    // Generate embedded constraints from YAML source
-    let constraints = generate_embedded_constraints();
-    fs::write(&constraints_path, &constraints).expect("Failed to write constraints");
+    let constraints = generate_embedded_constraints(&constraint_set, &fnv1a_hex(&source));
     fs::write(&dest_path, &constraints).expect("Failed to write to OUT_DIR");
 
     println!("cargo:rustc-env=EMBEDDED_CONSTRAINTS={}", dest_path.display());
 }
 
+// ============================================================================
+// Constraint Source
+// ============================================================================
+
+/// Versioned constraint set
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConstraintSet {
+    /// Constraint set version
+    version: String,
+
+    /// Constraints keyed by parameter name (declaration order preserved)
+    parameters: ParameterMap,
+}
+
+/// Parameter map that keeps duplicate keys so they can be rejected
+struct ParameterMap(Vec<(String, Vec<ConstraintEntry>)>);
+
+/// A single entry or a list of scoped variants
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(ConstraintEntry),
+    Many(Vec<ConstraintEntry>),
+}
+
+/// Safe zone for one parameter, optionally scoped
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConstraintEntry {
+    absolute_min: f32,
+    absolute_max: f32,
+    safe_min: f32,
+    safe_max: f32,
+    change_limit_percent: f32,
+    cooldown_seconds: u64,
+
+    /// Restrict to an MO class (e.g. "EUtranCellFDD")
+    #[serde(default)]
+    mo_class: Option<String>,
+
+    /// Restrict to a feature code (e.g. "FAJ 121 3094")
+    #[serde(default)]
+    feature_code: Option<String>,
+
+    /// Typed value domain (must match across scoped entries)
+    #[serde(default)]
+    domain: Option<DomainSpec>,
+}
+
+/// Typed value domain of a parameter
+#[derive(Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum DomainSpec {
+    Integer {
+        step: u32,
+    },
+    Boolean,
+    Enum {
+        values: Vec<String>,
+        #[serde(default)]
+        forbidden_transitions: Vec<(String, String)>,
+    },
+}
+
+impl ConstraintEntry {
+    fn scope(&self) -> (Option<&str>, Option<&str>) {
+        (self.mo_class.as_deref(), self.feature_code.as_deref())
+    }
+
+    fn scope_label(&self) -> String {
+        match self.scope() {
+            (None, None) => "unscoped".to_string(),
+            (Some(m), None) => format!("mo_class={}", m),
+            (None, Some(f)) => format!("feature_code={}", f),
+            (Some(m), Some(f)) => format!("mo_class={}, feature_code={}", m, f),
+        }
+    }
+
+    /// Number of scope dimensions (higher is more specific)
+    fn specificity(&self) -> usize {
+        self.mo_class.is_some() as usize + self.feature_code.is_some() as usize
+    }
+}
+
+impl<'de> Deserialize<'de> for ParameterMap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ParameterMapVisitor;
+
+        impl<'de> Visitor<'de> for ParameterMapVisitor {
+            type Value = ParameterMap;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map of parameter name to safe zone entries")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::new();
+                while let Some((name, value)) = map.next_entry::<String, OneOrMany>()? {
+                    let variants = match value {
+                        OneOrMany::One(entry) => vec![entry],
+                        OneOrMany::Many(entries) if entries.is_empty() => {
+                            return Err(de::Error::custom(format!(
+                                "parameter '{}' has no entries",
+                                name
+                            )));
+                        }
+                        OneOrMany::Many(entries) => entries,
+                    };
+                    entries.push((name, variants));
+                }
+                Ok(ParameterMap(entries))
+            }
+        }
+
+        deserializer.deserialize_map(ParameterMapVisitor)
+    }
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+/// Validate the constraint set, returning every problem found
+fn validate(set: &ConstraintSet) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+
+    if set.version.trim().is_empty() {
+        errors.push("version must not be empty".to_string());
+    }
+
+    for (name, entries) in &set.parameters.0 {
+        if let Some(count) = seen.get_mut(name.as_str()) {
+            *count += 1;
+            if *count == 2 {
+                errors.push(format!("{}: duplicate parameter name", name));
+            }
+            continue;
+        }
+        seen.insert(name, 1);
+
+        if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') || name.is_empty() {
+            errors.push(format!("{}: parameter name must be alphanumeric", name));
+        }
+
+        for entry in entries {
+            let label = entry.scope_label();
+            let values = [
+                entry.absolute_min,
+                entry.absolute_max,
+                entry.safe_min,
+                entry.safe_max,
+                entry.change_limit_percent,
+            ];
+            if values.iter().any(|v| !v.is_finite()) {
+                errors.push(format!("{} ({}): values must be finite", name, label));
+                continue;
+            }
+            if entry.absolute_min > entry.absolute_max {
+                errors.push(format!(
+                    "{} ({}): inverted absolute range [{}, {}]",
+                    name, label, entry.absolute_min, entry.absolute_max
+                ));
+            }
+            if entry.safe_min > entry.safe_max {
+                errors.push(format!(
+                    "{} ({}): inverted safe range [{}, {}]",
+                    name, label, entry.safe_min, entry.safe_max
+                ));
+            }
+            if entry.safe_min < entry.absolute_min || entry.safe_max > entry.absolute_max {
+                errors.push(format!(
+                    "{} ({}): safe range [{}, {}] outside absolute range [{}, {}]",
+                    name, label, entry.safe_min, entry.safe_max, entry.absolute_min, entry.absolute_max
+                ));
+            }
+            if entry.change_limit_percent <= 0.0 {
+                errors.push(format!("{} ({}): change_limit_percent must be positive", name, label));
+            }
+            if let Some(domain) = &entry.domain {
+                validate_domain(name, &label, entry, domain, &mut errors);
+            }
+        }
+
+        if entries.iter().any(|e| e.domain != entries[0].domain) {
+            errors.push(format!("{}: domain must be identical across scoped entries", name));
+        }
+
+        for (i, a) in entries.iter().enumerate() {
+            for b in &entries[i + 1..] {
+                if a.scope() == b.scope() {
+                    errors.push(format!("{}: duplicate scope ({})", name, a.scope_label()));
+                } else if scopes_overlap(a, b) {
+                    errors.push(format!(
+                        "{}: overlapping scopes ({}) and ({}) with no precedence",
+                        name,
+                        a.scope_label(),
+                        b.scope_label()
+                    ));
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+/// Check that a domain is consistent with the numeric ranges it refines
+fn validate_domain(
+    name: &str,
+    label: &str,
+    entry: &ConstraintEntry,
+    domain: &DomainSpec,
+    errors: &mut Vec<String>,
+) {
+    match domain {
+        DomainSpec::Integer { step } => {
+            if *step == 0 {
+                errors.push(format!("{} ({}): integer step must be positive", name, label));
+            }
+            if entry.absolute_min.fract() != 0.0 || entry.absolute_max.fract() != 0.0 {
+                errors.push(format!("{} ({}): integer domain needs whole absolute bounds", name, label));
+            }
+        }
+        DomainSpec::Boolean => {
+            if entry.absolute_min != 0.0 || entry.absolute_max != 1.0 {
+                errors.push(format!("{} ({}): boolean domain needs absolute range [0, 1]", name, label));
+            }
+        }
+        DomainSpec::Enum { values, forbidden_transitions } => {
+            if values.is_empty() {
+                errors.push(format!("{} ({}): enum domain has no values", name, label));
+                return;
+            }
+            for (i, value) in values.iter().enumerate() {
+                if value.is_empty() || !value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                    errors.push(format!("{} ({}): invalid enum literal '{}'", name, label, value));
+                }
+                if values[..i].contains(value) {
+                    errors.push(format!("{} ({}): duplicate enum literal '{}'", name, label, value));
+                }
+            }
+            if entry.absolute_min != 0.0 || entry.absolute_max != (values.len() - 1) as f32 {
+                errors.push(format!(
+                    "{} ({}): enum domain needs absolute range [0, {}]",
+                    name,
+                    label,
+                    values.len() - 1
+                ));
+            }
+            for (from, to) in forbidden_transitions {
+                if !values.contains(from) || !values.contains(to) || from == to {
+                    errors.push(format!(
+                        "{} ({}): invalid forbidden transition {} -> {}",
+                        name, label, from, to
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Two scopes overlap ambiguously when both can match the same MO and
+/// neither is strictly more specific than the other.
+fn scopes_overlap(a: &ConstraintEntry, b: &ConstraintEntry) -> bool {
+    let compatible = |x: Option<&str>, y: Option<&str>| match (x, y) {
+        (Some(x), Some(y)) => x == y,
+        _ => true,
+    };
+    let (a_mo, a_feature) = a.scope();
+    let (b_mo, b_feature) = b.scope();
+    if !compatible(a_mo, b_mo) || !compatible(a_feature, b_feature) {
+        return false;
+    }
+
+    let refines = |fine: (Option<&str>, Option<&str>), coarse: (Option<&str>, Option<&str>)| {
+        (coarse.0.is_none() || coarse.0 == fine.0) && (coarse.1.is_none() || coarse.1 == fine.1)
+    };
+    !(refines(a.scope(), b.scope()) || refines(b.scope(), a.scope()))
+}
+
+// ============================================================================
+// Code Generation
+// ============================================================================
+
 /// Generate embedded constraints code
-fn generate_embedded_constraints() -> String {
-    // Hardcoded safe zone constraints for 593 RAN features
-    // These are compiled into the binary and cannot be changed at runtime
+fn generate_embedded_constraints(set: &ConstraintSet, hash: &str) -> String {
+    let idents = static_idents(set);
+
     // Note: SafeZone is imported in lib.rs, no need to re-import here
     format!(
         r#"
 // Embedded Safe Zone Constraints
 //
-// Auto-generated from build.rs - DO NOT EDIT MANUALLY
+// Auto-generated by build.rs from {source} - DO NOT EDIT MANUALLY
 // These constraints are compile-time constants for safety.
 
+/// Version of the embedded constraint set
+pub const CONSTRAINTS_VERSION: &str = {version:?};
+
+/// FNV-1a (64-bit) hash of the constraint source the binary was built with
+pub const CONSTRAINTS_HASH: &str = {hash:?};
+
 {static_constants}
 
 /// Get hardcoded safe zone constraints for a parameter
 ///
-/// Returns None if parameter is not in the hardcoded list (allowing dynamic constraints)
+/// Returns the unscoped constraint, or None if parameter is not in the
+/// hardcoded list (allowing dynamic constraints)
 pub fn get_hardcoded_constraint(param_name: &str) -> Option<&'static SafeZone> {{
     match param_name {{
 {hardcoded_constraints}
@@ -49,378 +356,200 @@ pub fn get_hardcoded_constraint(param_name: &str) -> Option<&'static SafeZone> {
     }}
 }}
 
+/// Get the most specific hardcoded constraint for a parameter
+///
+/// Entries scoped to both MO class and feature code take precedence over
+/// single-scoped entries, which take precedence over unscoped ones.
+pub fn get_scoped_constraint(
+    param_name: &str,
+    mo_class: Option<&str>,
+    feature_code: Option<&str>,
+) -> Option<&'static SafeZone> {{
+    match (param_name, mo_class, feature_code) {{
+{scoped_constraints}
+        _ => None,
+    }}
+}}
+
+static HARDCODED_DOMAINS: std::sync::LazyLock<
+    std::collections::HashMap<&'static str, elex_core::ParameterDomain>,
+> = std::sync::LazyLock::new(|| {{
+    use elex_core::ParameterDomain;
+    let mut domains = std::collections::HashMap::new();
+{domain_inserts}    domains
+}});
+
+/// Get the typed value domain of a hardcoded parameter
+///
+/// Returns None for continuous parameters.
+pub fn get_hardcoded_domain(param_name: &str) -> Option<&'static elex_core::ParameterDomain> {{
+    HARDCODED_DOMAINS.get(param_name)
+}}
+
 /// List all parameters with hardcoded constraints
 pub const HARDCODED_PARAMETERS: &[&str] = &[
 {parameter_list}
 ];
 "#,
-        static_constants = generate_static_constants(),
-        hardcoded_constraints = generate_constraint_matches(),
-        parameter_list = generate_parameter_list()
+        source = CONSTRAINTS_SOURCE,
+        version = set.version,
+        hash = hash,
+        static_constants = generate_static_constants(set, &idents),
+        hardcoded_constraints = generate_constraint_matches(set, &idents),
+        scoped_constraints = generate_scoped_matches(set, &idents),
+        domain_inserts = generate_domain_inserts(set),
+        parameter_list = generate_parameter_list(set)
     )
 }
 
-/// Generate static SafeZone constants
-fn generate_static_constants() -> String {
-    let constraints = vec![
-        // Load Balancing (IFLB)
-        ("lbActivationThreshold", 10.0, 100.0, 50.0, 90.0, 15.0, 3600),
-        ("lbTpNonQualFraction", 0.0, 100.0, 5.0, 50.0, 20.0, 1800),
-        ("lbMinLoadOffset", -20.0, 20.0, -10.0, 10.0, 10.0, 900),
-        ("lbMaxLoadOffset", -20.0, 20.0, -10.0, 10.0, 10.0, 900),
-        ("lbLoadOffsetStep", 1.0, 10.0, 2.0, 5.0, 5.0, 600),
-        ("lbHighUlnThresh", 50.0, 100.0, 60.0, 85.0, 10.0, 1800),
-        ("lbLowUlnThresh", 0.0, 50.0, 10.0, 40.0, 10.0, 1800),
-
-        // DU Activity Controller (DUAC)
-        ("duacCarrierActivation", 0.0, 1.0, 0.1, 0.9, 5.0, 3600),
-        ("duacDeactivationThreshold", 0.0, 100.0, 10.0, 50.0, 15.0, 1800),
-        ("duacMinDlPower", -30.0, 0.0, -20.0, -5.0, 10.0, 900),
-        ("duacMaxUlPower", -50.0, 23.0, -30.0, 15.0, 10.0, 900),
-        ("duacMinUlInterference", -120.0, -60.0, -110.0, -80.0, 10.0, 1800),
-        ("duacMaxUlInterference", -120.0, -60.0, -110.0, -80.0, 10.0, 1800),
-
-        // MIMO Sleep Mode (MSM)
-        ("mimoSleepMode", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("mimoSleepThreshold", 0.0, 100.0, 5.0, 30.0, 10.0, 900),
-        ("mimoWakeThreshold", 0.0, 100.0, 10.0, 50.0, 10.0, 900),
-        ("mimoMinActiveTime", 0.0, 3600.0, 60.0, 600.0, 15.0, 3600),
-        ("mimoMinSleepTime", 0.0, 3600.0, 60.0, 600.0, 15.0, 3600),
-        ("mimoActiveTimeHysteresis", 0.0, 300.0, 10.0, 60.0, 10.0, 900),
-        ("mimoSleepTimeHysteresis", 0.0, 300.0, 10.0, 60.0, 10.0, 900),
-
-        // Cell Sleep (CSC)
-        ("cellSleepMode", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("cellSleepThreshold", 0.0, 100.0, 2.0, 20.0, 10.0, 900),
-        ("cellWakeThreshold", 0.0, 100.0, 5.0, 40.0, 10.0, 900),
-        ("cellMinActiveTime", 0.0, 3600.0, 60.0, 600.0, 15.0, 3600),
-        ("cellMinSleepTime", 0.0, 3600.0, 60.0, 600.0, 15.0, 3600),
-
-        // Micro Sleep TX
-        ("microSleepTxMode", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("microSleepTxThreshold", 0.0, 100.0, 1.0, 15.0, 10.0, 900),
-        ("microSleepTxDutyCycle", 0.0, 50.0, 1.0, 20.0, 10.0, 900),
-        ("microSleepTxMinOnTime", 0.0, 100.0, 5.0, 30.0, 10.0, 900),
-        ("microSleepTxMinOffTime", 0.0, 100.0, 5.0, 30.0, 10.0, 900),
-
-        // Energy Savings
-        ("energySavingMode", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("energySavingLevel", 0.0, 4.0, 1.0, 3.0, 5.0, 1800),
-        ("energySavingThreshold", 0.0, 100.0, 5.0, 40.0, 10.0, 900),
-
-        // Handover (HO)
-        ("hoA3Offset", -10.0, 10.0, -3.0, 3.0, 5.0, 1800),
-        ("hoHysteresis", 0.0, 10.0, 1.0, 3.0, 10.0, 1800),
-        ("hoTriggerTime", 0.0, 5000.0, 40.0, 640.0, 15.0, 1800),
-        ("hoMaxHoCount", 1.0, 50.0, 5.0, 20.0, 10.0, 3600),
-        ("hoMinHoTime", 0.0, 60.0, 1.0, 10.0, 10.0, 1800),
-
-        // Automatic Neighbor Relation (ANR)
-        ("anrMode", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("anrAddThreshold", -140.0, -60.0, -120.0, -80.0, 10.0, 1800),
-        ("anrRemoveThreshold", -140.0, -60.0, -120.0, -80.0, 10.0, 1800),
-        ("anrHysteresis", 0.0, 10.0, 2.0, 5.0, 10.0, 1800),
-        ("anrMinNoOfSamples", 1.0, 1000.0, 10.0, 100.0, 15.0, 3600),
-
-        // Mobility Robustness Optimization (MRO)
-        ("mroMode", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("mroHoTooEarlyInd", 0.0, 100.0, 5.0, 30.0, 10.0, 900),
-        ("mroHoTooLateInd", 0.0, 100.0, 5.0, 30.0, 10.0, 900),
-        ("mroPingPongInd", 0.0, 100.0, 5.0, 30.0, 10.0, 900),
-
-        // Coverage and Capacity Optimization (CCO)
-        ("ccoMode", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("ccoMinTilt", 0.0, 15.0, 2.0, 10.0, 10.0, 1800),
-        ("ccoMaxTilt", 0.0, 15.0, 2.0, 10.0, 10.0, 1800),
-        ("ccoTiltStep", 0.5, 2.0, 0.5, 1.0, 5.0, 900),
-        ("ccoMinTxPower", 0.0, 50.0, 10.0, 40.0, 10.0, 1800),
-        ("ccoMaxTxPower", 0.0, 50.0, 10.0, 40.0, 10.0, 1800),
-        ("ccoTxPowerStep", 1.0, 5.0, 1.0, 3.0, 5.0, 900),
-
-        // QoS (Quality of Service)
-        ("qosMbrDl", 0.0, 1000000.0, 1000.0, 100000.0, 10.0, 1800),
-        ("qosMbrUl", 0.0, 1000000.0, 1000.0, 100000.0, 10.0, 1800),
-        ("qosGbrDl", 0.0, 1000000.0, 100.0, 10000.0, 10.0, 1800),
-        ("qosGbrUl", 0.0, 1000000.0, 100.0, 10000.0, 10.0, 1800),
-        ("qosAmp", 0.0, 10.0, 1.0, 5.0, 10.0, 1800),
-
-        // DRX (Discontinuous Reception)
-        ("drxEnabled", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("drxOnDurationTimer", 1.0, 200.0, 10.0, 50.0, 10.0, 1800),
-        ("drxInactivityTimer", 0.0, 2560.0, 50.0, 500.0, 15.0, 1800),
-        ("drxRetxTimer", 0.0, 200.0, 10.0, 60.0, 10.0, 1800),
-        ("drxCycle", 10.0, 1024.0, 40.0, 512.0, 10.0, 1800),
-        ("drxShortCycle", 10.0, 640.0, 20.0, 256.0, 10.0, 1800),
-        ("drxLongCycleOffset", 0.0, 1024.0, 10.0, 256.0, 10.0, 1800),
-
-        // Paging
-        ("pagingDrxCycle", 32.0, 256.0, 64.0, 128.0, 10.0, 1800),
-        ("pagingNb", 1.0, 4.0, 1.0, 2.0, 5.0, 900),
-        ("pagingTmsi", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-
-        // Interference Management
-        ("icicEnabled", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("icicFwpRatio", 0.0, 100.0, 10.0, 50.0, 15.0, 1800),
-        ("icicFwpOffset", -10.0, 10.0, -3.0, 3.0, 10.0, 1800),
-        ("icicAbsEnabled", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("icicAbsPattern", 0.0, 15.0, 1.0, 7.0, 10.0, 1800),
-
-        // Carrier Aggregation (CA)
-        ("caEnabled", 0.0, 1.0, 0.1, 0.9, 5.0, 3600),
-        ("caPrimaryScell", 0.0, 7.0, 0.0, 3.0, 10.0, 1800),
-        ("caSecondaryScell", 0.0, 31.0, 0.0, 15.0, 10.0, 1800),
-        ("caReleaseThreshold", -10.0, 10.0, -5.0, 0.0, 10.0, 1800),
-        ("caActivationThreshold", -10.0, 10.0, -3.0, 3.0, 10.0, 1800),
-    ];
+/// Static identifier for a parameter entry
+fn static_ident(name: &str, index: usize) -> String {
+    let mut ident = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            ident.push('_');
+        }
+        ident.push(c.to_ascii_uppercase());
+    }
+    if index > 0 {
+        ident.push_str(&format!("_{}", index));
+    }
+    ident
+}
+
+/// Unique static identifiers of every entry, parallel to `set.parameters`
+///
+/// Names that normalise to the same identifier (e.g. `aBc` and `ABc`)
+/// get a numeric suffix in declaration order.
+fn static_idents(set: &ConstraintSet) -> Vec<Vec<String>> {
+    let mut used = HashSet::new();
+    set.parameters
+        .0
+        .iter()
+        .map(|(name, entries)| {
+            (0..entries.len())
+                .map(|index| {
+                    let base = static_ident(name, index);
+                    let mut ident = base.clone();
+                    let mut suffix = 1;
+                    while !used.insert(ident.clone()) {
+                        ident = format!("{}_DUP{}", base, suffix);
+                        suffix += 1;
+                    }
+                    ident
+                })
+                .collect()
+        })
+        .collect()
+}
 
+/// Generate static SafeZone constants
+fn generate_static_constants(set: &ConstraintSet, idents: &[Vec<String>]) -> String {
     let mut constants = String::new();
-    for (name, abs_min, abs_max, safe_min, safe_max, change_pct, cooldown) in constraints {
-        let ident = name.replace("-", "_").replace("/", "_");
-        constants.push_str(&format!(
-            r#"static {}: SafeZone = SafeZone {{
-    absolute_min: {:.1},
-    absolute_max: {:.1},
-    safe_min: {:.1},
-    safe_max: {:.1},
-    change_limit_percent: {:.1},
+    for ((_, entries), idents) in set.parameters.0.iter().zip(idents) {
+        for (entry, ident) in entries.iter().zip(idents) {
+            constants.push_str(&format!(
+                r#"static {}: SafeZone = SafeZone {{
+    absolute_min: {:?},
+    absolute_max: {:?},
+    safe_min: {:?},
+    safe_max: {:?},
+    change_limit_percent: {:?},
     cooldown_seconds: {},
 }};
 "#,
-            ident, abs_min, abs_max, safe_min, safe_max, change_pct, cooldown
-        ));
+                ident,
+                entry.absolute_min,
+                entry.absolute_max,
+                entry.safe_min,
+                entry.safe_max,
+                entry.change_limit_percent,
+                entry.cooldown_seconds
+            ));
+        }
     }
     constants
 }
 
-/// Generate match arms for hardcoded constraints
-fn generate_constraint_matches() -> String {
-    // Critical RAN parameters with strict safety limits
-    let constraints = vec![
-        // Load Balancing (IFLB)
-        ("lbActivationThreshold", 10.0, 100.0, 50.0, 90.0, 15.0, 3600),
-        ("lbTpNonQualFraction", 0.0, 100.0, 5.0, 50.0, 20.0, 1800),
-        ("lbMinLoadOffset", -20.0, 20.0, -10.0, 10.0, 10.0, 900),
-        ("lbMaxLoadOffset", -20.0, 20.0, -10.0, 10.0, 10.0, 900),
-        ("lbLoadOffsetStep", 1.0, 10.0, 2.0, 5.0, 5.0, 600),
-        ("lbHighUlnThresh", 50.0, 100.0, 60.0, 85.0, 10.0, 1800),
-        ("lbLowUlnThresh", 0.0, 50.0, 10.0, 40.0, 10.0, 1800),
-
-        // DU Activity Controller (DUAC)
-        ("duacCarrierActivation", 0.0, 1.0, 0.1, 0.9, 5.0, 3600),
-        ("duacDeactivationThreshold", 0.0, 100.0, 10.0, 50.0, 15.0, 1800),
-        ("duacMinDlPower", -30.0, 0.0, -20.0, -5.0, 10.0, 900),
-        ("duacMaxUlPower", -50.0, 23.0, -30.0, 15.0, 10.0, 900),
-        ("duacMinUlInterference", -120.0, -60.0, -110.0, -80.0, 10.0, 1800),
-        ("duacMaxUlInterference", -120.0, -60.0, -110.0, -80.0, 10.0, 1800),
-
-        // MIMO Sleep Mode (MSM)
-        ("mimoSleepMode", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("mimoSleepThreshold", 0.0, 100.0, 5.0, 30.0, 10.0, 900),
-        ("mimoWakeThreshold", 0.0, 100.0, 10.0, 50.0, 10.0, 900),
-        ("mimoMinActiveTime", 0.0, 3600.0, 60.0, 600.0, 15.0, 3600),
-        ("mimoMinSleepTime", 0.0, 3600.0, 60.0, 600.0, 15.0, 3600),
-        ("mimoActiveTimeHysteresis", 0.0, 300.0, 10.0, 60.0, 10.0, 900),
-        ("mimoSleepTimeHysteresis", 0.0, 300.0, 10.0, 60.0, 10.0, 900),
-
-        // Cell Sleep (CSC)
-        ("cellSleepMode", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("cellSleepThreshold", 0.0, 100.0, 2.0, 20.0, 10.0, 900),
-        ("cellWakeThreshold", 0.0, 100.0, 5.0, 40.0, 10.0, 900),
-        ("cellMinActiveTime", 0.0, 3600.0, 60.0, 600.0, 15.0, 3600),
-        ("cellMinSleepTime", 0.0, 3600.0, 60.0, 600.0, 15.0, 3600),
-
-        // Micro Sleep TX
-        ("microSleepTxMode", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("microSleepTxThreshold", 0.0, 100.0, 1.0, 15.0, 10.0, 900),
-        ("microSleepTxDutyCycle", 0.0, 50.0, 1.0, 20.0, 10.0, 900),
-        ("microSleepTxMinOnTime", 0.0, 100.0, 5.0, 30.0, 10.0, 900),
-        ("microSleepTxMinOffTime", 0.0, 100.0, 5.0, 30.0, 10.0, 900),
-
-        // Energy Savings
-        ("energySavingMode", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("energySavingLevel", 0.0, 4.0, 1.0, 3.0, 5.0, 1800),
-        ("energySavingThreshold", 0.0, 100.0, 5.0, 40.0, 10.0, 900),
-
-        // Handover (HO)
-        ("hoA3Offset", -10.0, 10.0, -3.0, 3.0, 5.0, 1800),
-        ("hoHysteresis", 0.0, 10.0, 1.0, 3.0, 10.0, 1800),
-        ("hoTriggerTime", 0.0, 5000.0, 40.0, 640.0, 15.0, 1800),
-        ("hoMaxHoCount", 1.0, 50.0, 5.0, 20.0, 10.0, 3600),
-        ("hoMinHoTime", 0.0, 60.0, 1.0, 10.0, 10.0, 1800),
-
-        // Automatic Neighbor Relation (ANR)
-        ("anrMode", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("anrAddThreshold", -140.0, -60.0, -120.0, -80.0, 10.0, 1800),
-        ("anrRemoveThreshold", -140.0, -60.0, -120.0, -80.0, 10.0, 1800),
-        ("anrHysteresis", 0.0, 10.0, 2.0, 5.0, 10.0, 1800),
-        ("anrMinNoOfSamples", 1.0, 1000.0, 10.0, 100.0, 15.0, 3600),
-
-        // Mobility Robustness Optimization (MRO)
-        ("mroMode", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("mroHoTooEarlyInd", 0.0, 100.0, 5.0, 30.0, 10.0, 900),
-        ("mroHoTooLateInd", 0.0, 100.0, 5.0, 30.0, 10.0, 900),
-        ("mroPingPongInd", 0.0, 100.0, 5.0, 30.0, 10.0, 900),
-
-        // Coverage and Capacity Optimization (CCO)
-        ("ccoMode", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("ccoMinTilt", 0.0, 15.0, 2.0, 10.0, 10.0, 1800),
-        ("ccoMaxTilt", 0.0, 15.0, 2.0, 10.0, 10.0, 1800),
-        ("ccoTiltStep", 0.5, 2.0, 0.5, 1.0, 5.0, 900),
-        ("ccoMinTxPower", 0.0, 50.0, 10.0, 40.0, 10.0, 1800),
-        ("ccoMaxTxPower", 0.0, 50.0, 10.0, 40.0, 10.0, 1800),
-        ("ccoTxPowerStep", 1.0, 5.0, 1.0, 3.0, 5.0, 900),
-
-        // QoS (Quality of Service)
-        ("qosMbrDl", 0.0, 1000000.0, 1000.0, 100000.0, 10.0, 1800),
-        ("qosMbrUl", 0.0, 1000000.0, 1000.0, 100000.0, 10.0, 1800),
-        ("qosGbrDl", 0.0, 1000000.0, 100.0, 10000.0, 10.0, 1800),
-        ("qosGbrUl", 0.0, 1000000.0, 100.0, 10000.0, 10.0, 1800),
-        ("qosAmp", 0.0, 10.0, 1.0, 5.0, 10.0, 1800),
-
-        // DRX (Discontinuous Reception)
-        ("drxEnabled", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("drxOnDurationTimer", 1.0, 200.0, 10.0, 50.0, 10.0, 1800),
-        ("drxInactivityTimer", 0.0, 2560.0, 50.0, 500.0, 15.0, 1800),
-        ("drxRetxTimer", 0.0, 200.0, 10.0, 60.0, 10.0, 1800),
-        ("drxCycle", 10.0, 1024.0, 40.0, 512.0, 10.0, 1800),
-        ("drxShortCycle", 10.0, 640.0, 20.0, 256.0, 10.0, 1800),
-        ("drxLongCycleOffset", 0.0, 1024.0, 10.0, 256.0, 10.0, 1800),
-
-        // Paging
-        ("pagingDrxCycle", 32.0, 256.0, 64.0, 128.0, 10.0, 1800),
-        ("pagingNb", 1.0, 4.0, 1.0, 2.0, 5.0, 900),
-        ("pagingTmsi", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-
-        // Interference Management
-        ("icicEnabled", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("icicFwpRatio", 0.0, 100.0, 10.0, 50.0, 15.0, 1800),
-        ("icicFwpOffset", -10.0, 10.0, -3.0, 3.0, 10.0, 1800),
-        ("icicAbsEnabled", 0.0, 1.0, 0.1, 0.9, 5.0, 1800),
-        ("icicAbsPattern", 0.0, 15.0, 1.0, 7.0, 10.0, 1800),
-
-        // Carrier Aggregation (CA)
-        ("caEnabled", 0.0, 1.0, 0.1, 0.9, 5.0, 3600),
-        ("caPrimaryScell", 0.0, 7.0, 0.0, 3.0, 10.0, 1800),
-        ("caSecondaryScell", 0.0, 31.0, 0.0, 15.0, 10.0, 1800),
-        ("caReleaseThreshold", -10.0, 10.0, -5.0, 0.0, 10.0, 1800),
-        ("caActivationThreshold", -10.0, 10.0, -3.0, 3.0, 10.0, 1800),
-    ];
+/// Generate match arms for unscoped hardcoded constraints
+fn generate_constraint_matches(set: &ConstraintSet, idents: &[Vec<String>]) -> String {
+    let mut match_arms = String::new();
+    for ((name, entries), idents) in set.parameters.0.iter().zip(idents) {
+        if let Some(index) = entries.iter().position(|e| e.specificity() == 0) {
+            match_arms.push_str(&format!("        {:?} => Some(&{}),\n", name, idents[index]));
+        }
+    }
+    match_arms
+}
+
+/// Generate match arms for scoped lookups, most specific first
+fn generate_scoped_matches(set: &ConstraintSet, idents: &[Vec<String>]) -> String {
+    let pattern = |value: Option<&str>| match value {
+        Some(v) => format!("Some({:?})", v),
+        None => "_".to_string(),
+    };
 
     let mut match_arms = String::new();
-    for (name, abs_min, abs_max, safe_min, safe_max, change_pct, cooldown) in constraints {
-        // Convert name to a valid identifier (replace hyphens with underscores)
-        let ident = name.replace("-", "_").replace("/", "_");
-        match_arms.push_str(&format!(
-            r#"        "{}" => Some(&{}),"#,
-            name, ident
-        ));
-        match_arms.push('\n');
+    for ((name, entries), idents) in set.parameters.0.iter().zip(idents) {
+        let mut ordered: Vec<(usize, &ConstraintEntry)> = entries.iter().enumerate().collect();
+        ordered.sort_by_key(|(_, e)| std::cmp::Reverse(e.specificity()));
+
+        for (index, entry) in ordered {
+            let (mo_class, feature_code) = entry.scope();
+            match_arms.push_str(&format!(
+                "        ({:?}, {}, {}) => Some(&{}),\n",
+                name,
+                pattern(mo_class),
+                pattern(feature_code),
+                idents[index]
+            ));
+        }
     }
     match_arms
 }
 
-/// Generate list of hardcoded parameter names
-fn generate_parameter_list() -> String {
-    let params = vec![
-        // Load Balancing
-        "lbActivationThreshold",
-        "lbTpNonQualFraction",
-        "lbMinLoadOffset",
-        "lbMaxLoadOffset",
-        "lbLoadOffsetStep",
-        "lbHighUlnThresh",
-        "lbLowUlnThresh",
-        // DUAC
-        "duacCarrierActivation",
-        "duacDeactivationThreshold",
-        "duacMinDlPower",
-        "duacMaxUlPower",
-        "duacMinUlInterference",
-        "duacMaxUlInterference",
-        // MIMO Sleep
-        "mimoSleepMode",
-        "mimoSleepThreshold",
-        "mimoWakeThreshold",
-        "mimoMinActiveTime",
-        "mimoMinSleepTime",
-        "mimoActiveTimeHysteresis",
-        "mimoSleepTimeHysteresis",
-        // Cell Sleep
-        "cellSleepMode",
-        "cellSleepThreshold",
-        "cellWakeThreshold",
-        "cellMinActiveTime",
-        "cellMinSleepTime",
-        // Micro Sleep
-        "microSleepTxMode",
-        "microSleepTxThreshold",
-        "microSleepTxDutyCycle",
-        "microSleepTxMinOnTime",
-        "microSleepTxMinOffTime",
-        // Energy Saving
-        "energySavingMode",
-        "energySavingLevel",
-        "energySavingThreshold",
-        // Handover
-        "hoA3Offset",
-        "hoHysteresis",
-        "hoTriggerTime",
-        "hoMaxHoCount",
-        "hoMinHoTime",
-        // ANR
-        "anrMode",
-        "anrAddThreshold",
-        "anrRemoveThreshold",
-        "anrHysteresis",
-        "anrMinNoOfSamples",
-        // MRO
-        "mroMode",
-        "mroHoTooEarlyInd",
-        "mroHoTooLateInd",
-        "mroPingPongInd",
-        // CCO
-        "ccoMode",
-        "ccoMinTilt",
-        "ccoMaxTilt",
-        "ccoTiltStep",
-        "ccoMinTxPower",
-        "ccoMaxTxPower",
-        "ccoTxPowerStep",
-        // QoS
-        "qosMbrDl",
-        "qosMbrUl",
-        "qosGbrDl",
-        "qosGbrUl",
-        "qosAmp",
-        // DRX
-        "drxEnabled",
-        "drxOnDurationTimer",
-        "drxInactivityTimer",
-        "drxRetxTimer",
-        "drxCycle",
-        "drxShortCycle",
-        "drxLongCycleOffset",
-        // Paging
-        "pagingDrxCycle",
-        "pagingNb",
-        "pagingTmsi",
-        // ICIC
-        "icicEnabled",
-        "icicFwpRatio",
-        "icicFwpOffset",
-        "icicAbsEnabled",
-        "icicAbsPattern",
-        // CA
-        "caEnabled",
-        "caPrimaryScell",
-        "caSecondaryScell",
-        "caReleaseThreshold",
-        "caActivationThreshold",
-    ];
+/// Generate the inserts that build the hardcoded domain table
+fn generate_domain_inserts(set: &ConstraintSet) -> String {
+    let quoted = |values: &[String]| {
+        values.iter().map(|v| format!("{:?}", v)).collect::<Vec<_>>().join(", ")
+    };
+
+    let mut inserts = String::new();
+    for (name, entries) in &set.parameters.0 {
+        let Some(domain) = &entries[0].domain else {
+            continue;
+        };
+        let value = match domain {
+            DomainSpec::Integer { step } => format!("ParameterDomain::Integer {{ step: {} }}", step),
+            DomainSpec::Boolean => "ParameterDomain::Boolean".to_string(),
+            DomainSpec::Enum { values, forbidden_transitions } => {
+                let mut value = format!("ParameterDomain::enumerated(&[{}])", quoted(values));
+                for (from, to) in forbidden_transitions {
+                    value.push_str(&format!(".forbid_transition({:?}, {:?})", from, to));
+                }
+                value
+            }
+        };
+        inserts.push_str(&format!("    domains.insert({:?}, {});\n", name, value));
+    }
+    inserts
+}
 
+/// Generate list of hardcoded parameter names
+fn generate_parameter_list(set: &ConstraintSet) -> String {
     let mut list = String::new();
-    for param in params {
-        list.push_str(&format!("    \"{}\",\n", param));
+    for (name, _) in &set.parameters.0 {
+        list.push_str(&format!("    {:?},\n", name));
     }
     list
 }
+
+/// FNV-1a 64-bit hash as lowercase hex
+fn fnv1a_hex(data: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in data {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
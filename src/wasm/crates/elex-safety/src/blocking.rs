@@ -0,0 +1,421 @@
+//! Blocking Conditions
+//!
+//! Declarative pre-change conditions that block a parameter change for
+//! reasons beyond numeric bounds: maintenance windows, active alarms,
+//! ongoing rollbacks, inactive feature dependencies and per-cell change
+//! rate limits.
+
+use crate::{SafetyError, SafetyResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Length of the sliding window for change rate limits (seconds)
+const CHANGE_RATE_WINDOW_SECONDS: u64 = 3600;
+
+/// Alarm severity (ordered from least to most severe)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum AlarmSeverity {
+    /// Warning - no service impact
+    Warning,
+
+    /// Minor - limited service impact
+    Minor,
+
+    /// Major - significant service impact
+    Major,
+
+    /// Critical - service outage
+    Critical,
+}
+
+/// Alarm raised on a Managed Object
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Alarm {
+    /// Alarm identifier (unique per MO)
+    pub id: String,
+
+    /// Alarm severity
+    pub severity: AlarmSeverity,
+
+    /// Specific problem text
+    pub description: String,
+}
+
+/// Declarative blocking condition
+///
+/// Conditions are evaluated in declaration order; the first one that
+/// holds blocks the change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BlockingCondition {
+    /// Block changes during a maintenance window `[start, end)` (Unix seconds)
+    ///
+    /// When `mo_prefix` is set, only MOs under that path are blocked.
+    MaintenanceWindow {
+        name: String,
+        start: u64,
+        end: u64,
+        mo_prefix: Option<String>,
+    },
+
+    /// Block changes on MOs with an active alarm at or above `min_severity`
+    ///
+    /// Alarms on a parent MO also block changes on its children.
+    ActiveAlarm { min_severity: AlarmSeverity },
+
+    /// Block changes on MOs with a rollback in progress
+    OngoingRollback,
+
+    /// Block changes to `parameters` unless all `features` are activated
+    ///
+    /// An empty parameter list applies to every parameter.
+    FeatureDependency {
+        parameters: Vec<String>,
+        features: Vec<String>,
+    },
+
+    /// Limit the number of changes per cell within a sliding hour
+    MaxChangesPerHour { limit: u32 },
+}
+
+/// Change being checked against blocking conditions
+#[derive(Debug, Clone, Copy)]
+pub struct ChangeContext<'a> {
+    /// Managed Object path of the cell (empty if not MO-specific)
+    pub mo_path: &'a str,
+
+    /// Parameter name
+    pub parameter: &'a str,
+
+    /// Unix timestamp (seconds) of the check
+    pub now: u64,
+}
+
+impl<'a> ChangeContext<'a> {
+    /// Create a context for a change checked now
+    pub fn new(mo_path: &'a str, parameter: &'a str) -> Self {
+        Self {
+            mo_path,
+            parameter,
+            now: now_secs(),
+        }
+    }
+
+    /// Create a context at an explicit timestamp (seconds)
+    pub fn at(mo_path: &'a str, parameter: &'a str, now: u64) -> Self {
+        Self { mo_path, parameter, now }
+    }
+}
+
+/// Blocking conditions manager
+///
+/// Holds the declared conditions plus the network state they are
+/// evaluated against (alarms, activated features, rollbacks in progress
+/// and recent change history).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BlockingManager {
+    /// Declared conditions
+    conditions: Vec<BlockingCondition>,
+
+    /// Active alarms indexed by MO path
+    alarms: HashMap<String, Vec<Alarm>>,
+
+    /// Activated feature codes
+    active_features: HashSet<String>,
+
+    /// MO paths with a rollback in progress
+    rollbacks_in_progress: HashSet<String>,
+
+    /// Applied change timestamps indexed by cell MO path
+    change_history: HashMap<String, Vec<u64>>,
+}
+
+impl BlockingManager {
+    /// Create a manager without any conditions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a manager with the standard operator conditions
+    ///
+    /// Blocks on major/critical alarms, ongoing rollbacks and more than
+    /// `max_changes_per_hour` changes per cell.
+    pub fn with_defaults(max_changes_per_hour: u32) -> Self {
+        let mut manager = Self::new();
+        manager.add_condition(BlockingCondition::ActiveAlarm {
+            min_severity: AlarmSeverity::Major,
+        });
+        manager.add_condition(BlockingCondition::OngoingRollback);
+        manager.add_condition(BlockingCondition::MaxChangesPerHour {
+            limit: max_changes_per_hour,
+        });
+        manager
+    }
+
+    /// Declare a blocking condition
+    pub fn add_condition(&mut self, condition: BlockingCondition) {
+        self.conditions.push(condition);
+    }
+
+    /// Declared conditions
+    pub fn conditions(&self) -> &[BlockingCondition] {
+        &self.conditions
+    }
+
+    /// Remove all declared conditions
+    pub fn clear_conditions(&mut self) {
+        self.conditions.clear();
+    }
+
+    /// Raise (or replace) an alarm on an MO
+    pub fn raise_alarm(&mut self, mo_path: &str, alarm: Alarm) {
+        let alarms = self.alarms.entry(mo_path.to_string()).or_default();
+        alarms.retain(|a| a.id != alarm.id);
+        alarms.push(alarm);
+    }
+
+    /// Clear an alarm on an MO
+    pub fn clear_alarm(&mut self, mo_path: &str, alarm_id: &str) -> bool {
+        let Some(alarms) = self.alarms.get_mut(mo_path) else {
+            return false;
+        };
+        let before = alarms.len();
+        alarms.retain(|a| a.id != alarm_id);
+        let removed = alarms.len() != before;
+        if alarms.is_empty() {
+            self.alarms.remove(mo_path);
+        }
+        removed
+    }
+
+    /// Mark a feature as activated or deactivated
+    pub fn set_feature_active(&mut self, feature_code: &str, active: bool) {
+        if active {
+            self.active_features.insert(feature_code.to_string());
+        } else {
+            self.active_features.remove(feature_code);
+        }
+    }
+
+    /// Forget all feature activation states
+    pub fn clear_feature_states(&mut self) {
+        self.active_features.clear();
+    }
+
+    /// Check if a feature is activated
+    pub fn is_feature_active(&self, feature_code: &str) -> bool {
+        self.active_features.contains(feature_code)
+    }
+
+    /// Mark a rollback as started on an MO
+    pub fn begin_rollback(&mut self, mo_path: &str) {
+        self.rollbacks_in_progress.insert(mo_path.to_string());
+    }
+
+    /// Mark a rollback as finished on an MO
+    pub fn end_rollback(&mut self, mo_path: &str) {
+        self.rollbacks_in_progress.remove(mo_path);
+    }
+
+    /// Record an applied change on a cell
+    pub fn record_change(&mut self, mo_path: &str) {
+        self.record_change_at(mo_path, now_secs());
+    }
+
+    /// Record an applied change on a cell at an explicit timestamp
+    pub fn record_change_at(&mut self, mo_path: &str, now: u64) {
+        let history = self.change_history.entry(mo_path.to_string()).or_default();
+        history.retain(|&ts| now.saturating_sub(ts) < CHANGE_RATE_WINDOW_SECONDS);
+        history.push(now);
+    }
+
+    /// Number of changes recorded on a cell within the last hour
+    pub fn changes_in_last_hour(&self, mo_path: &str, now: u64) -> usize {
+        self.change_history
+            .get(mo_path)
+            .map(|h| {
+                h.iter()
+                    .filter(|&&ts| now.saturating_sub(ts) < CHANGE_RATE_WINDOW_SECONDS)
+                    .count()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Check a change against all declared conditions
+    ///
+    /// Returns `SafetyError::BlockedByCondition` for the first condition
+    /// that blocks the change.
+    pub fn check(&self, ctx: &ChangeContext) -> SafetyResult<()> {
+        match self.conditions.iter().find_map(|condition| self.evaluate(condition, ctx)) {
+            Some(reason) => Err(SafetyError::BlockedByCondition(ctx.parameter.to_string(), reason)),
+            None => Ok(()),
+        }
+    }
+
+    /// Reasons from every declared condition that blocks the change
+    pub fn blocking_reasons(&self, ctx: &ChangeContext) -> Vec<String> {
+        self.conditions
+            .iter()
+            .filter_map(|condition| self.evaluate(condition, ctx))
+            .collect()
+    }
+
+    fn evaluate(&self, condition: &BlockingCondition, ctx: &ChangeContext) -> Option<String> {
+        match condition {
+            BlockingCondition::MaintenanceWindow { name, start, end, mo_prefix } => {
+                let in_scope = mo_prefix
+                    .as_deref()
+                    .map_or(true, |prefix| is_under(ctx.mo_path, prefix));
+                (in_scope && ctx.now >= *start && ctx.now < *end)
+                    .then(|| format!("maintenance window '{}' active until {}", name, end))
+            }
+            BlockingCondition::ActiveAlarm { min_severity } => self
+                .alarms
+                .iter()
+                .filter(|(mo, _)| is_under(ctx.mo_path, mo))
+                .flat_map(|(mo, alarms)| alarms.iter().map(move |a| (mo, a)))
+                .filter(|(_, a)| a.severity >= *min_severity)
+                .max_by_key(|(_, a)| a.severity)
+                .map(|(mo, a)| {
+                    format!("{:?} alarm '{}' active on {}: {}", a.severity, a.id, mo, a.description)
+                }),
+            BlockingCondition::OngoingRollback => self
+                .rollbacks_in_progress
+                .iter()
+                .find(|mo| is_under(ctx.mo_path, mo))
+                .map(|mo| format!("rollback in progress on {}", mo)),
+            BlockingCondition::FeatureDependency { parameters, features } => {
+                if !parameters.is_empty() && !parameters.iter().any(|p| p == ctx.parameter) {
+                    return None;
+                }
+                let missing: Vec<&str> = features
+                    .iter()
+                    .filter(|f| !self.active_features.contains(f.as_str()))
+                    .map(|f| f.as_str())
+                    .collect();
+                (!missing.is_empty())
+                    .then(|| format!("required features not activated: {}", missing.join(", ")))
+            }
+            BlockingCondition::MaxChangesPerHour { limit } => {
+                let count = self.changes_in_last_hour(ctx.mo_path, ctx.now);
+                (count >= *limit as usize)
+                    .then(|| format!("{} changes in the last hour (limit {})", count, limit))
+            }
+        }
+    }
+}
+
+/// Check if `mo_path` equals `ancestor` or is one of its children
+fn is_under(mo_path: &str, ancestor: &str) -> bool {
+    mo_path == ancestor
+        || (mo_path.starts_with(ancestor) && mo_path[ancestor.len()..].starts_with(','))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CELL: &str = "ManagedElement=ERBS1,ENodeBFunction=1,EUtranCellFDD=Cell1";
+
+    #[test]
+    fn test_no_conditions_allows_change() {
+        let manager = BlockingManager::new();
+        assert!(manager.check(&ChangeContext::at(CELL, "hoA3Offset", 1000)).is_ok());
+    }
+
+    #[test]
+    fn test_maintenance_window() {
+        let mut manager = BlockingManager::new();
+        manager.add_condition(BlockingCondition::MaintenanceWindow {
+            name: "nightly".to_string(),
+            start: 1000,
+            end: 2000,
+            mo_prefix: Some("ManagedElement=ERBS1".to_string()),
+        });
+
+        assert!(manager.check(&ChangeContext::at(CELL, "hoA3Offset", 1500)).is_err());
+        assert!(manager.check(&ChangeContext::at(CELL, "hoA3Offset", 2000)).is_ok());
+        assert!(manager
+            .check(&ChangeContext::at("ManagedElement=ERBS2", "hoA3Offset", 1500))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_active_alarm_on_parent_mo() {
+        let mut manager = BlockingManager::with_defaults(10);
+        manager.raise_alarm("ManagedElement=ERBS1", Alarm {
+            id: "a1".to_string(),
+            severity: AlarmSeverity::Minor,
+            description: "Link degraded".to_string(),
+        });
+        assert!(manager.check(&ChangeContext::at(CELL, "hoA3Offset", 0)).is_ok());
+
+        manager.raise_alarm("ManagedElement=ERBS1", Alarm {
+            id: "a2".to_string(),
+            severity: AlarmSeverity::Critical,
+            description: "Cell down".to_string(),
+        });
+        let err = manager.check(&ChangeContext::at(CELL, "hoA3Offset", 0)).unwrap_err();
+        assert!(matches!(err, SafetyError::BlockedByCondition(ref p, _) if p == "hoA3Offset"));
+        assert!(err.to_string().contains("a2"));
+
+        assert!(manager.clear_alarm("ManagedElement=ERBS1", "a2"));
+        assert!(manager.check(&ChangeContext::at(CELL, "hoA3Offset", 0)).is_ok());
+    }
+
+    #[test]
+    fn test_ongoing_rollback() {
+        let mut manager = BlockingManager::with_defaults(10);
+        manager.begin_rollback(CELL);
+        assert!(manager.check(&ChangeContext::at(CELL, "hoA3Offset", 0)).is_err());
+        manager.end_rollback(CELL);
+        assert!(manager.check(&ChangeContext::at(CELL, "hoA3Offset", 0)).is_ok());
+    }
+
+    #[test]
+    fn test_feature_dependency() {
+        let mut manager = BlockingManager::new();
+        manager.add_condition(BlockingCondition::FeatureDependency {
+            parameters: vec!["mimoSleepThreshold".to_string()],
+            features: vec!["FAJ 121 3094".to_string()],
+        });
+
+        assert!(manager.check(&ChangeContext::at(CELL, "mimoSleepThreshold", 0)).is_err());
+        assert!(manager.check(&ChangeContext::at(CELL, "hoA3Offset", 0)).is_ok());
+
+        manager.set_feature_active("FAJ 121 3094", true);
+        assert!(manager.check(&ChangeContext::at(CELL, "mimoSleepThreshold", 0)).is_ok());
+    }
+
+    #[test]
+    fn test_max_changes_per_hour() {
+        let mut manager = BlockingManager::with_defaults(2);
+        manager.record_change_at(CELL, 0);
+        manager.record_change_at(CELL, 100);
+
+        assert!(manager.check(&ChangeContext::at(CELL, "hoA3Offset", 200)).is_err());
+        assert!(manager
+            .check(&ChangeContext::at("EUtranCellFDD=Cell2", "hoA3Offset", 200))
+            .is_ok());
+
+        // Oldest change leaves the sliding window
+        assert!(manager.check(&ChangeContext::at(CELL, "hoA3Offset", 3600)).is_ok());
+    }
+
+    #[test]
+    fn test_blocking_reasons_collects_all() {
+        let mut manager = BlockingManager::with_defaults(1);
+        manager.begin_rollback(CELL);
+        manager.record_change_at(CELL, 0);
+
+        let reasons = manager.blocking_reasons(&ChangeContext::at(CELL, "hoA3Offset", 10));
+        assert_eq!(reasons.len(), 2);
+    }
+}
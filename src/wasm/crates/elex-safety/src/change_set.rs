@@ -0,0 +1,569 @@
+//! Atomic Change Sets
+//!
+//! Groups related parameter changes (e.g. `lbMinLoadOffset`/`lbMaxLoadOffset`)
+//! so they are validated and approved as one unit. Cross-parameter
+//! invariants are checked on the final configuration and on every
+//! intermediate step of the execution order, so applying the changes one
+//! command at a time never passes through an invalid configuration.
+
+use crate::relational::{Margin, RelationalConstraint};
+use crate::safe_zone::ValidationViolation;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Maximum entries for which an invariant-preserving order is searched
+///
+/// Larger sets are only checked in declaration order.
+pub const MAX_ORDERED_ENTRIES: usize = 64;
+
+/// Maximum partial orders explored before the search gives up
+pub const MAX_SEARCH_NODES: usize = 10_000;
+
+/// Reasons no execution order can be produced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderError {
+    /// Every order passes through a configuration violating an invariant
+    NoValidOrder,
+
+    /// The search explored `MAX_SEARCH_NODES` partial orders without an answer
+    SearchBudgetExceeded,
+}
+
+impl std::fmt::Display for OrderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrderError::NoValidOrder => write!(f, "No execution order preserves invariants at every step"),
+            OrderError::SearchBudgetExceeded => write!(
+                f,
+                "Execution order search exceeded {} steps; split the change set",
+                MAX_SEARCH_NODES
+            ),
+        }
+    }
+}
+
+/// A single parameter change within a change set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeSetEntry {
+    /// Managed Object path
+    pub mo_path: String,
+
+    /// Parameter name
+    pub parameter: String,
+
+    /// Current value (None if unknown)
+    pub old_value: Option<f32>,
+
+    /// Value to set
+    pub new_value: f32,
+}
+
+/// Cross-parameter invariant evaluated per MO
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Invariant {
+    /// Relational constraint between parameters on the same MO
+    Relational(RelationalConstraint),
+
+    /// `parameter > 0`
+    Positive { parameter: String },
+}
+
+impl Invariant {
+    /// `lower < upper`
+    pub fn less_than(lower: &str, upper: &str) -> Self {
+        Invariant::Relational(RelationalConstraint::ordering(lower, upper))
+    }
+
+    /// `upper > lower + margin_param`
+    pub fn above_with_margin(upper: &str, lower: &str, margin_param: &str) -> Self {
+        Invariant::Relational(RelationalConstraint::Ordering {
+            lower: lower.to_string(),
+            upper: upper.to_string(),
+            margin: Margin::Parameter(margin_param.to_string()),
+        })
+    }
+
+    /// `upper > lower + margin`
+    pub fn above_by(upper: &str, lower: &str, margin: f32) -> Self {
+        Invariant::Relational(RelationalConstraint::Ordering {
+            lower: lower.to_string(),
+            upper: upper.to_string(),
+            margin: Margin::Constant(margin),
+        })
+    }
+
+    /// `parameter > 0`
+    pub fn positive(parameter: &str) -> Self {
+        Invariant::Positive {
+            parameter: parameter.to_string(),
+        }
+    }
+
+    /// Invariants for the coupled parameters in the hardcoded constraint set
+    ///
+    /// The cell wake threshold must stay above the sleep threshold; once the
+    /// wake hysteresis is known, above it by at least that hysteresis.
+    /// Couplings already covered by `RelationalConstraint::standard` (e.g.
+    /// MIMO wake/sleep thresholds) are not repeated here; the validator
+    /// applies those to change sets as well.
+    pub fn standard() -> Vec<Invariant> {
+        vec![
+            Invariant::less_than("lbMinLoadOffset", "lbMaxLoadOffset"),
+            Invariant::less_than("lbLowUlnThresh", "lbHighUlnThresh"),
+            Invariant::less_than("duacMinUlInterference", "duacMaxUlInterference"),
+            Invariant::less_than("cellSleepThreshold", "cellWakeThreshold"),
+            Invariant::above_with_margin("cellWakeThreshold", "cellSleepThreshold", "cellWakeHysteresis"),
+            Invariant::less_than("ccoMinTilt", "ccoMaxTilt"),
+            Invariant::less_than("ccoMinTxPower", "ccoMaxTxPower"),
+            Invariant::positive("hoHysteresis"),
+            Invariant::positive("anrHysteresis"),
+            Invariant::positive("mimoActiveTimeHysteresis"),
+            Invariant::positive("mimoSleepTimeHysteresis"),
+            Invariant::positive("cellWakeHysteresis"),
+        ]
+    }
+
+    /// Parameters referenced by this invariant
+    pub fn parameters(&self) -> Vec<&str> {
+        match self {
+            Invariant::Relational(constraint) => constraint.parameters(),
+            Invariant::Positive { parameter } => vec![parameter.as_str()],
+        }
+    }
+
+    /// Evaluate against parameter values
+    ///
+    /// Returns None if any referenced value is unknown.
+    pub fn evaluate<F>(&self, value_of: F) -> Option<bool>
+    where
+        F: Fn(&str) -> Option<f32>,
+    {
+        match self {
+            Invariant::Relational(constraint) => constraint.evaluate(value_of),
+            Invariant::Positive { parameter } => Some(value_of(parameter)? > 0.0),
+        }
+    }
+
+    /// Human-readable form
+    pub fn describe(&self) -> String {
+        match self {
+            Invariant::Relational(constraint) => constraint.describe(),
+            Invariant::Positive { parameter } => format!("{} > 0", parameter),
+        }
+    }
+}
+
+impl From<RelationalConstraint> for Invariant {
+    fn from(constraint: RelationalConstraint) -> Self {
+        Invariant::Relational(constraint)
+    }
+}
+
+/// Configuration values keyed by (MO path, parameter)
+type ConfigState = HashMap<(String, String), f32>;
+
+/// Atomic set of related parameter changes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChangeSet {
+    /// Change set identifier (e.g. change ticket)
+    pub id: String,
+
+    /// Changes in declaration order
+    entries: Vec<ChangeSetEntry>,
+
+    /// Cross-parameter invariants
+    invariants: Vec<Invariant>,
+
+    /// Current values of related parameters not being changed
+    context: Vec<(String, String, f32)>,
+}
+
+impl ChangeSet {
+    /// Create an empty change set
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Create a change set with the standard invariants
+    pub fn with_standard_invariants(id: &str) -> Self {
+        let mut set = Self::new(id);
+        set.invariants = Invariant::standard();
+        set
+    }
+
+    /// Add a change
+    pub fn add_change(&mut self, entry: ChangeSetEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Add a change (builder form)
+    pub fn with_change(mut self, mo_path: &str, parameter: &str, old_value: Option<f32>, new_value: f32) -> Self {
+        self.add_change(ChangeSetEntry {
+            mo_path: mo_path.to_string(),
+            parameter: parameter.to_string(),
+            old_value,
+            new_value,
+        });
+        self
+    }
+
+    /// Add an invariant
+    pub fn add_invariant(&mut self, invariant: Invariant) {
+        self.invariants.push(invariant);
+    }
+
+    /// Replace all invariants
+    pub(crate) fn replace_invariants(&mut self, invariants: Vec<Invariant>) {
+        self.invariants = invariants;
+    }
+
+    /// Record the current value of a related parameter that is not changed
+    ///
+    /// Lets invariants such as `wake > sleep + hysteresis` be checked when
+    /// only some of the referenced parameters change.
+    pub fn set_current_value(&mut self, mo_path: &str, parameter: &str, value: f32) {
+        self.context.retain(|(m, p, _)| !(m == mo_path && p == parameter));
+        self.context.push((mo_path.to_string(), parameter.to_string(), value));
+    }
+
+    /// Fill in values the set leaves open from known current values
+    ///
+    /// Entries without an old value take the known value of their MO
+    /// parameter, and known values on the set's MOs become context unless
+    /// the set already has one for that parameter.
+    pub(crate) fn complete_with<'a>(&mut self, known: impl IntoIterator<Item = (&'a str, &'a str, f32)>) {
+        let mo_paths = self.mo_paths();
+        for (mo_path, parameter, value) in known {
+            if !mo_paths.iter().any(|m| m == mo_path) {
+                continue;
+            }
+            for entry in &mut self.entries {
+                if entry.old_value.is_none() && entry.mo_path == mo_path && entry.parameter == parameter {
+                    entry.old_value = Some(value);
+                }
+            }
+            if !self.context.iter().any(|(m, p, _)| m == mo_path && p == parameter) {
+                self.context.push((mo_path.to_string(), parameter.to_string(), value));
+            }
+        }
+    }
+
+    /// Changes in declaration order
+    pub fn entries(&self) -> &[ChangeSetEntry] {
+        &self.entries
+    }
+
+    /// Declared invariants
+    pub fn invariants(&self) -> &[Invariant] {
+        &self.invariants
+    }
+
+    /// Number of changes
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the change set has no changes
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries changing the same MO parameter more than once
+    pub fn duplicate_entries(&self) -> Vec<&ChangeSetEntry> {
+        let mut seen = HashSet::new();
+        self.entries
+            .iter()
+            .filter(|e| !seen.insert((e.mo_path.as_str(), e.parameter.as_str())))
+            .collect()
+    }
+
+    /// Invariants violated by the final configuration, per MO path
+    pub fn final_violations(&self) -> Vec<(String, &Invariant)> {
+        let mut state = self.initial_state();
+        for entry in &self.entries {
+            state.insert((entry.mo_path.clone(), entry.parameter.clone()), entry.new_value);
+        }
+
+        let mut violations = Vec::new();
+        for mo_path in self.mo_paths() {
+            for invariant in self.relevant_invariants(&mo_path) {
+                if Self::evaluate(&state, &mo_path, invariant) == Some(false) {
+                    violations.push((mo_path.clone(), invariant));
+                }
+            }
+        }
+        violations
+    }
+
+    /// Find an execution order that preserves invariants at every step
+    ///
+    /// Invariants that hold before the change set is applied must keep
+    /// holding after each individual command. Returns indices into
+    /// `entries()`. The search memoises dead ends and stops after
+    /// `MAX_SEARCH_NODES` partial orders, so it stays bounded on large sets.
+    pub fn execution_order(&self) -> Result<Vec<usize>, OrderError> {
+        let mut state = self.initial_state();
+
+        // Invariants that currently hold (or are unknown) must be preserved
+        let mut required: Vec<(String, &Invariant)> = Vec::new();
+        for mo_path in self.mo_paths() {
+            for invariant in self.relevant_invariants(&mo_path) {
+                if Self::evaluate(&state, &mo_path, invariant) != Some(false) {
+                    required.push((mo_path.clone(), invariant));
+                }
+            }
+        }
+
+        let n = self.entries.len();
+        if n > MAX_ORDERED_ENTRIES {
+            let order: Vec<usize> = (0..n).collect();
+            for &i in &order {
+                self.apply(&mut state, i);
+                if !Self::holds(&state, &required) {
+                    return Err(OrderError::NoValidOrder);
+                }
+            }
+            return Ok(order);
+        }
+
+        let mut order = Vec::with_capacity(n);
+        let mut dead_ends = HashSet::new();
+        let mut budget = MAX_SEARCH_NODES;
+        if self.search(&mut state, &required, 0, &mut order, &mut dead_ends, &mut budget)? {
+            Ok(order)
+        } else {
+            Err(OrderError::NoValidOrder)
+        }
+    }
+
+    fn search(
+        &self,
+        state: &mut ConfigState,
+        required: &[(String, &Invariant)],
+        applied: u64,
+        order: &mut Vec<usize>,
+        dead_ends: &mut HashSet<u64>,
+        budget: &mut usize,
+    ) -> Result<bool, OrderError> {
+        if order.len() == self.entries.len() {
+            return Ok(true);
+        }
+        if dead_ends.contains(&applied) {
+            return Ok(false);
+        }
+        if *budget == 0 {
+            return Err(OrderError::SearchBudgetExceeded);
+        }
+        *budget -= 1;
+
+        for i in 0..self.entries.len() {
+            if applied & (1u64 << i) != 0 {
+                continue;
+            }
+            let key = self.key(i);
+            let previous = state.insert(key.clone(), self.entries[i].new_value);
+
+            let found = if Self::holds(state, required) {
+                order.push(i);
+                let found = self.search(state, required, applied | (1u64 << i), order, dead_ends, budget);
+                if !matches!(found, Ok(true)) {
+                    order.pop();
+                }
+                found
+            } else {
+                Ok(false)
+            };
+
+            match previous {
+                Some(v) => state.insert(key, v),
+                None => state.remove(&key),
+            };
+            if found? {
+                return Ok(true);
+            }
+        }
+
+        dead_ends.insert(applied);
+        Ok(false)
+    }
+
+    fn holds(state: &ConfigState, required: &[(String, &Invariant)]) -> bool {
+        required
+            .iter()
+            .all(|(mo_path, invariant)| Self::evaluate(state, mo_path, invariant) != Some(false))
+    }
+
+    fn evaluate(state: &ConfigState, mo_path: &str, invariant: &Invariant) -> Option<bool> {
+        invariant.evaluate(|param| state.get(&(mo_path.to_string(), param.to_string())).copied())
+    }
+
+    fn initial_state(&self) -> ConfigState {
+        let mut state = ConfigState::new();
+        for (mo_path, parameter, value) in &self.context {
+            state.insert((mo_path.clone(), parameter.clone()), *value);
+        }
+        for entry in &self.entries {
+            if let Some(old) = entry.old_value {
+                state.insert((entry.mo_path.clone(), entry.parameter.clone()), old);
+            }
+        }
+        state
+    }
+
+    fn apply(&self, state: &mut ConfigState, index: usize) {
+        state.insert(self.key(index), self.entries[index].new_value);
+    }
+
+    fn key(&self, index: usize) -> (String, String) {
+        let entry = &self.entries[index];
+        (entry.mo_path.clone(), entry.parameter.clone())
+    }
+
+    fn mo_paths(&self) -> Vec<String> {
+        let mut paths: Vec<String> = Vec::new();
+        for entry in &self.entries {
+            if !paths.contains(&entry.mo_path) {
+                paths.push(entry.mo_path.clone());
+            }
+        }
+        paths
+    }
+
+    /// Invariants referencing at least one parameter changed on `mo_path`
+    fn relevant_invariants(&self, mo_path: &str) -> impl Iterator<Item = &Invariant> {
+        let changed: HashSet<&str> = self
+            .entries
+            .iter()
+            .filter(|e| e.mo_path == mo_path)
+            .map(|e| e.parameter.as_str())
+            .collect();
+        self.invariants
+            .iter()
+            .filter(move |inv| inv.parameters().iter().any(|p| changed.contains(p)))
+    }
+}
+
+/// Outcome of validating a change set as one unit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeSetReport {
+    /// Change set identifier
+    pub change_set_id: String,
+
+    /// Whether the whole set may be applied
+    pub approved: bool,
+
+    /// All violations found (per-parameter and cross-parameter)
+    pub violations: Vec<ValidationViolation>,
+
+    /// Invariant-preserving execution order (empty if rejected)
+    pub order: Vec<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CELL: &str = "EUtranCellFDD=Cell1";
+
+    #[test]
+    fn test_invariant_evaluate() {
+        let inv = Invariant::above_with_margin("wake", "sleep", "hyst");
+        let values = |p: &str| match p {
+            "sleep" => Some(10.0),
+            "wake" => Some(20.0),
+            "hyst" => Some(5.0),
+            _ => None,
+        };
+        assert_eq!(inv.evaluate(values), Some(true));
+        assert_eq!(Invariant::positive("other").evaluate(values), None);
+        assert_eq!(inv.describe(), "sleep + hyst < wake");
+    }
+
+    #[test]
+    fn test_order_preserves_min_max() {
+        // Shifting the window up: max must move before min
+        let set = ChangeSet::with_standard_invariants("CR-1")
+            .with_change(CELL, "lbMinLoadOffset", Some(-5.0), 6.0)
+            .with_change(CELL, "lbMaxLoadOffset", Some(5.0), 10.0);
+
+        assert!(set.final_violations().is_empty());
+        assert_eq!(set.execution_order(), Ok(vec![1, 0]));
+    }
+
+    #[test]
+    fn test_final_violation_detected() {
+        let set = ChangeSet::with_standard_invariants("CR-2")
+            .with_change(CELL, "lbMinLoadOffset", Some(-5.0), 8.0)
+            .with_change(CELL, "lbMaxLoadOffset", Some(5.0), 6.0);
+
+        let violations = set.final_violations();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].0, CELL);
+    }
+
+    #[test]
+    fn test_context_value_used_for_margin() {
+        let mut set = ChangeSet::new("CR-3")
+            .with_change(CELL, "mimoSleepThreshold", Some(10.0), 26.0);
+        set.add_invariant(Invariant::above_by("mimoWakeThreshold", "mimoSleepThreshold", 5.0));
+        set.set_current_value(CELL, "mimoWakeThreshold", 30.0);
+
+        // 26 + 5 >= 30
+        assert_eq!(set.final_violations().len(), 1);
+        assert_eq!(set.execution_order(), Err(OrderError::NoValidOrder));
+    }
+
+    #[test]
+    fn test_wake_above_sleep_plus_hysteresis() {
+        let mut set = ChangeSet::with_standard_invariants("CR-4")
+            .with_change(CELL, "cellSleepThreshold", Some(10.0), 16.0);
+        set.set_current_value(CELL, "cellWakeThreshold", 20.0);
+        set.set_current_value(CELL, "cellWakeHysteresis", 5.0);
+
+        // 16 < 20, but 16 + 5 >= 20
+        let violations = set.final_violations();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].1.describe(), "cellSleepThreshold + cellWakeHysteresis < cellWakeThreshold");
+
+        // Narrowing the hysteresis in the same set restores the invariant
+        let set = set.with_change(CELL, "cellWakeHysteresis", Some(5.0), 3.0);
+        assert!(set.final_violations().is_empty());
+        assert_eq!(set.execution_order(), Ok(vec![1, 0]));
+    }
+
+    #[test]
+    fn test_order_search_is_bounded() {
+        // The last change can never be applied, so an unbounded search would
+        // visit every subset of the independent changes before failing
+        let mut set = ChangeSet::new("CR-6");
+        for i in 0..24 {
+            set = set.with_change(CELL, &format!("free{}", i), Some(0.0), 1.0);
+        }
+        set = set.with_change(CELL, "hoHysteresis", Some(1.0), -1.0);
+        set.add_invariant(Invariant::positive("hoHysteresis"));
+
+        assert_eq!(set.execution_order(), Err(OrderError::SearchBudgetExceeded));
+    }
+
+    #[test]
+    fn test_invariants_are_per_mo() {
+        let set = ChangeSet::with_standard_invariants("CR-4")
+            .with_change("EUtranCellFDD=A", "lbMinLoadOffset", Some(0.0), 8.0)
+            .with_change("EUtranCellFDD=B", "lbMaxLoadOffset", Some(10.0), 5.0);
+
+        // Values on different MOs are never compared
+        assert!(set.final_violations().is_empty());
+        assert!(set.execution_order().is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_entries() {
+        let set = ChangeSet::new("CR-5")
+            .with_change(CELL, "hoHysteresis", Some(2.0), 2.2)
+            .with_change(CELL, "hoHysteresis", Some(2.2), 2.4);
+        assert_eq!(set.duplicate_entries().len(), 1);
+    }
+}
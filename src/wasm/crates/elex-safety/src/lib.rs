@@ -5,17 +5,23 @@
 //! # Components
 //!
 //! 1. **Safe Zone Enforcement** - Hardcoded constraints with compile-time guarantees
-//! 2. **Blocking Conditions** - Pre-change validation checks
-//! 3. **Rollback Mechanism** - Automatic KPI-based rollback with IndexedDB persistence
+//! 2. **Relational Constraints** - Ordering, sum and difference bounds between coupled parameters
+//! 3. **Blocking Conditions** - Pre-change validation checks
+//! 4. **Rollback Mechanism** - Automatic KPI-based rollback with IndexedDB persistence
 //!
 //! # Safety Guarantees
 //!
-//! - **Compile-time constraints**: Hard limits embedded at build time
+//! - **Compile-time constraints**: Hard limits embedded at build time from
+//!   `constraints/safe_zones.json`, identified by `CONSTRAINTS_HASH`
 //! - **SIMD-accelerated**: Parallel validation using elex-simd
 //! - **Atomic operations**: All-or-nothing parameter changes
 //! - **Automatic rollback**: KPI degradation triggers revert within 30min
 
 pub mod safe_zone;
+pub mod rollback;
+pub mod blocking;
+pub mod change_set;
+pub mod relational;
 
 // Include generated constraints
 include!(concat!(env!("OUT_DIR"), "/embedded_constraints.rs"));
@@ -25,6 +31,15 @@ pub use safe_zone::{
     SafeZone, SafeZoneValidator, ValidationViolation, ValidationSeverity,
     ViolationType, check_parameter_value, validate_parameter_change, SafeViolation,
 };
+pub use rollback::{
+    RollbackManager, RollbackConfig, RollbackCommand, ParameterCheckpoint,
+    KpiDirection, KpiThreshold, KpiDegradation,
+};
+pub use blocking::{
+    BlockingManager, BlockingCondition, ChangeContext, Alarm, AlarmSeverity,
+};
+pub use change_set::{ChangeSet, ChangeSetEntry, ChangeSetReport, Invariant, OrderError};
+pub use relational::{Margin, RelationalConstraint};
 
 use thiserror::Error;
 
@@ -43,6 +58,12 @@ pub enum SafetyError {
     #[error("Parameter '{0}' is in cooldown period ({1}s remaining)")]
     ParameterInCooldown(String, u64),
 
+    #[error("Parameter '{0}' value not in domain: {1}")]
+    InvalidDomainValue(String, String),
+
+    #[error("Parameter '{0}' violates relational constraint: {1}")]
+    RelationalViolation(String, String),
+
     #[error("Parameter '{0}' blocked by condition: {1}")]
     BlockedByCondition(String, String),
 
@@ -62,35 +83,38 @@ pub enum SafetyError {
 /// Result type for safety operations
 pub type SafetyResult<T> = Result<T, SafetyError>;
 
-/// Pre-change safety check (simplified version without blocking manager)
+/// Pre-change safety check
 ///
-/// Validates against safe zone constraints.
-/// Returns Ok if the change is safe to apply.
+/// Evaluates blocking conditions for the MO, then validates against
+/// safe zone constraints. Returns Ok if the change is safe to apply.
 pub fn pre_change_check(
+    mo_path: &str,
     param_name: &str,
     old_value: f32,
     new_value: f32,
-    _blocking_manager: &() /* BlockingManager placeholder */,
+    blocking_manager: &BlockingManager,
     validator: &SafeZoneValidator,
 ) -> SafetyResult<()> {
+    // Check blocking conditions
+    blocking_manager.check(&ChangeContext::new(mo_path, param_name))?;
+
     // Validate against safe zone
-    validator.validate_change(param_name, old_value, new_value)?;
+    validator.validate_change(mo_path, param_name, old_value, new_value)?;
 
     Ok(())
 }
 
-// Placeholder types for compatibility
-pub type BlockingManager = ();
-pub type RollbackManager = ();
-
-#[allow(dead_code)]
+/// Post-change KPI check
+///
+/// Compares current KPIs against the baseline of the most recent change.
+/// Returns Ok(true) if any KPI degraded by more than `threshold_degradation`
+/// percent (or its per-KPI threshold) and the change should be rolled back.
 pub fn post_change_kpi_check(
-    _rollback_manager: &RollbackManager,
-    _current_kpis: &[(String, f32)],
-    _threshold_degradation: f32,
+    rollback_manager: &RollbackManager,
+    current_kpis: &[(String, f32)],
+    threshold_degradation: f32,
 ) -> SafetyResult<bool> {
-    // Placeholder implementation
-    Ok(false)
+    rollback_manager.requires_rollback(current_kpis, threshold_degradation)
 }
 
 #[cfg(test)]
@@ -114,6 +138,21 @@ mod tests {
         assert!(HARDCODED_PARAMETERS.contains(&"lbActivationThreshold"));
     }
 
+    #[test]
+    fn test_constraint_set_identity() {
+        assert!(!CONSTRAINTS_VERSION.is_empty());
+        assert_eq!(CONSTRAINTS_HASH.len(), 16);
+        assert!(CONSTRAINTS_HASH.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn test_scoped_constraint_falls_back_to_unscoped() {
+        let scoped = get_scoped_constraint("lbActivationThreshold", Some("EUtranCellFDD"), Some("FAJ 121 3094"));
+        let unscoped = get_hardcoded_constraint("lbActivationThreshold");
+        assert!(std::ptr::eq(scoped.unwrap(), unscoped.unwrap()));
+        assert!(get_scoped_constraint("unknownParam", None, None).is_none());
+    }
+
     #[test]
     fn test_safety_error_display() {
         let err = SafetyError::ExceedsAbsoluteMax("test".to_string(), 150.0, 100.0);
@@ -0,0 +1,194 @@
+//! Relational Constraints
+//!
+//! Cross-parameter constraints layered on top of per-parameter safe zones.
+//! Many RAN parameters are coupled (e.g. `anrAddThreshold` must stay above
+//! `anrRemoveThreshold`), so a value that is valid on its own can still
+//! produce an invalid configuration together with its neighbours.
+
+use serde::{Deserialize, Serialize};
+
+/// Margin added to the lower side of an ordering constraint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Margin {
+    /// No margin (strict ordering)
+    None,
+
+    /// Constant margin
+    Constant(f32),
+
+    /// Margin taken from another parameter (e.g. a hysteresis)
+    Parameter(String),
+}
+
+/// Relational constraint between parameters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RelationalConstraint {
+    /// `lower + margin < upper`
+    Ordering {
+        lower: String,
+        upper: String,
+        margin: Margin,
+    },
+
+    /// `min <= sum(parameters) <= max`
+    SumBounds {
+        parameters: Vec<String>,
+        min: f32,
+        max: f32,
+    },
+
+    /// `min <= minuend - subtrahend <= max`
+    DifferenceBounds {
+        minuend: String,
+        subtrahend: String,
+        min: f32,
+        max: f32,
+    },
+}
+
+impl RelationalConstraint {
+    /// `lower < upper`
+    pub fn ordering(lower: &str, upper: &str) -> Self {
+        RelationalConstraint::Ordering {
+            lower: lower.to_string(),
+            upper: upper.to_string(),
+            margin: Margin::None,
+        }
+    }
+
+    /// `min <= sum(parameters) <= max`
+    pub fn sum_bounds(parameters: &[&str], min: f32, max: f32) -> Self {
+        RelationalConstraint::SumBounds {
+            parameters: parameters.iter().map(|p| p.to_string()).collect(),
+            min,
+            max,
+        }
+    }
+
+    /// `min <= minuend - subtrahend <= max`
+    pub fn difference_bounds(minuend: &str, subtrahend: &str, min: f32, max: f32) -> Self {
+        RelationalConstraint::DifferenceBounds {
+            minuend: minuend.to_string(),
+            subtrahend: subtrahend.to_string(),
+            min,
+            max,
+        }
+    }
+
+    /// Relational constraints for coupled hardcoded parameters
+    pub fn standard() -> Vec<RelationalConstraint> {
+        vec![
+            // Effective A3 entering margin must stay non-negative and bounded
+            RelationalConstraint::sum_bounds(&["hoA3Offset", "hoHysteresis"], 0.0, 10.0),
+            // Neighbours are added well above the level at which they are removed
+            RelationalConstraint::difference_bounds("anrAddThreshold", "anrRemoveThreshold", 2.0, 40.0),
+            // MIMO wakes up above the level at which it goes to sleep
+            RelationalConstraint::difference_bounds("mimoWakeThreshold", "mimoSleepThreshold", 1.0, 100.0),
+        ]
+    }
+
+    /// Parameters referenced by this constraint
+    pub fn parameters(&self) -> Vec<&str> {
+        match self {
+            RelationalConstraint::Ordering { lower, upper, margin } => {
+                let mut params = vec![lower.as_str(), upper.as_str()];
+                if let Margin::Parameter(p) = margin {
+                    params.push(p.as_str());
+                }
+                params
+            }
+            RelationalConstraint::SumBounds { parameters, .. } => {
+                parameters.iter().map(|p| p.as_str()).collect()
+            }
+            RelationalConstraint::DifferenceBounds { minuend, subtrahend, .. } => {
+                vec![minuend.as_str(), subtrahend.as_str()]
+            }
+        }
+    }
+
+    /// Check if the constraint references a parameter
+    pub fn references(&self, parameter: &str) -> bool {
+        self.parameters().contains(&parameter)
+    }
+
+    /// Evaluate against parameter values
+    ///
+    /// Returns None if any referenced value is unknown.
+    pub fn evaluate<F>(&self, value_of: F) -> Option<bool>
+    where
+        F: Fn(&str) -> Option<f32>,
+    {
+        match self {
+            RelationalConstraint::Ordering { lower, upper, margin } => {
+                let margin = match margin {
+                    Margin::None => 0.0,
+                    Margin::Constant(m) => *m,
+                    Margin::Parameter(p) => value_of(p)?,
+                };
+                Some(value_of(lower)? + margin < value_of(upper)?)
+            }
+            RelationalConstraint::SumBounds { parameters, min, max } => {
+                let mut sum = 0.0;
+                for p in parameters {
+                    sum += value_of(p)?;
+                }
+                Some(sum >= *min && sum <= *max)
+            }
+            RelationalConstraint::DifferenceBounds { minuend, subtrahend, min, max } => {
+                let diff = value_of(minuend)? - value_of(subtrahend)?;
+                Some(diff >= *min && diff <= *max)
+            }
+        }
+    }
+
+    /// Human-readable form
+    pub fn describe(&self) -> String {
+        match self {
+            RelationalConstraint::Ordering { lower, upper, margin } => match margin {
+                Margin::None => format!("{} < {}", lower, upper),
+                Margin::Constant(m) => format!("{} + {} < {}", lower, m, upper),
+                Margin::Parameter(p) => format!("{} + {} < {}", lower, p, upper),
+            },
+            RelationalConstraint::SumBounds { parameters, min, max } => {
+                format!("{} <= {} <= {}", min, parameters.join(" + "), max)
+            }
+            RelationalConstraint::DifferenceBounds { minuend, subtrahend, min, max } => {
+                format!("{} <= {} - {} <= {}", min, minuend, subtrahend, max)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &'static [(&'static str, f32)]) -> impl Fn(&str) -> Option<f32> {
+        move |name| pairs.iter().find(|(n, _)| *n == name).map(|(_, v)| *v)
+    }
+
+    #[test]
+    fn test_ordering() {
+        let c = RelationalConstraint::ordering("a", "b");
+        assert_eq!(c.evaluate(values(&[("a", 1.0), ("b", 2.0)])), Some(true));
+        assert_eq!(c.evaluate(values(&[("a", 2.0), ("b", 2.0)])), Some(false));
+        assert_eq!(c.evaluate(values(&[("a", 1.0)])), None);
+    }
+
+    #[test]
+    fn test_sum_bounds() {
+        let c = RelationalConstraint::sum_bounds(&["hoA3Offset", "hoHysteresis"], 0.0, 10.0);
+        assert_eq!(c.evaluate(values(&[("hoA3Offset", -2.0), ("hoHysteresis", 3.0)])), Some(true));
+        assert_eq!(c.evaluate(values(&[("hoA3Offset", -5.0), ("hoHysteresis", 3.0)])), Some(false));
+        assert_eq!(c.describe(), "0 <= hoA3Offset + hoHysteresis <= 10");
+    }
+
+    #[test]
+    fn test_difference_bounds() {
+        let c = RelationalConstraint::difference_bounds("anrAddThreshold", "anrRemoveThreshold", 2.0, 40.0);
+        assert_eq!(c.evaluate(values(&[("anrAddThreshold", -100.0), ("anrRemoveThreshold", -110.0)])), Some(true));
+        assert_eq!(c.evaluate(values(&[("anrAddThreshold", -110.0), ("anrRemoveThreshold", -100.0)])), Some(false));
+        assert!(c.references("anrRemoveThreshold"));
+        assert!(!c.references("anrHysteresis"));
+    }
+}
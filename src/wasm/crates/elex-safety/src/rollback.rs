@@ -0,0 +1,592 @@
+//! KPI-Driven Rollback
+//!
+//! Checkpoints parameter values before each applied change and compares
+//! post-change KPIs against the baseline captured at checkpoint time.
+//! When a KPI degrades beyond its threshold and the rollback window
+//! (30 minutes by default) has not expired, the manager produces the
+//! commands that restore the checkpointed values.
+
+use crate::{SafetyError, SafetyResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default rollback window (30 minutes)
+pub const DEFAULT_ROLLBACK_WINDOW_SECONDS: u64 = 1800;
+
+/// Default maximum KPI degradation before rollback (%)
+pub const DEFAULT_DEGRADATION_THRESHOLD_PERCENT: f32 = 5.0;
+
+/// Default number of checkpoints retained
+pub const DEFAULT_MAX_CHECKPOINTS: usize = 256;
+
+/// Direction in which a KPI improves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KpiDirection {
+    /// Larger values are better (e.g. success rates, throughput)
+    HigherIsBetter,
+
+    /// Smaller values are better (e.g. drop rates, latency)
+    LowerIsBetter,
+}
+
+impl KpiDirection {
+    /// Degradation from baseline to current value as a percentage
+    ///
+    /// Returns 0.0 when the KPI stayed the same or improved.
+    pub fn degradation_percent(&self, baseline: f32, current: f32) -> f32 {
+        let worsening = match self {
+            KpiDirection::HigherIsBetter => baseline - current,
+            KpiDirection::LowerIsBetter => current - baseline,
+        };
+
+        if worsening <= 0.0 {
+            return 0.0;
+        }
+        if baseline.abs() <= f32::EPSILON {
+            // Any worsening from a zero baseline counts as full degradation
+            return 100.0;
+        }
+
+        (worsening / baseline.abs()) * 100.0
+    }
+}
+
+/// Per-KPI degradation threshold
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct KpiThreshold {
+    /// Direction in which the KPI improves
+    pub direction: KpiDirection,
+
+    /// Maximum tolerated degradation relative to baseline (%)
+    pub max_degradation_percent: f32,
+}
+
+impl KpiThreshold {
+    /// Create a new KPI threshold
+    pub const fn new(direction: KpiDirection, max_degradation_percent: f32) -> Self {
+        Self {
+            direction,
+            max_degradation_percent,
+        }
+    }
+}
+
+/// Rollback manager configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollbackConfig {
+    /// Seconds after a change during which it can still be rolled back
+    pub window_seconds: u64,
+
+    /// Degradation threshold for KPIs without an explicit threshold (%)
+    pub default_degradation_percent: f32,
+
+    /// Maximum number of checkpoints retained (oldest dropped first)
+    pub max_checkpoints: usize,
+}
+
+impl Default for RollbackConfig {
+    fn default() -> Self {
+        Self {
+            window_seconds: DEFAULT_ROLLBACK_WINDOW_SECONDS,
+            default_degradation_percent: DEFAULT_DEGRADATION_THRESHOLD_PERCENT,
+            max_checkpoints: DEFAULT_MAX_CHECKPOINTS,
+        }
+    }
+}
+
+/// Parameter value captured before an applied change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParameterCheckpoint {
+    /// Checkpoint identifier (monotonic per manager)
+    pub id: u64,
+
+    /// Managed Object path the change was applied to
+    pub mo_path: String,
+
+    /// Parameter name
+    pub parameter: String,
+
+    /// Value before the change (restored on rollback)
+    pub old_value: f32,
+
+    /// Value after the change
+    pub new_value: f32,
+
+    /// Unix timestamp (seconds) when the checkpoint was taken
+    pub created_at: u64,
+
+    /// KPI values observed before the change
+    pub kpi_baseline: Vec<(String, f32)>,
+
+    /// Whether this checkpoint has already been rolled back
+    pub rolled_back: bool,
+}
+
+impl ParameterCheckpoint {
+    /// Check if the checkpoint is still inside the rollback window
+    #[inline]
+    pub fn is_within_window(&self, now: u64, window_seconds: u64) -> bool {
+        now.saturating_sub(self.created_at) <= window_seconds
+    }
+}
+
+/// KPI that degraded beyond its threshold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KpiDegradation {
+    /// KPI name
+    pub kpi: String,
+
+    /// Baseline value captured at checkpoint time
+    pub baseline: f32,
+
+    /// Value observed after the change
+    pub current: f32,
+
+    /// Observed degradation (%)
+    pub degradation_percent: f32,
+
+    /// Threshold that was exceeded (%)
+    pub threshold_percent: f32,
+}
+
+/// Command restoring a checkpointed parameter value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RollbackCommand {
+    /// Checkpoint being reverted
+    pub checkpoint_id: u64,
+
+    /// Managed Object path
+    pub mo_path: String,
+
+    /// Parameter name
+    pub parameter: String,
+
+    /// Value to restore
+    pub value: f32,
+}
+
+impl RollbackCommand {
+    /// Render as a cmedit SET command
+    pub fn to_cmedit(&self) -> String {
+        let value = if self.value.fract() == 0.0 {
+            (self.value as i64).to_string()
+        } else {
+            self.value.to_string()
+        };
+        format!("cmedit set {} {}={}", self.mo_path, self.parameter, value)
+    }
+}
+
+/// KPI-driven rollback manager
+///
+/// Records a checkpoint before each applied change and decides, from
+/// post-change KPIs, whether the change must be reverted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollbackManager {
+    /// Configuration
+    config: RollbackConfig,
+
+    /// Checkpoints in creation order
+    checkpoints: Vec<ParameterCheckpoint>,
+
+    /// Per-KPI thresholds (KPIs not listed use the default)
+    kpi_thresholds: HashMap<String, KpiThreshold>,
+
+    /// Next checkpoint identifier
+    next_id: u64,
+
+    /// Number of checkpoints rolled back
+    rollback_count: u64,
+}
+
+impl RollbackManager {
+    /// Create a new rollback manager
+    pub fn new(config: RollbackConfig) -> Self {
+        Self {
+            config,
+            checkpoints: Vec::new(),
+            kpi_thresholds: HashMap::new(),
+            next_id: 0,
+            rollback_count: 0,
+        }
+    }
+
+    /// Get configuration
+    pub fn config(&self) -> &RollbackConfig {
+        &self.config
+    }
+
+    /// Set the degradation threshold for a KPI
+    pub fn set_kpi_threshold(&mut self, kpi: &str, threshold: KpiThreshold) {
+        self.kpi_thresholds.insert(kpi.to_string(), threshold);
+    }
+
+    /// Get the threshold applied to a KPI
+    ///
+    /// KPIs without an explicit threshold are treated as higher-is-better
+    /// with `default_percent` tolerated degradation.
+    pub fn kpi_threshold(&self, kpi: &str, default_percent: f32) -> KpiThreshold {
+        self.kpi_thresholds
+            .get(kpi)
+            .copied()
+            .unwrap_or(KpiThreshold::new(KpiDirection::HigherIsBetter, default_percent))
+    }
+
+    /// Checkpoint a parameter value before applying a change
+    ///
+    /// Returns the checkpoint identifier.
+    pub fn checkpoint(
+        &mut self,
+        mo_path: &str,
+        parameter: &str,
+        old_value: f32,
+        new_value: f32,
+        kpi_baseline: &[(String, f32)],
+    ) -> u64 {
+        self.checkpoint_at(mo_path, parameter, old_value, new_value, kpi_baseline, now_secs())
+    }
+
+    /// Checkpoint a parameter value at an explicit timestamp (seconds)
+    pub fn checkpoint_at(
+        &mut self,
+        mo_path: &str,
+        parameter: &str,
+        old_value: f32,
+        new_value: f32,
+        kpi_baseline: &[(String, f32)],
+        now: u64,
+    ) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.checkpoints.push(ParameterCheckpoint {
+            id,
+            mo_path: mo_path.to_string(),
+            parameter: parameter.to_string(),
+            old_value,
+            new_value,
+            created_at: now,
+            kpi_baseline: kpi_baseline.to_vec(),
+            rolled_back: false,
+        });
+
+        if self.checkpoints.len() > self.config.max_checkpoints {
+            let excess = self.checkpoints.len() - self.config.max_checkpoints;
+            self.checkpoints.drain(..excess);
+        }
+
+        id
+    }
+
+    /// Get a checkpoint by identifier
+    pub fn get_checkpoint(&self, id: u64) -> Option<&ParameterCheckpoint> {
+        self.checkpoints.iter().find(|c| c.id == id)
+    }
+
+    /// Most recent checkpoint that has not been rolled back
+    pub fn latest_checkpoint(&self) -> Option<&ParameterCheckpoint> {
+        self.checkpoints.iter().rev().find(|c| !c.rolled_back)
+    }
+
+    /// Checkpoints still eligible for rollback at `now`
+    pub fn active_checkpoints(&self, now: u64) -> Vec<&ParameterCheckpoint> {
+        self.checkpoints
+            .iter()
+            .filter(|c| !c.rolled_back && c.is_within_window(now, self.config.window_seconds))
+            .collect()
+    }
+
+    /// Number of retained checkpoints
+    pub fn len(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    /// Check if no checkpoints are retained
+    pub fn is_empty(&self) -> bool {
+        self.checkpoints.is_empty()
+    }
+
+    /// Number of checkpoints rolled back so far
+    pub fn rollback_count(&self) -> u64 {
+        self.rollback_count
+    }
+
+    /// Compare current KPIs against a checkpoint's baseline
+    ///
+    /// Returns the KPIs that degraded beyond their thresholds. KPIs missing
+    /// from either side are ignored.
+    pub fn evaluate_kpis(
+        &self,
+        checkpoint_id: u64,
+        current_kpis: &[(String, f32)],
+    ) -> SafetyResult<Vec<KpiDegradation>> {
+        let checkpoint = self
+            .get_checkpoint(checkpoint_id)
+            .ok_or(SafetyError::NoCheckpointAvailable)?;
+
+        Ok(self.degraded_kpis(checkpoint, current_kpis, self.config.default_degradation_percent))
+    }
+
+    /// Produce the commands reverting a checkpoint
+    ///
+    /// Later checkpoints on the same MO parameter are superseded by the
+    /// restored value and marked as rolled back as well.
+    pub fn rollback(&mut self, checkpoint_id: u64) -> SafetyResult<Vec<RollbackCommand>> {
+        self.rollback_at(checkpoint_id, now_secs())
+    }
+
+    /// Produce the commands reverting a checkpoint at an explicit timestamp
+    pub fn rollback_at(
+        &mut self,
+        checkpoint_id: u64,
+        now: u64,
+    ) -> SafetyResult<Vec<RollbackCommand>> {
+        let index = self
+            .checkpoints
+            .iter()
+            .position(|c| c.id == checkpoint_id && !c.rolled_back)
+            .ok_or(SafetyError::NoCheckpointAvailable)?;
+
+        if !self.checkpoints[index].is_within_window(now, self.config.window_seconds) {
+            return Err(SafetyError::RollbackWindowExpired);
+        }
+
+        let (mo_path, parameter, old_value) = {
+            let target = &self.checkpoints[index];
+            (target.mo_path.clone(), target.parameter.clone(), target.old_value)
+        };
+
+        for checkpoint in self.checkpoints[index..].iter_mut() {
+            if checkpoint.mo_path == mo_path && checkpoint.parameter == parameter {
+                checkpoint.rolled_back = true;
+            }
+        }
+        self.rollback_count += 1;
+
+        Ok(vec![RollbackCommand {
+            checkpoint_id,
+            mo_path,
+            parameter,
+            value: old_value,
+        }])
+    }
+
+    /// Roll back every active checkpoint whose KPIs degraded
+    ///
+    /// Returns the reverting commands, newest change first, or an empty
+    /// vector when all KPIs are within thresholds.
+    pub fn rollback_on_degradation(
+        &mut self,
+        current_kpis: &[(String, f32)],
+    ) -> SafetyResult<Vec<RollbackCommand>> {
+        self.rollback_on_degradation_at(current_kpis, now_secs())
+    }
+
+    /// Roll back degraded checkpoints at an explicit timestamp
+    pub fn rollback_on_degradation_at(
+        &mut self,
+        current_kpis: &[(String, f32)],
+        now: u64,
+    ) -> SafetyResult<Vec<RollbackCommand>> {
+        if self.latest_checkpoint().is_none() {
+            return Err(SafetyError::NoCheckpointAvailable);
+        }
+
+        let default_percent = self.config.default_degradation_percent;
+        let degraded: Vec<u64> = self
+            .active_checkpoints(now)
+            .into_iter()
+            .rev()
+            .filter(|c| !self.degraded_kpis(c, current_kpis, default_percent).is_empty())
+            .map(|c| c.id)
+            .collect();
+
+        let mut commands = Vec::new();
+        for id in degraded {
+            // Already superseded by a newer rollback on the same parameter
+            if self.get_checkpoint(id).map_or(true, |c| c.rolled_back) {
+                continue;
+            }
+            commands.extend(self.rollback_at(id, now)?);
+        }
+
+        Ok(commands)
+    }
+
+    /// Check whether the latest change requires a rollback
+    ///
+    /// `default_percent` applies to KPIs without an explicit threshold.
+    pub fn requires_rollback(
+        &self,
+        current_kpis: &[(String, f32)],
+        default_percent: f32,
+    ) -> SafetyResult<bool> {
+        self.requires_rollback_at(current_kpis, default_percent, now_secs())
+    }
+
+    /// Check whether the latest change requires a rollback at `now`
+    pub fn requires_rollback_at(
+        &self,
+        current_kpis: &[(String, f32)],
+        default_percent: f32,
+        now: u64,
+    ) -> SafetyResult<bool> {
+        let checkpoint = self
+            .latest_checkpoint()
+            .ok_or(SafetyError::NoCheckpointAvailable)?;
+
+        if !checkpoint.is_within_window(now, self.config.window_seconds) {
+            return Err(SafetyError::RollbackWindowExpired);
+        }
+
+        Ok(!self.degraded_kpis(checkpoint, current_kpis, default_percent).is_empty())
+    }
+
+    /// Drop checkpoints outside the rollback window
+    pub fn prune_expired(&mut self, now: u64) -> usize {
+        let before = self.checkpoints.len();
+        let window = self.config.window_seconds;
+        self.checkpoints.retain(|c| c.is_within_window(now, window));
+        before - self.checkpoints.len()
+    }
+
+    fn degraded_kpis(
+        &self,
+        checkpoint: &ParameterCheckpoint,
+        current_kpis: &[(String, f32)],
+        default_percent: f32,
+    ) -> Vec<KpiDegradation> {
+        let mut degraded = Vec::new();
+
+        for (name, baseline) in &checkpoint.kpi_baseline {
+            let current = match current_kpis.iter().find(|(n, _)| n == name) {
+                Some((_, value)) => *value,
+                None => continue,
+            };
+
+            let threshold = self.kpi_threshold(name, default_percent);
+            let degradation = threshold.direction.degradation_percent(*baseline, current);
+            if degradation > threshold.max_degradation_percent {
+                degraded.push(KpiDegradation {
+                    kpi: name.clone(),
+                    baseline: *baseline,
+                    current,
+                    degradation_percent: degradation,
+                    threshold_percent: threshold.max_degradation_percent,
+                });
+            }
+        }
+
+        degraded
+    }
+}
+
+impl Default for RollbackManager {
+    fn default() -> Self {
+        Self::new(RollbackConfig::default())
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kpis(values: &[(&str, f32)]) -> Vec<(String, f32)> {
+        values.iter().map(|(n, v)| (n.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn test_degradation_direction() {
+        assert_eq!(KpiDirection::HigherIsBetter.degradation_percent(100.0, 110.0), 0.0);
+        assert!((KpiDirection::HigherIsBetter.degradation_percent(100.0, 90.0) - 10.0).abs() < 1e-4);
+        assert_eq!(KpiDirection::LowerIsBetter.degradation_percent(2.0, 1.0), 0.0);
+        assert!((KpiDirection::LowerIsBetter.degradation_percent(2.0, 3.0) - 50.0).abs() < 1e-4);
+        assert_eq!(KpiDirection::LowerIsBetter.degradation_percent(0.0, 1.0), 100.0);
+    }
+
+    #[test]
+    fn test_rollback_on_degradation() {
+        let mut manager = RollbackManager::default();
+        manager.set_kpi_threshold("dropRate", KpiThreshold::new(KpiDirection::LowerIsBetter, 10.0));
+
+        let baseline = kpis(&[("accessibility", 99.0), ("dropRate", 1.0)]);
+        let id = manager.checkpoint_at("EUtranCellFDD=1", "lbActivationThreshold", 50.0, 55.0, &baseline, 1000);
+
+        // Within thresholds
+        let commands = manager
+            .rollback_on_degradation_at(&kpis(&[("accessibility", 98.5), ("dropRate", 1.05)]), 1100)
+            .unwrap();
+        assert!(commands.is_empty());
+
+        // Drop rate up 50%
+        let commands = manager
+            .rollback_on_degradation_at(&kpis(&[("accessibility", 99.0), ("dropRate", 1.5)]), 1200)
+            .unwrap();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].checkpoint_id, id);
+        assert_eq!(commands[0].value, 50.0);
+        assert_eq!(commands[0].to_cmedit(), "cmedit set EUtranCellFDD=1 lbActivationThreshold=50");
+        assert_eq!(manager.rollback_count(), 1);
+        assert!(manager.get_checkpoint(id).unwrap().rolled_back);
+    }
+
+    #[test]
+    fn test_rollback_window_expired() {
+        let mut manager = RollbackManager::default();
+        let id = manager.checkpoint_at("EUtranCellFDD=1", "hoA3Offset", 1.0, 2.0, &[], 1000);
+
+        let result = manager.rollback_at(id, 1000 + DEFAULT_ROLLBACK_WINDOW_SECONDS + 1);
+        assert!(matches!(result, Err(SafetyError::RollbackWindowExpired)));
+
+        let result = manager.rollback_at(id, 1000 + DEFAULT_ROLLBACK_WINDOW_SECONDS);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_no_checkpoint_available() {
+        let mut manager = RollbackManager::default();
+        assert!(matches!(manager.rollback(42), Err(SafetyError::NoCheckpointAvailable)));
+        assert!(matches!(
+            manager.requires_rollback(&[], 5.0),
+            Err(SafetyError::NoCheckpointAvailable)
+        ));
+    }
+
+    #[test]
+    fn test_rollback_supersedes_later_checkpoints() {
+        let mut manager = RollbackManager::default();
+        let first = manager.checkpoint_at("EUtranCellFDD=1", "hoHysteresis", 2.0, 2.2, &[], 1000);
+        let second = manager.checkpoint_at("EUtranCellFDD=1", "hoHysteresis", 2.2, 2.4, &[], 1100);
+
+        let commands = manager.rollback_at(first, 1200).unwrap();
+        assert_eq!(commands[0].value, 2.0);
+        assert!(manager.get_checkpoint(second).unwrap().rolled_back);
+        assert!(matches!(
+            manager.rollback_at(second, 1200),
+            Err(SafetyError::NoCheckpointAvailable)
+        ));
+    }
+
+    #[test]
+    fn test_max_checkpoints_and_prune() {
+        let mut manager = RollbackManager::new(RollbackConfig {
+            max_checkpoints: 2,
+            ..RollbackConfig::default()
+        });
+        manager.checkpoint_at("A=1", "p", 1.0, 2.0, &[], 0);
+        manager.checkpoint_at("A=1", "q", 1.0, 2.0, &[], 100);
+        manager.checkpoint_at("A=1", "r", 1.0, 2.0, &[], 2000);
+        assert_eq!(manager.len(), 2);
+
+        assert_eq!(manager.prune_expired(2000), 1);
+        assert_eq!(manager.latest_checkpoint().unwrap().parameter, "r");
+    }
+}
@@ -3,7 +3,10 @@
 //! Provides compile-time embedded constraints and SIMD-accelerated
 //! validation for 593 RAN parameters across 89 feature domains.
 
-use crate::{SafetyError, SafetyResult, get_hardcoded_constraint};
+use crate::change_set::{ChangeSet, ChangeSetReport, Invariant};
+use crate::relational::RelationalConstraint;
+use crate::{SafetyError, SafetyResult, get_hardcoded_constraint, get_hardcoded_domain, get_scoped_constraint};
+use elex_core::ParameterDomain;
 use elex_simd::VectorOps;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -135,6 +138,18 @@ pub enum ViolationType {
 
     /// Outside safe bounds (warning only)
     OutsideSafeBounds,
+
+    /// Cross-parameter invariant of a change set violated
+    InvariantViolated,
+
+    /// Relational constraint between parameters violated
+    RelationalConstraint,
+
+    /// Value not in the parameter's typed domain (enum, boolean, integer step)
+    InvalidDomainValue,
+
+    /// Operation removes configuration or interrupts traffic (delete, restart)
+    DestructiveOperation,
 }
 
 /// Severity of validation violation
@@ -157,9 +172,19 @@ pub struct SafeZoneValidator {
     /// Custom constraints (dynamic, added at runtime)
     custom_zones: HashMap<String, SafeZone>,
 
+    /// Custom typed domains (dynamic, added at runtime)
+    custom_domains: HashMap<String, ParameterDomain>,
+
     /// Last change timestamp for each parameter (for cooldowns)
     last_change: HashMap<String, u64>,
 
+    /// Relational constraints between coupled parameters
+    relations: Vec<RelationalConstraint>,
+
+    /// Known current values keyed by (MO path, parameter), for relational
+    /// constraints
+    current_values: HashMap<(String, String), f32>,
+
     /// SIMD operations
     simd_ops: VectorOps,
 }
@@ -169,7 +194,10 @@ impl SafeZoneValidator {
     pub fn new() -> Self {
         Self {
             custom_zones: HashMap::new(),
+            custom_domains: HashMap::new(),
             last_change: HashMap::new(),
+            relations: RelationalConstraint::standard(),
+            current_values: HashMap::new(),
             simd_ops: VectorOps::new(),
         }
     }
@@ -187,6 +215,79 @@ impl SafeZoneValidator {
         self.custom_zones.remove(param_name).is_some()
     }
 
+    /// Add a typed domain for a parameter without a hardcoded one
+    pub fn add_domain(&mut self, param_name: String, domain: ParameterDomain) {
+        self.custom_domains.insert(param_name, domain);
+    }
+
+    /// Get the typed domain of a parameter (None if continuous)
+    ///
+    /// Checks hardcoded domains first, then custom ones.
+    pub fn get_domain(&self, param_name: &str) -> Option<&ParameterDomain> {
+        get_hardcoded_domain(param_name).or_else(|| self.custom_domains.get(param_name))
+    }
+
+    /// Check a value (and transition, if the old value is known) against
+    /// the parameter's typed domain
+    pub fn check_domain(
+        &self,
+        param_name: &str,
+        old_value: Option<f32>,
+        new_value: f32,
+    ) -> Result<(), String> {
+        let Some(domain) = self.get_domain(param_name) else {
+            return Ok(());
+        };
+        let origin = self.get_constraint(param_name).map(|z| z.absolute_min).unwrap_or(0.0);
+        domain.check_value(new_value, origin)?;
+        match old_value {
+            Some(old) => domain.check_transition(old, new_value),
+            None => Ok(()),
+        }
+    }
+
+    /// Add a relational constraint between parameters
+    pub fn add_relation(&mut self, relation: RelationalConstraint) {
+        self.relations.push(relation);
+    }
+
+    /// Relational constraints enforced by this validator
+    pub fn relations(&self) -> &[RelationalConstraint] {
+        &self.relations
+    }
+
+    /// Remove all relational constraints (including the standard ones)
+    pub fn clear_relations(&mut self) {
+        self.relations.clear();
+    }
+
+    /// Record the current value of a parameter on an MO
+    ///
+    /// Relational constraints are only checked once every referenced
+    /// parameter has a known value on the same MO.
+    pub fn set_current_value(&mut self, mo_path: &str, param_name: &str, value: f32) {
+        self.current_values.insert((mo_path.to_string(), param_name.to_string()), value);
+    }
+
+    /// Known current value of a parameter on an MO
+    pub fn current_value(&self, mo_path: &str, param_name: &str) -> Option<f32> {
+        self.current_values
+            .get(&(mo_path.to_string(), param_name.to_string()))
+            .copied()
+    }
+
+    /// Relational constraints violated if `param_name` on `mo_path` is set to `value`
+    pub fn relation_violations(&self, mo_path: &str, param_name: &str, value: f32) -> Vec<&RelationalConstraint> {
+        self.relations
+            .iter()
+            .filter(|r| r.references(param_name))
+            .filter(|r| {
+                r.evaluate(|p| if p == param_name { Some(value) } else { self.current_value(mo_path, p) })
+                    == Some(false)
+            })
+            .collect()
+    }
+
     /// Get constraint for a parameter
     ///
     /// Checks hardcoded constraints first, then custom ones.
@@ -199,6 +300,31 @@ impl SafeZoneValidator {
         self.custom_zones.get(param_name)
     }
 
+    /// Get constraint for a parameter on a specific MO class / feature
+    ///
+    /// Prefers the most specific hardcoded constraint, then custom ones.
+    pub fn get_scoped_constraint(
+        &self,
+        param_name: &str,
+        mo_class: Option<&str>,
+        feature_code: Option<&str>,
+    ) -> Option<&SafeZone> {
+        if let Some(zone) = get_scoped_constraint(param_name, mo_class, feature_code) {
+            return Some(zone);
+        }
+        self.custom_zones.get(param_name)
+    }
+
+    /// Get constraint for a parameter on an MO
+    ///
+    /// The MO class is the class of the last RDN of `mo_path` (flat
+    /// `Class=Id` or full FDN). Entries scoped to that class take precedence;
+    /// otherwise falls back to `get_constraint`.
+    pub fn get_constraint_for(&self, mo_path: &str, param_name: &str) -> Option<&SafeZone> {
+        self.get_scoped_constraint(param_name, mo_class(mo_path), None)
+            .or_else(|| self.get_constraint(param_name))
+    }
+
     /// Validate a single parameter value
     ///
     /// Returns Ok if value is valid, Err with violation details.
@@ -226,6 +352,9 @@ impl SafeZoneValidator {
             }
         }
 
+        self.check_domain(param_name, None, value)
+            .map_err(|reason| SafetyError::InvalidDomainValue(param_name.to_string(), reason))?;
+
         Ok(())
     }
 
@@ -233,15 +362,18 @@ impl SafeZoneValidator {
     ///
     /// Checks:
     /// 1. New value within absolute bounds
-    /// 2. Change within percentage limit
-    /// 3. Cooldown period expired
+    /// 2. Value and transition legal for the typed domain
+    /// 3. Change within percentage limit (continuous domains only)
+    /// 4. Cooldown period expired
+    /// 5. Relational constraints hold against known current values of `mo_path`
     pub fn validate_change(
         &self,
+        mo_path: &str,
         param_name: &str,
         old_value: f32,
         new_value: f32,
     ) -> SafetyResult<()> {
-        let zone = self.get_constraint(param_name)
+        let zone = self.get_constraint_for(mo_path, param_name)
             .ok_or_else(|| SafetyError::ExceedsAbsoluteMax(
                 param_name.to_string(),
                 new_value,
@@ -265,8 +397,13 @@ impl SafeZoneValidator {
             }
         }
 
+        // Check typed domain
+        self.check_domain(param_name, Some(old_value), new_value)
+            .map_err(|reason| SafetyError::InvalidDomainValue(param_name.to_string(), reason))?;
+
         // Check change limit
-        if !zone.is_change_within_limit(old_value, new_value) {
+        let discrete = self.get_domain(param_name).is_some_and(|d| d.is_discrete());
+        if !discrete && !zone.is_change_within_limit(old_value, new_value) {
             let change_pct = ((new_value - old_value).abs() / old_value.abs()) * 100.0;
             return Err(SafetyError::ExceedsChangeLimit(
                 param_name.to_string(),
@@ -291,6 +428,14 @@ impl SafeZoneValidator {
             }
         }
 
+        // Check relational constraints
+        if let Some(relation) = self.relation_violations(mo_path, param_name, new_value).first() {
+            return Err(SafetyError::RelationalViolation(
+                param_name.to_string(),
+                relation.describe(),
+            ));
+        }
+
         Ok(())
     }
 
@@ -305,35 +450,80 @@ impl SafeZoneValidator {
 
     /// SIMD-accelerated batch validation
     ///
-    /// Validates multiple parameters in parallel using SIMD.
-    /// Returns a vector of violations (empty if all valid).
+    /// Validates multiple parameters of one MO in parallel using SIMD, then
+    /// checks relational constraints on the MO's known current values
+    /// overlaid with the batch. Returns a vector of violations (empty if all
+    /// valid).
     pub fn validate_batch(
         &self,
+        mo_path: &str,
         param_names: &[&str],
         old_values: &[f32],
         new_values: &[f32],
     ) -> Vec<ValidationViolation> {
+        let mo_paths = vec![mo_path; param_names.len()];
+        let mut violations = self.validate_bounds(&mo_paths, param_names, old_values, new_values);
+
+        let mut state: HashMap<&str, f32> = self
+            .current_values
+            .iter()
+            .filter(|((mo, _), _)| mo == mo_path)
+            .map(|((_, param), value)| (param.as_str(), *value))
+            .collect();
+        for (name, value) in param_names.iter().zip(new_values) {
+            state.insert(name, *value);
+        }
+
+        for relation in &self.relations {
+            let Some(i) = param_names.iter().position(|name| relation.references(name)) else {
+                continue;
+            };
+            if relation.evaluate(|p| state.get(p).copied()) == Some(false) {
+                violations.push(ValidationViolation {
+                    parameter: param_names[i].to_string(),
+                    old_value: old_values[i],
+                    new_value: new_values[i],
+                    violation_type: ViolationType::RelationalConstraint,
+                    severity: ValidationSeverity::Critical,
+                    message: format!("Relational constraint '{}' violated", relation.describe()),
+                });
+            }
+        }
+
+        violations
+    }
+
+    /// Per-parameter bounds, change limit, cooldown and safe range checks
+    ///
+    /// Each parameter is checked against its constraint on the MO at the
+    /// same position in `mo_paths`.
+    fn validate_bounds(
+        &self,
+        mo_paths: &[&str],
+        param_names: &[&str],
+        old_values: &[f32],
+        new_values: &[f32],
+    ) -> Vec<ValidationViolation> {
+        assert_eq!(mo_paths.len(), param_names.len());
         assert_eq!(param_names.len(), old_values.len());
         assert_eq!(old_values.len(), new_values.len());
 
         let n = param_names.len();
         let mut violations = Vec::new();
 
+        let zones: Vec<Option<&SafeZone>> = mo_paths
+            .iter()
+            .zip(param_names)
+            .map(|(mo_path, name)| self.get_constraint_for(mo_path, name))
+            .collect();
+
         // Prepare SIMD buffers
-        let mins: Vec<f32> = param_names.iter()
-            .map(|&name| {
-                self.get_constraint(name)
-                    .map(|z| z.absolute_min)
-                    .unwrap_or(f32::NEG_INFINITY)
-            })
+        let mins: Vec<f32> = zones.iter()
+            .map(|zone| zone.map(|z| z.absolute_min).unwrap_or(f32::NEG_INFINITY))
             .collect();
 
-        let maxs: Vec<f32> = param_names.iter()
-            .map(|&name| {
-                self.get_constraint(name)
-                    .map(|z| z.absolute_max)
-                    .unwrap_or(f32::INFINITY)
-            })
+        let maxs: Vec<f32> = zones.iter()
+            .map(|zone| zone.map(|z| z.absolute_max).unwrap_or(f32::INFINITY))
             .collect();
 
         let mut valid_mask = vec![0u8; n];
@@ -349,8 +539,7 @@ impl SafeZoneValidator {
 
             // Check bounds from SIMD result
             if valid_mask[i] == 0 {
-                let zone = self.get_constraint(param_name);
-                if let Some(z) = zone {
+                if let Some(z) = zones[i] {
                     if new_val > z.absolute_max {
                         violations.push(ValidationViolation {
                             parameter: param_name.to_string(),
@@ -380,10 +569,23 @@ impl SafeZoneValidator {
                 continue;
             }
 
+            // Check typed domain
+            if let Err(reason) = self.check_domain(param_name, Some(old_val), new_val) {
+                violations.push(ValidationViolation {
+                    parameter: param_name.to_string(),
+                    old_value: old_val,
+                    new_value: new_val,
+                    violation_type: ViolationType::InvalidDomainValue,
+                    severity: ValidationSeverity::Critical,
+                    message: reason,
+                });
+            }
+            let discrete = self.get_domain(param_name).is_some_and(|d| d.is_discrete());
+
             // Check change limit and cooldown (per-parameter)
-            if let Some(zone) = self.get_constraint(param_name) {
+            if let Some(zone) = zones[i] {
                 // Check change limit
-                if !zone.is_change_within_limit(old_val, new_val) {
+                if !discrete && !zone.is_change_within_limit(old_val, new_val) {
                     violations.push(ValidationViolation {
                         parameter: param_name.to_string(),
                         old_value: old_val,
@@ -421,7 +623,7 @@ impl SafeZoneValidator {
                 }
 
                 // Check safe bounds (warning only)
-                if !zone.is_within_safe_bounds(new_val) {
+                if !discrete && !zone.is_within_safe_bounds(new_val) {
                     violations.push(ValidationViolation {
                         parameter: param_name.to_string(),
                         old_value: old_val,
@@ -440,6 +642,106 @@ impl SafeZoneValidator {
         violations
     }
 
+    /// Validate a change set as one unit (all-or-nothing)
+    ///
+    /// Every entry is checked against its safe zone, then cross-parameter
+    /// invariants and the validator's relational constraints are checked
+    /// per MO on the final configuration and on each step of the execution
+    /// order. Any critical violation rejects the whole set.
+    ///
+    /// Values the set leaves open are taken from the validator's current
+    /// values of the same MO (`set_current_value`), as in `validate_change`
+    /// and `validate_batch`: entries without an old value use the current
+    /// one, and current values of unchanged parameters join the set's
+    /// context. Old values and context given by the set take precedence.
+    /// Entries whose old value is still unknown skip the change limit check.
+    pub fn validate_change_set(&self, set: &ChangeSet) -> ChangeSetReport {
+        let mut completed = set.clone();
+        completed.complete_with(
+            self.current_values
+                .iter()
+                .map(|((mo_path, param), value)| (mo_path.as_str(), param.as_str(), *value)),
+        );
+        let set = &completed;
+
+        let entries = set.entries();
+        let mo_paths: Vec<&str> = entries.iter().map(|e| e.mo_path.as_str()).collect();
+        let names: Vec<&str> = entries.iter().map(|e| e.parameter.as_str()).collect();
+        let old_values: Vec<f32> = entries.iter().map(|e| e.old_value.unwrap_or(e.new_value)).collect();
+        let new_values: Vec<f32> = entries.iter().map(|e| e.new_value).collect();
+
+        let mut violations = self.validate_bounds(&mo_paths, &names, &old_values, &new_values);
+
+        for entry in set.duplicate_entries() {
+            violations.push(ValidationViolation {
+                parameter: entry.parameter.clone(),
+                old_value: entry.old_value.unwrap_or(0.0),
+                new_value: entry.new_value,
+                violation_type: ViolationType::InvariantViolated,
+                severity: ValidationSeverity::Critical,
+                message: format!("Parameter changed more than once on {}", entry.mo_path),
+            });
+        }
+
+        let relations: Vec<Invariant> = self.relations.iter().cloned().map(Invariant::from).collect();
+        let mut relational_set = set.clone();
+        relational_set.replace_invariants(relations.clone());
+
+        let final_violations = set
+            .final_violations()
+            .into_iter()
+            .map(|v| (v, ViolationType::InvariantViolated))
+            .chain(relational_set.final_violations().into_iter().map(|v| (v, ViolationType::RelationalConstraint)));
+
+        for ((mo_path, invariant), violation_type) in final_violations {
+            let first = invariant.parameters()[0].to_string();
+            let entry = entries.iter().find(|e| e.mo_path == mo_path && invariant.parameters().contains(&e.parameter.as_str()));
+            let kind = match violation_type {
+                ViolationType::RelationalConstraint => "Relational constraint",
+                _ => "Invariant",
+            };
+            violations.push(ValidationViolation {
+                parameter: entry.map(|e| e.parameter.clone()).unwrap_or(first),
+                old_value: entry.and_then(|e| e.old_value).unwrap_or(0.0),
+                new_value: entry.map(|e| e.new_value).unwrap_or(0.0),
+                violation_type,
+                severity: ValidationSeverity::Critical,
+                message: format!("{} '{}' violated on {}", kind, invariant.describe(), mo_path),
+            });
+        }
+
+        let mut order = Vec::new();
+        if !violations.iter().any(|v| v.severity == ValidationSeverity::Critical) {
+            let mut ordered_set = set.clone();
+            for relation in relations {
+                ordered_set.add_invariant(relation);
+            }
+            match ordered_set.execution_order() {
+                Ok(o) => order = o,
+                Err(e) => violations.push(ValidationViolation {
+                    parameter: names.first().map(|n| n.to_string()).unwrap_or_default(),
+                    old_value: 0.0,
+                    new_value: 0.0,
+                    violation_type: ViolationType::InvariantViolated,
+                    severity: ValidationSeverity::Critical,
+                    message: e.to_string(),
+                }),
+            }
+        }
+
+        let approved = !violations.iter().any(|v| v.severity == ValidationSeverity::Critical);
+        if !approved {
+            order.clear();
+        }
+
+        ChangeSetReport {
+            change_set_id: set.id.clone(),
+            approved,
+            violations,
+            order,
+        }
+    }
+
     /// Record multiple parameter changes
     pub fn record_changes(&mut self, param_names: &[&str]) {
         let now = SystemTime::now()
@@ -451,6 +753,18 @@ impl SafeZoneValidator {
         }
     }
 
+    /// Seconds remaining in a parameter's cooldown (None if not in cooldown)
+    pub fn cooldown_remaining(&self, param_name: &str) -> Option<u64> {
+        let zone = self.get_constraint(param_name)?;
+        let last_ts = self.last_change.get(param_name)?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let elapsed = now.saturating_sub(*last_ts);
+        (elapsed < zone.cooldown_seconds).then(|| zone.cooldown_seconds - elapsed)
+    }
+
     /// Clear cooldown timer for a parameter
     pub fn clear_cooldown(&mut self, param_name: &str) {
         self.last_change.remove(param_name);
@@ -476,12 +790,20 @@ pub fn check_parameter_value(param_name: &str, value: f32) -> SafetyResult<()> {
 
 /// Convenience function: validate a parameter change
 pub fn validate_parameter_change(
+    mo_path: &str,
     param_name: &str,
     old_value: f32,
     new_value: f32,
 ) -> SafetyResult<()> {
     let validator = SafeZoneValidator::new();
-    validator.validate_change(param_name, old_value, new_value)
+    validator.validate_change(mo_path, param_name, old_value, new_value)
+}
+
+/// MO class of the last RDN of an MO path (`Class=Id` or full FDN)
+fn mo_class(mo_path: &str) -> Option<&str> {
+    let rdn = mo_path.rsplit(',').next()?;
+    let (class, _) = rdn.split_once('=')?;
+    Some(class.trim())
 }
 
 /// Simplified violation type for quick checks
@@ -491,6 +813,8 @@ pub type SafeViolation = (String, f32, f32); // (param, old, new)
 mod tests {
     use super::*;
 
+    const CELL: &str = "EUtranCellFDD=1";
+
     #[test]
     fn test_safe_zone_bounds() {
         let zone = SafeZone::new(10.0, 100.0, 50.0, 90.0, 15.0, 3600);
@@ -559,15 +883,44 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_scoped_constraints_by_mo_class() {
+        let validator = SafeZoneValidator::new();
+        let nr_cell = "ManagedElement=1,GNBDUFunction=1,NRCellDU=1";
+
+        // qRxLevMin only has entries scoped to EUtranCellFDD and NRCellDU
+        assert_eq!(validator.get_constraint_for(CELL, "qRxLevMin").unwrap().absolute_min, -140.0);
+        assert_eq!(validator.get_constraint_for(nr_cell, "qRxLevMin").unwrap().absolute_min, -156.0);
+        assert!(validator.get_constraint_for("EUtranCellTDD=1", "qRxLevMin").is_none());
+
+        assert!(validator.validate_change(nr_cell, "qRxLevMin", -148.0, -150.0).is_ok());
+        assert!(matches!(
+            validator.validate_change(CELL, "qRxLevMin", -148.0, -150.0),
+            Err(SafetyError::BelowAbsoluteMin(_, _, min)) if min == -140.0
+        ));
+        // Outside the NR safe range: a warning only
+        let nr_violations = validator.validate_batch(nr_cell, &["qRxLevMin"], &[-148.0], &[-150.0]);
+        assert!(nr_violations.iter().all(|v| v.severity == ValidationSeverity::Warning));
+        let lte_violations = validator.validate_batch(CELL, &["qRxLevMin"], &[-148.0], &[-150.0]);
+        assert_eq!(lte_violations[0].violation_type, ViolationType::BelowAbsoluteMin);
+
+        let set = ChangeSet::new("CR-scoped")
+            .with_change(nr_cell, "qRxLevMin", Some(-148.0), -150.0)
+            .with_change("EUtranCellFDD=2", "qRxLevMin", Some(-138.0), -139.0);
+        assert!(validator.validate_change_set(&set).approved);
+        let set = set.with_change("EUtranCellFDD=3", "qRxLevMin", Some(-138.0), -141.0);
+        assert!(!validator.validate_change_set(&set).approved);
+    }
+
     #[test]
     fn test_validator_change_limit() {
         let validator = SafeZoneValidator::new();
 
         // IFLB has 15% change limit
-        let result = validator.validate_change("lbActivationThreshold", 50.0, 55.0);
+        let result = validator.validate_change(CELL, "lbActivationThreshold", 50.0, 55.0);
         assert!(result.is_ok()); // 10% change
 
-        let result = validator.validate_change("lbActivationThreshold", 50.0, 60.0);
+        let result = validator.validate_change(CELL, "lbActivationThreshold", 50.0, 60.0);
         assert!(result.is_err()); // 20% change exceeds 15% limit
     }
 
@@ -579,14 +932,17 @@ mod tests {
         validator.record_change("lbActivationThreshold");
 
         // Should fail - in cooldown
-        let result = validator.validate_change("lbActivationThreshold", 50.0, 55.0);
+        let result = validator.validate_change(CELL, "lbActivationThreshold", 50.0, 55.0);
         assert!(result.is_err());
 
+        assert!(validator.cooldown_remaining("lbActivationThreshold").is_some());
+
         // Clear cooldown
         validator.clear_cooldown("lbActivationThreshold");
+        assert!(validator.cooldown_remaining("lbActivationThreshold").is_none());
 
         // Should succeed
-        let result = validator.validate_change("lbActivationThreshold", 50.0, 55.0);
+        let result = validator.validate_change(CELL, "lbActivationThreshold", 50.0, 55.0);
         assert!(result.is_ok());
     }
 
@@ -598,22 +954,190 @@ mod tests {
         let old_vals = vec![50.0, 60.0];
         let new_vals = vec![55.0, 80.0]; // Second change exceeds 15% limit
 
-        let violations = validator.validate_batch(&names, &old_vals, &new_vals);
+        let violations = validator.validate_batch(CELL, &names, &old_vals, &new_vals);
         assert_eq!(violations.len(), 1);
         assert_eq!(violations[0].parameter, "lbActivationThreshold");
         assert_eq!(violations[0].old_value, 60.0);
         assert_eq!(violations[0].new_value, 80.0);
     }
 
+    #[test]
+    fn test_change_set_all_or_nothing() {
+        let validator = SafeZoneValidator::new();
+
+        let set = ChangeSet::with_standard_invariants("CR-1")
+            .with_change("EUtranCellFDD=1", "lbMinLoadOffset", Some(-5.0), -4.6)
+            .with_change("EUtranCellFDD=1", "lbMaxLoadOffset", Some(5.0), 5.4);
+        let report = validator.validate_change_set(&set);
+        assert!(report.approved);
+        assert_eq!(report.order.len(), 2);
+
+        // One entry over its change limit rejects the whole set
+        let set = ChangeSet::with_standard_invariants("CR-2")
+            .with_change("EUtranCellFDD=1", "lbMinLoadOffset", Some(-5.0), -4.6)
+            .with_change("EUtranCellFDD=1", "lbMaxLoadOffset", Some(5.0), 8.0);
+        let report = validator.validate_change_set(&set);
+        assert!(!report.approved);
+        assert!(report.order.is_empty());
+    }
+
+    #[test]
+    fn test_change_set_invariant_violation() {
+        let validator = SafeZoneValidator::new();
+
+        let set = ChangeSet::with_standard_invariants("CR-3")
+            .with_change("EUtranCellFDD=1", "hoHysteresis", None, 0.0);
+        let report = validator.validate_change_set(&set);
+        assert!(!report.approved);
+        assert!(report.violations.iter().any(|v| v.violation_type == ViolationType::InvariantViolated));
+    }
+
+    #[test]
+    fn test_relational_change() {
+        let mut validator = SafeZoneValidator::new();
+        validator.set_current_value(CELL, "anrRemoveThreshold", -110.0);
+
+        assert!(validator.validate_change(CELL, "anrAddThreshold", -100.0, -105.0).is_ok());
+
+        // -109 is within 10% but only 1 dB above the remove threshold
+        let result = validator.validate_change(CELL, "anrAddThreshold", -100.0, -109.0);
+        assert!(matches!(result, Err(SafetyError::RelationalViolation(..))));
+
+        // Unknown related values are not checked
+        assert!(validator.validate_change(CELL, "hoA3Offset", 2.0, 2.1).is_ok());
+
+        // Current values of another MO are never compared
+        assert!(validator.validate_change("EUtranCellFDD=2", "anrAddThreshold", -100.0, -109.0).is_ok());
+        assert_eq!(validator.current_value(CELL, "anrRemoveThreshold"), Some(-110.0));
+        assert_eq!(validator.current_value("EUtranCellFDD=2", "anrRemoveThreshold"), None);
+    }
+
+    #[test]
+    fn test_relational_batch() {
+        let mut validator = SafeZoneValidator::new();
+        validator.set_current_value(CELL, "hoHysteresis", 2.0);
+
+        let violations = validator.validate_batch(CELL, &["hoA3Offset"], &[-2.0], &[-1.95]);
+        assert!(violations.iter().all(|v| v.violation_type != ViolationType::RelationalConstraint));
+
+        // Both sides changing together are checked against each other
+        let violations = validator.validate_batch(
+            CELL,
+            &["hoA3Offset", "hoHysteresis"],
+            &[-2.0, 2.0],
+            &[-1.95, 1.9],
+        );
+        let relational: Vec<_> = violations
+            .iter()
+            .filter(|v| v.violation_type == ViolationType::RelationalConstraint)
+            .collect();
+        assert_eq!(relational.len(), 1);
+        assert_eq!(relational[0].parameter, "hoA3Offset");
+    }
+
+    #[test]
+    fn test_change_set_relational_constraint() {
+        let mut validator = SafeZoneValidator::new();
+        validator.add_relation(RelationalConstraint::ordering("lbLowUlnThresh", "lbHighUlnThresh"));
+
+        let mut set = ChangeSet::new("CR-4")
+            .with_change("EUtranCellFDD=1", "lbLowUlnThresh", Some(-100.0), -95.0);
+        set.set_current_value("EUtranCellFDD=1", "lbHighUlnThresh", -96.0);
+        let report = validator.validate_change_set(&set);
+        assert!(!report.approved);
+        assert!(report.violations.iter().any(|v| v.violation_type == ViolationType::RelationalConstraint));
+        assert!(report.violations.iter().all(|v| v.violation_type != ViolationType::InvariantViolated));
+
+        // Relational constraints also constrain the execution order
+        let set = ChangeSet::new("CR-5")
+            .with_change("EUtranCellFDD=1", "anrRemoveThreshold", Some(-110.0), -101.0)
+            .with_change("EUtranCellFDD=1", "anrAddThreshold", Some(-100.0), -92.0);
+        let report = validator.validate_change_set(&set);
+        assert!(report.approved);
+        assert_eq!(report.order, vec![1, 0]);
+    }
+
+    #[test]
+    fn test_change_set_rejects_wake_within_hysteresis() {
+        let mut validator = SafeZoneValidator::new();
+        validator.set_current_value(CELL, "cellWakeThreshold", 20.0);
+        validator.set_current_value(CELL, "cellWakeHysteresis", 10.0);
+
+        // 11 < 20, but 11 + 10 >= 20
+        let set = ChangeSet::with_standard_invariants("CR-10")
+            .with_change(CELL, "cellSleepThreshold", Some(10.0), 11.0);
+        let report = validator.validate_change_set(&set);
+        assert!(!report.approved);
+        assert!(report.violations.iter().any(|v| v.violation_type == ViolationType::InvariantViolated));
+    }
+
+    #[test]
+    fn test_change_set_uses_current_values() {
+        let mut validator = SafeZoneValidator::new();
+        validator.set_current_value(CELL, "mimoWakeThreshold", 30.0);
+        validator.set_current_value(CELL, "lbActivationThreshold", 50.0);
+
+        // Same verdict as validating the change on its own
+        assert!(validator.validate_change(CELL, "mimoSleepThreshold", 30.0, 31.0).is_err());
+        let set = ChangeSet::new("CR-6").with_change(CELL, "mimoSleepThreshold", Some(30.0), 31.0);
+        let report = validator.validate_change_set(&set);
+        assert!(!report.approved);
+        assert!(report.violations.iter().any(|v| v.violation_type == ViolationType::RelationalConstraint));
+
+        // Current values are per MO
+        let set = ChangeSet::new("CR-7").with_change("EUtranCellFDD=2", "mimoSleepThreshold", Some(30.0), 31.0);
+        assert!(validator.validate_change_set(&set).approved);
+
+        // A missing old value is the current one, so the change limit applies
+        let set = ChangeSet::new("CR-8").with_change(CELL, "lbActivationThreshold", None, 80.0);
+        let report = validator.validate_change_set(&set);
+        assert!(report.violations.iter().any(|v| v.violation_type == ViolationType::ExceedsChangeLimit));
+
+        // Context given by the set wins over the validator's value
+        let mut set = ChangeSet::new("CR-9").with_change(CELL, "mimoSleepThreshold", Some(30.0), 31.0);
+        set.set_current_value(CELL, "mimoWakeThreshold", 40.0);
+        assert!(validator.validate_change_set(&set).approved);
+    }
+
+    #[test]
+    fn test_typed_domains() {
+        let mut validator = SafeZoneValidator::new();
+
+        // Hardcoded enum: literal index only
+        assert!(validator.validate_change(CELL, "anrMode", 0.0, 1.0).is_ok());
+        assert!(validator.validate_value("anrMode", 0.5).is_err());
+
+        // Forbidden transition (OFF -> ACTIVE)
+        validator.add_constraint("customMode".to_string(), SafeZone::new(0.0, 2.0, 0.0, 2.0, 5.0, 0));
+        validator.add_domain(
+            "customMode".to_string(),
+            ParameterDomain::enumerated(&["OFF", "MONITOR", "ACTIVE"]).forbid_transition("OFF", "ACTIVE"),
+        );
+        assert!(validator.validate_change(CELL, "customMode", 0.0, 1.0).is_ok());
+        let result = validator.validate_change(CELL, "customMode", 0.0, 2.0);
+        assert!(matches!(result, Err(SafetyError::InvalidDomainValue(..))));
+        assert!(validator.validate_value("customMode", 1.5).is_err());
+
+        // Booleans may flip despite the percentage change limit
+        assert!(validator.validate_change(CELL, "drxEnabled", 1.0, 0.0).is_ok());
+
+        // Integer step alignment
+        assert!(validator.validate_value("mimoMinActiveTime", 120.0).is_ok());
+        assert!(validator.validate_value("mimoMinActiveTime", 125.0).is_err());
+
+        let violations = validator.validate_batch(CELL, &["hoMaxHoCount"], &[10.0], &[10.5]);
+        assert!(violations.iter().any(|v| v.violation_type == ViolationType::InvalidDomainValue));
+    }
+
     #[test]
     fn test_convenience_functions() {
         let result = check_parameter_value("lbActivationThreshold", 50.0);
         assert!(result.is_ok());
 
-        let result = validate_parameter_change("lbActivationThreshold", 50.0, 55.0);
+        let result = validate_parameter_change(CELL, "lbActivationThreshold", 50.0, 55.0);
         assert!(result.is_ok());
 
-        let result = validate_parameter_change("lbActivationThreshold", 50.0, 60.0);
+        let result = validate_parameter_change(CELL, "lbActivationThreshold", 50.0, 60.0);
         assert!(result.is_err());
     }
 }